@@ -0,0 +1,567 @@
+/// Two-point boundary value problems for systems of first order ordinary
+/// differential equations
+///     y' = f(x, y),  a <= x <= b,  bc(y(a), y(b)) = 0,
+/// in the manner of SciPy's solve_bvp. The solution is approximated by a
+/// C1 continuous cubic spline satisfying the differential equations at the
+/// mesh nodes and interval midpoints (4th order Lobatto IIIA collocation).
+/// The collocation equations are solved by a damped Newton method, factorizing
+/// the global Jacobian with `lapacke`, after which the mesh is refined
+/// wherever the relative residuals of the spline exceed the tolerance.
+/// Higher order equations are first rewritten as first order systems, so a
+/// beam EI y'''' = q(x) becomes four equations for y, y', y'' and y'''.
+/// For further information see Kierzenka & Shampine, A BVP Solver Based on
+/// Residual Control and the MATLAB PSE, ACM TOMS 27 (2001).
+
+use std::f64;
+use array::Array;
+use array::Order;
+use matrix::Matrix;
+use lapacke;
+use dense;
+
+/// Maximum number of mesh refinements
+const MAX_ITERATION: usize = 10;
+
+/// Options for `solve_bvp`
+pub struct BvpOptions<'a>
+{
+    /// Tolerance on the relative residuals of the spline, 1e-3 by default.
+    /// Values below 100 times machine epsilon are raised to that.
+    pub tol: f64,
+    /// Tolerance on the boundary condition residuals, tol if None
+    pub bc_tol: Option<f64>,
+    /// Largest number of mesh nodes allowed, 1000 by default
+    pub max_nodes: usize,
+    /// Jacobian df/dy of the right-hand side, estimated by forward
+    /// differences if None
+    pub jac: Option<Box<Fn(f64, &Array<f64>) -> Matrix<f64> + 'a>>,
+    /// Jacobians of the boundary conditions with respect to y(a) and y(b),
+    /// estimated by forward differences if None
+    pub bc_jac: Option<Box<Fn(&Array<f64>, &Array<f64>) -> (Matrix<f64>, Matrix<f64>) + 'a>>,
+}
+
+impl<'a> Default for BvpOptions<'a>
+{
+    fn default() -> BvpOptions<'a>
+    {
+        BvpOptions{
+            tol: 1e-3,
+            bc_tol: None,
+            max_nodes: 1000,
+            jac: None,
+            bc_jac: None,
+        }
+    }
+}
+
+/// Solution returned by `solve_bvp`
+pub struct BvpResult
+{
+    /// Continuous solution, a cubic spline through the final mesh
+    pub sol: BvpSolution,
+    /// Final mesh
+    pub x: Array<f64>,
+    /// Solution at each node of the mesh
+    pub y: Vec<Array<f64>>,
+    /// Derivative of the solution at each node of the mesh
+    pub yp: Vec<Array<f64>>,
+    /// Root mean square of the relative residual over each mesh interval
+    pub rms_residuals: Array<f64>,
+    /// Number of mesh refinements done
+    pub niter: usize,
+    /// 0 if the solver converged, 1 if the maximum number of mesh nodes was
+    /// exceeded, 2 if the collocation system was singular, 3 if the boundary
+    /// conditions could not be satisfied to bc_tol
+    pub status: i32,
+    /// Description of the reason the solver stopped
+    pub message: String,
+    /// True if status is 0
+    pub success: bool,
+}
+
+/// Cubic Hermite spline through the solution and its derivative at the
+/// nodes of a mesh
+pub struct BvpSolution
+{
+    x: Vec<f64>,
+    y: Vec<Vec<f64>>,
+    yp: Vec<Vec<f64>>,
+}
+
+impl BvpSolution
+{
+    /// Index of the interval containing t, the first or last interval if t
+    /// lies outside the mesh
+    fn interval(&self, t: f64) -> usize
+    {
+        self.x[1..self.x.len() - 1].iter().take_while(|&&s| s <= t).count()
+    }
+
+    /// Value and derivative of the spline at t
+    fn hermite(&self, t: f64) -> (Vec<f64>, Vec<f64>)
+    {
+        let i = self.interval(t);
+        let h = self.x[i + 1] - self.x[i];
+        let s = (t - self.x[i]) / h;
+        let (y0, y1, d0, d1) = (&self.y[i], &self.y[i + 1], &self.yp[i], &self.yp[i + 1]);
+        let s2 = s * s;
+        let s3 = s2 * s;
+        let (h00, h10, h01, h11) = (2f64 * s3 - 3f64 * s2 + 1f64, s3 - 2f64 * s2 + s,
+                                    -2f64 * s3 + 3f64 * s2, s3 - s2);
+        let (g00, g10, g01, g11) = (6f64 * (s2 - s), 3f64 * s2 - 4f64 * s + 1f64,
+                                    6f64 * (s - s2), 3f64 * s2 - 2f64 * s);
+        let n = y0.len();
+        let value = (0..n)
+            .map(|k| h00 * y0[k] + h * h10 * d0[k] + h01 * y1[k] + h * h11 * d1[k])
+            .collect();
+        let slope = (0..n)
+            .map(|k| (g00 * y0[k] + g01 * y1[k]) / h + g10 * d0[k] + g11 * d1[k])
+            .collect();
+        (value, slope)
+    }
+
+    /// Interval covered by the mesh, as (a, b)
+    pub fn span(&self) -> (f64, f64)
+    {
+        (self.x[0], self.x[self.x.len() - 1])
+    }
+
+    /// Solution at t. Outside the mesh the end intervals are extrapolated.
+    pub fn eval(&self, t: f64) -> Array<f64>
+    {
+        Array::new(self.hermite(t).0, Order::Row)
+    }
+
+    /// Derivative of the solution at t
+    pub fn derivative(&self, t: f64) -> Array<f64>
+    {
+        Array::new(self.hermite(t).1, Order::Row)
+    }
+
+    /// Solution at each of the points in t
+    pub fn eval_array(&self, t: &Array<f64>) -> Vec<Array<f64>>
+    {
+        t.as_slice().iter().map(|&ti| self.eval(ti)).collect()
+    }
+}
+
+
+////////////
+// System //
+////////////
+
+/// The differential equations and boundary conditions with their Jacobians
+struct System<'a>
+{
+    fun: &'a Fn(f64, &Array<f64>) -> Array<f64>,
+    bc: &'a Fn(&Array<f64>, &Array<f64>) -> Array<f64>,
+    jac: Option<&'a Fn(f64, &Array<f64>) -> Matrix<f64>>,
+    bc_jac: Option<&'a Fn(&Array<f64>, &Array<f64>) -> (Matrix<f64>, Matrix<f64>)>,
+    n: usize,
+}
+
+/// Forward difference steps for the components of y
+fn fd_steps(y: &[f64]) -> Vec<f64>
+{
+    y.iter().map(|&v| dense::fd_step(v)).collect()
+}
+
+impl<'a> System<'a>
+{
+    fn fun(&self, x: f64, y: &[f64]) -> Vec<f64>
+    {
+        let f = (self.fun)(x, &Array::new(y.to_vec(), Order::Row));
+        if f.len() != self.n
+        {
+            panic!("Right-hand side returned {} values, expected {}", f.len(), self.n);
+        }
+        f.as_slice().to_vec()
+    }
+
+    fn bc(&self, ya: &[f64], yb: &[f64]) -> Vec<f64>
+    {
+        let r = (self.bc)(&Array::new(ya.to_vec(), Order::Row),
+                          &Array::new(yb.to_vec(), Order::Row));
+        if r.len() != self.n
+        {
+            panic!("Boundary conditions returned {} residuals, expected {}", r.len(), self.n);
+        }
+        r.as_slice().to_vec()
+    }
+
+    /// Jacobian of f at (x, y) as a row-major n * n vector, given f there
+    fn jacobian(&self, x: f64, y: &[f64], f: &[f64]) -> Vec<f64>
+    {
+        let n = self.n;
+        if let Some(jac) = self.jac
+        {
+            return dense::row_major(&jac(x, &Array::new(y.to_vec(), Order::Row)), n, n, "Jacobian");
+        }
+        dense::forward_jacobian(|yh| self.fun(x, yh), y, f, &fd_steps(y))
+    }
+
+    /// Jacobians of the boundary conditions with respect to ya and yb,
+    /// given their residuals
+    fn bc_jacobian(&self, ya: &[f64], yb: &[f64], r: &[f64]) -> (Vec<f64>, Vec<f64>)
+    {
+        let n = self.n;
+        if let Some(bc_jac) = self.bc_jac
+        {
+            let (da, db) = bc_jac(&Array::new(ya.to_vec(), Order::Row),
+                                  &Array::new(yb.to_vec(), Order::Row));
+            return (dense::row_major(&da, n, n, "Boundary Jacobian"),
+                    dense::row_major(&db, n, n, "Boundary Jacobian"));
+        }
+        (dense::forward_jacobian(|yh| self.bc(yh, yb), ya, r, &fd_steps(ya)),
+         dense::forward_jacobian(|yh| self.bc(ya, yh), yb, r, &fd_steps(yb)))
+    }
+}
+
+
+///////////////////////////
+// Collocation equations //
+///////////////////////////
+
+/// Residuals of the collocation equations on a mesh
+struct Collocation
+{
+    /// Residual of each interval, y_(i+1) - y_i - h/6 (f_i + 4 f_mid + f_(i+1))
+    res: Vec<Vec<f64>>,
+    /// Solution estimate at each interval midpoint
+    y_middle: Vec<Vec<f64>>,
+    /// Right-hand side at each node
+    f: Vec<Vec<f64>>,
+    /// Right-hand side at each interval midpoint
+    f_middle: Vec<Vec<f64>>,
+}
+
+fn collocation(sys: &System, x: &[f64], h: &[f64], y: &[Vec<f64>]) -> Collocation
+{
+    let n = sys.n;
+    let f: Vec<Vec<f64>> = (0..x.len()).map(|i| sys.fun(x[i], &y[i])).collect();
+    let mut res = Vec::with_capacity(h.len());
+    let mut y_middle = Vec::with_capacity(h.len());
+    let mut f_middle = Vec::with_capacity(h.len());
+    for i in 0..h.len()
+    {
+        let ym: Vec<f64> = (0..n)
+            .map(|k| 0.5 * (y[i + 1][k] + y[i][k]) - 0.125 * h[i] * (f[i + 1][k] - f[i][k]))
+            .collect();
+        let fm = sys.fun(x[i] + 0.5 * h[i], &ym);
+        res.push((0..n)
+            .map(|k| y[i + 1][k] - y[i][k] - h[i] / 6f64 * (f[i][k] + f[i + 1][k] + 4f64 * fm[k]))
+            .collect());
+        y_middle.push(ym);
+        f_middle.push(fm);
+    }
+    Collocation{res: res, y_middle: y_middle, f: f, f_middle: f_middle}
+}
+
+/// Residual vector of the whole system, the collocation residuals interval
+/// by interval followed by the boundary conditions
+fn residual_vector(col: &Collocation, bc: &[f64]) -> Vec<f64>
+{
+    let mut r: Vec<f64> = col.res.iter().flat_map(|v| v.iter().cloned()).collect();
+    r.extend_from_slice(bc);
+    r
+}
+
+/// Row-major Jacobian of the residual vector with respect to the solution at
+/// every node, the unknowns ordered node by node
+fn global_jacobian(sys: &System, x: &[f64], h: &[f64], y: &[Vec<f64>],
+                   col: &Collocation, bc_res: &[f64]) -> Vec<f64>
+{
+    let n = sys.n;
+    let m = x.len();
+    let size = n * m;
+    let mut jac = vec![0f64; size * size];
+    let df: Vec<Vec<f64>> = (0..m).map(|i| sys.jacobian(x[i], &y[i], &col.f[i])).collect();
+    for i in 0..m - 1
+    {
+        let dm = sys.jacobian(x[i] + 0.5 * h[i], &col.y_middle[i], &col.f_middle[i]);
+        let hi = h[i];
+        for r in 0..n
+        {
+            for c in 0..n
+            {
+                // Products of the midpoint Jacobian with the node Jacobians
+                let (mut t0, mut t1) = (0f64, 0f64);
+                for l in 0..n
+                {
+                    t0 += dm[r * n + l] * df[i][l * n + c];
+                    t1 += dm[r * n + l] * df[i + 1][l * n + c];
+                }
+                let delta = if r == c { 1f64 } else { 0f64 };
+                let d0 = -delta - hi / 6f64 * (df[i][r * n + c] + 2f64 * dm[r * n + c])
+                    - hi * hi / 12f64 * t0;
+                let d1 = delta - hi / 6f64 * (df[i + 1][r * n + c] + 2f64 * dm[r * n + c])
+                    + hi * hi / 12f64 * t1;
+                let row = i * n + r;
+                jac[row * size + i * n + c] = d0;
+                jac[row * size + (i + 1) * n + c] = d1;
+            }
+        }
+    }
+    let (da, db) = sys.bc_jacobian(&y[0], &y[m - 1], bc_res);
+    for r in 0..n
+    {
+        let row = (m - 1) * n + r;
+        for c in 0..n
+        {
+            jac[row * size + c] = da[r * n + c];
+            jac[row * size + (m - 1) * n + c] = db[r * n + c];
+        }
+    }
+    jac
+}
+
+/// Damped Newton iterations for the collocation system, reusing the
+/// Jacobian after full steps as in BVP_SOLVER. Returns the new solution
+/// and whether the Jacobian was singular.
+fn solve_newton(sys: &System, x: &[f64], h: &[f64], mut y: Vec<Vec<f64>>,
+                tol: f64, bc_tol: f64) -> (Vec<Vec<f64>>, bool)
+{
+    const MAX_NJEV: usize = 4;
+    const MAX_ITER: usize = 8;
+    const SIGMA: f64 = 0.2;
+    const TAU: f64 = 0.5;
+    const N_TRIAL: usize = 4;
+
+    let n = sys.n;
+    let m = x.len();
+    // Tolerance on the collocation residuals, which are related to the
+    // residuals of the spline at the midpoints by r_middle = 1.5 res / h
+    let tol_r: Vec<f64> = h.iter().map(|hi| 2f64 / 3f64 * hi * 5e-2 * tol).collect();
+
+    let mut col = collocation(sys, x, h, &y);
+    let mut bc_res = sys.bc(&y[0], &y[m - 1]);
+    let mut res = residual_vector(&col, &bc_res);
+    let mut njev = 0;
+    let mut recompute_jac = true;
+    let mut lu = None;
+    let mut step = Vec::new();
+    let mut cost = 0f64;
+    for _ in 0..MAX_ITER
+    {
+        if recompute_jac
+        {
+            let jac = global_jacobian(sys, x, h, &y, &col, &bc_res);
+            njev += 1;
+            let factors = lapacke::lu_factor(&Matrix::new_from_vec(jac, n * m, n * m));
+            if factors.is_singular()
+            {
+                return (y, true);
+            }
+            step = lapacke::lu_solve(&factors, &Array::new(res.clone(), Order::Row))
+                .as_slice().to_vec();
+            cost = step.iter().fold(0f64, |s, v| s + v * v);
+            lu = Some(factors);
+        }
+        let factors = lu.as_ref().unwrap();
+
+        let mut alpha = 1f64;
+        let mut y_new = Vec::new();
+        let mut step_new = Vec::new();
+        let mut cost_new = 0f64;
+        for trial in 0..N_TRIAL + 1
+        {
+            y_new = (0..m)
+                .map(|i| (0..n).map(|k| y[i][k] - alpha * step[i * n + k]).collect())
+                .collect();
+            col = collocation(sys, x, h, &y_new);
+            bc_res = sys.bc(&y_new[0], &y_new[m - 1]);
+            res = residual_vector(&col, &bc_res);
+            step_new = lapacke::lu_solve(factors, &Array::new(res.clone(), Order::Row))
+                .as_slice().to_vec();
+            cost_new = step_new.iter().fold(0f64, |s, v| s + v * v);
+            if cost_new < (1f64 - 2f64 * alpha * SIGMA) * cost
+            {
+                break;
+            }
+            if trial < N_TRIAL
+            {
+                alpha *= TAU;
+            }
+        }
+        y = y_new;
+
+        if njev == MAX_NJEV
+        {
+            break;
+        }
+        let col_ok = (0..m - 1).all(|i| {
+            (0..n).all(|k| col.res[i][k].abs() < tol_r[i] * (1f64 + col.f_middle[i][k].abs()))
+        });
+        if col_ok && bc_res.iter().all(|r| r.abs() < bc_tol)
+        {
+            break;
+        }
+        if alpha == 1f64
+        {
+            step = step_new;
+            cost = cost_new;
+            recompute_jac = false;
+        } else {
+            recompute_jac = true;
+        }
+    }
+    (y, false)
+}
+
+/// Root mean square of the relative residual of the spline over each
+/// interval, by 5 point Lobatto quadrature. r_middle is the residual at the
+/// midpoints, which follows from the collocation residuals.
+fn rms_residuals(sys: &System, sol: &BvpSolution, x: &[f64], h: &[f64],
+                 r_middle: &[Vec<f64>], f_middle: &[Vec<f64>]) -> Vec<f64>
+{
+    let n = sys.n;
+    let relative = |r: &[f64], f: &[f64]| {
+        (0..n).fold(0f64, |s, k| s + (r[k] / (1f64 + f[k].abs())).powi(2))
+    };
+    (0..h.len())
+        .map(|i| {
+            let mid = x[i] + 0.5 * h[i];
+            let s = 0.5 * h[i] * (3f64 / 7f64).sqrt();
+            let mut sum = 0f64;
+            for &xi in [mid + s, mid - s].iter()
+            {
+                let (yi, ypi) = sol.hermite(xi);
+                let fi = sys.fun(xi, &yi);
+                let ri: Vec<f64> = (0..n).map(|k| ypi[k] - fi[k]).collect();
+                sum += relative(&ri, &fi);
+            }
+            let rm = relative(&r_middle[i], &f_middle[i]);
+            (0.5 * (32f64 / 45f64 * rm + 49f64 / 90f64 * sum)).sqrt()
+        })
+        .collect()
+}
+
+
+///////////////
+// solve_bvp //
+///////////////
+
+/// Solves y' = f(x, y) subject to bc(y(a), y(b)) = 0, where bc returns one
+/// residual per component of y. x is the initial mesh, strictly increasing
+/// from a to b, and y the initial guess at each of its nodes.
+/// The mesh is refined until the relative residuals of the solution are
+/// within tol on every interval; a result with a positive status is returned
+/// if that fails.
+pub fn solve_bvp<F, B>(f: F, bc: B, x: &Array<f64>, y: &[Array<f64>],
+                       opts: &BvpOptions) -> BvpResult
+    where F: Fn(f64, &Array<f64>) -> Array<f64>,
+          B: Fn(&Array<f64>, &Array<f64>) -> Array<f64>
+{
+    let mut x = x.as_slice().to_vec();
+    if x.len() < 2
+    {
+        panic!("The mesh needs at least 2 nodes, got {}", x.len());
+    }
+    if x.windows(2).any(|w| !(w[1] > w[0]))
+    {
+        panic!("The mesh must be strictly increasing.");
+    }
+    if y.len() != x.len()
+    {
+        panic!("Initial guess has {} nodes, expected {}", y.len(), x.len());
+    }
+    let n = y[0].len();
+    if n == 0 || y.iter().any(|yi| yi.len() != n)
+    {
+        panic!("Initial guess must have the same non-zero length at every node.");
+    }
+    if opts.max_nodes < x.len()
+    {
+        panic!("Invalid value for max_nodes: {}", opts.max_nodes);
+    }
+    let tol = opts.tol.max(100f64 * f64::EPSILON);
+    let bc_tol = opts.bc_tol.unwrap_or(tol);
+
+    let sys = System{
+        fun: &f,
+        bc: &bc,
+        jac: opts.jac.as_ref().map(|j| &**j),
+        bc_jac: opts.bc_jac.as_ref().map(|j| &**j),
+        n: n,
+    };
+    let mut y: Vec<Vec<f64>> = y.iter().map(|yi| yi.as_slice().to_vec()).collect();
+    let mut iteration = 0;
+    let status;
+    let sol;
+    let rms;
+    loop
+    {
+        let m = x.len();
+        let h: Vec<f64> = x.windows(2).map(|w| w[1] - w[0]).collect();
+        let (y_new, singular) = solve_newton(&sys, &x, &h, y, tol, bc_tol);
+        y = y_new;
+        iteration += 1;
+
+        let col = collocation(&sys, &x, &h, &y);
+        let bc_res = sys.bc(&y[0], &y[m - 1]);
+        let max_bc_res = bc_res.iter().fold(0f64, |mx, r| mx.max(r.abs()));
+        let r_middle: Vec<Vec<f64>> = (0..m - 1)
+            .map(|i| col.res[i].iter().map(|r| 1.5 * r / h[i]).collect())
+            .collect();
+        let spline = BvpSolution{x: x.clone(), y: y.clone(), yp: col.f.clone()};
+        let residuals = rms_residuals(&sys, &spline, &x, &h, &r_middle, &col.f_middle);
+        if singular
+        {
+            status = 2;
+            sol = spline;
+            rms = residuals;
+            break;
+        }
+
+        // One node in moderately inaccurate intervals, two in bad ones
+        let mut added = Vec::new();
+        for i in 0..m - 1
+        {
+            if residuals[i] >= 100f64 * tol
+            {
+                added.push((2f64 * x[i] + x[i + 1]) / 3f64);
+                added.push((x[i] + 2f64 * x[i + 1]) / 3f64);
+            } else if residuals[i] > tol {
+                added.push(0.5 * (x[i] + x[i + 1]));
+            }
+        }
+        if m + added.len() > opts.max_nodes
+        {
+            status = 1;
+        } else if !added.is_empty() {
+            x.extend(added);
+            x.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            y = x.iter().map(|&xi| spline.hermite(xi).0).collect();
+            continue;
+        } else if max_bc_res <= bc_tol {
+            status = 0;
+        } else if iteration >= MAX_ITERATION {
+            status = 3;
+        } else {
+            continue;
+        }
+        sol = spline;
+        rms = residuals;
+        break;
+    }
+
+    let message = match status
+    {
+        0 => String::from("The algorithm converged to the desired accuracy."),
+        1 => String::from("The maximum number of mesh nodes is exceeded."),
+        2 => String::from("A singular Jacobian encountered when solving the collocation system."),
+        _ => format!("The solver was unable to satisfy boundary conditions tolerance on \
+                      iteration {}.", MAX_ITERATION),
+    };
+    BvpResult{
+        x: Array::new(sol.x.clone(), Order::Row),
+        y: sol.y.iter().map(|v| Array::new(v.clone(), Order::Row)).collect(),
+        yp: sol.yp.iter().map(|v| Array::new(v.clone(), Order::Row)).collect(),
+        sol: sol,
+        rms_residuals: Array::new(rms, Order::Row),
+        niter: iteration,
+        status: status,
+        message: message,
+        success: status == 0,
+    }
+}
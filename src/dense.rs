@@ -0,0 +1,129 @@
+/// Small dense helpers on row-major vectors, shared by the solvers that
+/// keep their Jacobians and Hessians as plain vectors between calls into
+/// `lapacke`.
+
+use std::f64;
+use array::Array;
+use array::Order;
+use matrix::Matrix;
+use lapacke;
+
+/// Row-major copy of an m * n matrix, checking its dimensions. name is the
+/// matrix as reported if they are wrong.
+pub fn row_major(a: &Matrix<f64>, m: usize, n: usize, name: &str) -> Vec<f64>
+{
+    if a.get_dims() != (m, n)
+    {
+        panic!("{} has dimensions {:?}, expected ({}, {})", name, a.get_dims(), m, n);
+    }
+    let mut out = vec![0f64; m * n];
+    for r in 0..m
+    {
+        for c in 0..n
+        {
+            out[r * n + c] = *a.get(r, c);
+        }
+    }
+    out
+}
+
+/// Row-major n * n matrix times vector
+pub fn mat_vec(a: &[f64], x: &[f64]) -> Vec<f64>
+{
+    let n = x.len();
+    (0..n).map(|r| (0..n).fold(0f64, |s, c| s + a[r * n + c] * x[c])).collect()
+}
+
+/// Transpose of a row-major n * n matrix times vector
+pub fn mat_t_vec(a: &[f64], x: &[f64]) -> Vec<f64>
+{
+    let n = x.len();
+    (0..n).map(|c| (0..n).fold(0f64, |s, r| s + a[r * n + c] * x[r])).collect()
+}
+
+/// Solution of a x = b for a row-major n * n matrix, None if singular
+pub fn lin_solve(a: &[f64], b: &[f64]) -> Option<Vec<f64>>
+{
+    let n = b.len();
+    let factors = lapacke::lu_factor(&Matrix::new_from_vec(a.to_vec(), n, n));
+    if factors.is_singular()
+    {
+        return None;
+    }
+    let x = lapacke::lu_solve(&factors, &Array::new(b.to_vec(), Order::Row));
+    let x = x.as_slice().to_vec();
+    if x.iter().all(|v| v.is_finite()) { Some(x) } else { None }
+}
+
+/// Inverse of a row-major n * n matrix, None if singular
+pub fn inverse(a: &[f64], n: usize) -> Option<Vec<f64>>
+{
+    let factors = lapacke::lu_factor(&Matrix::new_from_vec(a.to_vec(), n, n));
+    if factors.is_singular()
+    {
+        return None;
+    }
+    let mut inv = vec![0f64; n * n];
+    for c in 0..n
+    {
+        let mut e = vec![0f64; n];
+        e[c] = 1f64;
+        let col = lapacke::lu_solve(&factors, &Array::new(e, Order::Row));
+        for r in 0..n
+        {
+            inv[r * n + c] = col[r];
+        }
+    }
+    if inv.iter().all(|v| v.is_finite()) { Some(inv) } else { None }
+}
+
+/// Forward difference step for the component x
+pub fn fd_step(x: f64) -> f64
+{
+    f64::EPSILON.sqrt() * x.abs().max(1f64)
+}
+
+/// Forward difference step for the component x within [lower, upper]. The
+/// step is reversed if it would cross the upper bound, and shrunk to the
+/// wider side when the interval is narrower than the step.
+pub fn bounded_fd_step(x: f64, lower: f64, upper: f64) -> f64
+{
+    let h = fd_step(x);
+    if x + h <= upper
+    {
+        h
+    } else if x - h >= lower {
+        -h
+    } else if upper - x >= x - lower {
+        upper - x
+    } else {
+        lower - x
+    }
+}
+
+/// Forward difference approximation of the Jacobian of fun at x, given
+/// f = fun(x), as a row-major vector with a row for each component of f.
+/// h is the step for each component of x; a zero step gives a zero column.
+pub fn forward_jacobian<F>(mut fun: F, x: &[f64], f: &[f64], h: &[f64]) -> Vec<f64>
+    where F: FnMut(&[f64]) -> Vec<f64>
+{
+    let (m, n) = (f.len(), x.len());
+    let mut out = vec![0f64; m * n];
+    let mut xh = x.to_vec();
+    for c in 0..n
+    {
+        xh[c] = x[c] + h[c];
+        // The step actually taken, after rounding
+        let h = xh[c] - x[c];
+        if h != 0f64
+        {
+            let fh = fun(&xh);
+            for r in 0..m
+            {
+                out[r * n + c] = (fh[r] - f[r]) / h;
+            }
+        }
+        xh[c] = x[c];
+    }
+    out
+}
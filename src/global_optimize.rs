@@ -0,0 +1,982 @@
+/// Global minimization of functions of several variables, f: R^n -> R, in
+/// the manner of SciPy: differential evolution and dual annealing search a
+/// box-bounded domain, basin hopping perturbs the results of `minimize` and
+/// accepts or rejects them by the Metropolis criterion.
+/// All three draw their random numbers from a xoshiro256** generator seeded
+/// by the `seed` option, so that runs are reproducible; different seeds
+/// explore the domain differently.
+/// For further information see Storn & Price, Differential Evolution - A
+/// Simple and Efficient Heuristic for Global Optimization over Continuous
+/// Spaces, J. Global Optim. 11 (1997), Wales & Doye, Global Optimization by
+/// Basin-Hopping, J. Phys. Chem. A 101 (1997), and Xiang, Sun, Fan & Gong,
+/// Generalized Simulated Annealing Algorithm and its Application to the
+/// Thomson Model, Phys. Lett. A 233 (1997).
+
+use std::f64;
+use array::Array;
+use array::Order;
+use optimize;
+use optimize::Bounds;
+use optimize::Method;
+use optimize::MinimizeOptions;
+use optimize::OptimizeResult;
+
+
+//////////////////////
+// Random generator //
+//////////////////////
+
+/// xoshiro256** generator, with its state seeded through splitmix64
+struct Rng
+{
+    s: [u64; 4],
+}
+
+impl Rng
+{
+    fn new(seed: u64) -> Rng
+    {
+        let mut z = seed;
+        let mut s = [0u64; 4];
+        for v in s.iter_mut()
+        {
+            z = z.wrapping_add(0x9e3779b97f4a7c15);
+            let mut x = z;
+            x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+            x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+            *v = x ^ (x >> 31);
+        }
+        Rng{s: s}
+    }
+
+    fn next(&mut self) -> u64
+    {
+        let result = self.s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = self.s[1] << 17;
+        self.s[2] ^= self.s[0];
+        self.s[3] ^= self.s[1];
+        self.s[1] ^= self.s[2];
+        self.s[0] ^= self.s[3];
+        self.s[2] ^= t;
+        self.s[3] = self.s[3].rotate_left(45);
+        result
+    }
+
+    /// Uniform on [0, 1)
+    fn uniform(&mut self) -> f64
+    {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform on 0..n
+    fn below(&mut self, n: usize) -> usize
+    {
+        ((self.uniform() * n as f64) as usize).min(n - 1)
+    }
+
+    /// Standard normal, by the Box-Muller transform
+    fn normal(&mut self) -> f64
+    {
+        let u = 1f64 - self.uniform();
+        let v = self.uniform();
+        (-2f64 * u.ln()).sqrt() * (2f64 * f64::consts::PI * v).cos()
+    }
+
+    /// Fisher-Yates shuffle
+    fn shuffle<T>(&mut self, v: &mut [T])
+    {
+        for i in (1..v.len()).rev()
+        {
+            let j = self.below(i + 1);
+            v.swap(i, j);
+        }
+    }
+}
+
+
+////////////
+// Common //
+////////////
+
+const SUCCESS: &'static str = "Optimization terminated successfully.";
+const MAXITER: &'static str = "Maximum number of iterations has been exceeded.";
+
+/// Lower and upper bounds on n variables, which must be finite with each
+/// lower bound below its upper bound
+fn finite_bounds(bounds: &Bounds) -> (Vec<f64>, Vec<f64>)
+{
+    let n = bounds.lower.len();
+    if n == 0
+    {
+        panic!("Cannot minimize over no variables.");
+    }
+    if bounds.upper.len() != n
+    {
+        panic!("Bounds have {} components, expected {}", bounds.upper.len(), n);
+    }
+    for i in 0..n
+    {
+        if !(bounds.lower[i].is_finite() && bounds.upper[i].is_finite())
+        {
+            panic!("Bounds for component {} must be finite", i);
+        }
+        if !(bounds.lower[i] < bounds.upper[i])
+        {
+            panic!("Lower bound {} must be less than upper bound {} for component {}",
+                   bounds.lower[i], bounds.upper[i], i);
+        }
+    }
+    (bounds.lower.as_slice().to_vec(), bounds.upper.as_slice().to_vec())
+}
+
+/// f at x, counting evaluations; NaN counts as infinitely bad
+fn evaluate(f: &Fn(&Array<f64>) -> f64, x: &[f64], nfev: &mut usize) -> f64
+{
+    *nfev += 1;
+    let v = f(&Array::new(x.to_vec(), Order::Row));
+    if v.is_nan() { f64::INFINITY } else { v }
+}
+
+/// Local minimization by L-BFGS-B within the bounds, from x
+fn local_minimize(f: &Fn(&Array<f64>) -> f64, x: &[f64], lower: &[f64], upper: &[f64],
+                  maxiter: Option<usize>) -> OptimizeResult
+{
+    let bounds = Bounds::new(Array::new(lower.to_vec(), Order::Row),
+                             Array::new(upper.to_vec(), Order::Row));
+    let opts = MinimizeOptions{method: Method::LBFGSB,
+                               bounds: Some(bounds),
+                               maxiter: maxiter,
+                               ..MinimizeOptions::default()};
+    optimize::minimize(|x: &Array<f64>| f(x), &Array::new(x.to_vec(), Order::Row), &opts)
+}
+
+
+////////////////////////////
+// Differential evolution //
+////////////////////////////
+
+/// Differential evolution strategy: how the mutant vector is formed, and
+/// whether crossover with the candidate is binomial or exponential
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Strategy
+{
+    /// best + F (r0 - r1), binomial crossover
+    Best1Bin,
+    /// best + F (r0 - r1), exponential crossover
+    Best1Exp,
+    /// r0 + F (r1 - r2), binomial crossover
+    Rand1Bin,
+    /// r0 + F (r1 - r2), exponential crossover
+    Rand1Exp,
+    /// r0 + F (best - r0) + F (r1 - r2), binomial crossover
+    RandToBest1Bin,
+    /// r0 + F (best - r0) + F (r1 - r2), exponential crossover
+    RandToBest1Exp,
+    /// x + F (best - x) + F (r0 - r1) for the candidate x, binomial
+    /// crossover
+    CurrentToBest1Bin,
+    /// x + F (best - x) + F (r0 - r1) for the candidate x, exponential
+    /// crossover
+    CurrentToBest1Exp,
+    /// best + F (r0 + r1 - r2 - r3), binomial crossover
+    Best2Bin,
+    /// best + F (r0 + r1 - r2 - r3), exponential crossover
+    Best2Exp,
+    /// r0 + F (r1 + r2 - r3 - r4), binomial crossover
+    Rand2Bin,
+    /// r0 + F (r1 + r2 - r3 - r4), exponential crossover
+    Rand2Exp,
+}
+
+impl Strategy
+{
+    fn binomial(&self) -> bool
+    {
+        match *self
+        {
+            Strategy::Best1Bin | Strategy::Rand1Bin | Strategy::RandToBest1Bin
+                | Strategy::CurrentToBest1Bin | Strategy::Best2Bin
+                | Strategy::Rand2Bin => true,
+            _ => false,
+        }
+    }
+
+    /// Mutant vector from the population, the candidate c, the random
+    /// members r other than c and the differential weight
+    fn mutant(&self, pop: &[Vec<f64>], c: usize, r: &[usize], scale: f64) -> Vec<f64>
+    {
+        let n = pop[0].len();
+        (0..n).map(|j|
+        {
+            let best = pop[0][j];
+            let x = |k: usize| pop[r[k]][j];
+            match *self
+            {
+                Strategy::Best1Bin | Strategy::Best1Exp => best + scale * (x(0) - x(1)),
+                Strategy::Rand1Bin | Strategy::Rand1Exp => x(0) + scale * (x(1) - x(2)),
+                Strategy::RandToBest1Bin | Strategy::RandToBest1Exp =>
+                {
+                    x(0) + scale * (best - x(0)) + scale * (x(1) - x(2))
+                },
+                Strategy::CurrentToBest1Bin | Strategy::CurrentToBest1Exp =>
+                {
+                    pop[c][j] + scale * (best - pop[c][j]) + scale * (x(0) - x(1))
+                },
+                Strategy::Best2Bin | Strategy::Best2Exp =>
+                {
+                    best + scale * (x(0) + x(1) - x(2) - x(3))
+                },
+                Strategy::Rand2Bin | Strategy::Rand2Exp =>
+                {
+                    x(0) + scale * (x(1) + x(2) - x(3) - x(4))
+                },
+            }
+        }).collect()
+    }
+}
+
+/// Options for `differential_evolution`
+pub struct DifferentialEvolutionOptions
+{
+    /// Mutation and crossover strategy, Best1Bin by default
+    pub strategy: Strategy,
+    /// Maximum number of generations, 1000 by default
+    pub maxiter: usize,
+    /// The population has popsize * n members for n variables, but no fewer
+    /// than six; 15 by default
+    pub popsize: usize,
+    /// The method stops once the standard deviation of the function values
+    /// of the population is below atol + tol |mean|; tol is 0.01 and atol
+    /// 0 by default
+    pub tol: f64,
+    pub atol: f64,
+    /// Range of the differential weight F, drawn anew for each generation
+    /// (dithering); equal values fix it. (0.5, 1) by default
+    pub mutation: (f64, f64),
+    /// Crossover probability, 0.7 by default
+    pub recombination: f64,
+    /// Seed of the random generator, 0 by default
+    pub seed: u64,
+    /// Whether to refine the best member by L-BFGS-B at the end, true by
+    /// default
+    pub polish: bool,
+}
+
+impl Default for DifferentialEvolutionOptions
+{
+    fn default() -> DifferentialEvolutionOptions
+    {
+        DifferentialEvolutionOptions{
+            strategy: Strategy::Best1Bin,
+            maxiter: 1000,
+            popsize: 15,
+            tol: 0.01,
+            atol: 0f64,
+            mutation: (0.5, 1f64),
+            recombination: 0.7,
+            seed: 0,
+            polish: true,
+        }
+    }
+}
+
+/// Finds the global minimum of f within the bounds, which must be finite,
+/// by differential evolution with a Latin hypercube initial population.
+/// The population is kept in coordinates scaled to the unit cube; trial
+/// components leaving it are redrawn at random.
+pub fn differential_evolution<F>(f: F, bounds: &Bounds, opts: &DifferentialEvolutionOptions)
+    -> OptimizeResult
+    where F: Fn(&Array<f64>) -> f64
+{
+    let (lower, upper) = finite_bounds(bounds);
+    let n = lower.len();
+    let (m0, m1) = opts.mutation;
+    if !(0f64 <= m0 && m0 <= m1 && m1 <= 2f64)
+    {
+        panic!("Invalid value for mutation");
+    }
+    if !(0f64 <= opts.recombination && opts.recombination <= 1f64)
+    {
+        panic!("Invalid value for recombination");
+    }
+    if !(opts.tol >= 0f64 && opts.atol >= 0f64)
+    {
+        panic!("Invalid value for tol/atol");
+    }
+    let f: &Fn(&Array<f64>) -> f64 = &f;
+    let mut rng = Rng::new(opts.seed);
+    let np = (opts.popsize * n).max(6);
+    let scale_params = |p: &[f64]| -> Vec<f64>
+    {
+        (0..n).map(|j| lower[j] + p[j] * (upper[j] - lower[j])).collect()
+    };
+
+    // Latin hypercube: one member in each of np slices of every coordinate
+    let mut pop = vec![vec![0f64; n]; np];
+    for j in 0..n
+    {
+        let mut order: Vec<usize> = (0..np).collect();
+        rng.shuffle(&mut order);
+        for i in 0..np
+        {
+            pop[order[i]][j] = (i as f64 + rng.uniform()) / np as f64;
+        }
+    }
+    let mut nfev = 0;
+    let mut energies: Vec<f64> = pop.iter()
+        .map(|p| evaluate(f, &scale_params(p), &mut nfev))
+        .collect();
+    let best = (0..np).fold(0, |b, i| if energies[i] < energies[b] { i } else { b });
+    pop.swap(0, best);
+    energies.swap(0, best);
+
+    let samples = match opts.strategy
+    {
+        Strategy::Best1Bin | Strategy::Best1Exp
+            | Strategy::CurrentToBest1Bin | Strategy::CurrentToBest1Exp => 2,
+        Strategy::Rand1Bin | Strategy::Rand1Exp
+            | Strategy::RandToBest1Bin | Strategy::RandToBest1Exp => 3,
+        Strategy::Best2Bin | Strategy::Best2Exp => 4,
+        Strategy::Rand2Bin | Strategy::Rand2Exp => 5,
+    };
+    let mut nit = 0;
+    let mut converged = false;
+    while nit < opts.maxiter
+    {
+        nit += 1;
+        let scale = if m0 < m1 { m0 + rng.uniform() * (m1 - m0) } else { m0 };
+        for c in 0..np
+        {
+            let mut others: Vec<usize> = (0..np).filter(|&i| i != c).collect();
+            rng.shuffle(&mut others);
+            let mutant = opts.strategy.mutant(&pop, c, &others[..samples], scale);
+
+            // Crossover, always taking at least one component of the mutant
+            let mut trial = pop[c].clone();
+            let mut fill = rng.below(n);
+            if opts.strategy.binomial()
+            {
+                for j in 0..n
+                {
+                    if j == fill || rng.uniform() < opts.recombination
+                    {
+                        trial[j] = mutant[j];
+                    }
+                }
+            } else {
+                let mut i = 0;
+                while i < n && (i == 0 || rng.uniform() < opts.recombination)
+                {
+                    trial[fill] = mutant[fill];
+                    fill = (fill + 1) % n;
+                    i += 1;
+                }
+            }
+            for j in 0..n
+            {
+                if !(0f64 <= trial[j] && trial[j] <= 1f64)
+                {
+                    trial[j] = rng.uniform();
+                }
+            }
+
+            let energy = evaluate(f, &scale_params(&trial), &mut nfev);
+            if energy <= energies[c]
+            {
+                pop[c] = trial;
+                energies[c] = energy;
+                if energy < energies[0]
+                {
+                    pop.swap(0, c);
+                    energies.swap(0, c);
+                }
+            }
+        }
+
+        let mean = energies.iter().fold(0f64, |s, e| s + e) / np as f64;
+        let var = energies.iter().fold(0f64, |s, e| s + (e - mean).powi(2)) / np as f64;
+        if var.sqrt() <= opts.atol + opts.tol * mean.abs()
+        {
+            converged = true;
+            break;
+        }
+    }
+
+    let mut x = scale_params(&pop[0]);
+    let mut fun = energies[0];
+    let mut jac = None;
+    let mut njev = 0;
+    if opts.polish
+    {
+        let res = local_minimize(f, &x, &lower, &upper, None);
+        nfev += res.nfev;
+        njev += res.njev;
+        if res.fun < fun
+        {
+            x = res.x.as_slice().to_vec();
+            fun = res.fun;
+            jac = res.jac;
+        }
+    }
+    OptimizeResult{
+        x: Array::new(x, Order::Row),
+        fun: fun,
+        jac: jac,
+        nit: nit,
+        nfev: nfev,
+        njev: njev,
+        nhev: 0,
+        status: if converged { 0 } else { 1 },
+        success: converged,
+        message: (if converged { SUCCESS } else { MAXITER }).to_string(),
+    }
+}
+
+
+///////////////////
+// Basin hopping //
+///////////////////
+
+/// Options for `basin_hopping`
+pub struct BasinHoppingOptions<'a>
+{
+    /// Number of hops, 100 by default
+    pub niter: usize,
+    /// Temperature T of the Metropolis criterion, accepting a rise dE of
+    /// the local minimum with probability exp(-dE / T); 1 by default
+    pub temperature: f64,
+    /// Initial largest displacement of each component per hop, 0.5 by
+    /// default
+    pub stepsize: f64,
+    /// Every interval hops the step size is adjusted towards an acceptance
+    /// rate of one half; 50 by default
+    pub interval: usize,
+    /// Stop once the global minimum has not improved for this many hops
+    pub niter_success: Option<usize>,
+    /// Seed of the random generator, 0 by default
+    pub seed: u64,
+    /// Options of the local minimizations. Hops are clipped to bounds given
+    /// here.
+    pub minimizer: MinimizeOptions<'a>,
+}
+
+impl<'a> Default for BasinHoppingOptions<'a>
+{
+    fn default() -> BasinHoppingOptions<'a>
+    {
+        BasinHoppingOptions{
+            niter: 100,
+            temperature: 1f64,
+            stepsize: 0.5,
+            interval: 50,
+            niter_success: None,
+            seed: 0,
+            minimizer: MinimizeOptions::default(),
+        }
+    }
+}
+
+/// Finds the global minimum of f by basin hopping from x0: each hop
+/// displaces the current local minimum uniformly at random, minimizes
+/// locally from there, and accepts the new minimum by the Metropolis
+/// criterion. Returns the lowest local minimization result, with the
+/// evaluations of all hops counted.
+pub fn basin_hopping<F>(f: F, x0: &Array<f64>, opts: &BasinHoppingOptions) -> OptimizeResult
+    where F: Fn(&Array<f64>) -> f64
+{
+    if !(opts.temperature >= 0f64)
+    {
+        panic!("Invalid value for temperature");
+    }
+    if !(opts.stepsize > 0f64) || opts.interval == 0
+    {
+        panic!("Invalid value for stepsize/interval");
+    }
+    let n = x0.len();
+    let mut rng = Rng::new(opts.seed);
+    let mut res = optimize::minimize(&f, x0, &opts.minimizer);
+    let (mut nfev, mut njev, mut nhev) = (res.nfev, res.njev, res.nhev);
+    let mut best = res.clone();
+    let mut stepsize = opts.stepsize;
+    let mut naccept = 0;
+    let mut count = 0;
+    let mut nit = 0;
+    let mut message = "requested number of basinhopping iterations completed successfully";
+    while nit < opts.niter
+    {
+        nit += 1;
+        if nit % opts.interval == 0
+        {
+            // Take larger steps if too many are accepted, smaller otherwise
+            if naccept as f64 / nit as f64 > 0.5
+            {
+                stepsize /= 0.9;
+            } else {
+                stepsize *= 0.9;
+            }
+        }
+        let mut x: Vec<f64> = (0..n)
+            .map(|i| res.x[i] + stepsize * (2f64 * rng.uniform() - 1f64))
+            .collect();
+        if let Some(ref b) = opts.minimizer.bounds
+        {
+            for i in 0..n
+            {
+                x[i] = x[i].max(b.lower[i]).min(b.upper[i]);
+            }
+        }
+        let trial = optimize::minimize(&f, &Array::new(x, Order::Row), &opts.minimizer);
+        nfev += trial.nfev;
+        njev += trial.njev;
+        nhev += trial.nhev;
+
+        // Metropolis criterion, not trading a converged minimum for one
+        // which is not
+        let w = if opts.temperature > 0f64
+        {
+            (-(trial.fun - res.fun) / opts.temperature).min(0f64).exp()
+        } else if trial.fun < res.fun {
+            1f64
+        } else {
+            0f64
+        };
+        let accept = w >= rng.uniform() && (trial.success || !res.success);
+        count += 1;
+        if accept
+        {
+            naccept += 1;
+            res = trial;
+            if res.fun < best.fun
+            {
+                best = res.clone();
+                count = 0;
+            }
+        }
+        if let Some(limit) = opts.niter_success
+        {
+            if count > limit
+            {
+                message = "success condition satisfied";
+                break;
+            }
+        }
+    }
+    OptimizeResult{
+        nit: nit,
+        nfev: nfev,
+        njev: njev,
+        nhev: nhev,
+        message: if best.success { message.to_string() } else { best.message.clone() },
+        ..best
+    }
+}
+
+
+////////////////////
+// Dual annealing //
+////////////////////
+
+/// Options for `dual_annealing`
+pub struct DualAnnealingOptions
+{
+    /// Number of annealing iterations, 1000 by default
+    pub maxiter: usize,
+    /// Initial temperature, in (0.01, 5e4]; 5230 by default
+    pub initial_temp: f64,
+    /// The annealing restarts from a random point once the temperature has
+    /// fallen to this fraction of the initial one, in (0, 1); 2e-5 by
+    /// default
+    pub restart_temp_ratio: f64,
+    /// Parameter q_v of the visiting distribution, in (1, 3]; larger values
+    /// give it heavier tails. 2.62 by default
+    pub visit: f64,
+    /// Parameter q_a of the acceptance distribution, in (-1e4, -5]; smaller
+    /// values accept fewer rises. -5 by default
+    pub accept: f64,
+    /// Maximum number of evaluations of f, 1e7 by default
+    pub maxfun: usize,
+    /// Skip the local searches with L-BFGS-B, giving plain generalized
+    /// simulated annealing; false by default
+    pub no_local_search: bool,
+    /// Starting point, drawn at random within the bounds if None
+    pub x0: Option<Array<f64>>,
+    /// Seed of the random generator, 0 by default
+    pub seed: u64,
+}
+
+impl Default for DualAnnealingOptions
+{
+    fn default() -> DualAnnealingOptions
+    {
+        DualAnnealingOptions{
+            maxiter: 1000,
+            initial_temp: 5230f64,
+            restart_temp_ratio: 2e-5,
+            visit: 2.62,
+            accept: -5f64,
+            maxfun: 10000000,
+            no_local_search: false,
+            x0: None,
+            seed: 0,
+        }
+    }
+}
+
+/// Log of the absolute value of the Gamma function, by the Lanczos
+/// approximation with g = 7 and the reflection formula below 1/2
+fn ln_gamma(x: f64) -> f64
+{
+    const LANCZOS: [f64; 9] = [0.99999999999980993, 676.5203681218851, -1259.1392167224028,
+                               771.32342877765313, -176.61502916214059, 12.507343278686905,
+                               -0.13857109526572012, 9.9843695780195716e-6,
+                               1.5056327351493116e-7];
+    let pi = f64::consts::PI;
+    if x < 0.5
+    {
+        return (pi / (pi * x).sin().abs()).ln() - ln_gamma(1f64 - x);
+    }
+    let x = x - 1f64;
+    let t = x + 7.5;
+    let a = (1..9).fold(LANCZOS[0], |a, i| a + LANCZOS[i] / (x + i as f64));
+    0.5 * (2f64 * pi).ln() + (x + 0.5) * t.ln() - t + a.ln()
+}
+
+/// Largest magnitude of a visiting step
+const TAIL_LIMIT: f64 = 1e8;
+/// Points this close to a lower bound are moved off it
+const MIN_VISIT_BOUND: f64 = 1e-10;
+/// Random restarts tried while f is not finite at the starting point
+const MAX_REINIT: usize = 1000;
+
+/// State of the generalized simulated annealing with local searches
+struct Annealer<'a>
+{
+    fun: &'a Fn(&Array<f64>) -> f64,
+    lower: Vec<f64>,
+    upper: Vec<f64>,
+    qv: f64,
+    qa: f64,
+    /// Constants of the visiting distribution
+    factor4_p: f64,
+    factor6: f64,
+    rng: Rng,
+    nfev: usize,
+    maxfun: usize,
+    local_maxiter: usize,
+    current: (Vec<f64>, f64),
+    best: (Vec<f64>, f64),
+    /// Location for a local search when there has been no improvement
+    min: (Vec<f64>, f64),
+    improved: bool,
+    not_improved: usize,
+    not_improved_max: usize,
+    temperature_step: f64,
+}
+
+impl<'a> Annealer<'a>
+{
+    fn eval(&mut self, x: &[f64]) -> f64
+    {
+        evaluate(self.fun, x, &mut self.nfev)
+    }
+
+    fn random_point(&mut self) -> Vec<f64>
+    {
+        let n = self.lower.len();
+        (0..n).map(|i| self.lower[i] + self.rng.uniform() * (self.upper[i] - self.lower[i]))
+            .collect()
+    }
+
+    /// Moves the current location to x, or a random point if f is not
+    /// finite there
+    fn reset(&mut self, x: Option<Vec<f64>>)
+    {
+        let mut x = match x
+        {
+            Some(x) => x,
+            None => self.random_point(),
+        };
+        let mut e = self.eval(&x);
+        let mut tries = 0;
+        while !e.is_finite()
+        {
+            if tries == MAX_REINIT
+            {
+                panic!("Function values are not finite even at {} random points", MAX_REINIT);
+            }
+            x = self.random_point();
+            e = self.eval(&x);
+            tries += 1;
+        }
+        if e < self.best.1
+        {
+            self.best = (x.clone(), e);
+        }
+        self.current = (x, e);
+    }
+
+    /// Step from the Tsallis visiting distribution at the temperature
+    fn visit_step(&mut self, temperature: f64) -> f64
+    {
+        let qv = self.qv;
+        let factor1 = (temperature.ln() / (qv - 1f64)).exp();
+        let factor4 = self.factor4_p * factor1;
+        let sigma = (-(qv - 1f64) * (self.factor6 / factor4).ln() / (3f64 - qv)).exp();
+        let x = self.rng.normal() * sigma;
+        let y = self.rng.normal();
+        let den = ((qv - 1f64) * y.abs().ln() / (3f64 - qv)).exp();
+        let v = x / den;
+        if v > TAIL_LIMIT
+        {
+            TAIL_LIMIT * self.rng.uniform()
+        } else if v < -TAIL_LIMIT {
+            -TAIL_LIMIT * self.rng.uniform()
+        } else if v.is_finite() {
+            v
+        } else {
+            0f64
+        }
+    }
+
+    /// Candidate for step j of a chain: the first n steps move every
+    /// component, the next n one component each, wrapping around the bounds
+    fn visit(&mut self, j: usize, temperature: f64) -> Vec<f64>
+    {
+        let n = self.lower.len();
+        let mut x = self.current.0.clone();
+        let components: Vec<usize> = if j < n { (0..n).collect() } else { vec![j - n] };
+        for i in components
+        {
+            let range = self.upper[i] - self.lower[i];
+            let v = x[i] + self.visit_step(temperature);
+            let a = (v - self.lower[i]) % range + range;
+            x[i] = a % range + self.lower[i];
+            if (x[i] - self.lower[i]).abs() < MIN_VISIT_BOUND
+            {
+                x[i] += 1e-10;
+            }
+        }
+        x
+    }
+
+    /// Markov chain of 2n visits at the temperature of iteration `step`.
+    /// Returns true if the evaluation limit was reached.
+    fn chain(&mut self, step: usize, temperature: f64) -> bool
+    {
+        let n = self.lower.len();
+        self.temperature_step = temperature / (step + 1) as f64;
+        self.not_improved += 1;
+        for j in 0..2 * n
+        {
+            if j == 0
+            {
+                self.improved = step == 0;
+            }
+            let x = self.visit(j, temperature);
+            let e = self.eval(&x);
+            if e < self.current.1
+            {
+                self.current = (x.clone(), e);
+                if e < self.best.1
+                {
+                    self.best = (x, e);
+                    self.improved = true;
+                    self.not_improved = 0;
+                }
+            } else {
+                // Acceptance by the generalized Metropolis criterion
+                let r = self.rng.uniform();
+                let p = 1f64 - (1f64 - self.qa) * (e - self.current.1) / self.temperature_step;
+                let p = if p <= 0f64 { 0f64 } else { (p.ln() / (1f64 - self.qa)).exp() };
+                if r <= p
+                {
+                    self.current = (x, e);
+                    self.min = self.current.clone();
+                }
+                if self.not_improved >= self.not_improved_max
+                    && (j == 0 || self.current.1 < self.min.1)
+                {
+                    self.min = self.current.clone();
+                }
+            }
+            if self.nfev >= self.maxfun
+            {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// L-BFGS-B from x, keeping x if the result is not better
+    fn local_search(&mut self, x: &[f64], e: f64) -> (Vec<f64>, f64)
+    {
+        let res = local_minimize(self.fun, x, &self.lower, &self.upper,
+                                 Some(self.local_maxiter));
+        self.nfev += res.nfev;
+        if res.fun < e { (res.x.as_slice().to_vec(), res.fun) } else { (x.to_vec(), e) }
+    }
+
+    /// Local search from the best point if the chain improved it, or from
+    /// the chain's minimum after a long time without improvement. Returns
+    /// true if the evaluation limit was reached.
+    fn search(&mut self) -> bool
+    {
+        if self.improved
+        {
+            let (x, e) = self.best.clone();
+            let (x, e) = self.local_search(&x, e);
+            if e < self.best.1
+            {
+                self.not_improved = 0;
+                self.best = (x.clone(), e);
+                self.current = (x, e);
+            }
+            if self.nfev >= self.maxfun
+            {
+                return true;
+            }
+        }
+        if self.not_improved >= self.not_improved_max
+        {
+            let (x, e) = self.min.clone();
+            let (x, e) = self.local_search(&x, e);
+            self.min = (x.clone(), e);
+            self.not_improved = 0;
+            self.not_improved_max = self.lower.len();
+            if e < self.best.1
+            {
+                self.best = (x.clone(), e);
+                self.current = (x, e);
+            }
+            if self.nfev >= self.maxfun
+            {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// Finds the global minimum of f within the bounds, which must be finite,
+/// by dual annealing: generalized simulated annealing with the Tsallis
+/// visiting distribution, restarted as the temperature falls, combined with
+/// local searches by L-BFGS-B. The annealing runs for all maxiter
+/// iterations; only reaching maxfun counts as failure.
+pub fn dual_annealing<F>(f: F, bounds: &Bounds, opts: &DualAnnealingOptions) -> OptimizeResult
+    where F: Fn(&Array<f64>) -> f64
+{
+    let (lower, upper) = finite_bounds(bounds);
+    let n = lower.len();
+    let qv = opts.visit;
+    if !(1f64 < qv && qv <= 3f64)
+    {
+        panic!("Invalid value for visit");
+    }
+    if !(-1e4 < opts.accept && opts.accept <= -5f64)
+    {
+        panic!("Invalid value for accept");
+    }
+    if !(0.01 < opts.initial_temp && opts.initial_temp <= 5e4)
+    {
+        panic!("Invalid value for initial_temp");
+    }
+    if !(0f64 < opts.restart_temp_ratio && opts.restart_temp_ratio < 1f64)
+    {
+        panic!("Invalid value for restart_temp_ratio");
+    }
+    let x0 = opts.x0.as_ref().map(|x0|
+    {
+        if x0.len() != n
+        {
+            panic!("x0 has {} components, expected {}", x0.len(), n);
+        }
+        let x0 = x0.as_slice().to_vec();
+        if (0..n).any(|i| !(lower[i] <= x0[i] && x0[i] <= upper[i]))
+        {
+            panic!("x0 is outside the bounds");
+        }
+        x0
+    });
+
+    let pi = f64::consts::PI;
+    let factor2 = ((4f64 - qv) * (qv - 1f64).ln()).exp();
+    let factor3 = ((2f64 - qv) * 2f64.ln() / (qv - 1f64)).exp();
+    let factor5 = 1f64 / (qv - 1f64) - 0.5;
+    let f: &Fn(&Array<f64>) -> f64 = &f;
+    let mut state = Annealer{
+        fun: f,
+        lower: lower,
+        upper: upper,
+        qv: qv,
+        qa: opts.accept,
+        factor4_p: pi.sqrt() * factor2 / (factor3 * (3f64 - qv)),
+        factor6: pi * (1f64 - factor5) / (pi * (1f64 - factor5)).sin()
+            / ln_gamma(2f64 - factor5).exp(),
+        rng: Rng::new(opts.seed),
+        nfev: 0,
+        maxfun: opts.maxfun,
+        local_maxiter: (6 * n).max(100).min(1000),
+        current: (vec![], f64::INFINITY),
+        best: (vec![], f64::INFINITY),
+        min: (vec![], f64::INFINITY),
+        improved: false,
+        not_improved: 0,
+        not_improved_max: 1000,
+        temperature_step: 0f64,
+    };
+    state.reset(x0);
+    state.min = state.current.clone();
+
+    let restart_temp = opts.initial_temp * opts.restart_temp_ratio;
+    let t1 = ((qv - 1f64) * 2f64.ln()).exp() - 1f64;
+    let mut nit = 0;
+    let status;
+    let message;
+    'anneal: loop
+    {
+        let mut i = 0;
+        loop
+        {
+            if nit >= opts.maxiter
+            {
+                status = 0;
+                message = "Maximum number of iteration reached";
+                break 'anneal;
+            }
+            let t2 = ((qv - 1f64) * (i as f64 + 2f64).ln()).exp() - 1f64;
+            let temperature = opts.initial_temp * t1 / t2;
+            if temperature < restart_temp
+            {
+                state.reset(None);
+                break;
+            }
+            if state.chain(i, temperature)
+            {
+                status = 1;
+                message = "Maximum number of function call reached during annealing";
+                break 'anneal;
+            }
+            if !opts.no_local_search && state.search()
+            {
+                status = 1;
+                message = "Maximum number of function call reached during local search";
+                break 'anneal;
+            }
+            nit += 1;
+            i += 1;
+        }
+    }
+    let (x, fun) = state.best;
+    OptimizeResult{
+        x: Array::new(x, Order::Row),
+        fun: fun,
+        jac: None,
+        nit: nit,
+        nfev: state.nfev,
+        njev: 0,
+        nhev: 0,
+        status: status,
+        success: status == 0,
+        message: message.to_string(),
+    }
+}
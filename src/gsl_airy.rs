@@ -86,10 +86,16 @@ extern
 
 /// Compute the Airy function Ai(x), returning the value and estimated error
 pub fn airy_ai(x: f64) -> (f64, f64)
+{
+    airy_ai_mode(x, gsl_sf::Precision::Double)
+}
+
+/// Compute the Airy function Ai(x) with an accuracy specified by mode
+pub fn airy_ai_mode(x: f64, mode: gsl_sf::Precision) -> (f64, f64)
 {
     let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
     unsafe {
-        if gsl_sf_airy_Ai_e(x, gsl_sf::GSL_PREC_DOUBLE, &mut s) != 0
+        if gsl_sf_airy_Ai_e(x, mode.as_mode(), &mut s) != 0
         {
             panic!("Airy function calculation failed");
         }
@@ -99,10 +105,16 @@ pub fn airy_ai(x: f64) -> (f64, f64)
 
 /// Compute the Airy function Bi(x), returning the value and estimated error
 pub fn airy_bi(x: f64) -> (f64, f64)
+{
+    airy_bi_mode(x, gsl_sf::Precision::Double)
+}
+
+/// Compute the Airy function Bi(x) with an accuracy specified by mode
+pub fn airy_bi_mode(x: f64, mode: gsl_sf::Precision) -> (f64, f64)
 {
     let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
     unsafe {
-        if gsl_sf_airy_Bi_e(x, gsl_sf::GSL_PREC_DOUBLE, &mut s) != 0
+        if gsl_sf_airy_Bi_e(x, mode.as_mode(), &mut s) != 0
         {
             panic!("Airy function calculation failed");
         }
@@ -113,10 +125,17 @@ pub fn airy_bi(x: f64) -> (f64, f64)
 /// Compute a scaled version of the Airy function S_A(x) Ai(x).
 /// For x>0 the scaling factor S_A(x) is exp(+(2/3) x^(3/2)), 1 for x<0. 
 pub fn airy_ai_scaled(x: f64) -> (f64, f64)
+{
+    airy_ai_scaled_mode(x, gsl_sf::Precision::Double)
+}
+
+/// Compute the scaled Airy function S_A(x) Ai(x) with an accuracy specified
+/// by mode
+pub fn airy_ai_scaled_mode(x: f64, mode: gsl_sf::Precision) -> (f64, f64)
 {
     let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
     unsafe {
-        if gsl_sf_airy_Ai_scaled_e(x, gsl_sf::GSL_PREC_DOUBLE, &mut s) != 0
+        if gsl_sf_airy_Ai_scaled_e(x, mode.as_mode(), &mut s) != 0
         {
             panic!("Airy function calculation failed");
         }
@@ -127,10 +146,17 @@ pub fn airy_ai_scaled(x: f64) -> (f64, f64)
 /// Compute a scaled version of the Airy function S_B(x) Bi(x).
 /// For x>0 the scaling factor S_B(x) is exp(-(2/3) x^(3/2)), and is 1 for x<0.
 pub fn airy_bi_scaled(x: f64) -> (f64, f64)
+{
+    airy_bi_scaled_mode(x, gsl_sf::Precision::Double)
+}
+
+/// Compute the scaled Airy function S_B(x) Bi(x) with an accuracy specified
+/// by mode
+pub fn airy_bi_scaled_mode(x: f64, mode: gsl_sf::Precision) -> (f64, f64)
 {
     let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
     unsafe {
-        if gsl_sf_airy_Bi_scaled_e(x, gsl_sf::GSL_PREC_DOUBLE, &mut s) != 0
+        if gsl_sf_airy_Bi_scaled_e(x, mode.as_mode(), &mut s) != 0
         {
             panic!("Airy function calculation failed");
         }
@@ -140,10 +166,17 @@ pub fn airy_bi_scaled(x: f64) -> (f64, f64)
 
 /// Compute the Airy function derivative Ai'(x)
 pub fn airy_ai_deriv(x: f64) -> (f64, f64)
+{
+    airy_ai_deriv_mode(x, gsl_sf::Precision::Double)
+}
+
+/// Compute the Airy function derivative Ai'(x) with an accuracy specified by
+/// mode
+pub fn airy_ai_deriv_mode(x: f64, mode: gsl_sf::Precision) -> (f64, f64)
 {
     let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
     unsafe {
-        if gsl_sf_airy_Ai_deriv_e(x, gsl_sf::GSL_PREC_DOUBLE, &mut s) != 0
+        if gsl_sf_airy_Ai_deriv_e(x, mode.as_mode(), &mut s) != 0
         {
             panic!("Airy derivative function calculation failed");
         }
@@ -153,10 +186,17 @@ pub fn airy_ai_deriv(x: f64) -> (f64, f64)
 
 /// Compute the Airy function derivative Bi'(x)
 pub fn airy_bi_deriv(x: f64) -> (f64, f64)
+{
+    airy_bi_deriv_mode(x, gsl_sf::Precision::Double)
+}
+
+/// Compute the Airy function derivative Bi'(x) with an accuracy specified by
+/// mode
+pub fn airy_bi_deriv_mode(x: f64, mode: gsl_sf::Precision) -> (f64, f64)
 {
     let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
     unsafe {
-        if gsl_sf_airy_Bi_deriv_e(x, gsl_sf::GSL_PREC_DOUBLE, &mut s) != 0
+        if gsl_sf_airy_Bi_deriv_e(x, mode.as_mode(), &mut s) != 0
         {
             panic!("Airy derivative function calculation failed");
         }
@@ -167,10 +207,17 @@ pub fn airy_bi_deriv(x: f64) -> (f64, f64)
 /// Compute the scaled Airy function derivative S_A(x) Ai'(x). For x>0 the
 /// scaling factor S_A(x) is exp(+(2/3) x^(3/2)), and is 1 for x<0.
 pub fn airy_ai_deriv_scaled(x: f64) -> (f64, f64)
+{
+    airy_ai_deriv_scaled_mode(x, gsl_sf::Precision::Double)
+}
+
+/// Compute the scaled Airy function derivative S_A(x) Ai'(x) with an
+/// accuracy specified by mode
+pub fn airy_ai_deriv_scaled_mode(x: f64, mode: gsl_sf::Precision) -> (f64, f64)
 {
     let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
     unsafe {
-        if gsl_sf_airy_Ai_deriv_scaled_e(x, gsl_sf::GSL_PREC_DOUBLE, &mut s) != 0
+        if gsl_sf_airy_Ai_deriv_scaled_e(x, mode.as_mode(), &mut s) != 0
         {
             panic!("Airy derivative function calculation failed");
         }
@@ -181,10 +228,17 @@ pub fn airy_ai_deriv_scaled(x: f64) -> (f64, f64)
 /// Compute the scaled Airy function derivative S_B(x) Bi'(x). For x>0 the
 /// scaling factor S_B(x) is exp(-(2/3) x^(3/2)), and is 1 for x<0.
 pub fn airy_bi_deriv_scaled(x: f64) -> (f64, f64)
+{
+    airy_bi_deriv_scaled_mode(x, gsl_sf::Precision::Double)
+}
+
+/// Compute the scaled Airy function derivative S_B(x) Bi'(x) with an
+/// accuracy specified by mode
+pub fn airy_bi_deriv_scaled_mode(x: f64, mode: gsl_sf::Precision) -> (f64, f64)
 {
     let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
     unsafe {
-        if gsl_sf_airy_Bi_deriv_scaled_e(x, gsl_sf::GSL_PREC_DOUBLE, &mut s) != 0
+        if gsl_sf_airy_Bi_deriv_scaled_e(x, mode.as_mode(), &mut s) != 0
         {
             panic!("Airy derivative function calculation failed");
         }
@@ -1,8 +1,9 @@
 /// Coulomb functions from the GSL
 use libc::{c_int, c_double};
 use gsl_sf;
+use gsl_math;
 use array::Array;
-use array::Order;
+use gsl_guard::GuardedBuffer;
 
 #[link(name = "gsl")]
 extern
@@ -55,7 +56,7 @@ extern
         exp_G: *mut c_double) -> c_int;
 
 
-    /// F_L(eta,x) as array
+    /// F_L(eta,x) as array, for L = lam_min, lam_min+1, ..., lam_min+kmax
     fn gsl_sf_coulomb_wave_F_array(
         lam_min: c_double,
         kmax: c_int,
@@ -64,7 +65,7 @@ extern
         fc_array: *mut c_double,
         F_exponent: *mut c_double) -> c_int;
 
-    /// F_L(eta,x), G_L(eta,x) as arrays
+    /// F_L(eta,x), G_L(eta,x) as arrays, for L = lam_min, ..., lam_min+kmax
     fn gsl_sf_coulomb_wave_FG_array(
         lam_min: c_double,
         kmax: c_int,
@@ -75,7 +76,8 @@ extern
         F_exponent: *mut c_double,
         G_exponent: *mut c_double) -> c_int;
 
-    /// F_L(eta,x), G_L(eta,x), F'_L(eta,x), G'_L(eta,x) as arrays
+    /// F_L(eta,x), G_L(eta,x), F'_L(eta,x), G'_L(eta,x) as arrays,
+    /// for L = lam_min, ..., lam_min+kmax
     fn gsl_sf_coulomb_wave_FGp_array(
         lam_min: c_double,
         kmax: c_int,
@@ -199,14 +201,65 @@ pub fn coulomb_wave_fg(
 }
 
 
-/// F_L(eta,x) as array
+/// Coulomb wave functions F_{lam_F}(eta,x), G_{lam_G}(eta,x) and their
+/// derivatives as extended-range results [F, F', G, G'], so that the
+/// exponents signalled by GSL on overflow are folded into each result
+/// instead of being dropped. GSL's error handler is switched off during the
+/// call so that the overflow status reaches this wrapper rather than aborting.
+pub fn coulomb_wave_fg_e10(
+    eta: f64,
+    x: f64,
+    lam_f: f64,
+    k: usize) -> [gsl_sf::ScaledResult; 4]
+{
+    if x < 0f64
+    {
+        panic!("x cannot be less than zero");
+    }
+    if lam_f - (k as f64) < -0.5f64
+    {
+        panic!("Invalid value for k");
+    }
+    let mut f = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    let mut fp = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    let mut g = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    let mut gp = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    let mut exp_f = 0.0f64;
+    let mut exp_g = 0.0f64;
+    let _handler = gsl_math::ErrorHandlerGuard::off();
+    unsafe {
+        let status = gsl_sf_coulomb_wave_FG_e(
+            eta,
+            x,
+            lam_f,
+            k as i32,
+            &mut f,
+            &mut fp,
+            &mut g,
+            &mut gp,
+            &mut exp_f as *mut c_double,
+            &mut exp_g as *mut c_double);
+        // Overflow is reported through exp_F and exp_G, not a failure here
+        if status != 0 && status != gsl_sf::GSL_EOVRFLW
+        {
+            panic!("Coloumb function failed");
+        }
+    }
+    [gsl_sf::ScaledResult::from_ln_scale(f.val, f.err, exp_f),
+     gsl_sf::ScaledResult::from_ln_scale(fp.val, fp.err, exp_f),
+     gsl_sf::ScaledResult::from_ln_scale(g.val, g.err, exp_g),
+     gsl_sf::ScaledResult::from_ln_scale(gp.val, gp.err, exp_g)]
+}
+
+
+/// F_L(eta,x) as array, for L = lam_min, lam_min+1, ..., lam_min+kmax
 pub fn coulomb_wave_f_array(
     lam_min: f64,
     kmax: usize,
     eta: f64,
     x: f64) -> Array<f64>
 {
-    let mut fc_array = Array::<f64>::new_filled(0f64, kmax, Order::Row);
+    let mut fc_array = GuardedBuffer::new(kmax + 1);
     let mut f_exponent: c_double = 0.0;
     unsafe {
          if 0 != gsl_sf_coulomb_wave_F_array(
@@ -220,18 +273,18 @@ pub fn coulomb_wave_f_array(
             panic!("Coloumb function failed");
         }
     }
-    fc_array
+    fc_array.into_array()
 }
 
-/// F_L(eta,x), G_L(eta,x) as arrays
+/// F_L(eta,x), G_L(eta,x) as arrays, for L = lam_min, ..., lam_min+kmax
 pub fn coulomb_wave_fg_array(
     lam_min: f64,
     kmax: usize,
     eta: f64,
     x: f64) -> (Array<f64>, Array<f64>)
 {
-    let mut fc_array = Array::<f64>::new_filled(0f64, kmax, Order::Row);
-    let mut gc_array = Array::<f64>::new_filled(0f64, kmax, Order::Row);
+    let mut fc_array = GuardedBuffer::new(kmax + 1);
+    let mut gc_array = GuardedBuffer::new(kmax + 1);
     let mut f_exponent: c_double = 0f64;
     let mut g_exponent: c_double = 0f64;
     unsafe {
@@ -248,20 +301,21 @@ pub fn coulomb_wave_fg_array(
             panic!("Coloumb function failed");
         }
     }
-    (fc_array, gc_array)
+    (fc_array.into_array(), gc_array.into_array())
 }
 
-/// F_L(eta,x), G_L(eta,x), F'_L(eta,x), G'_L(eta,x) as arrays
+/// F_L(eta,x), G_L(eta,x), F'_L(eta,x), G'_L(eta,x) as arrays,
+/// for L = lam_min, ..., lam_min+kmax
 pub fn coulomb_wave_fgp_array(
     lam_min: f64,
     kmax: usize,
     eta: f64,
     x: f64) -> (Array<f64>, Array<f64>, Array<f64>, Array<f64>)
 {
-    let mut fc_array = Array::<f64>::new_filled(0f64, kmax, Order::Row);
-    let mut gc_array = Array::<f64>::new_filled(0f64, kmax, Order::Row);
-    let mut fcp_array = Array::<f64>::new_filled(0f64, kmax, Order::Row);
-    let mut gcp_array = Array::<f64>::new_filled(0f64, kmax, Order::Row);
+    let mut fc_array = GuardedBuffer::new(kmax + 1);
+    let mut gc_array = GuardedBuffer::new(kmax + 1);
+    let mut fcp_array = GuardedBuffer::new(kmax + 1);
+    let mut gcp_array = GuardedBuffer::new(kmax + 1);
     let mut f_exponent: c_double = 0f64;
     let mut g_exponent: c_double = 0f64;
     unsafe {
@@ -280,19 +334,21 @@ pub fn coulomb_wave_fgp_array(
             panic!("Coloumb function failed");
         }
     }
-    (fc_array, gc_array, fcp_array, gcp_array)
+    (fc_array.into_array(), gc_array.into_array(),
+     fcp_array.into_array(), gcp_array.into_array())
 }
 
 //// Coulomb wave function divided by the argument,
 /// F(eta, x)/x. This is the function which reduces to
 /// spherical Bessel functions in the limit eta->0.
+/// L = lam_min, lam_min+1, ..., lam_min+kmax
 pub fn coulomb_wave_sphf_array(
     lam_min: f64,
     kmax: usize,
     eta: f64,
     x: f64) -> Array<f64>
 {
-    let mut fc_array = Array::<f64>::new_filled(0f64, kmax, Order::Row);
+    let mut fc_array = GuardedBuffer::new(kmax + 1);
     let mut f_exponent: c_double = 0.0;
     unsafe {
         if 0 != gsl_sf_coulomb_wave_sphF_array(
@@ -306,7 +362,7 @@ pub fn coulomb_wave_sphf_array(
             panic!("Coloumb function failed");
         }
     }
-    fc_array
+    fc_array.into_array()
 }
 
 
@@ -325,10 +381,11 @@ pub fn coulomb_cl_e(l: f64, eta: f64) -> (f64, f64)
 }
 
 //// Coulomb wave function normalization constant, array version
-/// [Abramowitz+Stegun 14.1.8, 14.1.9]    
+/// [Abramowitz+Stegun 14.1.8, 14.1.9]
+/// L = lmin, lmin+1, ..., lmin+kmax
 pub fn coulomb_cl_array(lmin: f64, kmax: usize, eta: f64) -> Array<f64>
 {
-    let mut cl: Array<f64> = Array::<f64>::new_filled(0f64, kmax, Order::Row);
+    let mut cl = GuardedBuffer::new(kmax + 1);
     unsafe {
         if 0 != gsl_sf_coulomb_CL_array(
             lmin,
@@ -339,5 +396,5 @@ pub fn coulomb_cl_array(lmin: f64, kmax: usize, eta: f64) -> Array<f64>
             panic!("Coloumb function failed");
         }
     }
-    cl
+    cl.into_array()
 }
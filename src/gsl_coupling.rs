@@ -0,0 +1,199 @@
+/// Angular momentum coupling coefficients from the GSL: the Wigner 3-j,
+/// 6-j and 9-j symbols.
+/// GSL takes every angular momentum as twice its value so that half-integer
+/// spins can be passed as integers; here they are given as `Spin` values and
+/// the doubling is handled internally.
+/// For further information see Abramowitz & Stegun, Section 27.9.
+
+use libc::c_int;
+use gsl_sf;
+
+#[link(name = "gsl")]
+extern
+{
+    /// 3j Symbols:  / ja jb jc \
+    ///              \ ma mb mc /
+    /// exceptions: GSL_EDOM, GSL_EOVRFLW
+    fn gsl_sf_coupling_3j_e(
+        two_ja: c_int, two_jb: c_int, two_jc: c_int,
+        two_ma: c_int, two_mb: c_int, two_mc: c_int,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// 6j Symbols:  / ja jb jc \
+    ///              \ jd je jf /
+    /// exceptions: GSL_EDOM, GSL_EOVRFLW
+    fn gsl_sf_coupling_6j_e(
+        two_ja: c_int, two_jb: c_int, two_jc: c_int,
+        two_jd: c_int, two_je: c_int, two_jf: c_int,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// 9j Symbols:  / ja jb jc \
+    ///              | jd je jf |
+    ///              \ jg jh ji /
+    /// exceptions: GSL_EDOM, GSL_EOVRFLW
+    fn gsl_sf_coupling_9j_e(
+        two_ja: c_int, two_jb: c_int, two_jc: c_int,
+        two_jd: c_int, two_je: c_int, two_jf: c_int,
+        two_jg: c_int, two_jh: c_int, two_ji: c_int,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+}
+
+/// An integer or half-integer angular momentum quantum number, used both for
+/// total angular momenta j and their projections m
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Spin
+{
+    twice: i32,
+}
+
+impl Spin
+{
+    /// Creates a spin from its value, which must be a multiple of 1/2
+    pub fn new(value: f64) -> Spin
+    {
+        let twice = 2f64 * value;
+        if twice != twice.round()
+        {
+            panic!("Spin must be an integer or half-integer: {}", value);
+        }
+        Spin{twice: twice as i32}
+    }
+
+    /// Creates a spin from twice its value, GSL's convention, so that
+    /// Spin::from_twice(1) is spin 1/2
+    pub fn from_twice(twice: i32) -> Spin
+    {
+        Spin{twice: twice}
+    }
+
+    /// Twice the value of the spin
+    pub fn twice(&self) -> i32
+    {
+        self.twice
+    }
+
+    /// The value of the spin
+    pub fn value(&self) -> f64
+    {
+        self.twice as f64 / 2f64
+    }
+
+    /// True for integer spins, false for half-integer spins
+    pub fn is_integer(&self) -> bool
+    {
+        self.twice % 2 == 0
+    }
+}
+
+/// Panics if an angular momentum j is negative
+fn check_j(j: Spin)
+{
+    if j.twice < 0
+    {
+        panic!("Invalid value for j: {}", j.value());
+    }
+}
+
+/// Triangle condition |ja - jb| <= jc <= ja + jb with ja + jb + jc an integer
+fn triangle(ja: Spin, jb: Spin, jc: Spin) -> bool
+{
+    (ja.twice - jb.twice).abs() <= jc.twice
+        && jc.twice <= ja.twice + jb.twice
+        && (ja.twice + jb.twice + jc.twice) % 2 == 0
+}
+
+/// Projection condition |m| <= j with j - m an integer
+fn projection(j: Spin, m: Spin) -> bool
+{
+    m.twice.abs() <= j.twice && (j.twice - m.twice) % 2 == 0
+}
+
+/// Wigner 3-j symbol
+///     / ja jb jc \
+///     \ ma mb mc /
+/// The symbol vanishes unless the triangle condition holds, |m| <= j with
+/// j - m integer for each column, and ma + mb + mc = 0; in that case
+/// (0.0, 0.0) is returned without calling into the GSL.
+pub fn coupling_3j(ja: Spin, jb: Spin, jc: Spin,
+                   ma: Spin, mb: Spin, mc: Spin) -> (f64, f64)
+{
+    check_j(ja);
+    check_j(jb);
+    check_j(jc);
+    if !triangle(ja, jb, jc)
+        || !projection(ja, ma) || !projection(jb, mb) || !projection(jc, mc)
+        || ma.twice + mb.twice + mc.twice != 0
+    {
+        return (0f64, 0f64);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_coupling_3j_e(ja.twice, jb.twice, jc.twice,
+                                ma.twice, mb.twice, mc.twice, &mut s) != 0
+        {
+            panic!("Coupling coefficient calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Wigner 6-j symbol
+///     { ja jb jc }
+///     { jd je jf }
+/// The symbol vanishes unless each of the triads (ja, jb, jc), (ja, je, jf),
+/// (jd, jb, jf) and (jd, je, jc) satisfies the triangle condition; in that
+/// case (0.0, 0.0) is returned without calling into the GSL.
+pub fn coupling_6j(ja: Spin, jb: Spin, jc: Spin,
+                   jd: Spin, je: Spin, jf: Spin) -> (f64, f64)
+{
+    for &j in [ja, jb, jc, jd, je, jf].iter()
+    {
+        check_j(j);
+    }
+    if !triangle(ja, jb, jc) || !triangle(ja, je, jf)
+        || !triangle(jd, jb, jf) || !triangle(jd, je, jc)
+    {
+        return (0f64, 0f64);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_coupling_6j_e(ja.twice, jb.twice, jc.twice,
+                                jd.twice, je.twice, jf.twice, &mut s) != 0
+        {
+            panic!("Coupling coefficient calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Wigner 9-j symbol
+///     { ja jb jc }
+///     { jd je jf }
+///     { jg jh ji }
+/// The symbol vanishes unless every row and every column satisfies the
+/// triangle condition; in that case (0.0, 0.0) is returned without calling
+/// into the GSL.
+pub fn coupling_9j(ja: Spin, jb: Spin, jc: Spin,
+                   jd: Spin, je: Spin, jf: Spin,
+                   jg: Spin, jh: Spin, ji: Spin) -> (f64, f64)
+{
+    for &j in [ja, jb, jc, jd, je, jf, jg, jh, ji].iter()
+    {
+        check_j(j);
+    }
+    if !triangle(ja, jb, jc) || !triangle(jd, je, jf) || !triangle(jg, jh, ji)
+        || !triangle(ja, jd, jg) || !triangle(jb, je, jh) || !triangle(jc, jf, ji)
+    {
+        return (0f64, 0f64);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_coupling_9j_e(ja.twice, jb.twice, jc.twice,
+                                jd.twice, je.twice, jf.twice,
+                                jg.twice, jh.twice, ji.twice, &mut s) != 0
+        {
+            panic!("Coupling coefficient calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
@@ -0,0 +1,155 @@
+/// Debye functions from the GSL,
+///     D_n(x) = n/x^n Integral[t^n / (Exp[t] - 1), {t, 0, x}]
+/// for n = 1, ..., 6.
+/// For further information see Abramowitz & Stegun, Section 27.1.
+
+use libc::{c_int, c_double};
+use gsl_sf;
+
+#[link(name = "gsl")]
+extern
+{
+    /// D_1(x) := 1/x^1 Integrate[t^1/(e^t - 1), {t,0,x}]
+    /// exceptions: GSL_EUNDRFLW
+    fn gsl_sf_debye_1_e(
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// D_2(x) := 2/x^2 Integrate[t^2/(e^t - 1), {t,0,x}]
+    /// exceptions: GSL_EUNDRFLW
+    fn gsl_sf_debye_2_e(
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// D_3(x) := 3/x^3 Integrate[t^3/(e^t - 1), {t,0,x}]
+    /// exceptions: GSL_EUNDRFLW
+    fn gsl_sf_debye_3_e(
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// D_4(x) := 4/x^4 Integrate[t^4/(e^t - 1), {t,0,x}]
+    /// exceptions: GSL_EUNDRFLW
+    fn gsl_sf_debye_4_e(
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// D_5(x) := 5/x^5 Integrate[t^5/(e^t - 1), {t,0,x}]
+    /// exceptions: GSL_EUNDRFLW
+    fn gsl_sf_debye_5_e(
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// D_6(x) := 6/x^6 Integrate[t^6/(e^t - 1), {t,0,x}]
+    /// exceptions: GSL_EUNDRFLW
+    fn gsl_sf_debye_6_e(
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+}
+
+/// Debye function D_1(x) = 1/x^1 Integral[t^1/(e^t - 1), {t, 0, x}]
+/// x >= 0.0
+pub fn debye_1(x: f64) -> (f64, f64)
+{
+    if x < 0f64
+    {
+        panic!("Invalid value for x: {}", x);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_debye_1_e(x, &mut s) != 0
+        {
+            panic!("Debye calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Debye function D_2(x) = 2/x^2 Integral[t^2/(e^t - 1), {t, 0, x}]
+/// x >= 0.0
+pub fn debye_2(x: f64) -> (f64, f64)
+{
+    if x < 0f64
+    {
+        panic!("Invalid value for x: {}", x);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_debye_2_e(x, &mut s) != 0
+        {
+            panic!("Debye calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Debye function D_3(x) = 3/x^3 Integral[t^3/(e^t - 1), {t, 0, x}]
+/// x >= 0.0
+pub fn debye_3(x: f64) -> (f64, f64)
+{
+    if x < 0f64
+    {
+        panic!("Invalid value for x: {}", x);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_debye_3_e(x, &mut s) != 0
+        {
+            panic!("Debye calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Debye function D_4(x) = 4/x^4 Integral[t^4/(e^t - 1), {t, 0, x}]
+/// x >= 0.0
+pub fn debye_4(x: f64) -> (f64, f64)
+{
+    if x < 0f64
+    {
+        panic!("Invalid value for x: {}", x);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_debye_4_e(x, &mut s) != 0
+        {
+            panic!("Debye calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Debye function D_5(x) = 5/x^5 Integral[t^5/(e^t - 1), {t, 0, x}]
+/// x >= 0.0
+pub fn debye_5(x: f64) -> (f64, f64)
+{
+    if x < 0f64
+    {
+        panic!("Invalid value for x: {}", x);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_debye_5_e(x, &mut s) != 0
+        {
+            panic!("Debye calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Debye function D_6(x) = 6/x^6 Integral[t^6/(e^t - 1), {t, 0, x}]
+/// x >= 0.0
+pub fn debye_6(x: f64) -> (f64, f64)
+{
+    if x < 0f64
+    {
+        panic!("Invalid value for x: {}", x);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_debye_6_e(x, &mut s) != 0
+        {
+            panic!("Debye calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
@@ -0,0 +1,253 @@
+/// Exponential functions from the GSL, including extended-range variants
+/// which return results too large or small to be represented by a double.
+
+use libc::{c_int, c_double};
+use gsl_sf;
+
+#[link(name = "gsl")]
+extern
+{
+    /// Provide an exp() function with GSL semantics,
+    /// i.e. with proper error checking, etc.
+    /// exceptions: GSL_EOVRFLW, GSL_EUNDRFLW
+    fn gsl_sf_exp_e(x: c_double, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Exp(x)
+    /// exceptions: GSL_EOVRFLW, GSL_EUNDRFLW
+    fn gsl_sf_exp_e10_e(
+        x: c_double, result: *mut gsl_sf::gsl_sf_result_e10) -> c_int;
+
+    /// Exponentiate and multiply by a given factor:  y * Exp(x)
+    /// exceptions: GSL_EOVRFLW, GSL_EUNDRFLW
+    fn gsl_sf_exp_mult_e(
+        x: c_double,
+        y: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Exponentiate and multiply by a given factor:  y * Exp(x)
+    /// exceptions: GSL_EOVRFLW, GSL_EUNDRFLW
+    fn gsl_sf_exp_mult_e10_e(
+        x: c_double,
+        y: c_double,
+        result: *mut gsl_sf::gsl_sf_result_e10) -> c_int;
+
+    /// Exp(x)-1
+    /// exceptions: GSL_EOVRFLW
+    fn gsl_sf_expm1_e(x: c_double, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// (Exp(x)-1)/x = 1 + x/2 + x^2/(2*3) + x^3/(2*3*4) + ...
+    /// exceptions: GSL_EOVRFLW
+    fn gsl_sf_exprel_e(x: c_double, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// 2(Exp(x)-1-x)/x^2 = 1 + x/3 + x^2/(3*4) + x^3/(3*4*5) + ...
+    /// exceptions: GSL_EOVRFLW
+    fn gsl_sf_exprel_2_e(x: c_double, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Similarly for the N-th generalization of
+    /// the above. The so-called N-relative exponential
+    ///
+    /// exprel_N(x) = N!/x^N (exp(x) - Sum[x^k/k!, {k,0,N-1}])
+    ///             = 1 + x/(N+1) + x^2/((N+1)(N+2)) + ...
+    ///             = 1F1(1,1+N,x)
+    fn gsl_sf_exprel_n_e(
+        n: c_int, x: c_double, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Exponentiate a quantity with an associated error.
+    /// exceptions: GSL_EOVRFLW, GSL_EUNDRFLW
+    fn gsl_sf_exp_err_e(
+        x: c_double,
+        dx: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Exponentiate a quantity with an associated error.
+    /// exceptions: GSL_EOVRFLW, GSL_EUNDRFLW
+    fn gsl_sf_exp_err_e10_e(
+        x: c_double,
+        dx: c_double,
+        result: *mut gsl_sf::gsl_sf_result_e10) -> c_int;
+
+    /// Exponentiate and multiply a quantity with an associated error,
+    /// y * Exp(x)
+    /// exceptions: GSL_EOVRFLW, GSL_EUNDRFLW
+    fn gsl_sf_exp_mult_err_e(
+        x: c_double,
+        dx: c_double,
+        y: c_double,
+        dy: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Exponentiate and multiply a quantity with an associated error,
+    /// y * Exp(x)
+    /// exceptions: GSL_EOVRFLW, GSL_EUNDRFLW
+    fn gsl_sf_exp_mult_err_e10_e(
+        x: c_double,
+        dx: c_double,
+        y: c_double,
+        dy: c_double,
+        result: *mut gsl_sf::gsl_sf_result_e10) -> c_int;
+}
+
+/// Exponential function exp(x) with error checking
+pub fn exp(x: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_exp_e(x, &mut s) != 0
+        {
+            panic!("Exponential calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Exponential function exp(x) as an extended-range result, val * 10^e10
+pub fn exp_e10(x: f64) -> gsl_sf::ScaledResult
+{
+    let mut s = gsl_sf::gsl_sf_result_e10_struct{val: 0f64, err: 0f64, e10: 0};
+    unsafe {
+        if gsl_sf_exp_e10_e(x, &mut s) != 0
+        {
+            panic!("Exponential calculation failed");
+        }
+    }
+    gsl_sf::ScaledResult::from(s)
+}
+
+/// Exponentiate and multiply by a given factor, y * exp(x), avoiding
+/// overflow of exp(x) where the product is representable
+pub fn exp_mult(x: f64, y: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_exp_mult_e(x, y, &mut s) != 0
+        {
+            panic!("Exponential calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Exponentiate and multiply by a given factor, y * exp(x), as an
+/// extended-range result
+pub fn exp_mult_e10(x: f64, y: f64) -> gsl_sf::ScaledResult
+{
+    let mut s = gsl_sf::gsl_sf_result_e10_struct{val: 0f64, err: 0f64, e10: 0};
+    unsafe {
+        if gsl_sf_exp_mult_e10_e(x, y, &mut s) != 0
+        {
+            panic!("Exponential calculation failed");
+        }
+    }
+    gsl_sf::ScaledResult::from(s)
+}
+
+/// Computes exp(x) - 1 in a way that is accurate for small x
+pub fn expm1(x: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_expm1_e(x, &mut s) != 0
+        {
+            panic!("Exponential calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Relative exponential (exp(x) - 1)/x, accurate for small x
+pub fn exprel(x: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_exprel_e(x, &mut s) != 0
+        {
+            panic!("Exponential calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Second relative exponential 2(exp(x) - 1 - x)/x^2, accurate for small x
+pub fn exprel_2(x: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_exprel_2_e(x, &mut s) != 0
+        {
+            panic!("Exponential calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// N-relative exponential
+///   exprel_N(x) = N!/x^N (exp(x) - Sum[x^k/k!, {k,0,N-1}])
+pub fn exprel_n(n: i32, x: f64) -> (f64, f64)
+{
+    if n < 0
+    {
+        panic!("n must be non-negative");
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_exprel_n_e(n, x, &mut s) != 0
+        {
+            panic!("Exponential calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Exponentiate x with an associated absolute error dx
+pub fn exp_err(x: f64, dx: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_exp_err_e(x, dx, &mut s) != 0
+        {
+            panic!("Exponential calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Computes y * exp(x) for x and y with associated absolute errors dx and dy
+pub fn exp_mult_err(x: f64, dx: f64, y: f64, dy: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_exp_mult_err_e(x, dx, y, dy, &mut s) != 0
+        {
+            panic!("Exponential calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Exponentiate x with an associated absolute error dx, as an extended-range
+/// result
+pub fn exp_err_e10(x: f64, dx: f64) -> gsl_sf::ScaledResult
+{
+    let mut s = gsl_sf::gsl_sf_result_e10_struct{val: 0f64, err: 0f64, e10: 0};
+    unsafe {
+        if gsl_sf_exp_err_e10_e(x, dx, &mut s) != 0
+        {
+            panic!("Exponential calculation failed");
+        }
+    }
+    gsl_sf::ScaledResult::from(s)
+}
+
+/// Computes y * exp(x) for x and y with associated absolute errors dx and dy,
+/// as an extended-range result
+pub fn exp_mult_err_e10(x: f64, dx: f64, y: f64, dy: f64) -> gsl_sf::ScaledResult
+{
+    let mut s = gsl_sf::gsl_sf_result_e10_struct{val: 0f64, err: 0f64, e10: 0};
+    unsafe {
+        if gsl_sf_exp_mult_err_e10_e(x, dx, y, dy, &mut s) != 0
+        {
+            panic!("Exponential calculation failed");
+        }
+    }
+    gsl_sf::ScaledResult::from(s)
+}
@@ -0,0 +1,325 @@
+/// Exponential integrals, hyperbolic and trigonometric integrals from the GSL.
+///   E_n(x) = \int_1^\infty e^(-xt) / t^n dt
+///   Ei(x) = - PV(\int_(-x)^\infty e^(-t) / t dt)
+///   Si(x) = \int_0^x sin(t) / t dt
+///   Ci(x) = - \int_x^\infty cos(t) / t dt
+/// For further information see Abramowitz & Stegun, Chapter 5.
+
+use libc::{c_int, c_double};
+use gsl_sf;
+
+#[link(name = "gsl")]
+extern
+{
+    /// E_1(x) := Re[ Integrate[ Exp[-xt]/t, {t,1,Infinity}] ]
+    /// x != 0.0
+    /// exceptions: GSL_EDOM, GSL_EOVRFLW, GSL_EUNDRFLW
+    fn gsl_sf_expint_E1_e(x: c_double, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// E_2(x) := Re[ Integrate[ Exp[-xt]/t^2, {t,1,Infinity}] ]
+    /// x != 0.0
+    /// exceptions: GSL_EDOM, GSL_EOVRFLW, GSL_EUNDRFLW
+    fn gsl_sf_expint_E2_e(x: c_double, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// E_n(x) := Re[ Integrate[ Exp[-xt]/t^n, {t,1,Infinity}] ]
+    /// x != 0.0
+    /// exceptions: GSL_EDOM, GSL_EOVRFLW, GSL_EUNDRFLW
+    fn gsl_sf_expint_En_e(
+        n: c_int, x: c_double, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// E_1_scaled(x) := exp(x) E_1(x)
+    /// x != 0.0
+    /// exceptions: GSL_EDOM, GSL_EOVRFLW, GSL_EUNDRFLW
+    fn gsl_sf_expint_E1_scaled_e(
+        x: c_double, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// E_2_scaled(x) := exp(x) E_2(x)
+    /// x != 0.0
+    /// exceptions: GSL_EDOM, GSL_EOVRFLW, GSL_EUNDRFLW
+    fn gsl_sf_expint_E2_scaled_e(
+        x: c_double, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// E_n_scaled(x) := exp(x) E_n(x)
+    /// x != 0.0
+    /// exceptions: GSL_EDOM, GSL_EOVRFLW, GSL_EUNDRFLW
+    fn gsl_sf_expint_En_scaled_e(
+        n: c_int, x: c_double, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Ei(x) := - PV Integrate[ Exp[-t]/t, {t,-x,Infinity}]
+    ///       :=   PV Integrate[ Exp[t]/t, {t,-Infinity,x}]
+    /// x != 0.0
+    /// exceptions: GSL_EDOM, GSL_EOVRFLW, GSL_EUNDRFLW
+    fn gsl_sf_expint_Ei_e(x: c_double, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Ei_scaled(x) := exp(-x) Ei(x)
+    /// x != 0.0
+    /// exceptions: GSL_EDOM, GSL_EOVRFLW, GSL_EUNDRFLW
+    fn gsl_sf_expint_Ei_scaled_e(
+        x: c_double, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Shi(x) := Integrate[ Sinh[t]/t, {t,0,x}]
+    /// exceptions: GSL_EOVRFLW, GSL_EUNDRFLW
+    fn gsl_sf_Shi_e(x: c_double, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Chi(x) := Re[ M_EULER + log(x) + Integrate[(Cosh[t]-1)/t, {t,0,x}] ]
+    /// x != 0.0
+    /// exceptions: GSL_EDOM, GSL_EOVRFLW, GSL_EUNDRFLW
+    fn gsl_sf_Chi_e(x: c_double, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Ei_3(x) := Integral[ Exp[-t^3], {t,0,x}]
+    /// x >= 0.0
+    /// exceptions: GSL_EDOM
+    fn gsl_sf_expint_3_e(x: c_double, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Si(x) := Integrate[ Sin[t]/t, {t,0,x}]
+    /// exceptions: none
+    fn gsl_sf_Si_e(x: c_double, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Ci(x) := -Integrate[ Cos[t]/t, {t,x,Infinity}]
+    /// x > 0.0
+    /// exceptions: GSL_EDOM
+    fn gsl_sf_Ci_e(x: c_double, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// AtanInt(x) := Integral[ Arctan[t]/t, {t,0,x}]
+    /// exceptions:
+    fn gsl_sf_atanint_e(x: c_double, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+}
+
+/// Exponential integral E_1(x)
+/// x != 0.0
+/// exceptions: GSL_EDOM, GSL_EOVRFLW, GSL_EUNDRFLW
+pub fn expint_e1(x: f64) -> (f64, f64)
+{
+    if x == 0.0
+    {
+        panic!("Invalid value for x: {}", x);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_expint_E1_e(x, &mut s) != 0
+        {
+            panic!("Exponential integral calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Second-order exponential integral E_2(x)
+/// exceptions: GSL_EDOM, GSL_EOVRFLW, GSL_EUNDRFLW
+pub fn expint_e2(x: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_expint_E2_e(x, &mut s) != 0
+        {
+            panic!("Exponential integral calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Exponential integral E_n(x) of order n
+/// exceptions: GSL_EDOM, GSL_EOVRFLW, GSL_EUNDRFLW
+pub fn expint_en(n: i32, x: f64) -> (f64, f64)
+{
+    if n < 0
+    {
+        panic!("n must be non-negative");
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_expint_En_e(n, x, &mut s) != 0
+        {
+            panic!("Exponential integral calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Scaled exponential integral exp(x) E_1(x)
+/// x != 0.0
+/// exceptions: GSL_EDOM, GSL_EOVRFLW, GSL_EUNDRFLW
+pub fn expint_e1_scaled(x: f64) -> (f64, f64)
+{
+    if x == 0.0
+    {
+        panic!("Invalid value for x: {}", x);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_expint_E1_scaled_e(x, &mut s) != 0
+        {
+            panic!("Exponential integral calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Scaled second-order exponential integral exp(x) E_2(x)
+/// exceptions: GSL_EDOM, GSL_EOVRFLW, GSL_EUNDRFLW
+pub fn expint_e2_scaled(x: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_expint_E2_scaled_e(x, &mut s) != 0
+        {
+            panic!("Exponential integral calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Scaled exponential integral exp(x) E_n(x) of order n
+/// exceptions: GSL_EDOM, GSL_EOVRFLW, GSL_EUNDRFLW
+pub fn expint_en_scaled(n: i32, x: f64) -> (f64, f64)
+{
+    if n < 0
+    {
+        panic!("n must be non-negative");
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_expint_En_scaled_e(n, x, &mut s) != 0
+        {
+            panic!("Exponential integral calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Exponential integral Ei(x)
+/// x != 0.0
+/// exceptions: GSL_EDOM, GSL_EOVRFLW, GSL_EUNDRFLW
+pub fn expint_ei(x: f64) -> (f64, f64)
+{
+    if x == 0.0
+    {
+        panic!("Invalid value for x: {}", x);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_expint_Ei_e(x, &mut s) != 0
+        {
+            panic!("Exponential integral calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Scaled exponential integral exp(-x) Ei(x)
+/// x != 0.0
+/// exceptions: GSL_EDOM, GSL_EOVRFLW, GSL_EUNDRFLW
+pub fn expint_ei_scaled(x: f64) -> (f64, f64)
+{
+    if x == 0.0
+    {
+        panic!("Invalid value for x: {}", x);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_expint_Ei_scaled_e(x, &mut s) != 0
+        {
+            panic!("Exponential integral calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Hyperbolic sine integral Shi(x) = \int_0^x sinh(t)/t dt
+/// exceptions: GSL_EOVRFLW, GSL_EUNDRFLW
+pub fn shi(x: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_Shi_e(x, &mut s) != 0
+        {
+            panic!("Hyperbolic integral calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Hyperbolic cosine integral
+/// Chi(x) = gamma + log(x) + \int_0^x (cosh(t)-1)/t dt
+/// x != 0.0
+/// exceptions: GSL_EDOM, GSL_EOVRFLW, GSL_EUNDRFLW
+pub fn chi(x: f64) -> (f64, f64)
+{
+    if x == 0.0
+    {
+        panic!("Invalid value for x: {}", x);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_Chi_e(x, &mut s) != 0
+        {
+            panic!("Hyperbolic integral calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Third-order exponential integral Ei_3(x) = \int_0^x exp(-t^3) dt
+/// x >= 0.0
+/// exceptions: GSL_EDOM
+pub fn expint_3(x: f64) -> (f64, f64)
+{
+    if x < 0.0
+    {
+        panic!("Invalid value for x: {}", x);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_expint_3_e(x, &mut s) != 0
+        {
+            panic!("Exponential integral calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Sine integral Si(x) = \int_0^x sin(t)/t dt
+/// exceptions: none
+pub fn si(x: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_Si_e(x, &mut s) != 0
+        {
+            panic!("Trigonometric integral calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Cosine integral Ci(x) = -\int_x^\infty cos(t)/t dt
+/// x > 0.0
+/// exceptions: GSL_EDOM
+pub fn ci(x: f64) -> (f64, f64)
+{
+    if x <= 0.0
+    {
+        panic!("Invalid value for x: {}", x);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_Ci_e(x, &mut s) != 0
+        {
+            panic!("Trigonometric integral calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Arctangent integral AtanInt(x) = \int_0^x arctan(t)/t dt
+pub fn atanint(x: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_atanint_e(x, &mut s) != 0
+        {
+            panic!("Arctangent integral calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
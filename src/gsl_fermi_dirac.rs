@@ -0,0 +1,193 @@
+/// Complete and incomplete Fermi-Dirac integrals from the GSL. The complete
+/// integral of order j is
+///     F_j(x) = 1/Gamma(j+1) Integral[t^j / (Exp[t-x] + 1), {t, 0, Infinity}]
+/// and the incomplete integral F_j(x, b) starts the integration at t = b
+/// instead of zero; the GSL provides the incomplete integral for j = 0 only.
+/// Note the 1/Gamma(j+1) normalization, which differs from some physics texts.
+/// For further information see Blakemore, Semiconductor Statistics (1962).
+
+use libc::{c_int, c_double};
+use gsl_sf;
+
+#[link(name = "gsl")]
+extern
+{
+    /// Complete integral F_{-1}(x) = e^x / (1 + e^x)
+    /// exceptions: GSL_EUNDRFLW
+    fn gsl_sf_fermi_dirac_m1_e(
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Complete integral F_0(x) = ln(1 + e^x)
+    /// exceptions: GSL_EUNDRFLW
+    fn gsl_sf_fermi_dirac_0_e(
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Complete integral F_1(x)
+    /// exceptions: GSL_EUNDRFLW, GSL_EOVRFLW
+    fn gsl_sf_fermi_dirac_1_e(
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Complete integral F_2(x)
+    /// exceptions: GSL_EUNDRFLW, GSL_EOVRFLW
+    fn gsl_sf_fermi_dirac_2_e(
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Complete integral F_j(x)
+    /// for integer j
+    /// exceptions: GSL_EUNDRFLW, GSL_EOVRFLW
+    fn gsl_sf_fermi_dirac_int_e(
+        j: c_int,
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Complete integral F_{-1/2}(x)
+    /// exceptions: GSL_EUNDRFLW, GSL_EOVRFLW
+    fn gsl_sf_fermi_dirac_mhalf_e(
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Complete integral F_{1/2}(x)
+    /// exceptions: GSL_EUNDRFLW, GSL_EOVRFLW
+    fn gsl_sf_fermi_dirac_half_e(
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Complete integral F_{3/2}(x)
+    /// exceptions: GSL_EUNDRFLW, GSL_EOVRFLW
+    fn gsl_sf_fermi_dirac_3half_e(
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Incomplete integral F_0(x,b) = ln(1 + e^(b-x)) - (b-x)
+    /// exceptions: GSL_EUNDRFLW, GSL_EDOM
+    fn gsl_sf_fermi_dirac_inc_0_e(
+        x: c_double,
+        b: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+}
+
+/// Complete Fermi-Dirac integral F_{-1}(x) = e^x / (1 + e^x)
+pub fn fermi_dirac_m1(x: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_fermi_dirac_m1_e(x, &mut s) != 0
+        {
+            panic!("Fermi-Dirac calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Complete Fermi-Dirac integral F_0(x) = ln(1 + e^x)
+pub fn fermi_dirac_0(x: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_fermi_dirac_0_e(x, &mut s) != 0
+        {
+            panic!("Fermi-Dirac calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Complete Fermi-Dirac integral F_1(x)
+pub fn fermi_dirac_1(x: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_fermi_dirac_1_e(x, &mut s) != 0
+        {
+            panic!("Fermi-Dirac calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Complete Fermi-Dirac integral F_2(x)
+pub fn fermi_dirac_2(x: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_fermi_dirac_2_e(x, &mut s) != 0
+        {
+            panic!("Fermi-Dirac calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Complete Fermi-Dirac integral F_j(x) for integer j
+pub fn fermi_dirac_int(j: i32, x: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_fermi_dirac_int_e(j, x, &mut s) != 0
+        {
+            panic!("Fermi-Dirac calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Complete Fermi-Dirac integral F_{-1/2}(x)
+pub fn fermi_dirac_mhalf(x: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_fermi_dirac_mhalf_e(x, &mut s) != 0
+        {
+            panic!("Fermi-Dirac calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Complete Fermi-Dirac integral F_{1/2}(x)
+pub fn fermi_dirac_half(x: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_fermi_dirac_half_e(x, &mut s) != 0
+        {
+            panic!("Fermi-Dirac calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Complete Fermi-Dirac integral F_{3/2}(x)
+pub fn fermi_dirac_3half(x: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_fermi_dirac_3half_e(x, &mut s) != 0
+        {
+            panic!("Fermi-Dirac calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Incomplete Fermi-Dirac integral F_0(x, b) = ln(1 + e^(b-x)) - (b-x)
+/// b >= 0.0
+pub fn fermi_dirac_inc_0(x: f64, b: f64) -> (f64, f64)
+{
+    if b < 0f64
+    {
+        panic!("Invalid value for b: {}", b);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_fermi_dirac_inc_0_e(x, b, &mut s) != 0
+        {
+            panic!("Fermi-Dirac calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
@@ -0,0 +1,86 @@
+/// Guard-padded output buffers for GSL routines which write their results
+/// into caller-supplied arrays.
+/// The buffer is surrounded on both sides by guard words holding a NaN
+/// payload which GSL never produces. If a routine is handed a buffer shorter
+/// than the output it writes, the overrun lands in the guard words instead of
+/// in unrelated heap memory, and is reported when the buffer is converted
+/// into an `Array`.
+
+use array::Array;
+use array::Order;
+
+/// Number of guard words on each side of the buffer
+pub const GUARD_LEN: usize = 8;
+
+/// Bit pattern of the guard words: a quiet NaN with a distinctive payload
+const GUARD_BITS: u64 = 0x7ff8_dead_beef_cafe;
+
+pub struct GuardedBuffer
+{
+    /// Backing store, GUARD_LEN guard words either side of len elements
+    buf: Vec<f64>,
+    /// Number of elements available to the callee
+    len: usize,
+}
+
+impl GuardedBuffer
+{
+    /// Creates a zero-filled buffer of len elements padded with guard words
+    pub fn new(len: usize) -> GuardedBuffer
+    {
+        let mut buf = vec![f64::from_bits(GUARD_BITS); len + 2 * GUARD_LEN];
+        for i in 0..len
+        {
+            buf[GUARD_LEN + i] = 0f64;
+        }
+        GuardedBuffer{buf: buf, len: len}
+    }
+
+    /// Creates a buffer holding a copy of the contents of arr, for routines
+    /// which overwrite their input in place
+    pub fn from_array(arr: &Array<f64>) -> GuardedBuffer
+    {
+        let mut guarded = GuardedBuffer::new(arr.len());
+        for i in 0..arr.len()
+        {
+            guarded.buf[GUARD_LEN + i] = arr[i];
+        }
+        guarded
+    }
+
+    /// Number of elements available to the callee
+    pub fn len(&self) -> usize
+    {
+        self.len
+    }
+
+    /// Pointer to the first element available to the callee
+    pub fn as_mut_ptr(&mut self) -> *mut f64
+    {
+        // Derived from the whole buffer so that the pointer may reach the
+        // guard words when the callee overruns
+        unsafe {
+            self.buf.as_mut_ptr().offset(GUARD_LEN as isize)
+        }
+    }
+
+    /// True if anything has been written to the guard words on either side
+    pub fn overrun(&self) -> bool
+    {
+        self.buf[..GUARD_LEN].iter()
+            .chain(self.buf[GUARD_LEN + self.len..].iter())
+            .any(|x| x.to_bits() != GUARD_BITS)
+    }
+
+    /// Strips the guard words, returning the contents as an array.
+    /// Panics if the callee wrote outside the buffer.
+    pub fn into_array(self) -> Array<f64>
+    {
+        if self.overrun()
+        {
+            panic!("Write outside of a {} element output buffer", self.len);
+        }
+        let contents = self.buf[GUARD_LEN..GUARD_LEN + self.len].to_vec();
+        Array::new(contents, Order::Row)
+    }
+}
@@ -0,0 +1,314 @@
+/// Hypergeometric functions from the GSL: the confluent hypergeometric
+/// functions 0F1(c; x), M(a, b, x) = 1F1(a; b; x) and U(a, b, x), the Gauss
+/// hypergeometric function 2F1(a, b; c; x) and 2F0(a, b; x).
+/// For further information see Abramowitz & Stegun, Chapters 13 and 15.
+
+use libc::{c_int, c_double};
+use gsl_sf;
+
+#[link(name = "gsl")]
+extern
+{
+    /// Hypergeometric function related to Bessel functions
+    /// 0F1[c,x] =
+    ///            Gamma[c]    x^(1/2(1-c)) I_{c-1}(2 Sqrt[x])
+    ///            Gamma[c] (-x)^(1/2(1-c)) J_{c-1}(2 Sqrt[-x])
+    /// exceptions: GSL_EOVRFLW, GSL_EUNDRFLW
+    fn gsl_sf_hyperg_0F1_e(
+        c: c_double,
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Confluent hypergeometric function  for integer parameters.
+    /// 1F1[m,n,x] = M(m,n,x)
+    /// exceptions:
+    fn gsl_sf_hyperg_1F1_int_e(
+        m: c_int,
+        n: c_int,
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Confluent hypergeometric function.
+    /// 1F1[a,b,x] = M(a,b,x)
+    /// exceptions:
+    fn gsl_sf_hyperg_1F1_e(
+        a: c_double,
+        b: c_double,
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Confluent hypergeometric function for integer parameters.
+    /// U(m,n,x)
+    /// exceptions:
+    fn gsl_sf_hyperg_U_int_e(
+        m: c_int,
+        n: c_int,
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Confluent hypergeometric function for integer parameters.
+    /// U(m,n,x)
+    /// exceptions:
+    fn gsl_sf_hyperg_U_int_e10_e(
+        m: c_int,
+        n: c_int,
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result_e10) -> c_int;
+
+    /// Confluent hypergeometric function.
+    /// U(a,b,x)
+    /// exceptions:
+    fn gsl_sf_hyperg_U_e(
+        a: c_double,
+        b: c_double,
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Confluent hypergeometric function.
+    /// U(a,b,x)
+    /// exceptions:
+    fn gsl_sf_hyperg_U_e10_e(
+        a: c_double,
+        b: c_double,
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result_e10) -> c_int;
+
+    /// Gauss hypergeometric function 2F1[a,b,c,x]
+    /// |x| < 1
+    /// exceptions:
+    fn gsl_sf_hyperg_2F1_e(
+        a: c_double,
+        b: c_double,
+        c: c_double,
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Gauss hypergeometric function
+    /// 2F1[aR + I aI, aR - I aI, c, x]
+    /// |x| < 1
+    /// exceptions:
+    fn gsl_sf_hyperg_2F1_conj_e(
+        aR: c_double,
+        aI: c_double,
+        c: c_double,
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Renormalized Gauss hypergeometric function
+    /// 2F1[a,b,c,x] / Gamma[c]
+    /// |x| < 1
+    /// exceptions:
+    fn gsl_sf_hyperg_2F1_renorm_e(
+        a: c_double,
+        b: c_double,
+        c: c_double,
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Renormalized Gauss hypergeometric function
+    /// 2F1[aR + I aI, aR - I aI, c, x] / Gamma[c]
+    /// |x| < 1
+    /// exceptions:
+    fn gsl_sf_hyperg_2F1_conj_renorm_e(
+        aR: c_double,
+        aI: c_double,
+        c: c_double,
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Mysterious hypergeometric function. The series representation
+    /// is a divergent hypergeometric series. However, for x < 0 we
+    /// have 2F0(a,b,x) = (-1/x)^a U(a,1+a-b,-1/x)
+    /// exceptions: GSL_EDOM
+    fn gsl_sf_hyperg_2F0_e(
+        a: c_double,
+        b: c_double,
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+}
+
+/// Hypergeometric function 0F1(c; x), related to Bessel functions
+pub fn hyperg_0f1(c: f64, x: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_hyperg_0F1_e(c, x, &mut s) != 0
+        {
+            panic!("Hypergeometric function calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Confluent hypergeometric function M(m, n, x) = 1F1(m; n; x) for integer
+/// parameters m, n
+pub fn hyperg_1f1_int(m: i32, n: i32, x: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_hyperg_1F1_int_e(m, n, x, &mut s) != 0
+        {
+            panic!("Hypergeometric function calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Confluent hypergeometric function M(a, b, x) = 1F1(a; b; x)
+pub fn hyperg_1f1(a: f64, b: f64, x: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_hyperg_1F1_e(a, b, x, &mut s) != 0
+        {
+            panic!("Hypergeometric function calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Confluent hypergeometric function U(m, n, x) for integer parameters m, n
+pub fn hyperg_u_int(m: i32, n: i32, x: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_hyperg_U_int_e(m, n, x, &mut s) != 0
+        {
+            panic!("Hypergeometric function calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Confluent hypergeometric function U(m, n, x) for integer parameters m, n,
+/// as an extended-range result, val * 10^e10
+pub fn hyperg_u_int_e10(m: i32, n: i32, x: f64) -> gsl_sf::ScaledResult
+{
+    let mut s = gsl_sf::gsl_sf_result_e10_struct{val: 0f64, err: 0f64, e10: 0};
+    unsafe {
+        if gsl_sf_hyperg_U_int_e10_e(m, n, x, &mut s) != 0
+        {
+            panic!("Hypergeometric function calculation failed");
+        }
+    }
+    gsl_sf::ScaledResult::from(s)
+}
+
+/// Confluent hypergeometric function U(a, b, x)
+pub fn hyperg_u(a: f64, b: f64, x: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_hyperg_U_e(a, b, x, &mut s) != 0
+        {
+            panic!("Hypergeometric function calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Confluent hypergeometric function U(a, b, x) as an extended-range result,
+/// val * 10^e10
+pub fn hyperg_u_e10(a: f64, b: f64, x: f64) -> gsl_sf::ScaledResult
+{
+    let mut s = gsl_sf::gsl_sf_result_e10_struct{val: 0f64, err: 0f64, e10: 0};
+    unsafe {
+        if gsl_sf_hyperg_U_e10_e(a, b, x, &mut s) != 0
+        {
+            panic!("Hypergeometric function calculation failed");
+        }
+    }
+    gsl_sf::ScaledResult::from(s)
+}
+
+/// Gauss hypergeometric function 2F1(a, b; c; x)
+/// |x| < 1
+pub fn hyperg_2f1(a: f64, b: f64, c: f64, x: f64) -> (f64, f64)
+{
+    if x.abs() >= 1f64
+    {
+        panic!("Invalid value for x: {}", x);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_hyperg_2F1_e(a, b, c, x, &mut s) != 0
+        {
+            panic!("Hypergeometric function calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Gauss hypergeometric function 2F1(a_r + i a_i, a_r - i a_i; c; x) with
+/// complex conjugate parameters
+/// |x| < 1
+pub fn hyperg_2f1_conj(a_r: f64, a_i: f64, c: f64, x: f64) -> (f64, f64)
+{
+    if x.abs() >= 1f64
+    {
+        panic!("Invalid value for x: {}", x);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_hyperg_2F1_conj_e(a_r, a_i, c, x, &mut s) != 0
+        {
+            panic!("Hypergeometric function calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Renormalized Gauss hypergeometric function 2F1(a, b; c; x) / Gamma(c)
+/// |x| < 1
+pub fn hyperg_2f1_renorm(a: f64, b: f64, c: f64, x: f64) -> (f64, f64)
+{
+    if x.abs() >= 1f64
+    {
+        panic!("Invalid value for x: {}", x);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_hyperg_2F1_renorm_e(a, b, c, x, &mut s) != 0
+        {
+            panic!("Hypergeometric function calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Renormalized Gauss hypergeometric function with complex conjugate
+/// parameters, 2F1(a_r + i a_i, a_r - i a_i; c; x) / Gamma(c)
+/// |x| < 1
+pub fn hyperg_2f1_conj_renorm(a_r: f64, a_i: f64, c: f64, x: f64) -> (f64, f64)
+{
+    if x.abs() >= 1f64
+    {
+        panic!("Invalid value for x: {}", x);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_hyperg_2F1_conj_renorm_e(a_r, a_i, c, x, &mut s) != 0
+        {
+            panic!("Hypergeometric function calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Hypergeometric function 2F0(a, b; x), defined for x < 0 through
+/// 2F0(a, b, x) = (-1/x)^a U(a, 1+a-b, -1/x)
+pub fn hyperg_2f0(a: f64, b: f64, x: f64) -> (f64, f64)
+{
+    if x >= 0f64
+    {
+        panic!("Invalid value for x: {}", x);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_hyperg_2F0_e(a, b, x, &mut s) != 0
+        {
+            panic!("Hypergeometric function calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
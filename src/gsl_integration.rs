@@ -0,0 +1,720 @@
+/// Adaptive numerical integration of Rust closures, built on the QUADPACK
+/// routines of the GSL: QAGS for finite intervals with integrable
+/// singularities, QAGP for known break points, QAGI for infinite intervals,
+/// QAWO and QAWF for oscillatory sin/cos weights, QAWC for Cauchy principal
+/// values and QAG for a fixed choice of Gauss-Kronrod rule.
+/// Closures are passed to the GSL through a `gsl_function` trampoline. A
+/// panic inside the integrand is caught at the FFI boundary, the integration
+/// is abandoned and the panic resumed once the GSL has returned.
+/// Each routine returns the pair (value, abserr) and panics if the GSL
+/// reports that the requested accuracy could not be reached.
+/// `quad_gk` is a pure Rust adaptive Gauss-Kronrod integrator which does not
+/// call into the GSL at all.
+/// For further information see Piessens et al., QUADPACK (1983).
+
+use libc::{c_int, c_double, c_void, size_t};
+use std::any::Any;
+use std::f64;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use array::Array;
+use gsl_math;
+
+/// Integrand in the layout expected by the GSL
+#[repr(C)]
+#[allow(non_camel_case_types)]
+struct gsl_function
+{
+    function: extern "C" fn(x: c_double, params: *mut c_void) -> c_double,
+    params: *mut c_void,
+}
+
+/// Opaque GSL integration workspace, only ever handled through a pointer
+#[allow(non_camel_case_types)]
+enum gsl_integration_workspace {}
+
+/// Opaque GSL table of Chebyshev moments for QAWO and QAWF
+#[allow(non_camel_case_types)]
+enum gsl_integration_qawo_table {}
+
+#[link(name = "gsl")]
+extern
+{
+    /// Allocates a workspace sufficient to hold n double precision intervals,
+    /// their integration results and error estimates.
+    fn gsl_integration_workspace_alloc(n: size_t) -> *mut gsl_integration_workspace;
+
+    /// Frees the memory associated with the workspace w.
+    fn gsl_integration_workspace_free(w: *mut gsl_integration_workspace);
+
+    /// Applies an integration rule adaptively until an estimate of the
+    /// integral of f over (a, b) is achieved within the desired absolute and
+    /// relative error limits. The rule is chosen by key, from 15 (key = 1) to
+    /// 61 (key = 6) point Gauss-Kronrod.
+    fn gsl_integration_qag(
+        f: *const gsl_function,
+        a: c_double, b: c_double,
+        epsabs: c_double, epsrel: c_double,
+        limit: size_t, key: c_int,
+        workspace: *mut gsl_integration_workspace,
+        result: *mut c_double, abserr: *mut c_double) -> c_int;
+
+    /// Applies the 21-point Gauss-Kronrod rule adaptively, accelerating the
+    /// convergence with the epsilon-algorithm, which handles integrable
+    /// singularities in the interval.
+    fn gsl_integration_qags(
+        f: *const gsl_function,
+        a: c_double, b: c_double,
+        epsabs: c_double, epsrel: c_double,
+        limit: size_t,
+        workspace: *mut gsl_integration_workspace,
+        result: *mut c_double, abserr: *mut c_double) -> c_int;
+
+    /// Applies QAGS taking account of the locations of singular points given
+    /// in pts, an array of length npts holding the end points and break
+    /// points of the integration region in ascending order.
+    fn gsl_integration_qagp(
+        f: *const gsl_function,
+        pts: *const c_double, npts: size_t,
+        epsabs: c_double, epsrel: c_double,
+        limit: size_t,
+        workspace: *mut gsl_integration_workspace,
+        result: *mut c_double, abserr: *mut c_double) -> c_int;
+
+    /// Computes the integral of f over (-inf, +inf) by mapping it onto (0, 1].
+    fn gsl_integration_qagi(
+        f: *const gsl_function,
+        epsabs: c_double, epsrel: c_double,
+        limit: size_t,
+        workspace: *mut gsl_integration_workspace,
+        result: *mut c_double, abserr: *mut c_double) -> c_int;
+
+    /// Computes the integral of f over (a, +inf) by mapping it onto (0, 1].
+    fn gsl_integration_qagiu(
+        f: *const gsl_function,
+        a: c_double,
+        epsabs: c_double, epsrel: c_double,
+        limit: size_t,
+        workspace: *mut gsl_integration_workspace,
+        result: *mut c_double, abserr: *mut c_double) -> c_int;
+
+    /// Computes the integral of f over (-inf, b) by mapping it onto (0, 1].
+    fn gsl_integration_qagil(
+        f: *const gsl_function,
+        b: c_double,
+        epsabs: c_double, epsrel: c_double,
+        limit: size_t,
+        workspace: *mut gsl_integration_workspace,
+        result: *mut c_double, abserr: *mut c_double) -> c_int;
+
+    /// Computes the Cauchy principal value of the integral of f(x) / (x - c)
+    /// over (a, b), with a singularity at c.
+    fn gsl_integration_qawc(
+        f: *const gsl_function,
+        a: c_double, b: c_double, c: c_double,
+        epsabs: c_double, epsrel: c_double,
+        limit: size_t,
+        workspace: *mut gsl_integration_workspace,
+        result: *mut c_double, abserr: *mut c_double) -> c_int;
+
+    /// Allocates a table of Chebyshev moments for the weight sin(omega x)
+    /// (sine = 1) or cos(omega x) (sine = 0) over an interval of length L,
+    /// with n levels of bisection.
+    fn gsl_integration_qawo_table_alloc(
+        omega: c_double, l: c_double,
+        sine: c_int, n: size_t) -> *mut gsl_integration_qawo_table;
+
+    /// Frees all the memory associated with the table t.
+    fn gsl_integration_qawo_table_free(t: *mut gsl_integration_qawo_table);
+
+    /// Uses an adaptive algorithm to compute the integral of f over (a, a + L)
+    /// with the weight sin(omega x) or cos(omega x) defined by the table wf.
+    fn gsl_integration_qawo(
+        f: *const gsl_function,
+        a: c_double,
+        epsabs: c_double, epsrel: c_double,
+        limit: size_t,
+        workspace: *mut gsl_integration_workspace,
+        wf: *mut gsl_integration_qawo_table,
+        result: *mut c_double, abserr: *mut c_double) -> c_int;
+
+    /// Computes the Fourier integral of f over (a, +inf) with the weight
+    /// sin(omega x) or cos(omega x), integrating over successive cycles and
+    /// extrapolating the sum. Only an absolute error limit is accepted.
+    fn gsl_integration_qawf(
+        f: *const gsl_function,
+        a: c_double,
+        epsabs: c_double,
+        limit: size_t,
+        workspace: *mut gsl_integration_workspace,
+        cycle_workspace: *mut gsl_integration_workspace,
+        wf: *mut gsl_integration_qawo_table,
+        result: *mut c_double, abserr: *mut c_double) -> c_int;
+}
+
+
+/////////////
+// Options //
+/////////////
+
+/// Error limits and subdivision limit for the adaptive integrators
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct QuadOptions
+{
+    /// Absolute error limit
+    pub epsabs: f64,
+    /// Relative error limit
+    pub epsrel: f64,
+    /// Maximum number of subintervals
+    pub limit: usize,
+}
+
+impl Default for QuadOptions
+{
+    /// The SciPy defaults: 1.49e-8 absolute and relative, 50 subintervals
+    fn default() -> QuadOptions
+    {
+        QuadOptions{epsabs: 1.49e-8, epsrel: 1.49e-8, limit: 50}
+    }
+}
+
+/// Gauss-Kronrod rules available to `quad_rule`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Rule
+{
+    GK15,
+    GK21,
+    GK31,
+    GK41,
+    GK51,
+    GK61,
+}
+
+/// Oscillatory weight for `quad_oscillatory` and `quad_fourier`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Weight
+{
+    Cos,
+    Sin,
+}
+
+/// Number of bisection levels in the Chebyshev moment tables
+const QAWO_LEVELS: usize = 50;
+
+
+/////////////////////////
+// Closure trampolines //
+/////////////////////////
+
+/// An integrand together with the payload of any panic it raised
+struct Callback<F>
+{
+    f: F,
+    panic: Option<Box<Any + Send + 'static>>,
+}
+
+/// Evaluates the closure behind params for the GSL. A panic is not allowed
+/// to unwind into C: it is stored and NaN returned, as are all further
+/// evaluations, so that the GSL gives up and `integrate_with` can resume it.
+extern "C" fn trampoline<F>(x: c_double, params: *mut c_void) -> c_double
+    where F: Fn(f64) -> f64
+{
+    let cb = unsafe { &mut *(params as *mut Callback<F>) };
+    if cb.panic.is_some()
+    {
+        return f64::NAN;
+    }
+    match panic::catch_unwind(AssertUnwindSafe(|| (cb.f)(x)))
+    {
+        Ok(y) => y,
+        Err(payload) => {
+            cb.panic = Some(payload);
+            f64::NAN
+        },
+    }
+}
+
+/// Integration workspace, freed when dropped
+struct Workspace
+{
+    work: *mut gsl_integration_workspace,
+}
+
+impl Workspace
+{
+    fn new(limit: usize) -> Workspace
+    {
+        if limit == 0
+        {
+            panic!("Invalid value for limit: {}", limit);
+        }
+        let work = unsafe { gsl_integration_workspace_alloc(limit) };
+        if work.is_null()
+        {
+            panic!("Integration workspace allocation failed");
+        }
+        Workspace{work: work}
+    }
+}
+
+impl Drop for Workspace
+{
+    fn drop(&mut self)
+    {
+        unsafe {
+            gsl_integration_workspace_free(self.work);
+        }
+    }
+}
+
+/// Table of Chebyshev moments for the oscillatory weights, freed when dropped
+struct QawoTable
+{
+    table: *mut gsl_integration_qawo_table,
+}
+
+impl QawoTable
+{
+    fn new(omega: f64, length: f64, weight: Weight) -> QawoTable
+    {
+        let sine = match weight
+        {
+            Weight::Cos => 0,
+            Weight::Sin => 1,
+        };
+        let table = unsafe {
+            gsl_integration_qawo_table_alloc(omega, length, sine, QAWO_LEVELS)
+        };
+        if table.is_null()
+        {
+            panic!("Integration table allocation failed");
+        }
+        QawoTable{table: table}
+    }
+}
+
+impl Drop for QawoTable
+{
+    fn drop(&mut self)
+    {
+        unsafe {
+            gsl_integration_qawo_table_free(self.table);
+        }
+    }
+}
+
+/// Panics with the meaning of a nonzero GSL integration status
+fn check_status(status: c_int)
+{
+    let reason = match status
+    {
+        0 => return,
+        1 => "input domain error",
+        4 => "invalid argument",
+        8 => "out of memory",
+        11 => "maximum number of subdivisions reached",
+        13 => "invalid tolerance",
+        14 => "failed to reach the requested tolerance",
+        18 => "roundoff error prevents the requested tolerance",
+        21 => "extremely bad integrand behavior",
+        22 => "integral is divergent or slowly convergent",
+        26 => "table limit exceeded",
+        _ => "unknown error",
+    };
+    panic!("Integration failed: {} (GSL status {})", reason, status);
+}
+
+/// Wraps f in a `gsl_function`, hands it to call together with a workspace
+/// of the given limit and returns the (value, abserr) written by the GSL.
+/// A panic raised by f is resumed here, before the status is examined.
+/// GSL's error handler is switched off only for the duration of the call,
+/// so that failures come back as a status instead of aborting.
+fn integrate_with<F, G>(f: F, limit: usize, call: G) -> (f64, f64)
+    where F: Fn(f64) -> f64,
+          G: FnOnce(*const gsl_function, *mut gsl_integration_workspace,
+                    *mut c_double, *mut c_double) -> c_int
+{
+    let _handler = gsl_math::ErrorHandlerGuard::off();
+    let workspace = Workspace::new(limit);
+    let mut cb = Callback{f: f, panic: None};
+    let function = gsl_function{
+        function: trampoline::<F>,
+        params: &mut cb as *mut Callback<F> as *mut c_void,
+    };
+    let mut result = 0f64;
+    let mut abserr = 0f64;
+    let status = call(&function, workspace.work, &mut result, &mut abserr);
+    if let Some(payload) = cb.panic.take()
+    {
+        panic::resume_unwind(payload);
+    }
+    check_status(status);
+    (result, abserr)
+}
+
+/// Panics unless a and b are finite
+fn check_finite(a: f64, b: f64)
+{
+    if !a.is_finite() || !b.is_finite()
+    {
+        panic!("Integration limits must be finite: {}, {}", a, b);
+    }
+}
+
+
+//////////////
+// QUADPACK //
+//////////////
+
+/// Integral of f over (a, b) with the default `QuadOptions`, as SciPy's
+/// quad. Either limit may be infinite.
+pub fn quad<F>(f: F, a: f64, b: f64) -> (f64, f64)
+    where F: Fn(f64) -> f64
+{
+    quad_with(f, a, b, &QuadOptions::default())
+}
+
+/// Integral of f over (a, b). Finite intervals use QAGS, which copes with
+/// integrable end point singularities; infinite limits use QAGI, QAGIU or
+/// QAGIL. If b < a the integral over (b, a) is negated.
+pub fn quad_with<F>(f: F, a: f64, b: f64, opts: &QuadOptions) -> (f64, f64)
+    where F: Fn(f64) -> f64
+{
+    if a.is_nan() || b.is_nan()
+    {
+        panic!("Invalid integration limits: {}, {}", a, b);
+    }
+    if a == b
+    {
+        return (0f64, 0f64);
+    }
+    if b < a
+    {
+        let (value, abserr) = quad_with(f, b, a, opts);
+        return (-value, abserr);
+    }
+    let (epsabs, epsrel, limit) = (opts.epsabs, opts.epsrel, opts.limit);
+    integrate_with(f, limit, |func, work, result, abserr| {
+        unsafe {
+            match (a.is_infinite(), b.is_infinite())
+            {
+                (false, false) => gsl_integration_qags(
+                    func, a, b, epsabs, epsrel, limit, work, result, abserr),
+                (false, true) => gsl_integration_qagiu(
+                    func, a, epsabs, epsrel, limit, work, result, abserr),
+                (true, false) => gsl_integration_qagil(
+                    func, b, epsabs, epsrel, limit, work, result, abserr),
+                (true, true) => gsl_integration_qagi(
+                    func, epsabs, epsrel, limit, work, result, abserr),
+            }
+        }
+    })
+}
+
+/// Integral of f over the finite interval (a, b) with the given
+/// Gauss-Kronrod rule applied adaptively (QAG), without extrapolation.
+/// Higher order rules suit smooth integrands, lower order ones integrands
+/// with local difficulties.
+pub fn quad_rule<F>(f: F, a: f64, b: f64, rule: Rule, opts: &QuadOptions) -> (f64, f64)
+    where F: Fn(f64) -> f64
+{
+    check_finite(a, b);
+    let key = match rule
+    {
+        Rule::GK15 => 1,
+        Rule::GK21 => 2,
+        Rule::GK31 => 3,
+        Rule::GK41 => 4,
+        Rule::GK51 => 5,
+        Rule::GK61 => 6,
+    };
+    let (epsabs, epsrel, limit) = (opts.epsabs, opts.epsrel, opts.limit);
+    integrate_with(f, limit, |func, work, result, abserr| {
+        unsafe {
+            gsl_integration_qag(func, a, b, epsabs, epsrel, limit, key,
+                                work, result, abserr)
+        }
+    })
+}
+
+/// Integral of f over the finite interval (a, b) where f has singularities
+/// or discontinuities at the given points (QAGP). Points outside (a, b) are
+/// ignored, and they need not be sorted.
+pub fn quad_points<F>(f: F, a: f64, b: f64, points: &Array<f64>,
+                      opts: &QuadOptions) -> (f64, f64)
+    where F: Fn(f64) -> f64
+{
+    check_finite(a, b);
+    if b < a
+    {
+        let (value, abserr) = quad_points(f, b, a, points, opts);
+        return (-value, abserr);
+    }
+    let mut pts = vec![a];
+    let mut inner: Vec<f64> = points.as_slice().iter()
+        .cloned()
+        .filter(|&p| p > a && p < b)
+        .collect();
+    inner.sort_by(|x, y| x.partial_cmp(y).unwrap());
+    inner.dedup();
+    pts.extend(inner);
+    pts.push(b);
+    let (epsabs, epsrel, limit) = (opts.epsabs, opts.epsrel, opts.limit);
+    // QAGP needs room for at least one subinterval per break point
+    let limit = if limit < pts.len() { pts.len() } else { limit };
+    integrate_with(f, limit, |func, work, result, abserr| {
+        unsafe {
+            gsl_integration_qagp(func, pts.as_ptr(), pts.len(), epsabs, epsrel,
+                                 limit, work, result, abserr)
+        }
+    })
+}
+
+/// Cauchy principal value of the integral of f(x) / (x - c) over the finite
+/// interval (a, b) (QAWC). c must not coincide with either limit.
+pub fn quad_cauchy<F>(f: F, a: f64, b: f64, c: f64, opts: &QuadOptions) -> (f64, f64)
+    where F: Fn(f64) -> f64
+{
+    check_finite(a, b);
+    if c == a || c == b || !c.is_finite()
+    {
+        panic!("Invalid value for c: {}", c);
+    }
+    let (epsabs, epsrel, limit) = (opts.epsabs, opts.epsrel, opts.limit);
+    integrate_with(f, limit, |func, work, result, abserr| {
+        unsafe {
+            gsl_integration_qawc(func, a, b, c, epsabs, epsrel, limit,
+                                 work, result, abserr)
+        }
+    })
+}
+
+/// Integral of f(x) cos(omega x) or f(x) sin(omega x) over the finite
+/// interval (a, b) (QAWO)
+pub fn quad_oscillatory<F>(f: F, a: f64, b: f64, omega: f64, weight: Weight,
+                           opts: &QuadOptions) -> (f64, f64)
+    where F: Fn(f64) -> f64
+{
+    check_finite(a, b);
+    if b < a
+    {
+        let (value, abserr) = quad_oscillatory(f, b, a, omega, weight, opts);
+        return (-value, abserr);
+    }
+    if a == b
+    {
+        return (0f64, 0f64);
+    }
+    let table = QawoTable::new(omega, b - a, weight);
+    let (epsabs, epsrel, limit) = (opts.epsabs, opts.epsrel, opts.limit);
+    integrate_with(f, limit, |func, work, result, abserr| {
+        unsafe {
+            gsl_integration_qawo(func, a, epsabs, epsrel, limit, work,
+                                 table.table, result, abserr)
+        }
+    })
+}
+
+/// Fourier integral of f(x) cos(omega x) or f(x) sin(omega x) over
+/// (a, +inf) (QAWF). Only the absolute error limit of opts is used; f should
+/// decay so that the integral converges.
+pub fn quad_fourier<F>(f: F, a: f64, omega: f64, weight: Weight,
+                       opts: &QuadOptions) -> (f64, f64)
+    where F: Fn(f64) -> f64
+{
+    if !a.is_finite()
+    {
+        panic!("Invalid value for a: {}", a);
+    }
+    if omega == 0f64
+    {
+        panic!("Invalid value for omega: {}", omega);
+    }
+    // The table length is reset by QAWF for every cycle
+    let table = QawoTable::new(omega, 1f64, weight);
+    let cycles = Workspace::new(opts.limit);
+    let (epsabs, limit) = (opts.epsabs, opts.limit);
+    integrate_with(f, limit, |func, work, result, abserr| {
+        unsafe {
+            gsl_integration_qawf(func, a, epsabs, limit, work, cycles.work,
+                                 table.table, result, abserr)
+        }
+    })
+}
+
+
+////////////////////////
+// Multiple integrals //
+////////////////////////
+
+/// Double integral of f(x, y) for x in (a, b) and y in (gfun(x), hfun(x)),
+/// computed as nested `quad_with` calls with the same options at each level.
+/// The error returned is the estimate for the outer integral.
+pub fn dblquad<F, G, H>(f: F, a: f64, b: f64, gfun: G, hfun: H,
+                        opts: &QuadOptions) -> (f64, f64)
+    where F: Fn(f64, f64) -> f64,
+          G: Fn(f64) -> f64,
+          H: Fn(f64) -> f64
+{
+    quad_with(|x| quad_with(|y| f(x, y), gfun(x), hfun(x), opts).0, a, b, opts)
+}
+
+/// Triple integral of f(x, y, z) for x in (a, b), y in (gfun(x), hfun(x))
+/// and z in (qfun(x, y), rfun(x, y)), computed as nested `quad_with` calls.
+/// The error returned is the estimate for the outermost integral.
+pub fn tplquad<F, G, H, Q, R>(f: F, a: f64, b: f64, gfun: G, hfun: H,
+                              qfun: Q, rfun: R, opts: &QuadOptions) -> (f64, f64)
+    where F: Fn(f64, f64, f64) -> f64,
+          G: Fn(f64) -> f64,
+          H: Fn(f64) -> f64,
+          Q: Fn(f64, f64) -> f64,
+          R: Fn(f64, f64) -> f64
+{
+    dblquad(|x, y| quad_with(|z| f(x, y, z), qfun(x, y), rfun(x, y), opts).0,
+            a, b, gfun, hfun, opts)
+}
+
+
+/////////////////////////////
+// Pure Rust Gauss-Kronrod //
+/////////////////////////////
+
+/// Abscissae of the 15-point Kronrod rule; the odd entries, with the
+/// centre, are the 7-point Gauss abscissae
+const XGK15: [f64; 8] = [
+    0.991455371120812639206854697526329,
+    0.949107912342758524526189684047851,
+    0.864864423359769072789712788640926,
+    0.741531185599394439863864773280788,
+    0.586087235467691130294144845693013,
+    0.405845151377397166906606412076961,
+    0.207784955007898467600689403773245,
+    0.000000000000000000000000000000000,
+];
+
+/// Weights of the 15-point Kronrod rule
+const WGK15: [f64; 8] = [
+    0.022935322010529224963732008058970,
+    0.063092092629978553290700663189204,
+    0.104790010322250183839876322541518,
+    0.140653259715525918745189590510238,
+    0.169004726639267902826583426598550,
+    0.190350578064785409913256402421014,
+    0.204432940075298892414161999234649,
+    0.209482141084727828012999174891714,
+];
+
+/// Weights of the 7-point Gauss rule
+const WG7: [f64; 4] = [
+    0.129484966168869693270611432679082,
+    0.279705391489276667901467771423780,
+    0.381830050505118944950369775488975,
+    0.417959183673469387755102040816327,
+];
+
+/// 15-point Kronrod estimate of the integral of f over (a, b) and its
+/// difference from the embedded 7-point Gauss estimate
+fn gk15<F>(f: &F, a: f64, b: f64) -> (f64, f64)
+    where F: Fn(f64) -> f64
+{
+    let centre = 0.5 * (a + b);
+    let half = 0.5 * (b - a);
+    let fc = f(centre);
+    let mut kronrod = fc * WGK15[7];
+    let mut gauss = fc * WG7[3];
+    for j in 0..7
+    {
+        let dx = half * XGK15[j];
+        let pair = f(centre - dx) + f(centre + dx);
+        kronrod += WGK15[j] * pair;
+        if j % 2 == 1
+        {
+            gauss += WG7[j / 2] * pair;
+        }
+    }
+    (kronrod * half, ((kronrod - gauss) * half).abs())
+}
+
+/// Adaptive Gauss-Kronrod integration over the finite interval (a, b),
+/// always bisecting the subinterval with the largest error estimate
+fn adaptive_gk<F>(f: &F, a: f64, b: f64, opts: &QuadOptions) -> (f64, f64)
+    where F: Fn(f64) -> f64
+{
+    let (value, error) = gk15(f, a, b);
+    let mut intervals = vec![(a, b, value, error)];
+    let mut total = value;
+    let mut total_err = error;
+    while total_err > opts.epsabs.max(opts.epsrel * total.abs())
+    {
+        if intervals.len() >= opts.limit
+        {
+            panic!("Integration failed: maximum number of subdivisions reached");
+        }
+        let worst = (0..intervals.len())
+            .fold(0, |w, i| if intervals[i].3 > intervals[w].3 { i } else { w });
+        let (lo, hi, v, e) = intervals.swap_remove(worst);
+        let mid = 0.5 * (lo + hi);
+        if mid <= lo || mid >= hi
+        {
+            panic!("Integration failed: roundoff error prevents the requested tolerance");
+        }
+        let (v1, e1) = gk15(f, lo, mid);
+        let (v2, e2) = gk15(f, mid, hi);
+        intervals.push((lo, mid, v1, e1));
+        intervals.push((mid, hi, v2, e2));
+        total += v1 + v2 - v;
+        total_err += e1 + e2 - e;
+        if !total.is_finite()
+        {
+            panic!("Integration failed: non-finite integrand value");
+        }
+    }
+    // Resum to shed the rounding accumulated by the running updates
+    let value = intervals.iter().fold(0f64, |s, iv| s + iv.2);
+    let error = intervals.iter().fold(0f64, |s, iv| s + iv.3);
+    (value, error)
+}
+
+/// Integral of f over (a, b) by adaptive 15-point Gauss-Kronrod quadrature
+/// implemented in Rust, for use where the GSL is unavailable or undesirable.
+/// Infinite limits are handled by the QUADPACK substitutions
+/// x = a + (1 - t) / t for (a, +inf) and x = (1 - t) / t, -x for (-inf, +inf).
+/// The error estimate is the sum of the Gauss-Kronrod differences, which is
+/// more pessimistic than QUADPACK's, and there is no extrapolation, so
+/// singular integrands need a larger limit than with `quad_with`.
+pub fn quad_gk<F>(f: F, a: f64, b: f64, opts: &QuadOptions) -> (f64, f64)
+    where F: Fn(f64) -> f64
+{
+    if a.is_nan() || b.is_nan()
+    {
+        panic!("Invalid integration limits: {}, {}", a, b);
+    }
+    if a == b
+    {
+        return (0f64, 0f64);
+    }
+    if b < a
+    {
+        let (value, abserr) = quad_gk(f, b, a, opts);
+        return (-value, abserr);
+    }
+    match (a.is_infinite(), b.is_infinite())
+    {
+        (false, false) => adaptive_gk(&f, a, b, opts),
+        (false, true) => {
+            let g = |t: f64| { let x = (1f64 - t) / t; f(a + x) / (t * t) };
+            adaptive_gk(&g, 0f64, 1f64, opts)
+        },
+        (true, false) => {
+            let g = |t: f64| { let x = (1f64 - t) / t; f(b - x) / (t * t) };
+            adaptive_gk(&g, 0f64, 1f64, opts)
+        },
+        (true, true) => {
+            let g = |t: f64| {
+                let x = (1f64 - t) / t;
+                (f(x) + f(-x)) / (t * t)
+            };
+            adaptive_gk(&g, 0f64, 1f64, opts)
+        },
+    }
+}
@@ -0,0 +1,63 @@
+/// Lambert W functions from the GSL. W(x) is defined to be a solution of
+/// the equation W(x) exp(W(x)) = x. For -1/e < x < 0 there are two real
+/// branches: the principal branch W_0(x) >= -1 and the secondary branch
+/// W_{-1}(x) <= -1.
+/// For further information see Corless et al, Adv. Comput. Math. 5, 329 (1996).
+
+use libc::{c_int, c_double};
+use gsl_sf;
+use std::f64::consts::E;
+
+#[link(name = "gsl")]
+extern
+{
+    /// Lambert's Function W_0(x)
+    /// W_0(x) is the principal branch of the implicit function defined by
+    /// W e^W = x.
+    /// -1/E < x < \infty
+    /// exceptions: GSL_EMAXITER
+    fn gsl_sf_lambert_W0_e(x: c_double, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Lambert's Function W_{-1}(x)
+    /// W_{-1}(x) is the second real branch of the implicit function defined
+    /// by W e^W = x. It agrees with W_0(x) when x >= 0.
+    /// -1/E < x < \infty
+    /// exceptions: GSL_EMAXITER
+    fn gsl_sf_lambert_Wm1_e(x: c_double, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+}
+
+/// Principal branch of the Lambert W function, W_0(x)
+/// x >= -1/e
+pub fn lambert_w0(x: f64) -> (f64, f64)
+{
+    if x < -1f64 / E
+    {
+        panic!("Invalid value for x: {}", x);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_lambert_W0_e(x, &mut s) != 0
+        {
+            panic!("Lambert W calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Secondary real branch of the Lambert W function, W_{-1}(x)
+/// -1/e <= x < 0.0
+pub fn lambert_wm1(x: f64) -> (f64, f64)
+{
+    if x < -1f64 / E || x >= 0f64
+    {
+        panic!("Invalid value for x: {}", x);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_lambert_Wm1_e(x, &mut s) != 0
+        {
+            panic!("Lambert W calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
@@ -1,5 +1,7 @@
 /// Basic GSL mathematical function alternatives for Rust's standard library
-use libc::{c_int, c_double, c_uint};
+use libc::{c_int, c_double, c_uint, c_void};
+use std::sync::atomic::{AtomicBool, AtomicUsize, ATOMIC_BOOL_INIT, ATOMIC_USIZE_INIT};
+use std::sync::atomic::Ordering;
 
 #[link(name = "gsl")]
 extern
@@ -67,6 +69,16 @@ extern
     /// testing whether a value is approximately zero.  The implementation is
     /// based on the package fcmp by T.C. Belding.
     fn gsl_fcmp(x: c_double, y: c_double, epsilon: c_double) -> c_int;
+
+    /// This function turns off the error handler by defining an error handler
+    /// which does nothing. This will cause the program to continue after any
+    /// error, so the return values from any library routines must be checked.
+    /// The previous handler is returned.
+    fn gsl_set_error_handler_off() -> *mut c_void;
+
+    /// This function sets a new error handler for the GSL library routines.
+    /// The previous handler is returned so that it can be restored later.
+    fn gsl_set_error_handler(new_handler: *mut c_void) -> *mut c_void;
 }
 
 /// Computes the value of log(1 + x) in a way that is accurate for small x.
@@ -180,6 +192,62 @@ pub fn gslmath_fcmp(x: f64, y: f64, epsilon: f64) -> bool
     }
 }
 
+/// Number of live `ErrorHandlerGuard`s, the handler they replaced and a spin
+/// lock over both
+static GUARDS: AtomicUsize = ATOMIC_USIZE_INIT;
+static PREVIOUS_HANDLER: AtomicUsize = ATOMIC_USIZE_INIT;
+static GUARD_LOCK: AtomicBool = ATOMIC_BOOL_INIT;
+
+fn with_guard_lock<F: FnOnce()>(f: F)
+{
+    while GUARD_LOCK.compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed).is_err()
+    {
+    }
+    f();
+    GUARD_LOCK.store(false, Ordering::Release);
+}
+
+/// Switches GSL's error handler, which aborts the process by default, off
+/// for as long as the guard lives, so that errors only reach the caller
+/// through the status codes of the routines. The previous handler is
+/// restored when the last of the guards alive at the same time is dropped,
+/// whichever thread holds it.
+pub struct ErrorHandlerGuard
+{
+    _private: (),
+}
+
+impl ErrorHandlerGuard
+{
+    pub fn off() -> ErrorHandlerGuard
+    {
+        with_guard_lock(|| {
+            if GUARDS.fetch_add(1, Ordering::Relaxed) == 0
+            {
+                let previous = unsafe { gsl_set_error_handler_off() };
+                PREVIOUS_HANDLER.store(previous as usize, Ordering::Relaxed);
+            }
+        });
+        ErrorHandlerGuard{_private: ()}
+    }
+}
+
+impl Drop for ErrorHandlerGuard
+{
+    fn drop(&mut self)
+    {
+        with_guard_lock(|| {
+            if GUARDS.fetch_sub(1, Ordering::Relaxed) == 1
+            {
+                let previous = PREVIOUS_HANDLER.load(Ordering::Relaxed);
+                unsafe {
+                    gsl_set_error_handler(previous as *mut c_void);
+                }
+            }
+        });
+    }
+}
+
 /// Convenience macro for doing assert_eq using fcmp
 #[macro_export]
 macro_rules! assert_epeq {
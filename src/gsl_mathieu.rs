@@ -0,0 +1,488 @@
+/// Mathieu functions from the GSL: the characteristic values a_n(q), b_n(q),
+/// the angular Mathieu functions ce_n(q, z), se_n(q, z), which are the even
+/// and odd periodic solutions of
+///     y'' + (a - 2q cos 2z) y = 0,
+/// and the radial Mathieu functions Mc_n^(j)(q, z), Ms_n^(j)(q, z), solutions
+/// of the modified equation y'' - (a - 2q cosh 2z) y = 0.
+/// The array functions require a `MathieuWorkspace`, which may be reused
+/// across calls and is freed when it goes out of scope.
+/// For further information see Abramowitz & Stegun, Chapter 20.
+
+use libc::{c_int, c_double, size_t};
+use gsl_sf;
+use array::Array;
+use array::Order;
+
+/// Opaque GSL Mathieu workspace, only ever handled through a pointer
+#[allow(non_camel_case_types)]
+enum gsl_sf_mathieu_workspace {}
+
+#[link(name = "gsl")]
+extern
+{
+    /// Allocates a workspace for the array routines, for orders up to nn and
+    /// values of q up to qq.
+    fn gsl_sf_mathieu_alloc(nn: size_t, qq: c_double) -> *mut gsl_sf_mathieu_workspace;
+
+    /// Frees a workspace allocated by gsl_sf_mathieu_alloc.
+    fn gsl_sf_mathieu_free(workspace: *mut gsl_sf_mathieu_workspace);
+
+    /// Characteristic value a_n(q) for the even Mathieu functions
+    /// exceptions: GSL_EMAXITER
+    fn gsl_sf_mathieu_a_e(
+        order: c_int,
+        qq: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Characteristic value b_n(q) for the odd Mathieu functions
+    /// exceptions: GSL_EMAXITER
+    fn gsl_sf_mathieu_b_e(
+        order: c_int,
+        qq: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Characteristic values a_n(q) for order_min <= n <= order_max
+    /// exceptions: GSL_EINVAL
+    fn gsl_sf_mathieu_a_array(
+        order_min: c_int,
+        order_max: c_int,
+        qq: c_double,
+        work: *mut gsl_sf_mathieu_workspace,
+        result_array: *mut c_double) -> c_int;
+
+    /// Characteristic values b_n(q) for order_min <= n <= order_max
+    /// exceptions: GSL_EINVAL
+    fn gsl_sf_mathieu_b_array(
+        order_min: c_int,
+        order_max: c_int,
+        qq: c_double,
+        work: *mut gsl_sf_mathieu_workspace,
+        result_array: *mut c_double) -> c_int;
+
+    /// Angular Mathieu function ce_n(q, z)
+    /// exceptions: GSL_EDOM
+    fn gsl_sf_mathieu_ce_e(
+        order: c_int,
+        qq: c_double,
+        zz: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Angular Mathieu function se_n(q, z)
+    /// exceptions: GSL_EDOM
+    fn gsl_sf_mathieu_se_e(
+        order: c_int,
+        qq: c_double,
+        zz: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Angular Mathieu functions ce_n(q, z) for nmin <= n <= nmax
+    /// exceptions: GSL_EINVAL
+    fn gsl_sf_mathieu_ce_array(
+        nmin: c_int,
+        nmax: c_int,
+        qq: c_double,
+        zz: c_double,
+        work: *mut gsl_sf_mathieu_workspace,
+        result_array: *mut c_double) -> c_int;
+
+    /// Angular Mathieu functions se_n(q, z) for nmin <= n <= nmax
+    /// exceptions: GSL_EINVAL
+    fn gsl_sf_mathieu_se_array(
+        nmin: c_int,
+        nmax: c_int,
+        qq: c_double,
+        zz: c_double,
+        work: *mut gsl_sf_mathieu_workspace,
+        result_array: *mut c_double) -> c_int;
+
+    /// Radial Mathieu function Mc_n^(j)(q, z) of the first (j = 1) or
+    /// second (j = 2) kind
+    /// exceptions: GSL_EDOM
+    fn gsl_sf_mathieu_Mc_e(
+        kind: c_int,
+        order: c_int,
+        qq: c_double,
+        zz: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Radial Mathieu function Ms_n^(j)(q, z) of the first (j = 1) or
+    /// second (j = 2) kind
+    /// exceptions: GSL_EDOM
+    fn gsl_sf_mathieu_Ms_e(
+        kind: c_int,
+        order: c_int,
+        qq: c_double,
+        zz: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Radial Mathieu functions Mc_n^(j)(q, z) for nmin <= n <= nmax
+    /// exceptions: GSL_EINVAL
+    fn gsl_sf_mathieu_Mc_array(
+        kind: c_int,
+        nmin: c_int,
+        nmax: c_int,
+        qq: c_double,
+        zz: c_double,
+        work: *mut gsl_sf_mathieu_workspace,
+        result_array: *mut c_double) -> c_int;
+
+    /// Radial Mathieu functions Ms_n^(j)(q, z) for nmin <= n <= nmax
+    /// exceptions: GSL_EINVAL
+    fn gsl_sf_mathieu_Ms_array(
+        kind: c_int,
+        nmin: c_int,
+        nmax: c_int,
+        qq: c_double,
+        zz: c_double,
+        work: *mut gsl_sf_mathieu_workspace,
+        result_array: *mut c_double) -> c_int;
+}
+
+///////////////
+// Workspace //
+///////////////
+
+/// Workspace for the Mathieu array functions, sized for orders up to `nmax`
+/// and values of q up to `qmax`. The underlying GSL allocation is released
+/// when the workspace is dropped.
+pub struct MathieuWorkspace
+{
+    work: *mut gsl_sf_mathieu_workspace,
+    nmax: i32,
+    qmax: f64,
+}
+
+impl MathieuWorkspace
+{
+    /// Allocates a workspace for orders 0 <= n <= nmax and |q| <= qmax
+    pub fn new(nmax: i32, qmax: f64) -> MathieuWorkspace
+    {
+        if nmax < 0
+        {
+            panic!("Invalid value for nmax: {}", nmax);
+        }
+        if qmax < 0f64
+        {
+            panic!("Invalid value for qmax: {}", qmax);
+        }
+        let work = unsafe { gsl_sf_mathieu_alloc(nmax as size_t, qmax) };
+        if work.is_null()
+        {
+            panic!("Mathieu workspace allocation failed");
+        }
+        MathieuWorkspace{work: work, nmax: nmax, qmax: qmax}
+    }
+
+    /// Largest order the workspace can compute
+    pub fn nmax(&self) -> i32
+    {
+        self.nmax
+    }
+
+    /// Largest |q| the workspace can compute
+    pub fn qmax(&self) -> f64
+    {
+        self.qmax
+    }
+
+    /// Panics unless 0 <= nmin <= nmax fits in the workspace and |q| <= qmax
+    fn check(&self, nmin: i32, nmax: i32, q: f64)
+    {
+        if nmin < 0 || nmin > nmax || nmax > self.nmax
+        {
+            panic!("Invalid order range for workspace of size {}: [{}, {}]",
+                   self.nmax, nmin, nmax);
+        }
+        if q.abs() > self.qmax
+        {
+            panic!("Invalid value for q: {}, workspace allows |q| <= {}", q, self.qmax);
+        }
+    }
+}
+
+impl Drop for MathieuWorkspace
+{
+    fn drop(&mut self)
+    {
+        unsafe {
+            gsl_sf_mathieu_free(self.work);
+        }
+    }
+}
+
+///////////////////////////
+// Characteristic Values //
+///////////////////////////
+
+/// Characteristic value a_n(q) of the even Mathieu functions ce_n
+/// n >= 0
+pub fn mathieu_a(n: i32, q: f64) -> (f64, f64)
+{
+    if n < 0
+    {
+        panic!("Invalid value for n: {}", n);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_mathieu_a_e(n, q, &mut s) != 0
+        {
+            panic!("Mathieu calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Characteristic value b_n(q) of the odd Mathieu functions se_n
+/// n >= 1
+pub fn mathieu_b(n: i32, q: f64) -> (f64, f64)
+{
+    if n < 1
+    {
+        panic!("Invalid value for n: {}", n);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_mathieu_b_e(n, q, &mut s) != 0
+        {
+            panic!("Mathieu calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Characteristic values a_n(q) for n = nmin, ..., nmax
+/// 0 <= nmin <= nmax <= work.nmax(), |q| <= work.qmax()
+pub fn mathieu_a_array(nmin: i32, nmax: i32, q: f64, work: &mut MathieuWorkspace) -> Array<f64>
+{
+    work.check(nmin, nmax, q);
+    // GSL rejects a range holding a single order
+    if nmin == nmax
+    {
+        return Array::<f64>::new_filled(mathieu_a(nmin, q).0, 1, Order::Row);
+    }
+    let size = (nmax - nmin + 1) as usize;
+    let mut result_array = Array::<f64>::new_filled(0f64, size, Order::Row);
+    unsafe {
+        if gsl_sf_mathieu_a_array(nmin, nmax, q, work.work, result_array.as_mut_ptr()) != 0
+        {
+            panic!("Mathieu calculation failed");
+        }
+    }
+    result_array
+}
+
+/// Characteristic values b_n(q) for n = nmin, ..., nmax
+/// 1 <= nmin <= nmax <= work.nmax(), |q| <= work.qmax()
+pub fn mathieu_b_array(nmin: i32, nmax: i32, q: f64, work: &mut MathieuWorkspace) -> Array<f64>
+{
+    if nmin < 1
+    {
+        panic!("Invalid value for nmin: {}", nmin);
+    }
+    work.check(nmin, nmax, q);
+    // GSL rejects a range holding a single order
+    if nmin == nmax
+    {
+        return Array::<f64>::new_filled(mathieu_b(nmin, q).0, 1, Order::Row);
+    }
+    let size = (nmax - nmin + 1) as usize;
+    let mut result_array = Array::<f64>::new_filled(0f64, size, Order::Row);
+    unsafe {
+        if gsl_sf_mathieu_b_array(nmin, nmax, q, work.work, result_array.as_mut_ptr()) != 0
+        {
+            panic!("Mathieu calculation failed");
+        }
+    }
+    result_array
+}
+
+///////////////////////////////
+// Angular Mathieu Functions //
+///////////////////////////////
+
+/// Angular Mathieu function ce_n(q, z), normalized so that the integral of
+/// ce_n^2 over [0, 2 pi] is pi
+/// n >= 0
+pub fn mathieu_ce(n: i32, q: f64, z: f64) -> (f64, f64)
+{
+    if n < 0
+    {
+        panic!("Invalid value for n: {}", n);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_mathieu_ce_e(n, q, z, &mut s) != 0
+        {
+            panic!("Mathieu calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Angular Mathieu function se_n(q, z), normalized so that the integral of
+/// se_n^2 over [0, 2 pi] is pi
+/// n >= 1
+pub fn mathieu_se(n: i32, q: f64, z: f64) -> (f64, f64)
+{
+    if n < 1
+    {
+        panic!("Invalid value for n: {}", n);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_mathieu_se_e(n, q, z, &mut s) != 0
+        {
+            panic!("Mathieu calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Angular Mathieu functions ce_n(q, z) for n = nmin, ..., nmax
+/// 0 <= nmin <= nmax <= work.nmax(), |q| <= work.qmax()
+pub fn mathieu_ce_array(nmin: i32, nmax: i32, q: f64, z: f64,
+                        work: &mut MathieuWorkspace) -> Array<f64>
+{
+    work.check(nmin, nmax, q);
+    let size = (nmax - nmin + 1) as usize;
+    let mut result_array = Array::<f64>::new_filled(0f64, size, Order::Row);
+    unsafe {
+        if gsl_sf_mathieu_ce_array(nmin, nmax, q, z, work.work, result_array.as_mut_ptr()) != 0
+        {
+            panic!("Mathieu calculation failed");
+        }
+    }
+    result_array
+}
+
+/// Angular Mathieu functions se_n(q, z) for n = nmin, ..., nmax
+/// 1 <= nmin <= nmax <= work.nmax(), |q| <= work.qmax()
+pub fn mathieu_se_array(nmin: i32, nmax: i32, q: f64, z: f64,
+                        work: &mut MathieuWorkspace) -> Array<f64>
+{
+    if nmin < 1
+    {
+        panic!("Invalid value for nmin: {}", nmin);
+    }
+    work.check(nmin, nmax, q);
+    let size = (nmax - nmin + 1) as usize;
+    let mut result_array = Array::<f64>::new_filled(0f64, size, Order::Row);
+    unsafe {
+        if gsl_sf_mathieu_se_array(nmin, nmax, q, z, work.work, result_array.as_mut_ptr()) != 0
+        {
+            panic!("Mathieu calculation failed");
+        }
+    }
+    result_array
+}
+
+//////////////////////////////
+// Radial Mathieu Functions //
+//////////////////////////////
+
+/// Panics unless kind selects the first (1) or second (2) kind
+fn check_kind(kind: i32)
+{
+    if kind != 1 && kind != 2
+    {
+        panic!("Invalid value for kind: {}", kind);
+    }
+}
+
+/// Radial Mathieu function Mc_n^(kind)(q, z) of the first (kind = 1) or
+/// second (kind = 2) kind
+/// n >= 0, q > 0.0
+pub fn mathieu_mc(kind: i32, n: i32, q: f64, z: f64) -> (f64, f64)
+{
+    check_kind(kind);
+    if n < 0
+    {
+        panic!("Invalid value for n: {}", n);
+    }
+    if q <= 0f64
+    {
+        panic!("Invalid value for q: {}", q);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_mathieu_Mc_e(kind, n, q, z, &mut s) != 0
+        {
+            panic!("Mathieu calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Radial Mathieu function Ms_n^(kind)(q, z) of the first (kind = 1) or
+/// second (kind = 2) kind
+/// n >= 1, q > 0.0
+pub fn mathieu_ms(kind: i32, n: i32, q: f64, z: f64) -> (f64, f64)
+{
+    check_kind(kind);
+    if n < 1
+    {
+        panic!("Invalid value for n: {}", n);
+    }
+    if q <= 0f64
+    {
+        panic!("Invalid value for q: {}", q);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_mathieu_Ms_e(kind, n, q, z, &mut s) != 0
+        {
+            panic!("Mathieu calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Radial Mathieu functions Mc_n^(kind)(q, z) for n = nmin, ..., nmax
+/// 0 <= nmin <= nmax <= work.nmax(), 0.0 < q <= work.qmax()
+pub fn mathieu_mc_array(kind: i32, nmin: i32, nmax: i32, q: f64, z: f64,
+                        work: &mut MathieuWorkspace) -> Array<f64>
+{
+    check_kind(kind);
+    if q <= 0f64
+    {
+        panic!("Invalid value for q: {}", q);
+    }
+    work.check(nmin, nmax, q);
+    let size = (nmax - nmin + 1) as usize;
+    let mut result_array = Array::<f64>::new_filled(0f64, size, Order::Row);
+    unsafe {
+        if gsl_sf_mathieu_Mc_array(kind, nmin, nmax, q, z, work.work,
+                                   result_array.as_mut_ptr()) != 0
+        {
+            panic!("Mathieu calculation failed");
+        }
+    }
+    result_array
+}
+
+/// Radial Mathieu functions Ms_n^(kind)(q, z) for n = nmin, ..., nmax
+/// 1 <= nmin <= nmax <= work.nmax(), 0.0 < q <= work.qmax()
+pub fn mathieu_ms_array(kind: i32, nmin: i32, nmax: i32, q: f64, z: f64,
+                        work: &mut MathieuWorkspace) -> Array<f64>
+{
+    check_kind(kind);
+    if nmin < 1
+    {
+        panic!("Invalid value for nmin: {}", nmin);
+    }
+    if q <= 0f64
+    {
+        panic!("Invalid value for q: {}", q);
+    }
+    work.check(nmin, nmax, q);
+    let size = (nmax - nmin + 1) as usize;
+    let mut result_array = Array::<f64>::new_filled(0f64, size, Order::Row);
+    unsafe {
+        if gsl_sf_mathieu_Ms_array(kind, nmin, nmax, q, z, work.work,
+                                   result_array.as_mut_ptr()) != 0
+        {
+            panic!("Mathieu calculation failed");
+        }
+    }
+    result_array
+}
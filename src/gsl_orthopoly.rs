@@ -0,0 +1,723 @@
+/// Classical orthogonal polynomials: generalized Laguerre L_n^a(x),
+/// physicist's and probabilist's Hermite H_n(x), He_n(x) and the Hermite
+/// functions, Gegenbauer C_n^lambda(x), Jacobi P_n^(alpha,beta)(x) and
+/// Chebyshev T_n(x), U_n(x), along with the nodes and weights of the
+/// corresponding Gauss quadrature rules.
+/// For further information see Abramowitz & Stegun, Chapters 22 and 25.
+
+use libc::{c_int, c_double};
+use gsl_sf;
+use lapacke;
+use array::Array;
+use array::Order;
+use std::f64::consts::PI;
+
+#[link(name = "gsl")]
+extern
+{
+    /// L^a_1(x) = 1 + a - x
+    /// exceptions: none
+    fn gsl_sf_laguerre_1_e(
+        a: c_double,
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// L^a_2(x)
+    /// exceptions: none
+    fn gsl_sf_laguerre_2_e(
+        a: c_double,
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// L^a_3(x)
+    /// exceptions: none
+    fn gsl_sf_laguerre_3_e(
+        a: c_double,
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Evaluate generalized Laguerre polynomials.
+    /// a > -1.0
+    /// n >= 0
+    /// exceptions: GSL_EDOM
+    fn gsl_sf_laguerre_n_e(
+        n: c_int,
+        a: c_double,
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Evaluate Gegenbauer polynomials using explicit representations.
+    /// exceptions: none
+    fn gsl_sf_gegenpoly_1_e(
+        lambda: c_double,
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    fn gsl_sf_gegenpoly_2_e(
+        lambda: c_double,
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    fn gsl_sf_gegenpoly_3_e(
+        lambda: c_double,
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Evaluate Gegenbauer polynomials.
+    /// lambda > -1/2, n >= 0
+    /// exceptions: GSL_EDOM
+    fn gsl_sf_gegenpoly_n_e(
+        n: c_int,
+        lambda: c_double,
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Calculate array of Gegenbauer polynomials
+    /// for n = (0, 1, 2, ... nmax)
+    /// lambda > -1/2, nmax >= 0
+    /// exceptions: GSL_EDOM
+    fn gsl_sf_gegenpoly_array(
+        nmax: c_int,
+        lambda: c_double,
+        x: c_double,
+        result_array: *mut c_double) -> c_int;
+
+    /// Evaluate physicist Hermite polynomial H_n(x)
+    /// exceptions: GSL_EDOM, GSL_EOVRFLW
+    fn gsl_sf_hermite_e(
+        n: c_int,
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Array of physicist Hermite polynomials H_n(x)
+    /// for n = (0, 1, 2, ... nmax)
+    /// exceptions: GSL_EDOM, GSL_EOVRFLW
+    fn gsl_sf_hermite_array(
+        nmax: c_int,
+        x: c_double,
+        result_array: *mut c_double) -> c_int;
+
+    /// Evaluate probabilist Hermite polynomial He_n(x)
+    /// exceptions: GSL_EDOM, GSL_EOVRFLW
+    fn gsl_sf_hermite_prob_e(
+        n: c_int,
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Array of probabilist Hermite polynomials He_n(x)
+    /// for n = (0, 1, 2, ... nmax)
+    /// exceptions: GSL_EDOM, GSL_EOVRFLW
+    fn gsl_sf_hermite_prob_array(
+        nmax: c_int,
+        x: c_double,
+        result_array: *mut c_double) -> c_int;
+
+    /// Evaluate Hermite function
+    /// psi_n(x) = (2^n n! sqrt(pi))^(-1/2) exp(-x^2/2) H_n(x)
+    /// exceptions: GSL_EDOM
+    fn gsl_sf_hermite_func_e(
+        n: c_int,
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Array of Hermite functions psi_n(x)
+    /// for n = (0, 1, 2, ... nmax)
+    /// exceptions: GSL_EDOM
+    fn gsl_sf_hermite_func_array(
+        nmax: c_int,
+        x: c_double,
+        result_array: *mut c_double) -> c_int;
+
+    /// Log[Gamma(x)], x not a negative integer.
+    /// Used for the total weight of the Laguerre and Jacobi quadratures.
+    /// exceptions: GSL_EDOM, GSL_EROUND
+    fn gsl_sf_lngamma_e(x: c_double, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+}
+
+////////////////////////
+// Laguerre Functions //
+////////////////////////
+
+/// Generalized Laguerre polynomial L^a_1(x) = 1 + a - x
+pub fn laguerre_1(a: f64, x: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_laguerre_1_e(a, x, &mut s) != 0
+        {
+            panic!("Laguerre calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Generalized Laguerre polynomial L^a_2(x)
+pub fn laguerre_2(a: f64, x: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_laguerre_2_e(a, x, &mut s) != 0
+        {
+            panic!("Laguerre calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Generalized Laguerre polynomial L^a_3(x)
+pub fn laguerre_3(a: f64, x: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_laguerre_3_e(a, x, &mut s) != 0
+        {
+            panic!("Laguerre calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Generalized Laguerre polynomial L^a_n(x)
+/// a > -1.0, n >= 0
+pub fn laguerre_n(n: i32, a: f64, x: f64) -> (f64, f64)
+{
+    if n < 0
+    {
+        panic!("Invalid value for n: {}", n);
+    }
+    if a <= -1f64
+    {
+        panic!("Invalid value for a: {}", a);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_laguerre_n_e(n, a, x, &mut s) != 0
+        {
+            panic!("Laguerre calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Generalized Laguerre polynomials L^a_n(x) for n = 0, 1, ..., nmax,
+/// computed by upward recurrence
+/// a > -1.0, nmax >= 0
+pub fn laguerre_array(nmax: i32, a: f64, x: f64) -> Array<f64>
+{
+    if nmax < 0
+    {
+        panic!("Invalid value for nmax: {}", nmax);
+    }
+    if a <= -1f64
+    {
+        panic!("Invalid value for a: {}", a);
+    }
+    let size = (nmax + 1) as usize;
+    let mut result_array = Array::<f64>::new_filled(0f64, size, Order::Row);
+    result_array[0] = 1f64;
+    if size > 1
+    {
+        result_array[1] = 1f64 + a - x;
+    }
+    for k in 1..size - 1
+    {
+        let kf = k as f64;
+        result_array[k + 1] = ((2f64 * kf + 1f64 + a - x) * result_array[k]
+                               - (kf + a) * result_array[k - 1]) / (kf + 1f64);
+    }
+    result_array
+}
+
+///////////////////////
+// Hermite Functions //
+///////////////////////
+
+/// Physicist's Hermite polynomial H_n(x)
+/// n >= 0
+pub fn hermite(n: i32, x: f64) -> (f64, f64)
+{
+    if n < 0
+    {
+        panic!("Invalid value for n: {}", n);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_hermite_e(n, x, &mut s) != 0
+        {
+            panic!("Hermite calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Physicist's Hermite polynomials H_n(x) for n = 0, 1, ..., nmax
+/// nmax >= 0
+pub fn hermite_array(nmax: i32, x: f64) -> Array<f64>
+{
+    if nmax < 0
+    {
+        panic!("Invalid value for nmax: {}", nmax);
+    }
+    let size = (nmax + 1) as usize;
+    let mut result_array = Array::<f64>::new_filled(0f64, size, Order::Row);
+    unsafe {
+        if gsl_sf_hermite_array(nmax, x, result_array.as_mut_ptr()) != 0
+        {
+            panic!("Hermite calculation failed");
+        }
+    }
+    result_array
+}
+
+/// Probabilist's Hermite polynomial He_n(x)
+/// n >= 0
+pub fn hermite_prob(n: i32, x: f64) -> (f64, f64)
+{
+    if n < 0
+    {
+        panic!("Invalid value for n: {}", n);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_hermite_prob_e(n, x, &mut s) != 0
+        {
+            panic!("Hermite calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Probabilist's Hermite polynomials He_n(x) for n = 0, 1, ..., nmax
+/// nmax >= 0
+pub fn hermite_prob_array(nmax: i32, x: f64) -> Array<f64>
+{
+    if nmax < 0
+    {
+        panic!("Invalid value for nmax: {}", nmax);
+    }
+    let size = (nmax + 1) as usize;
+    let mut result_array = Array::<f64>::new_filled(0f64, size, Order::Row);
+    unsafe {
+        if gsl_sf_hermite_prob_array(nmax, x, result_array.as_mut_ptr()) != 0
+        {
+            panic!("Hermite calculation failed");
+        }
+    }
+    result_array
+}
+
+/// Hermite function psi_n(x) = (2^n n! sqrt(pi))^(-1/2) exp(-x^2/2) H_n(x)
+/// n >= 0
+pub fn hermite_func(n: i32, x: f64) -> (f64, f64)
+{
+    if n < 0
+    {
+        panic!("Invalid value for n: {}", n);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_hermite_func_e(n, x, &mut s) != 0
+        {
+            panic!("Hermite calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Hermite functions psi_n(x) for n = 0, 1, ..., nmax
+/// nmax >= 0
+pub fn hermite_func_array(nmax: i32, x: f64) -> Array<f64>
+{
+    if nmax < 0
+    {
+        panic!("Invalid value for nmax: {}", nmax);
+    }
+    let size = (nmax + 1) as usize;
+    let mut result_array = Array::<f64>::new_filled(0f64, size, Order::Row);
+    unsafe {
+        if gsl_sf_hermite_func_array(nmax, x, result_array.as_mut_ptr()) != 0
+        {
+            panic!("Hermite calculation failed");
+        }
+    }
+    result_array
+}
+
+//////////////////////////
+// Gegenbauer Functions //
+//////////////////////////
+
+/// Gegenbauer polynomial C^lambda_1(x)
+pub fn gegenpoly_1(lambda: f64, x: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_gegenpoly_1_e(lambda, x, &mut s) != 0
+        {
+            panic!("Gegenbauer calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Gegenbauer polynomial C^lambda_2(x)
+pub fn gegenpoly_2(lambda: f64, x: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_gegenpoly_2_e(lambda, x, &mut s) != 0
+        {
+            panic!("Gegenbauer calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Gegenbauer polynomial C^lambda_3(x)
+pub fn gegenpoly_3(lambda: f64, x: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_gegenpoly_3_e(lambda, x, &mut s) != 0
+        {
+            panic!("Gegenbauer calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Gegenbauer polynomial C^lambda_n(x)
+/// lambda > -1/2, n >= 0
+pub fn gegenpoly_n(n: i32, lambda: f64, x: f64) -> (f64, f64)
+{
+    if n < 0
+    {
+        panic!("Invalid value for n: {}", n);
+    }
+    if lambda <= -0.5f64
+    {
+        panic!("Invalid value for lambda: {}", lambda);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_gegenpoly_n_e(n, lambda, x, &mut s) != 0
+        {
+            panic!("Gegenbauer calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Gegenbauer polynomials C^lambda_n(x) for n = 0, 1, ..., nmax
+/// lambda > -1/2, nmax >= 0
+pub fn gegenpoly_array(nmax: i32, lambda: f64, x: f64) -> Array<f64>
+{
+    if nmax < 0
+    {
+        panic!("Invalid value for nmax: {}", nmax);
+    }
+    if lambda <= -0.5f64
+    {
+        panic!("Invalid value for lambda: {}", lambda);
+    }
+    let size = (nmax + 1) as usize;
+    let mut result_array = Array::<f64>::new_filled(0f64, size, Order::Row);
+    unsafe {
+        if gsl_sf_gegenpoly_array(nmax, lambda, x, result_array.as_mut_ptr()) != 0
+        {
+            panic!("Gegenbauer calculation failed");
+        }
+    }
+    result_array
+}
+
+//////////////////////
+// Jacobi Functions //
+//////////////////////
+
+/// Jacobi polynomials P^(alpha,beta)_n(x) for n = 0, 1, ..., nmax, computed
+/// by the three-term recurrence (A&S 22.7.1)
+/// alpha > -1, beta > -1, nmax >= 0
+pub fn jacobi_array(nmax: i32, alpha: f64, beta: f64, x: f64) -> Array<f64>
+{
+    if nmax < 0
+    {
+        panic!("Invalid value for nmax: {}", nmax);
+    }
+    if alpha <= -1f64 || beta <= -1f64
+    {
+        panic!("Invalid values for alpha, beta: {}, {}", alpha, beta);
+    }
+    let size = (nmax + 1) as usize;
+    let ab = alpha + beta;
+    let mut result_array = Array::<f64>::new_filled(0f64, size, Order::Row);
+    result_array[0] = 1f64;
+    if size > 1
+    {
+        result_array[1] = (alpha + 1f64) + 0.5f64 * (ab + 2f64) * (x - 1f64);
+    }
+    for k in 2..size
+    {
+        let n = k as f64;
+        let c = 2f64 * n + ab;
+        let a1 = 2f64 * n * (n + ab) * (c - 2f64);
+        let a2 = (c - 1f64) * (c * (c - 2f64) * x + alpha * alpha - beta * beta);
+        let a3 = 2f64 * (n + alpha - 1f64) * (n + beta - 1f64) * c;
+        result_array[k] = (a2 * result_array[k - 1] - a3 * result_array[k - 2]) / a1;
+    }
+    result_array
+}
+
+/// Jacobi polynomial P^(alpha,beta)_n(x)
+/// alpha > -1, beta > -1, n >= 0
+pub fn jacobi(n: i32, alpha: f64, beta: f64, x: f64) -> f64
+{
+    jacobi_array(n, alpha, beta, x)[n as usize]
+}
+
+/////////////////////////
+// Chebyshev Functions //
+/////////////////////////
+
+/// Chebyshev polynomials of the first kind T_n(x) for n = 0, 1, ..., nmax
+/// nmax >= 0
+pub fn chebyshev_t_array(nmax: i32, x: f64) -> Array<f64>
+{
+    if nmax < 0
+    {
+        panic!("Invalid value for nmax: {}", nmax);
+    }
+    let size = (nmax + 1) as usize;
+    let mut result_array = Array::<f64>::new_filled(0f64, size, Order::Row);
+    result_array[0] = 1f64;
+    if size > 1
+    {
+        result_array[1] = x;
+    }
+    for k in 2..size
+    {
+        result_array[k] = 2f64 * x * result_array[k - 1] - result_array[k - 2];
+    }
+    result_array
+}
+
+/// Chebyshev polynomial of the first kind T_n(x)
+/// n >= 0
+pub fn chebyshev_t(n: i32, x: f64) -> f64
+{
+    chebyshev_t_array(n, x)[n as usize]
+}
+
+/// Chebyshev polynomials of the second kind U_n(x) for n = 0, 1, ..., nmax
+/// nmax >= 0
+pub fn chebyshev_u_array(nmax: i32, x: f64) -> Array<f64>
+{
+    if nmax < 0
+    {
+        panic!("Invalid value for nmax: {}", nmax);
+    }
+    let size = (nmax + 1) as usize;
+    let mut result_array = Array::<f64>::new_filled(0f64, size, Order::Row);
+    result_array[0] = 1f64;
+    if size > 1
+    {
+        result_array[1] = 2f64 * x;
+    }
+    for k in 2..size
+    {
+        result_array[k] = 2f64 * x * result_array[k - 1] - result_array[k - 2];
+    }
+    result_array
+}
+
+/// Chebyshev polynomial of the second kind U_n(x)
+/// n >= 0
+pub fn chebyshev_u(n: i32, x: f64) -> f64
+{
+    chebyshev_u_array(n, x)[n as usize]
+}
+
+//////////////////////
+// Gauss Quadrature //
+//////////////////////
+
+/// Log of the Gamma function, used for the total weight of a quadrature rule
+fn lngamma(x: f64) -> f64
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_lngamma_e(x, &mut s) != 0
+        {
+            panic!("Gamma calculation failed");
+        }
+    }
+    s.val
+}
+
+/// Golub-Welsch algorithm: the nodes of an n-point Gauss rule are the
+/// eigenvalues of the Jacobi matrix built from the monic recurrence
+/// coefficients a_k (diagonal) and b_k (squared off-diagonal), and the weights
+/// are mu0 times the squared first components of the eigenvectors, where mu0
+/// is the integral of the weight function.
+fn golub_welsch<A, B>(n: usize, a: A, b: B, mu0: f64) -> (Array<f64>, Array<f64>)
+    where A: Fn(usize) -> f64, B: Fn(usize) -> f64
+{
+    let mut diag = Array::<f64>::new_filled(0f64, n, Order::Row);
+    let mut offdiag = Array::<f64>::new_filled(0f64, n - 1, Order::Row);
+    for k in 0..n
+    {
+        diag[k] = a(k);
+    }
+    for k in 1..n
+    {
+        offdiag[k - 1] = b(k).sqrt();
+    }
+    let (nodes, vecs) = lapacke::symmetric_tridiagonal_eigen(&diag, &offdiag);
+    let mut weights = Array::<f64>::new_filled(0f64, n, Order::Row);
+    for i in 0..n
+    {
+        let v = *vecs.get(i, 0);
+        weights[i] = mu0 * v * v;
+    }
+    (nodes, weights)
+}
+
+/// Nodes and weights of the n-point Gauss-Legendre rule on [-1, 1],
+/// weight function w(x) = 1
+/// n >= 1
+pub fn gauss_legendre(n: usize) -> (Array<f64>, Array<f64>)
+{
+    if n == 0
+    {
+        panic!("Invalid value for n: {}", n);
+    }
+    golub_welsch(n,
+                 |_| 0f64,
+                 |k| { let k = k as f64; k * k / (4f64 * k * k - 1f64) },
+                 2f64)
+}
+
+/// Nodes and weights of the n-point Gauss-Jacobi rule on [-1, 1],
+/// weight function w(x) = (1-x)^alpha (1+x)^beta
+/// alpha > -1, beta > -1, n >= 1
+pub fn gauss_jacobi(n: usize, alpha: f64, beta: f64) -> (Array<f64>, Array<f64>)
+{
+    if n == 0
+    {
+        panic!("Invalid value for n: {}", n);
+    }
+    if alpha <= -1f64 || beta <= -1f64
+    {
+        panic!("Invalid values for alpha, beta: {}, {}", alpha, beta);
+    }
+    let ab = alpha + beta;
+    let a = |k: usize| {
+        if k == 0
+        {
+            (beta - alpha) / (ab + 2f64)
+        } else {
+            let c = 2f64 * k as f64 + ab;
+            (beta * beta - alpha * alpha) / (c * (c + 2f64))
+        }
+    };
+    // The general expression is 0/0 for k = 1 when alpha + beta = -1
+    let b = |k: usize| {
+        if k == 1
+        {
+            4f64 * (1f64 + alpha) * (1f64 + beta)
+                / ((2f64 + ab) * (2f64 + ab) * (3f64 + ab))
+        } else {
+            let kf = k as f64;
+            let c = 2f64 * kf + ab;
+            4f64 * kf * (kf + alpha) * (kf + beta) * (kf + ab)
+                / (c * c * (c + 1f64) * (c - 1f64))
+        }
+    };
+    let mu0 = ((ab + 1f64) * 2f64.ln() + lngamma(alpha + 1f64)
+               + lngamma(beta + 1f64) - lngamma(ab + 2f64)).exp();
+    golub_welsch(n, a, b, mu0)
+}
+
+/// Nodes and weights of the n-point Gauss-Gegenbauer rule on [-1, 1],
+/// weight function w(x) = (1-x^2)^(lambda-1/2)
+/// lambda > -1/2, n >= 1
+pub fn gauss_gegenbauer(n: usize, lambda: f64) -> (Array<f64>, Array<f64>)
+{
+    if lambda <= -0.5f64
+    {
+        panic!("Invalid value for lambda: {}", lambda);
+    }
+    gauss_jacobi(n, lambda - 0.5f64, lambda - 0.5f64)
+}
+
+/// Nodes and weights of the n-point Gauss-Chebyshev rule of the first kind
+/// on [-1, 1], weight function w(x) = 1/sqrt(1-x^2)
+/// n >= 1
+pub fn gauss_chebyshev_t(n: usize) -> (Array<f64>, Array<f64>)
+{
+    if n == 0
+    {
+        panic!("Invalid value for n: {}", n);
+    }
+    let mut nodes = Array::<f64>::new_filled(0f64, n, Order::Row);
+    let weights = Array::<f64>::new_filled(PI / n as f64, n, Order::Row);
+    for i in 0..n
+    {
+        nodes[i] = -((2 * i + 1) as f64 * PI / (2 * n) as f64).cos();
+    }
+    (nodes, weights)
+}
+
+/// Nodes and weights of the n-point Gauss-Chebyshev rule of the second kind
+/// on [-1, 1], weight function w(x) = sqrt(1-x^2)
+/// n >= 1
+pub fn gauss_chebyshev_u(n: usize) -> (Array<f64>, Array<f64>)
+{
+    if n == 0
+    {
+        panic!("Invalid value for n: {}", n);
+    }
+    let mut nodes = Array::<f64>::new_filled(0f64, n, Order::Row);
+    let mut weights = Array::<f64>::new_filled(0f64, n, Order::Row);
+    for i in 0..n
+    {
+        let theta = (i + 1) as f64 * PI / (n + 1) as f64;
+        nodes[i] = -theta.cos();
+        weights[i] = PI / (n + 1) as f64 * theta.sin() * theta.sin();
+    }
+    (nodes, weights)
+}
+
+/// Nodes and weights of the n-point generalized Gauss-Laguerre rule on
+/// [0, inf), weight function w(x) = x^a exp(-x)
+/// a > -1, n >= 1
+pub fn gauss_laguerre(n: usize, a: f64) -> (Array<f64>, Array<f64>)
+{
+    if n == 0
+    {
+        panic!("Invalid value for n: {}", n);
+    }
+    if a <= -1f64
+    {
+        panic!("Invalid value for a: {}", a);
+    }
+    golub_welsch(n,
+                 |k| 2f64 * k as f64 + a + 1f64,
+                 |k| k as f64 * (k as f64 + a),
+                 lngamma(a + 1f64).exp())
+}
+
+/// Nodes and weights of the n-point Gauss-Hermite rule on (-inf, inf),
+/// weight function w(x) = exp(-x^2)
+/// n >= 1
+pub fn gauss_hermite(n: usize) -> (Array<f64>, Array<f64>)
+{
+    if n == 0
+    {
+        panic!("Invalid value for n: {}", n);
+    }
+    golub_welsch(n,
+                 |_| 0f64,
+                 |k| 0.5f64 * k as f64,
+                 PI.sqrt())
+}
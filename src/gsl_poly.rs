@@ -1,9 +1,29 @@
 /// Polynomial function utilities.
+/// Besides the GSL routines over raw coefficient arrays, this module provides
+/// the Polynomial type, which stores its coefficients in ascending order of
+/// power, c[0] + c[1]*x + ... + c[n]*x^n, like numpy.polynomial.
 use array::Array;
 use array::Order;
+use matrix::Matrix;
+use lapacke;
+use gsl_guard::GuardedBuffer;
 use std::f64;
+use std::cmp::Ordering;
+use std::ops::{Add, Sub, Mul, Div, Rem};
 use libc::{c_int, c_double, size_t};
 
+/// Complex number as laid out by the GSL, real part first
+#[repr(C)]
+#[allow(non_camel_case_types)]
+struct gsl_complex
+{
+    dat: [c_double; 2]
+}
+
+/// Opaque workspace for the general polynomial solver
+#[allow(non_camel_case_types)]
+enum gsl_poly_complex_workspace {}
+
 #[link(name = "gsl")]
 extern
 {
@@ -75,7 +95,7 @@ extern
         dd: *const c_double,
         xa: *const c_double,
         size: size_t,
-        w: *const c_double) -> c_int;
+        w: *mut c_double) -> c_int;
 
     /// This function computes a divided-difference representation of the
     /// interpolating Hermite polynomial for the points (x, y) stored in the
@@ -138,6 +158,57 @@ extern
         x1: *mut c_double,
         x2: *mut c_double) -> c_int;
 
+    /// This function finds the complex roots of the quadratic equation,
+    ///   a z^2 + b z + c = 0
+    /// The number of complex roots is returned (either one or two) and the
+    /// locations of the roots are stored in z0 and z1. The roots are returned
+    /// in ascending order, sorted first by their real components and then by
+    /// their imaginary components. If only one real root is found (i.e. if
+    /// a=0) then it is stored in z0.
+    fn gsl_poly_complex_solve_quadratic(
+        a: c_double,
+        b: c_double,
+        c: c_double,
+        z0: *mut gsl_complex,
+        z1: *mut gsl_complex) -> c_int;
+
+    /// This function finds the complex roots of the cubic equation,
+    ///   z^3 + a z^2 + b z + c = 0
+    /// The number of complex roots is returned (always three) and the
+    /// locations of the roots are stored in z0, z1 and z2. The roots are
+    /// returned in ascending order, sorted first by their real components and
+    /// then by their imaginary components.
+    fn gsl_poly_complex_solve_cubic(
+        a: c_double,
+        b: c_double,
+        c: c_double,
+        z0: *mut gsl_complex,
+        z1: *mut gsl_complex,
+        z2: *mut gsl_complex) -> c_int;
+
+    /// This function allocates space for a gsl_poly_complex_workspace struct
+    /// and a workspace suitable for solving a polynomial with n coefficients
+    /// using the routine gsl_poly_complex_solve.
+    fn gsl_poly_complex_workspace_alloc(n: size_t) -> *mut gsl_poly_complex_workspace;
+
+    /// This function frees all the memory associated with the workspace w.
+    fn gsl_poly_complex_workspace_free(w: *mut gsl_poly_complex_workspace);
+
+    /// This function computes the roots of the general polynomial
+    ///   P(x) = a_0 + a_1 x + a_2 x^2 + ... + a_{n-1} x^{n-1}
+    /// using balanced-QR reduction of the companion matrix. The parameter n
+    /// specifies the length of the coefficient array. The coefficient of the
+    /// highest order term must be non-zero. The function requires a workspace
+    /// w of the appropriate size. The n-1 roots are returned in the packed
+    /// complex array z of length 2(n-1), alternating real and imaginary parts.
+    /// The function returns GSL_SUCCESS if all the roots are found. If the QR
+    /// reduction does not converge, the error handler is invoked with an error
+    /// code of GSL_EFAILED.
+    fn gsl_poly_complex_solve(
+        a: *const c_double,
+        n: size_t,
+        w: *mut gsl_poly_complex_workspace,
+        z: *mut c_double) -> c_int;
 }
 
 /// Evaluate a polynomial with real coefficients for the real variable x
@@ -155,7 +226,7 @@ pub fn poly_eval_derivs(c: &Array<f64>, x: f64, k: usize) -> Array<f64>
     {
         panic!("Cannot take the 0th derivative");
     }
-    let mut derivs = Array::<f64>::new_filled(0f64, k, Order::Row);
+    let mut derivs = GuardedBuffer::new(k);
 
     unsafe {
         let result = gsl_poly_eval_derivs(
@@ -170,7 +241,7 @@ pub fn poly_eval_derivs(c: &Array<f64>, x: f64, k: usize) -> Array<f64>
         }
     }
 
-    return derivs;
+    return derivs.into_array();
 }
 
 /// Computes a divided-difference representation of the interpolating polynomial
@@ -181,7 +252,7 @@ pub fn poly_divdiff_init(xa: &Array<f64>, ya: &Array<f64>) -> Array<f64>
     {
         panic!("Inconsistent sizes for xa, ya: {} != {}", xa.len(), ya.len());
     }
-    let mut dd = Array::<f64>::new_filled(0f64, xa.len(), Order::Row);
+    let mut dd = GuardedBuffer::new(xa.len());
     unsafe {
         let result = gsl_poly_dd_init(
             dd.as_mut_ptr(),
@@ -193,7 +264,7 @@ pub fn poly_divdiff_init(xa: &Array<f64>, ya: &Array<f64>) -> Array<f64>
             panic!("An error occured while ");
         }
     }
-    return dd;
+    return dd.into_array();
 }
 
 // Evaluates the divided-difference polynomial at x
@@ -215,7 +286,12 @@ pub fn poly_divdiff_eval(dd: &Array<f64>, xa: &Array<f64>, x: f64) -> f64
 /// Converts the divided-difference polynomial to a Taylor expansion around a point
 pub fn poly_divdiff_to_taylor(xp: f64, dd: &Array<f64>, xa: &Array<f64>) -> Array<f64>
 {
-    let mut c = Array::<f64>::new_filled(0f64, xa.len(), Order::Row);
+    if dd.len() != xa.len()
+    {
+        panic!("Inconsistent sizes for xa, dd: {} != {}", xa.len(), dd.len());
+    }
+    let mut c = GuardedBuffer::new(xa.len());
+    let mut w = GuardedBuffer::new(xa.len());
     unsafe {
         let result = gsl_poly_dd_taylor(
             c.as_mut_ptr(),
@@ -223,17 +299,21 @@ pub fn poly_divdiff_to_taylor(xp: f64, dd: &Array<f64>, xa: &Array<f64>) -> Arra
             dd.as_ptr(),
             xa.as_ptr(),
             xa.len() as size_t,
-            Array::<f64>::new_filled(0f64, xa.len(), Order::Row).as_mut_ptr());
+            w.as_mut_ptr());
         if result != 0
         {
             panic!("Talyor expansion conversion failed");
         }
     }
-    return c;
+    if w.overrun()
+    {
+        panic!("Write outside of a {} element workspace", w.len());
+    }
+    return c.into_array();
 }
 
 /// Computes a divided-difference representation of the interpolating Hermite
-/// polynomial for the points (x, y).
+/// polynomial for the points (x, y). Both dd and za have twice the length of xa.
 /// The resulting Hermite polynomial can be evaluated by calling
 /// gsl_poly_dd_eval and using za for the input argument xa.
 pub fn poly_divdiff_to_hermite(
@@ -245,8 +325,9 @@ pub fn poly_divdiff_to_hermite(
     {
         panic!("Inconsistent sizes for xa, ya, dya");
     }
-    let mut dd = Array::<f64>::new_filled(0f64, xa.len(), Order::Row);
-    let mut za = Array::<f64>::new_filled(0f64, xa.len(), Order::Row);
+    // GSL writes 2*size divided differences and doubled abscissae
+    let mut dd = GuardedBuffer::new(2 * xa.len());
+    let mut za = GuardedBuffer::new(2 * xa.len());
 
     unsafe {
         let result = gsl_poly_dd_hermite_init(
@@ -262,7 +343,7 @@ pub fn poly_divdiff_to_hermite(
         }
     }
 
-    return (dd, za);
+    return (dd.into_array(), za.into_array());
 }
 
 //////////////////////
@@ -314,3 +395,410 @@ pub fn poly_solve_cubic(coeffs: [f64; 3]) -> Vec<f64>
         }
     }
 }
+
+/// Finds the complex roots of the quadratic equation a*z^2 + b*z + c = 0,
+/// returned as [re, im] pairs sorted by real and then imaginary part.
+/// Only one root is returned when a = 0, and none when a = b = 0.
+pub fn poly_complex_solve_quadratic(coeffs: [f64; 3]) -> Vec<[f64; 2]>
+{
+    let mut z0 = gsl_complex{dat: [f64::NAN; 2]};
+    let mut z1 = gsl_complex{dat: [f64::NAN; 2]};
+    unsafe {
+        let num_roots = gsl_poly_complex_solve_quadratic(
+            coeffs[0],
+            coeffs[1],
+            coeffs[2],
+            &mut z0,
+            &mut z1);
+        if num_roots == 0
+        {
+            return vec![];
+        } else if num_roots == 1 {
+            return vec![z0.dat];
+        } else {
+            return vec![z0.dat, z1.dat];
+        }
+    }
+}
+
+/// Finds the complex roots of the cubic equation z^3 + a*z^2 + b*z + c = 0,
+/// returned as [re, im] pairs sorted by real and then imaginary part.
+pub fn poly_complex_solve_cubic(coeffs: [f64; 3]) -> Vec<[f64; 2]>
+{
+    let mut z0 = gsl_complex{dat: [f64::NAN; 2]};
+    let mut z1 = gsl_complex{dat: [f64::NAN; 2]};
+    let mut z2 = gsl_complex{dat: [f64::NAN; 2]};
+    unsafe {
+        gsl_poly_complex_solve_cubic(
+            coeffs[0],
+            coeffs[1],
+            coeffs[2],
+            &mut z0,
+            &mut z1,
+            &mut z2);
+    }
+    vec![z0.dat, z1.dat, z2.dat]
+}
+
+/// Finds the complex roots of the general polynomial
+/// c[0] + c[1]*z + ... + c[n-1]*z^(n-1) from the eigenvalues of its companion
+/// matrix, returned as [re, im] pairs in no particular order.
+/// The leading coefficient c[n-1] must be non-zero.
+pub fn poly_complex_solve(c: &Array<f64>) -> Vec<[f64; 2]>
+{
+    let n = c.len();
+    if n < 2
+    {
+        panic!("Polynomial must have at least two coefficients");
+    }
+    if c[n - 1] == 0f64
+    {
+        panic!("Leading coefficient must be non-zero");
+    }
+    let mut z = GuardedBuffer::new(2 * (n - 1));
+    unsafe {
+        let w = gsl_poly_complex_workspace_alloc(n as size_t);
+        if w.is_null()
+        {
+            panic!("Failed to allocate polynomial workspace");
+        }
+        let result = gsl_poly_complex_solve(c.as_ptr(), n as size_t, w, z.as_mut_ptr());
+        gsl_poly_complex_workspace_free(w);
+        if result != 0
+        {
+            panic!("Root finding failed to converge");
+        }
+    }
+    z.into_array().chunks(2).map(|z| [z[0], z[1]]).collect()
+}
+
+/////////////////////
+// Polynomial Type //
+/////////////////////
+
+/// A polynomial c[0] + c[1]*x + ... + c[n]*x^n with coefficients of type T.
+/// Trailing zero coefficients are dropped, so that the last coefficient is
+/// the leading one; the zero polynomial has the single coefficient 0.
+/// Zero is taken to be T::default().
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polynomial<T> where T: Add + Sub + Copy + PartialEq
+{
+    /// Coefficients in ascending order of power
+    coeffs: Vec<T>,
+}
+
+impl<T> Polynomial<T>
+    where T: Add<Output=T> + Sub<Output=T> + Mul<Output=T> + Copy + PartialEq + Default
+{
+    /// Creates a polynomial from its coefficients in ascending order of power
+    pub fn new(coeffs: Vec<T>) -> Polynomial<T>
+    {
+        let mut coeffs = coeffs;
+        while coeffs.len() > 1 && coeffs[coeffs.len() - 1] == T::default()
+        {
+            coeffs.pop();
+        }
+        if coeffs.is_empty()
+        {
+            coeffs.push(T::default());
+        }
+        Polynomial{coeffs: coeffs}
+    }
+
+    /// Creates a polynomial from an array of coefficients in ascending order
+    pub fn from_array(coeffs: &Array<T>) -> Polynomial<T>
+    {
+        Polynomial::new(coeffs.as_slice().to_vec())
+    }
+
+    /// The coefficients in ascending order of power
+    pub fn coeffs(&self) -> &[T]
+    {
+        &self.coeffs
+    }
+
+    /// The coefficients as an array, in ascending order of power
+    pub fn to_array(&self) -> Array<T>
+    {
+        Array::new(self.coeffs.clone(), Order::Row)
+    }
+
+    /// Degree of the polynomial, taken to be 0 for the zero polynomial
+    pub fn degree(&self) -> usize
+    {
+        self.coeffs.len() - 1
+    }
+
+    /// Evaluates the polynomial at x using Horner's method
+    pub fn eval(&self, x: T) -> T
+    {
+        let n = self.coeffs.len();
+        let mut result = self.coeffs[n - 1];
+        for k in (0..n - 1).rev()
+        {
+            result = result * x + self.coeffs[k];
+        }
+        result
+    }
+
+    /// Evaluates the polynomial at each element of xs
+    pub fn eval_array(&self, xs: &Array<T>) -> Array<T>
+    {
+        let values = xs.as_slice().iter().map(|&x| self.eval(x)).collect();
+        Array::new(values, Order::Row)
+    }
+
+    /// Composition self(inner(x)), evaluated by Horner's method
+    pub fn compose(&self, inner: &Polynomial<T>) -> Polynomial<T>
+    {
+        let n = self.coeffs.len();
+        let mut result = Polynomial::new(vec![self.coeffs[n - 1]]);
+        for k in (0..n - 1).rev()
+        {
+            result = result * inner.clone() + Polynomial::new(vec![self.coeffs[k]]);
+        }
+        result
+    }
+}
+
+impl<T> Polynomial<T>
+    where T: Add<Output=T> + Sub<Output=T> + Mul<Output=T> + Div<Output=T>
+           + Copy + PartialEq + Default
+{
+    /// Polynomial long division, returning the quotient q and remainder r with
+    /// self = q * divisor + r and deg(r) < deg(divisor)
+    pub fn divmod(&self, divisor: &Polynomial<T>) -> (Polynomial<T>, Polynomial<T>)
+    {
+        let d = &divisor.coeffs;
+        let dn = d.len() - 1;
+        if dn == 0 && d[0] == T::default()
+        {
+            panic!("Division by the zero polynomial");
+        }
+        if self.coeffs.len() < d.len()
+        {
+            return (Polynomial::new(vec![]), self.clone());
+        }
+        let mut r = self.coeffs.clone();
+        let mut q = vec![T::default(); r.len() - dn];
+        for k in (0..q.len()).rev()
+        {
+            let qk = r[k + dn] / d[dn];
+            q[k] = qk;
+            for j in 0..dn + 1
+            {
+                r[k + j] = r[k + j] - qk * d[j];
+            }
+        }
+        // The leading terms have been cancelled exactly
+        r.truncate(dn);
+        (Polynomial::new(q), Polynomial::new(r))
+    }
+}
+
+impl<T> Add for Polynomial<T>
+    where T: Add<Output=T> + Sub<Output=T> + Mul<Output=T> + Copy + PartialEq + Default
+{
+    type Output = Polynomial<T>;
+
+    fn add(self, other: Polynomial<T>) -> Polynomial<T>
+    {
+        let n = self.coeffs.len().max(other.coeffs.len());
+        let mut sum = vec![T::default(); n];
+        for (k, &c) in self.coeffs.iter().enumerate()
+        {
+            sum[k] = sum[k] + c;
+        }
+        for (k, &c) in other.coeffs.iter().enumerate()
+        {
+            sum[k] = sum[k] + c;
+        }
+        Polynomial::new(sum)
+    }
+}
+
+impl<T> Sub for Polynomial<T>
+    where T: Add<Output=T> + Sub<Output=T> + Mul<Output=T> + Copy + PartialEq + Default
+{
+    type Output = Polynomial<T>;
+
+    fn sub(self, other: Polynomial<T>) -> Polynomial<T>
+    {
+        let n = self.coeffs.len().max(other.coeffs.len());
+        let mut diff = vec![T::default(); n];
+        for (k, &c) in self.coeffs.iter().enumerate()
+        {
+            diff[k] = diff[k] + c;
+        }
+        for (k, &c) in other.coeffs.iter().enumerate()
+        {
+            diff[k] = diff[k] - c;
+        }
+        Polynomial::new(diff)
+    }
+}
+
+impl<T> Mul for Polynomial<T>
+    where T: Add<Output=T> + Sub<Output=T> + Mul<Output=T> + Copy + PartialEq + Default
+{
+    type Output = Polynomial<T>;
+
+    fn mul(self, other: Polynomial<T>) -> Polynomial<T>
+    {
+        let mut prod = vec![T::default(); self.coeffs.len() + other.coeffs.len() - 1];
+        for (i, &a) in self.coeffs.iter().enumerate()
+        {
+            for (j, &b) in other.coeffs.iter().enumerate()
+            {
+                prod[i + j] = prod[i + j] + a * b;
+            }
+        }
+        Polynomial::new(prod)
+    }
+}
+
+/// Quotient of polynomial long division, see divmod
+impl<T> Div for Polynomial<T>
+    where T: Add<Output=T> + Sub<Output=T> + Mul<Output=T> + Div<Output=T>
+           + Copy + PartialEq + Default
+{
+    type Output = Polynomial<T>;
+
+    fn div(self, other: Polynomial<T>) -> Polynomial<T>
+    {
+        self.divmod(&other).0
+    }
+}
+
+/// Remainder of polynomial long division, see divmod
+impl<T> Rem for Polynomial<T>
+    where T: Add<Output=T> + Sub<Output=T> + Mul<Output=T> + Div<Output=T>
+           + Copy + PartialEq + Default
+{
+    type Output = Polynomial<T>;
+
+    fn rem(self, other: Polynomial<T>) -> Polynomial<T>
+    {
+        self.divmod(&other).1
+    }
+}
+
+impl Polynomial<f64>
+{
+    /// Creates the monic polynomial (x - r_0)(x - r_1)...(x - r_n-1) with the
+    /// given real roots
+    pub fn from_roots(roots: &Array<f64>) -> Polynomial<f64>
+    {
+        let mut result = Polynomial::new(vec![1f64]);
+        for &r in roots.as_slice()
+        {
+            result = result * Polynomial::new(vec![-r, 1f64]);
+        }
+        result
+    }
+
+    /// Least squares fit of a polynomial of degree deg to the points (x, y).
+    /// The columns of the Vandermonde matrix are scaled to unit norm before
+    /// solving, which improves its conditioning considerably.
+    pub fn fit(x: &Array<f64>, y: &Array<f64>, deg: usize) -> Polynomial<f64>
+    {
+        if x.len() != y.len()
+        {
+            panic!("Inconsistent sizes for x, y: {} != {}", x.len(), y.len());
+        }
+        if x.len() < deg + 1
+        {
+            panic!("Fitting a degree {} polynomial needs at least {} points", deg, deg + 1);
+        }
+        let (m, n) = (x.len(), deg + 1);
+        let mut vander = Matrix::<f64>::new_filled(0f64, m, n);
+        for i in 0..m
+        {
+            let mut p = 1f64;
+            for j in 0..n
+            {
+                vander.set(i, j, p);
+                p *= x[i];
+            }
+        }
+        let mut scale = vec![0f64; n];
+        for j in 0..n
+        {
+            scale[j] = (0..m).map(|i| vander.get(i, j).powi(2)).sum::<f64>().sqrt();
+            if scale[j] == 0f64
+            {
+                scale[j] = 1f64;
+            }
+            for i in 0..m
+            {
+                let v = *vander.get(i, j) / scale[j];
+                vander.set(i, j, v);
+            }
+        }
+        let solution = lapacke::least_squares(&vander, y);
+        Polynomial::new((0..n).map(|j| solution[j] / scale[j]).collect())
+    }
+
+    /// m'th derivative of the polynomial
+    pub fn derivative(&self, m: usize) -> Polynomial<f64>
+    {
+        let mut coeffs = self.coeffs.clone();
+        for _ in 0..m
+        {
+            if coeffs.len() == 1
+            {
+                return Polynomial::new(vec![]);
+            }
+            coeffs = (1..coeffs.len()).map(|k| k as f64 * coeffs[k]).collect();
+        }
+        Polynomial::new(coeffs)
+    }
+
+    /// m'th antiderivative of the polynomial, with every integration constant
+    /// chosen so that the result and its first m-1 derivatives vanish at 0
+    pub fn antiderivative(&self, m: usize) -> Polynomial<f64>
+    {
+        let mut coeffs = self.coeffs.clone();
+        for _ in 0..m
+        {
+            let mut integral = vec![0f64; coeffs.len() + 1];
+            for (k, &c) in coeffs.iter().enumerate()
+            {
+                integral[k + 1] = c / (k + 1) as f64;
+            }
+            coeffs = integral;
+        }
+        Polynomial::new(coeffs)
+    }
+
+    /// Definite integral of the polynomial from a to b
+    pub fn integrate(&self, a: f64, b: f64) -> f64
+    {
+        let p = self.antiderivative(1);
+        p.eval(b) - p.eval(a)
+    }
+
+    /// All complex roots of the polynomial, as [re, im] pairs sorted by real
+    /// and then imaginary part. Quadratics and cubics are solved in closed
+    /// form and higher degrees from the eigenvalues of the companion matrix.
+    /// Panics for the zero polynomial, whose roots are not isolated.
+    pub fn roots(&self) -> Vec<[f64; 2]>
+    {
+        let c = &self.coeffs;
+        let mut roots = match self.degree() {
+            0 => {
+                if c[0] == 0f64
+                {
+                    panic!("Every point is a root of the zero polynomial");
+                }
+                vec![]
+            },
+            1 => vec![[-c[0] / c[1], 0f64]],
+            2 => poly_complex_solve_quadratic([c[2], c[1], c[0]]),
+            3 => poly_complex_solve_cubic([c[2] / c[3], c[1] / c[3], c[0] / c[3]]),
+            _ => poly_complex_solve(&self.to_array()),
+        };
+        roots.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        roots
+    }
+}
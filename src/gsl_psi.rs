@@ -0,0 +1,154 @@
+/// Digamma, trigamma and polygamma functions from the GSL. The polygamma
+/// functions of order n are defined by psi^(n)(x) = (d/dx)^n psi(x)
+///                                                = (d/dx)^(n+1) log(Gamma(x))
+/// For further information see Abramowitz & Stegun, Sections 6.3 and 6.4.
+
+use libc::{c_int, c_double};
+use gsl_sf;
+
+#[link(name = "gsl")]
+extern
+{
+    /// Di-Gamma function for integer n
+    /// n > 0
+    /// exceptions: GSL_EDOM
+    fn gsl_sf_psi_int_e(n: c_int, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Di-Gamma function
+    /// x != 0.0, -1.0, -2.0, ...
+    /// exceptions: GSL_EDOM, GSL_ELOSS
+    fn gsl_sf_psi_e(x: c_double, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Di-Gamma function, Re[psi(1 + I y)]
+    /// exceptions: none
+    fn gsl_sf_psi_1piy_e(y: c_double, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Tri-Gamma function for integer n
+    /// n > 0
+    /// exceptions: GSL_EDOM
+    fn gsl_sf_psi_1_int_e(n: c_int, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Tri-Gamma function
+    /// x != 0.0, -1.0, -2.0, ...
+    /// exceptions: GSL_EDOM, GSL_ELOSS
+    fn gsl_sf_psi_1_e(x: c_double, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Poly-Gamma function psi^(n)(x)
+    /// n >= 0, x > 0.0
+    /// exceptions: GSL_EDOM
+    fn gsl_sf_psi_n_e(n: c_int, x: c_double, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+}
+
+/// Returns true if x is zero or a negative integer, the poles of psi(x)
+#[inline]
+fn is_pole(x: f64) -> bool
+{
+    x <= 0f64 && x == x.round()
+}
+
+/// Digamma function psi(n) for integer n
+/// n > 0
+pub fn psi_int(n: i32) -> (f64, f64)
+{
+    if n <= 0
+    {
+        panic!("Invalid value for n: {}", n);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_psi_int_e(n, &mut s) != 0
+        {
+            panic!("Psi calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Digamma function psi(x)
+/// x != 0.0, -1.0, -2.0, ...
+pub fn psi(x: f64) -> (f64, f64)
+{
+    if is_pole(x)
+    {
+        panic!("Invalid value for x: {}", x);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_psi_e(x, &mut s) != 0
+        {
+            panic!("Psi calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Real part of the digamma function on the line 1 + iy, Re[psi(1 + iy)]
+pub fn psi_1piy(y: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_psi_1piy_e(y, &mut s) != 0
+        {
+            panic!("Psi calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Trigamma function psi'(n) for integer n
+/// n > 0
+pub fn psi_1_int(n: i32) -> (f64, f64)
+{
+    if n <= 0
+    {
+        panic!("Invalid value for n: {}", n);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_psi_1_int_e(n, &mut s) != 0
+        {
+            panic!("Psi calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Trigamma function psi'(x)
+/// x != 0.0, -1.0, -2.0, ...
+pub fn psi_1(x: f64) -> (f64, f64)
+{
+    if is_pole(x)
+    {
+        panic!("Invalid value for x: {}", x);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_psi_1_e(x, &mut s) != 0
+        {
+            panic!("Psi calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Polygamma function psi^(n)(x)
+/// n >= 0, x > 0.0
+pub fn psi_n(n: i32, x: f64) -> (f64, f64)
+{
+    if n < 0
+    {
+        panic!("Invalid value for n: {}", n);
+    }
+    if x <= 0f64
+    {
+        panic!("Invalid value for x: {}", x);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_psi_n_e(n, x, &mut s) != 0
+        {
+            panic!("Psi calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
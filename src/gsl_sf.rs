@@ -1,10 +1,39 @@
 /// Special function common definitions
 use libc::{c_int, c_double};
+use std::f64;
 
 pub const GSL_PREC_DOUBLE: i32 = 0i32;
 pub const GSL_PREC_SINGLE: i32 = 1i32;
 pub const GSL_PREC_APPROX: i32 = 2i32;
 
+/// Accuracy requested from special functions which accept a gsl_mode_t.
+/// Double gives full double precision, Single relative accuracy of about
+/// 1e-7 and Approx relative accuracy of about 5e-4.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Precision { Double, Single, Approx }
+
+impl Precision
+{
+    /// Converts the precision into the corresponding GSL_PREC_* mode value
+    pub fn as_mode(&self) -> c_int
+    {
+        match *self {
+            Precision::Double => GSL_PREC_DOUBLE,
+            Precision::Single => GSL_PREC_SINGLE,
+            Precision::Approx => GSL_PREC_APPROX,
+        }
+    }
+}
+
+/// Special functions default to full double precision
+impl Default for Precision
+{
+    fn default() -> Precision
+    {
+        Precision::Double
+    }
+}
+
 #[repr(C)]
 pub struct gsl_sf_result_struct
 {
@@ -23,3 +52,102 @@ pub struct gsl_sf_result_e10_struct
 }
 #[allow(non_camel_case_types)]
 pub type gsl_sf_result_e10 = gsl_sf_result_e10_struct;
+
+/// GSL error code signalled when a result overflows a double. Functions
+/// which return extended-range results report this alongside a valid
+/// exponent rather than failing.
+pub const GSL_EOVRFLW: i32 = 16i32;
+
+/// Extended-range result of a special function, with the value represented
+/// as val * 10^e10 so that results beyond the range of a double are not lost.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct ScaledResult
+{
+    /// Mantissa of the result
+    pub val: f64,
+    /// Estimated absolute error of the mantissa
+    pub err: f64,
+    /// Base-10 exponent of the result
+    pub e10: i32,
+}
+
+impl ScaledResult
+{
+    /// Creates a result representing val * 10^e10 with error err * 10^e10
+    pub fn new(val: f64, err: f64, e10: i32) -> ScaledResult
+    {
+        ScaledResult { val: val, err: err, e10: e10 }
+    }
+
+    /// Creates a result representing val * exp(ln_scale), moving the natural
+    /// exponent into a base-10 exponent where possible.
+    pub fn from_ln_scale(val: f64, err: f64, ln_scale: f64) -> ScaledResult
+    {
+        let e10 = (ln_scale / f64::consts::LN_10).floor();
+        let factor = (ln_scale - e10 * f64::consts::LN_10).exp();
+        ScaledResult { val: val * factor, err: err * factor, e10: e10 as i32 }
+    }
+
+    /// Converts the result into a double, returning None if it overflows or
+    /// underflows the range of normal doubles.
+    pub fn to_f64(&self) -> Option<f64>
+    {
+        self.to_f64_err().map(|(val, _)| val)
+    }
+
+    /// Converts the result and its error into doubles, returning None if the
+    /// value overflows or underflows the range of normal doubles.
+    pub fn to_f64_err(&self) -> Option<(f64, f64)>
+    {
+        if self.e10 == 0
+        {
+            return Some((self.val, self.err));
+        }
+        if self.val == 0f64
+        {
+            return Some((0f64, scale_pow10(self.err, self.e10)));
+        }
+        let log_mag = self.log10_abs();
+        if log_mag >= f64::MAX.log10() || log_mag < f64::MIN_POSITIVE.log10()
+        {
+            return None;
+        }
+        Some((scale_pow10(self.val, self.e10), scale_pow10(self.err, self.e10)))
+    }
+
+    /// Base-10 logarithm of the magnitude of the result
+    pub fn log10_abs(&self) -> f64
+    {
+        self.val.abs().log10() + self.e10 as f64
+    }
+
+    /// Natural logarithm of the magnitude of the result
+    pub fn ln_abs(&self) -> f64
+    {
+        self.val.abs().ln() + (self.e10 as f64) * f64::consts::LN_10
+    }
+
+    /// Sign of the result: 1.0, -1.0, or 0.0 if the result is zero
+    pub fn signum(&self) -> f64
+    {
+        if self.val == 0f64 { 0f64 } else { self.val.signum() }
+    }
+}
+
+/// Conversion from the raw GSL extended-range result
+impl From<gsl_sf_result_e10> for ScaledResult
+{
+    fn from(r: gsl_sf_result_e10) -> ScaledResult
+    {
+        ScaledResult { val: r.val, err: r.err, e10: r.e10 }
+    }
+}
+
+/// Computes x * 10^e in two steps so that the power of ten cannot overflow
+/// on its own when x is small.
+#[inline]
+fn scale_pow10(x: f64, e: i32) -> f64
+{
+    let half = e / 2;
+    x * 10f64.powi(half) * 10f64.powi(e - half)
+}
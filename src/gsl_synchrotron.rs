@@ -0,0 +1,61 @@
+/// Synchrotron functions from the GSL, describing the spectrum of radiation
+/// emitted by relativistic electrons in a magnetic field.
+/// For further information see Rybicki & Lightman, Radiative Processes in
+/// Astrophysics, Chapter 6.
+
+use libc::{c_int, c_double};
+use gsl_sf;
+
+#[link(name = "gsl")]
+extern
+{
+    /// First synchrotron function:
+    /// synchrotron_1(x) = x Integral[ K_{5/3}(t), {t, x, Infinity}]
+    /// exceptions: GSL_EDOM, GSL_EUNDRFLW
+    fn gsl_sf_synchrotron_1_e(
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Second synchrotron function:
+    /// synchrotron_2(x) = x * K_{2/3}(x)
+    /// exceptions: GSL_EDOM, GSL_EUNDRFLW
+    fn gsl_sf_synchrotron_2_e(
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+}
+
+/// First synchrotron function x Integral[K_{5/3}(t), {t, x, Infinity}]
+/// x >= 0.0
+pub fn synchrotron_1(x: f64) -> (f64, f64)
+{
+    if x < 0f64
+    {
+        panic!("Invalid value for x: {}", x);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_synchrotron_1_e(x, &mut s) != 0
+        {
+            panic!("Synchrotron calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Second synchrotron function x K_{2/3}(x)
+/// x >= 0.0
+pub fn synchrotron_2(x: f64) -> (f64, f64)
+{
+    if x < 0f64
+    {
+        panic!("Invalid value for x: {}", x);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_synchrotron_2_e(x, &mut s) != 0
+        {
+            panic!("Synchrotron calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
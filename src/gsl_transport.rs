@@ -0,0 +1,107 @@
+/// Transport functions from the GSL,
+///     J(n, x) = Integral[t^n e^t / (e^t - 1)^2, {t, 0, x}]
+/// for n = 2, ..., 5, which appear in the theory of electrical and thermal
+/// conduction in metals.
+
+use libc::{c_int, c_double};
+use gsl_sf;
+
+#[link(name = "gsl")]
+extern
+{
+    /// Transport function J(2,x)
+    /// exceptions: GSL_EDOM
+    fn gsl_sf_transport_2_e(
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Transport function J(3,x)
+    /// exceptions: GSL_EDOM
+    fn gsl_sf_transport_3_e(
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Transport function J(4,x)
+    /// exceptions: GSL_EDOM
+    fn gsl_sf_transport_4_e(
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Transport function J(5,x)
+    /// exceptions: GSL_EDOM
+    fn gsl_sf_transport_5_e(
+        x: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+}
+
+/// Transport function J(2, x)
+/// x >= 0.0
+pub fn transport_2(x: f64) -> (f64, f64)
+{
+    if x < 0f64
+    {
+        panic!("Invalid value for x: {}", x);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_transport_2_e(x, &mut s) != 0
+        {
+            panic!("Transport calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Transport function J(3, x)
+/// x >= 0.0
+pub fn transport_3(x: f64) -> (f64, f64)
+{
+    if x < 0f64
+    {
+        panic!("Invalid value for x: {}", x);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_transport_3_e(x, &mut s) != 0
+        {
+            panic!("Transport calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Transport function J(4, x)
+/// x >= 0.0
+pub fn transport_4(x: f64) -> (f64, f64)
+{
+    if x < 0f64
+    {
+        panic!("Invalid value for x: {}", x);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_transport_4_e(x, &mut s) != 0
+        {
+            panic!("Transport calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Transport function J(5, x)
+/// x >= 0.0
+pub fn transport_5(x: f64) -> (f64, f64)
+{
+    if x < 0f64
+    {
+        panic!("Invalid value for x: {}", x);
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_transport_5_e(x, &mut s) != 0
+        {
+            panic!("Transport calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
@@ -0,0 +1,456 @@
+/// Zeta functions, the dilogarithm and the general polylogarithm.
+/// The Riemann zeta function is defined by the infinite sum
+///   zeta(s) = \sum_{k=1}^\infty k^{-s}
+/// and the polylogarithm by
+///   Li_s(z) = \sum_{k=1}^\infty z^k / k^s
+/// For further information see Abramowitz & Stegun, Section 23.2 and 27.7.
+
+use libc::{c_int, c_double};
+use std::f64;
+use gsl_sf;
+use gsl_math;
+
+#[link(name = "gsl")]
+extern
+{
+    /// Riemann Zeta Function
+    /// zeta(n) = Sum[ k^(-n), {k,1,Infinity} ]
+    /// n=integer, n != 1
+    /// exceptions: GSL_EDOM, GSL_EOVRFLW
+    fn gsl_sf_zeta_int_e(n: c_int, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Riemann Zeta Function
+    /// zeta(x) = Sum[ k^(-s), {k,1,Infinity} ], s != 1.0
+    /// exceptions: GSL_EDOM, GSL_EOVRFLW
+    fn gsl_sf_zeta_e(s: c_double, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Riemann Zeta Function minus 1
+    /// useful for evaluating the fractional part
+    /// of Riemann zeta for large argument
+    /// s != 1.0
+    /// exceptions: GSL_EDOM, GSL_EOVRFLW
+    fn gsl_sf_zetam1_e(s: c_double, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Riemann Zeta Function minus 1 for integer arg
+    /// n=integer, n != 1
+    /// exceptions: GSL_EDOM, GSL_EOVRFLW
+    fn gsl_sf_zetam1_int_e(s: c_int, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Hurwitz Zeta Function
+    /// zeta(s,q) = Sum[ (k+q)^(-s), {k,0,Infinity} ]
+    /// s > 1.0, q > 0.0
+    /// exceptions: GSL_EDOM, GSL_EUNDRFLW, GSL_EOVRFLW
+    fn gsl_sf_hzeta_e(
+        s: c_double,
+        q: c_double,
+        result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Eta Function
+    /// eta(n) = (1-2^(1-n)) zeta(n)
+    /// exceptions: GSL_EUNDRFLW, GSL_EOVRFLW
+    fn gsl_sf_eta_int_e(n: c_int, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Eta Function
+    /// eta(s) = (1-2^(1-s)) zeta(s)
+    /// exceptions: GSL_EUNDRFLW, GSL_EOVRFLW
+    fn gsl_sf_eta_e(s: c_double, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Real part of DiLogarithm(x), for real argument.
+    /// In Lewin's notation, this is Li_2(x).
+    ///   Li_2(x) = - Re[ Integrate[ Log[1-s] / s, {s, 0, x}] ]
+    /// exceptions: GSL_EDOM
+    fn gsl_sf_dilog_e(x: c_double, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// DiLogarithm(z), for complex argument z = x + i y.
+    /// Computes the principal branch.
+    /// exceptions: GSL_EDOM
+    fn gsl_sf_complex_dilog_xy_e(
+        x: c_double,
+        y: c_double,
+        result_re: *mut gsl_sf::gsl_sf_result,
+        result_im: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// DiLogarithm(z), for complex argument z = r Exp[i theta].
+    /// Computes the principal branch.
+    /// exceptions: GSL_EDOM
+    fn gsl_sf_complex_dilog_e(
+        r: c_double,
+        theta: c_double,
+        result_re: *mut gsl_sf::gsl_sf_result,
+        result_im: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// Spence integral; spence(s) := Li_2(1-s)
+    /// This function has a form for complex argument, specialized
+    /// from the complex dilogarithm.
+    /// exceptions: GSL_EDOM
+    fn gsl_sf_complex_spence_xy_e(
+        x: c_double,
+        y: c_double,
+        real_sp: *mut gsl_sf::gsl_sf_result,
+        imag_sp: *mut gsl_sf::gsl_sf_result) -> c_int;
+
+    /// The Gamma function, Gamma(x), x not a negative integer or zero.
+    /// Used for the leading term of the polylogarithm expansion about z = 1.
+    /// exceptions: GSL_EDOM, GSL_EOVRFLW, GSL_EROUND
+    fn gsl_sf_gamma_e(x: c_double, result: *mut gsl_sf::gsl_sf_result) -> c_int;
+}
+
+/// Riemann zeta function zeta(n) for integer n, n != 1
+pub fn zeta_int(n: i32) -> (f64, f64)
+{
+    if n == 1
+    {
+        panic!("Zeta function has a pole at n = 1");
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_zeta_int_e(n, &mut s) != 0
+        {
+            panic!("Zeta calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Riemann zeta function zeta(s) for arbitrary s, s != 1
+pub fn zeta(s: f64) -> (f64, f64)
+{
+    if s == 1f64
+    {
+        panic!("Zeta function has a pole at s = 1");
+    }
+    let mut r = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_zeta_e(s, &mut r) != 0
+        {
+            panic!("Zeta calculation failed");
+        }
+    }
+    (r.val, r.err)
+}
+
+/// Riemann zeta function minus one, zeta(s) - 1, for arbitrary s, s != 1.
+/// Useful for evaluating the fractional part of zeta(s) for large s.
+pub fn zetam1(s: f64) -> (f64, f64)
+{
+    if s == 1f64
+    {
+        panic!("Zeta function has a pole at s = 1");
+    }
+    let mut r = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_zetam1_e(s, &mut r) != 0
+        {
+            panic!("Zeta calculation failed");
+        }
+    }
+    (r.val, r.err)
+}
+
+/// Riemann zeta function minus one, zeta(n) - 1, for integer n, n != 1
+pub fn zetam1_int(n: i32) -> (f64, f64)
+{
+    if n == 1
+    {
+        panic!("Zeta function has a pole at n = 1");
+    }
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_zetam1_int_e(n, &mut s) != 0
+        {
+            panic!("Zeta calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Hurwitz zeta function zeta(s, q) = Sum[ (k+q)^(-s), {k,0,Infinity} ]
+/// s > 1.0, q > 0.0
+pub fn hzeta(s: f64, q: f64) -> (f64, f64)
+{
+    if s <= 1f64 || q <= 0f64
+    {
+        panic!("s, q out of range: s = {}, q = {}", s, q);
+    }
+    let mut r = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_hzeta_e(s, q, &mut r) != 0
+        {
+            panic!("Hurwitz zeta calculation failed");
+        }
+    }
+    (r.val, r.err)
+}
+
+/// Dirichlet eta function eta(n) = (1-2^(1-n)) zeta(n) for integer n
+pub fn eta_int(n: i32) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_eta_int_e(n, &mut s) != 0
+        {
+            panic!("Eta calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Dirichlet eta function eta(s) = (1-2^(1-s)) zeta(s) for arbitrary s
+pub fn eta(s: f64) -> (f64, f64)
+{
+    let mut r = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_eta_e(s, &mut r) != 0
+        {
+            panic!("Eta calculation failed");
+        }
+    }
+    (r.val, r.err)
+}
+
+/// Real part of the dilogarithm Li_2(x) for real argument x
+pub fn dilog(x: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_dilog_e(x, &mut s) != 0
+        {
+            panic!("Dilogarithm calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Principal branch of the dilogarithm Li_2(z) for complex z = x + i y.
+/// Returns the values [re, im] and their estimated errors.
+pub fn complex_dilog_xy(x: f64, y: f64) -> ([f64; 2], [f64; 2])
+{
+    let mut re = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    let mut im = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_complex_dilog_xy_e(x, y, &mut re, &mut im) != 0
+        {
+            panic!("Dilogarithm calculation failed");
+        }
+    }
+    ([re.val, im.val], [re.err, im.err])
+}
+
+/// Principal branch of the dilogarithm Li_2(z) for complex z = r exp(i theta).
+/// Returns the values [re, im] and their estimated errors.
+pub fn complex_dilog(r: f64, theta: f64) -> ([f64; 2], [f64; 2])
+{
+    let mut re = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    let mut im = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_complex_dilog_e(r, theta, &mut re, &mut im) != 0
+        {
+            panic!("Dilogarithm calculation failed");
+        }
+    }
+    ([re.val, im.val], [re.err, im.err])
+}
+
+/// Spence integral Li_2(1 - z) for complex z = x + i y.
+/// Returns the values [re, im] and their estimated errors.
+pub fn complex_spence_xy(x: f64, y: f64) -> ([f64; 2], [f64; 2])
+{
+    let mut re = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    let mut im = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_complex_spence_xy_e(x, y, &mut re, &mut im) != 0
+        {
+            panic!("Spence integral calculation failed");
+        }
+    }
+    ([re.val, im.val], [re.err, im.err])
+}
+
+///////////////////////////
+// General Polylogarithm //
+///////////////////////////
+
+/// Maximum number of terms summed by the polylogarithm series
+const POLYLOG_MAX_TERMS: usize = 2000;
+
+/// Gamma function, used for the non-integer polylogarithm expansion
+fn gamma(x: f64) -> (f64, f64)
+{
+    let mut s = gsl_sf::gsl_sf_result_struct{val: 0f64, err: 0f64};
+    unsafe {
+        if gsl_sf_gamma_e(x, &mut s) != 0
+        {
+            panic!("Gamma calculation failed");
+        }
+    }
+    (s.val, s.err)
+}
+
+/// Returns true if s is an exact integer
+#[inline]
+fn is_integer(s: f64) -> bool
+{
+    s == s.round()
+}
+
+/// n! as a float
+#[inline]
+fn factorial(n: u32) -> f64
+{
+    (1..(n + 1)).fold(1f64, |acc, k| acc * (k as f64))
+}
+
+/// Direct summation of Sum[ z^k / k^s ], used for |z| <= 1/2
+fn polylog_series(s: f64, z: f64) -> (f64, f64)
+{
+    let mut sum = 0f64;
+    let mut abs_sum = 0f64;
+    let mut zk = 1f64;
+    let mut term = 0f64;
+    for k in 1..POLYLOG_MAX_TERMS
+    {
+        zk *= z;
+        term = zk / (k as f64).powf(s);
+        sum += term;
+        abs_sum += term.abs();
+        if term.abs() < f64::EPSILON * sum.abs()
+        {
+            break;
+        }
+    }
+    (sum, 2f64 * f64::EPSILON * abs_sum + term.abs())
+}
+
+/// Expansion about z = 1 in powers of mu = ln(z), used for 1/2 < z < 1:
+///   Li_s(e^mu) = Gamma(1-s) (-mu)^(s-1) + Sum[ zeta(s-k) mu^k / k! ]
+/// For positive integer s = n the pole of zeta(1) is replaced by the term
+///   mu^(n-1) / (n-1)! [H_(n-1) - ln(-mu)]
+fn polylog_log_expansion(s: f64, z: f64) -> (f64, f64)
+{
+    let mu = z.ln();
+    let n = s.round() as i32;
+    let positive_int = is_integer(s) && n >= 1;
+
+    let (mut sum, mut err) = if positive_int
+    {
+        let harmonic = (1..n).fold(0f64, |acc, j| acc + 1f64 / (j as f64));
+        let lead = mu.powi(n - 1) / factorial((n - 1) as u32);
+        (lead * (harmonic - (-mu).ln()), f64::EPSILON * lead.abs())
+    } else {
+        let (g, g_err) = gamma(1f64 - s);
+        let p = (-mu).powf(s - 1f64);
+        (g * p, g_err * p.abs())
+    };
+    let mut abs_sum = sum.abs();
+
+    // mu^k / k!, updated incrementally
+    let mut coeff = 1f64;
+    let mut term = 0f64;
+    for k in 0..POLYLOG_MAX_TERMS
+    {
+        if k > 0
+        {
+            coeff *= mu / (k as f64);
+        }
+        if positive_int && k as i32 == n - 1
+        {
+            continue;
+        }
+        let (zv, zerr) = zeta(s - k as f64);
+        term = zv * coeff;
+        sum += term;
+        abs_sum += term.abs();
+        err += zerr * coeff.abs();
+        // zeta vanishes at the negative even integers, so zero terms say
+        // nothing about convergence
+        if k > 2 && term != 0f64 && term.abs() < f64::EPSILON * sum.abs()
+        {
+            break;
+        }
+    }
+    (sum, err + 2f64 * f64::EPSILON * abs_sum + term.abs())
+}
+
+/// Polylogarithm Li_s(z) = Sum[ z^k / k^s, {k,1,Infinity} ] for real order s
+/// and real argument z <= 1. For z < -1 the order s must be an integer, since
+/// the inversion formula used there is only available for integer orders.
+/// Returns the value and an estimate of its error.
+pub fn polylog(s: f64, z: f64) -> (f64, f64)
+{
+    if z > 1f64
+    {
+        panic!("Polylogarithm is complex-valued for z > 1: z = {}", z);
+    }
+    if z == 0f64
+    {
+        return (0f64, 0f64);
+    }
+    if s == 1f64
+    {
+        if z == 1f64
+        {
+            panic!("Li_1(z) diverges at z = 1");
+        }
+        let val = -gsl_math::gslmath_log1p(-z);
+        return (val, 2f64 * f64::EPSILON * val.abs());
+    }
+    if s == 2f64
+    {
+        return dilog(z);
+    }
+    if z == 1f64
+    {
+        if s < 1f64
+        {
+            panic!("Li_s(1) diverges for s < 1: s = {}", s);
+        }
+        return zeta(s);
+    }
+    if z == -1f64
+    {
+        let (val, err) = eta(s);
+        return (-val, err);
+    }
+
+    if z.abs() <= 0.5f64
+    {
+        polylog_series(s, z)
+    } else if z > 0f64 {
+        polylog_log_expansion(s, z)
+    } else if z > -1f64 {
+        // Duplication formula, Li_s(z) + Li_s(-z) = 2^(1-s) Li_s(z^2), moves
+        // the argument onto (1/4, 1) where the series above converge.
+        let (sq, sq_err) = polylog(s, z * z);
+        let (neg, neg_err) = polylog(s, -z);
+        let scale = 2f64.powf(1f64 - s);
+        (scale * sq - neg, scale * sq_err + neg_err)
+    } else {
+        if !is_integer(s)
+        {
+            panic!("Li_s(z) for z < -1 requires an integer order: s = {}", s);
+        }
+        // Inversion formula relating Li_n(z) to Li_n(1/z)
+        let n = s as i32;
+        let sign = if n % 2 == 0 { 1f64 } else { -1f64 };
+        let (inv, inv_err) = polylog(s, 1f64 / z);
+        if n <= 0
+        {
+            // Li_n(z) + (-1)^n Li_n(1/z) = -1 for n = 0 and 0 for n < 0
+            let rhs = if n == 0 { -1f64 } else { 0f64 };
+            return (rhs - sign * inv, inv_err);
+        }
+        // Li_n(z) + (-1)^n Li_n(1/z) = -ln(-z)^n / n!
+        //     - 2 Sum[ ln(-z)^(n-2k) / (n-2k)! eta(2k), {k,1,n/2} ]
+        let lx = (-z).ln();
+        let mut val = -sign * inv - lx.powi(n) / factorial(n as u32);
+        let mut err = inv_err + f64::EPSILON * val.abs();
+        for k in 1..(n / 2 + 1)
+        {
+            let (e, e_err) = eta_int(2 * k);
+            let c = 2f64 * lx.powi(n - 2 * k) / factorial((n - 2 * k) as u32);
+            val -= c * e;
+            err += c.abs() * e_err;
+        }
+        (val, err + 2f64 * f64::EPSILON * val.abs())
+    }
+}
@@ -0,0 +1,246 @@
+/// Integration of sampled data: the composite trapezoidal and Simpson rules,
+/// Romberg integration and cumulative trapezoidal integrals.
+/// Samples are either equally spaced, `Spacing::Uniform(dx)`, or taken at
+/// the abscissae of an array, `Spacing::Points(&x)`, which must be as long
+/// as the samples. The `_axis` variants integrate every row or every column
+/// of a `Matrix` independently.
+/// For integrating functions rather than samples see `gsl_integration`.
+
+use array::Array;
+use array::Order;
+use matrix::Matrix;
+
+/// Location of the samples
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Spacing<'a>
+{
+    /// Equally spaced samples a distance dx apart
+    Uniform(f64),
+    /// Samples at the given abscissae, in increasing or decreasing order
+    Points(&'a Array<f64>),
+}
+
+/// Direction of integration through a `Matrix`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Axis
+{
+    /// Integrate along each row, giving one result per row
+    Row,
+    /// Integrate down each column, giving one result per column
+    Column,
+}
+
+/// Widths of the n - 1 intervals between n samples
+fn steps(n: usize, spacing: Spacing) -> Vec<f64>
+{
+    match spacing
+    {
+        Spacing::Uniform(dx) => vec![dx; if n > 0 { n - 1 } else { 0 }],
+        Spacing::Points(x) => {
+            if x.len() != n
+            {
+                panic!("Sample points and values differ in length: {}, {}",
+                       x.len(), n);
+            }
+            (1..n).map(|i| x[i] - x[i - 1]).collect()
+        },
+    }
+}
+
+/// The rows or columns of m, each as an array
+fn lanes(m: &Matrix<f64>, axis: Axis) -> Vec<Array<f64>>
+{
+    let (rows, cols) = m.get_dims();
+    match axis
+    {
+        Axis::Row => (0..rows)
+            .map(|i| Array::new((0..cols).map(|j| *m.get(i, j)).collect(), Order::Row))
+            .collect(),
+        Axis::Column => (0..cols)
+            .map(|j| Array::new((0..rows).map(|i| *m.get(i, j)).collect(), Order::Row))
+            .collect(),
+    }
+}
+
+/// Composite Simpson's rule over y[start..start + 2 * pairs + 1]
+fn simpson_pairs(y: &[f64], h: &[f64], start: usize, pairs: usize) -> f64
+{
+    let mut sum = 0f64;
+    for p in 0..pairs
+    {
+        let i = start + 2 * p;
+        let (h0, h1) = (h[i], h[i + 1]);
+        let hsum = h0 + h1;
+        let ratio = h0 / h1;
+        sum += hsum / 6f64 * (y[i] * (2f64 - 1f64 / ratio)
+                              + y[i + 1] * hsum * hsum / (h0 * h1)
+                              + y[i + 2] * (2f64 - ratio));
+    }
+    sum
+}
+
+
+///////////////////
+// Sampled rules //
+///////////////////
+
+/// Integral of the samples y by the composite trapezoidal rule.
+/// Fewer than two samples integrate to zero.
+pub fn trapezoid(y: &Array<f64>, spacing: Spacing) -> f64
+{
+    let h = steps(y.len(), spacing);
+    (0..h.len()).fold(0f64, |s, i| s + 0.5 * h[i] * (y[i] + y[i + 1]))
+}
+
+/// Integral of the samples y by the composite Simpson's rule, which is exact
+/// for cubics given an odd number of equally spaced samples. Unequal spacing
+/// is handled by fitting a parabola through each consecutive triple. With an
+/// even number of samples, and so an odd number of intervals, the last
+/// interval is integrated with a parabola through the last three samples
+/// (Cartwright's correction), which is exact for quadratics. Two samples fall
+/// back to the trapezoidal rule.
+pub fn simpson(y: &Array<f64>, spacing: Spacing) -> f64
+{
+    let n = y.len();
+    let h = steps(n, spacing);
+    let y = y.as_slice();
+    if n < 3
+    {
+        return (0..h.len()).fold(0f64, |s, i| s + 0.5 * h[i] * (y[i] + y[i + 1]));
+    }
+    if n % 2 == 1
+    {
+        return simpson_pairs(y, &h, 0, (n - 1) / 2);
+    }
+    let (hm2, hm1) = (h[n - 3], h[n - 2]);
+    let alpha = (2f64 * hm1 * hm1 + 3f64 * hm1 * hm2) / (6f64 * (hm2 + hm1));
+    let beta = (hm1 * hm1 + 3f64 * hm1 * hm2) / (6f64 * hm2);
+    let eta = hm1 * hm1 * hm1 / (6f64 * hm2 * (hm2 + hm1));
+    simpson_pairs(y, &h, 0, (n - 2) / 2)
+        + alpha * y[n - 1] + beta * y[n - 2] - eta * y[n - 3]
+}
+
+/// Romberg integration of 2^k + 1 equally spaced samples a distance dx
+/// apart: the trapezoidal estimates on successively halved grids are
+/// combined by Richardson extrapolation.
+pub fn romb(y: &Array<f64>, dx: f64) -> f64
+{
+    let intervals = if y.len() > 0 { y.len() - 1 } else { 0 };
+    if intervals == 0 || !intervals.is_power_of_two()
+    {
+        panic!("Romberg integration needs 2^k + 1 samples, got {}", y.len());
+    }
+    let k = intervals.trailing_zeros() as usize;
+    let mut h = intervals as f64 * dx;
+    // Row i of the Romberg table, built from row i - 1
+    let mut row = vec![0.5 * h * (y[0] + y[intervals])];
+    let mut step = intervals;
+    for i in 1..k + 1
+    {
+        let start = step / 2;
+        let mut sum = 0f64;
+        let mut j = start;
+        while j < intervals
+        {
+            sum += y[j];
+            j += step;
+        }
+        let mut next = vec![0.5 * (row[0] + h * sum)];
+        for j in 1..i + 1
+        {
+            let prev = next[j - 1];
+            next.push(prev + (prev - row[j - 1]) / ((1u64 << (2 * j)) - 1) as f64);
+        }
+        row = next;
+        step = start;
+        h *= 0.5;
+    }
+    row[k]
+}
+
+/// Running integral of the samples y by the trapezoidal rule: element i is
+/// the integral from the first sample to sample i + 1. If initial is given
+/// it is prepended, so that the result is as long as y and element i is the
+/// integral up to sample i plus initial.
+pub fn cumulative_trapezoid(y: &Array<f64>, spacing: Spacing,
+                            initial: Option<f64>) -> Array<f64>
+{
+    let h = steps(y.len(), spacing);
+    let mut out = Vec::with_capacity(y.len());
+    let mut total = 0f64;
+    if let Some(c) = initial
+    {
+        total = c;
+        out.push(c);
+    }
+    for i in 0..h.len()
+    {
+        total += 0.5 * h[i] * (y[i] + y[i + 1]);
+        out.push(total);
+    }
+    Array::new(out, Order::Row)
+}
+
+
+/////////////////
+// Matrix axes //
+/////////////////
+
+/// `trapezoid` applied to each row or column of m
+pub fn trapezoid_axis(m: &Matrix<f64>, spacing: Spacing, axis: Axis) -> Array<f64>
+{
+    let out = lanes(m, axis).iter().map(|y| trapezoid(y, spacing)).collect();
+    Array::new(out, Order::Row)
+}
+
+/// `simpson` applied to each row or column of m
+pub fn simpson_axis(m: &Matrix<f64>, spacing: Spacing, axis: Axis) -> Array<f64>
+{
+    let out = lanes(m, axis).iter().map(|y| simpson(y, spacing)).collect();
+    Array::new(out, Order::Row)
+}
+
+/// `romb` applied to each row or column of m
+pub fn romb_axis(m: &Matrix<f64>, dx: f64, axis: Axis) -> Array<f64>
+{
+    let out = lanes(m, axis).iter().map(|y| romb(y, dx)).collect();
+    Array::new(out, Order::Row)
+}
+
+/// `cumulative_trapezoid` applied to each row or column of m. The result
+/// keeps the orientation of m, with one element fewer along the axis of
+/// integration unless initial is given.
+pub fn cumulative_trapezoid_axis(m: &Matrix<f64>, spacing: Spacing, axis: Axis,
+                                 initial: Option<f64>) -> Matrix<f64>
+{
+    let runs: Vec<Array<f64>> = lanes(m, axis).iter()
+        .map(|y| cumulative_trapezoid(y, spacing, initial))
+        .collect();
+    if runs.is_empty()
+    {
+        panic!("Cumulative integral of a matrix without rows or columns");
+    }
+    let count = runs.len();
+    let len = runs[0].len();
+    if len == 0
+    {
+        panic!("Cumulative integral of a single sample without an initial value");
+    }
+    let mut out = match axis
+    {
+        Axis::Row => Matrix::new_filled(0f64, count, len),
+        Axis::Column => Matrix::new_filled(0f64, len, count),
+    };
+    for (lane, run) in runs.iter().enumerate()
+    {
+        for k in 0..len
+        {
+            match axis
+            {
+                Axis::Row => out.set(lane, k, run[k]),
+                Axis::Column => out.set(k, lane, run[k]),
+            }
+        }
+    }
+    out
+}
@@ -0,0 +1,501 @@
+/// One-dimensional interpolation of data y_i = f(x_i) in the manner of
+/// SciPy's interp1d, as a stable alternative to the divided-difference
+/// polynomials of `gsl_poly` when there are many points.
+/// An `Interp1d` is piecewise polynomial between the data points: constant
+/// for nearest, previous and next neighbour interpolation, linear, or cubic.
+/// The cubic kinds are splines with continuous second derivatives, with
+/// natural, clamped or not-a-knot end conditions, or Hermite cubics whose
+/// slopes come from Akima's method or from the monotonicity preserving
+/// methods of Steffen (as in GSL) and Fritsch-Butland (SciPy's PCHIP).
+/// Besides values, an `Interp1d` evaluates derivatives and definite
+/// integrals of the interpolant. Outside the data the interpolant panics by
+/// default, or is extrapolated as chosen with `Extrapolate`.
+/// For further information see de Boor, A Practical Guide to Splines,
+/// Akima, J. ACM 17 (1970), Steffen, Astron. Astrophys. 239 (1990), and
+/// Fritsch & Butland, SIAM J. Sci. Stat. Comput. 5 (1984).
+
+use array::Array;
+use array::Order;
+
+/// Kind of interpolant
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Kind
+{
+    /// Straight lines between the points
+    Linear,
+    /// Value at the nearest point, the lower one at midpoints
+    Nearest,
+    /// Value at the nearest point at or below x
+    Previous,
+    /// Value at the nearest point at or above x
+    Next,
+    /// Cubic spline with zero second derivative at the ends
+    NaturalCubic,
+    /// Cubic spline with the given first derivatives at the first and last
+    /// points
+    ClampedCubic(f64, f64),
+    /// Cubic spline whose third derivative is continuous at the second and
+    /// second to last points; a parabola through three points
+    NotAKnotCubic,
+    /// Akima's cubic, whose slopes average the neighbouring secants so as to
+    /// avoid overshoots near outliers
+    Akima,
+    /// Steffen's monotone cubic, which has no extrema between the points
+    Steffen,
+    /// Monotone piecewise cubic Hermite interpolation by the method of
+    /// Fritsch & Butland
+    Pchip,
+}
+
+impl Kind
+{
+    fn step(&self) -> bool
+    {
+        match *self
+        {
+            Kind::Nearest | Kind::Previous | Kind::Next => true,
+            _ => false,
+        }
+    }
+}
+
+/// Behaviour outside the range of the data
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Extrapolate
+{
+    /// Panic, the default
+    Panic,
+    /// A constant value
+    Fill(f64),
+    /// The values at the first and last points
+    Hold,
+    /// Continue the polynomials of the first and last intervals; the same
+    /// as Hold for Nearest, Previous and Next
+    Extend,
+}
+
+/// Interpolant of one-dimensional data
+#[derive(Debug, Clone)]
+pub struct Interp1d
+{
+    x: Vec<f64>,
+    y: Vec<f64>,
+    kind: Kind,
+    extrapolate: Extrapolate,
+    /// Ends of the polynomial pieces: the data points, and for Nearest the
+    /// midpoints between them
+    breaks: Vec<f64>,
+    /// Coefficients [a, b, c, d] of a + b t + c t^2 + d t^3 on each piece,
+    /// with t the distance from its left end
+    coeffs: Vec<[f64; 4]>,
+}
+
+impl Interp1d
+{
+    /// Interpolant of kind through the points (x_i, y_i). Panics unless x
+    /// is strictly increasing and as long as y, with at least two points.
+    pub fn new(x: &Array<f64>, y: &Array<f64>, kind: Kind) -> Interp1d
+    {
+        let n = x.len();
+        if y.len() != n
+        {
+            panic!("x has {} points but y has {}", n, y.len());
+        }
+        if n < 2
+        {
+            panic!("Interpolation needs at least two points.");
+        }
+        let x = x.as_slice().to_vec();
+        let y = y.as_slice().to_vec();
+        for i in 0..n - 1
+        {
+            if !(x[i] < x[i + 1])
+            {
+                panic!("x must be strictly increasing, but x[{}] = {} and x[{}] = {}",
+                       i, x[i], i + 1, x[i + 1]);
+            }
+        }
+        if y.iter().any(|v| !v.is_finite())
+        {
+            panic!("y must be finite");
+        }
+
+        let h: Vec<f64> = (0..n - 1).map(|i| x[i + 1] - x[i]).collect();
+        let secants: Vec<f64> = (0..n - 1).map(|i| (y[i + 1] - y[i]) / h[i]).collect();
+        let (breaks, coeffs) = match kind
+        {
+            Kind::Linear =>
+            {
+                (x.clone(), (0..n - 1).map(|i| [y[i], secants[i], 0f64, 0f64]).collect())
+            },
+            Kind::Previous => (x.clone(), (0..n - 1).map(|i| [y[i], 0f64, 0f64, 0f64]).collect()),
+            Kind::Next => (x.clone(), (0..n - 1).map(|i| [y[i + 1], 0f64, 0f64, 0f64]).collect()),
+            Kind::Nearest =>
+            {
+                let mut breaks = vec![x[0]];
+                let mut coeffs = vec![];
+                for i in 0..n - 1
+                {
+                    breaks.push((x[i] + x[i + 1]) / 2f64);
+                    breaks.push(x[i + 1]);
+                    coeffs.push([y[i], 0f64, 0f64, 0f64]);
+                    coeffs.push([y[i + 1], 0f64, 0f64, 0f64]);
+                }
+                (breaks, coeffs)
+            },
+            _ =>
+            {
+                let slopes = match kind
+                {
+                    Kind::NaturalCubic | Kind::ClampedCubic(_, _) | Kind::NotAKnotCubic =>
+                    {
+                        spline_slopes(&h, &secants, kind)
+                    },
+                    Kind::Akima => akima_slopes(&secants),
+                    Kind::Steffen => steffen_slopes(&h, &secants),
+                    _ => pchip_slopes(&h, &secants),
+                };
+                (x.clone(), hermite(&y, &h, &secants, &slopes))
+            },
+        };
+        Interp1d{x: x, y: y, kind: kind, extrapolate: Extrapolate::Panic, breaks: breaks,
+                 coeffs: coeffs}
+    }
+
+    /// Sets the behaviour outside the range of the data
+    pub fn extrapolate(mut self, extrapolate: Extrapolate) -> Interp1d
+    {
+        self.extrapolate = extrapolate;
+        self
+    }
+
+    /// Range [x_0, x_n] of the data
+    pub fn range(&self) -> (f64, f64)
+    {
+        (self.x[0], self.x[self.x.len() - 1])
+    }
+
+    /// Polynomial coefficients in effect at x and the point they are taken
+    /// about
+    fn piece(&self, x: f64) -> ([f64; 4], f64)
+    {
+        let (lo, hi) = self.range();
+        if lo <= x && x <= hi
+        {
+            // Last break at or below x, within the pieces
+            let k = self.breaks.len() - 1;
+            let i = match self.breaks.binary_search_by(|b| b.partial_cmp(&x).unwrap())
+            {
+                Ok(i) => i,
+                Err(i) => i - 1,
+            };
+            let i = i.min(k - 1);
+            return (self.coeffs[i], self.breaks[i]);
+        }
+        let left = x < lo;
+        let extrapolate = match self.extrapolate
+        {
+            Extrapolate::Extend if self.kind.step() => Extrapolate::Hold,
+            e => e,
+        };
+        match extrapolate
+        {
+            Extrapolate::Panic =>
+            {
+                panic!("x = {} is outside the interpolation range [{}, {}]", x, lo, hi)
+            },
+            Extrapolate::Fill(v) => ([v, 0f64, 0f64, 0f64], x),
+            Extrapolate::Hold =>
+            {
+                let v = if left { self.y[0] } else { self.y[self.y.len() - 1] };
+                ([v, 0f64, 0f64, 0f64], x)
+            },
+            Extrapolate::Extend =>
+            {
+                let i = if left { 0 } else { self.coeffs.len() - 1 };
+                (self.coeffs[i], self.breaks[i])
+            },
+        }
+    }
+
+    /// Value of the interpolant at x
+    pub fn eval(&self, x: f64) -> f64
+    {
+        if x.is_nan()
+        {
+            return x;
+        }
+        let (lo, hi) = self.range();
+        if self.kind.step() && lo <= x && x <= hi
+        {
+            // The pieces are left-closed, which does not suit every end
+            // point of the step kinds
+            let n = self.x.len();
+            let above = match self.x.binary_search_by(|v| v.partial_cmp(&x).unwrap())
+            {
+                Ok(i) => return self.y[i],
+                Err(i) => i.min(n - 1),
+            };
+            return match self.kind
+            {
+                Kind::Previous => self.y[above - 1],
+                Kind::Next => self.y[above],
+                _ =>
+                {
+                    let below = above - 1;
+                    if x - self.x[below] <= self.x[above] - x
+                    {
+                        self.y[below]
+                    } else {
+                        self.y[above]
+                    }
+                },
+            };
+        }
+        let ([a, b, c, d], x0) = self.piece(x);
+        let t = x - x0;
+        a + t * (b + t * (c + t * d))
+    }
+
+    /// Values of the interpolant at each point of x
+    pub fn eval_array(&self, x: &Array<f64>) -> Array<f64>
+    {
+        Array::new(x.as_slice().iter().map(|&v| self.eval(v)).collect(), Order::Row)
+    }
+
+    /// Derivative of the given order of the interpolant at x; zero order
+    /// gives the value. At the data points the derivatives are those of the
+    /// piece to the right, or at the last point to the left.
+    pub fn derivative(&self, x: f64, order: usize) -> f64
+    {
+        if order == 0
+        {
+            return self.eval(x);
+        }
+        let ([_, b, c, d], x0) = self.piece(x);
+        let t = x - x0;
+        match order
+        {
+            1 => b + t * (2f64 * c + t * 3f64 * d),
+            2 => 2f64 * c + 6f64 * d * t,
+            3 => 6f64 * d,
+            _ => 0f64,
+        }
+    }
+
+    /// Integral of the interpolant from a to b
+    pub fn integral(&self, a: f64, b: f64) -> f64
+    {
+        if a > b
+        {
+            return -self.integral(b, a);
+        }
+        // Integrate piece by piece between the breaks inside [a, b]
+        let mut points = vec![a];
+        points.extend(self.breaks.iter().cloned().filter(|&v| a < v && v < b));
+        points.push(b);
+        let mut total = 0f64;
+        for w in points.windows(2)
+        {
+            if w[0] == w[1]
+            {
+                continue;
+            }
+            let ([c0, c1, c2, c3], x0) = self.piece((w[0] + w[1]) / 2f64);
+            let antiderivative = |x: f64|
+            {
+                let t = x - x0;
+                t * (c0 + t * (c1 / 2f64 + t * (c2 / 3f64 + t * c3 / 4f64)))
+            };
+            total += antiderivative(w[1]) - antiderivative(w[0]);
+        }
+        total
+    }
+}
+
+/// Piecewise cubic Hermite coefficients for the values y and slopes m
+fn hermite(y: &[f64], h: &[f64], secants: &[f64], m: &[f64]) -> Vec<[f64; 4]>
+{
+    (0..h.len()).map(|i|
+    {
+        let c = (3f64 * secants[i] - 2f64 * m[i] - m[i + 1]) / h[i];
+        let d = (m[i] + m[i + 1] - 2f64 * secants[i]) / (h[i] * h[i]);
+        [y[i], m[i], c, d]
+    }).collect()
+}
+
+/// Solution of a tridiagonal system with subdiagonal a, diagonal b and
+/// superdiagonal c by the Thomas algorithm; a[0] and c[n - 1] are unused
+fn tridiagonal(a: &[f64], b: &[f64], c: &[f64], r: &[f64]) -> Vec<f64>
+{
+    let n = b.len();
+    let mut cp = vec![0f64; n];
+    let mut rp = vec![0f64; n];
+    cp[0] = c[0] / b[0];
+    rp[0] = r[0] / b[0];
+    for i in 1..n
+    {
+        let den = b[i] - a[i] * cp[i - 1];
+        cp[i] = c[i] / den;
+        rp[i] = (r[i] - a[i] * rp[i - 1]) / den;
+    }
+    let mut x = rp;
+    for i in (0..n - 1).rev()
+    {
+        x[i] -= cp[i] * x[i + 1];
+    }
+    x
+}
+
+/// Slopes of the cubic spline with the end conditions of kind, from the
+/// continuity of the second derivative at the interior points
+fn spline_slopes(h: &[f64], secants: &[f64], kind: Kind) -> Vec<f64>
+{
+    let n = h.len() + 1;
+    if n == 2 || (n == 3 && kind == Kind::NotAKnotCubic)
+    {
+        return match kind
+        {
+            Kind::ClampedCubic(s0, s1) => vec![s0, s1],
+            Kind::NotAKnotCubic if n == 3 =>
+            {
+                // The parabola through the three points
+                let c = (secants[1] - secants[0]) / (h[0] + h[1]);
+                vec![secants[0] - c * h[0], secants[0] + c * h[0], secants[1] + c * h[1]]
+            },
+            _ => vec![secants[0]; 2],
+        };
+    }
+    let mut a = vec![0f64; n];
+    let mut b = vec![0f64; n];
+    let mut c = vec![0f64; n];
+    let mut r = vec![0f64; n];
+    for i in 1..n - 1
+    {
+        a[i] = h[i];
+        b[i] = 2f64 * (h[i - 1] + h[i]);
+        c[i] = h[i - 1];
+        r[i] = 3f64 * (h[i] * secants[i - 1] + h[i - 1] * secants[i]);
+    }
+    match kind
+    {
+        Kind::ClampedCubic(s0, s1) =>
+        {
+            b[0] = 1f64;
+            r[0] = s0;
+            b[n - 1] = 1f64;
+            r[n - 1] = s1;
+        },
+        Kind::NotAKnotCubic =>
+        {
+            let d = h[0] + h[1];
+            b[0] = h[1];
+            c[0] = d;
+            r[0] = ((h[0] + 2f64 * d) * h[1] * secants[0] + h[0] * h[0] * secants[1]) / d;
+            let d = h[n - 3] + h[n - 2];
+            a[n - 1] = d;
+            b[n - 1] = h[n - 3];
+            r[n - 1] = (h[n - 2] * h[n - 2] * secants[n - 3]
+                        + (2f64 * d + h[n - 2]) * h[n - 3] * secants[n - 2]) / d;
+        },
+        _ =>
+        {
+            b[0] = 2f64;
+            c[0] = 1f64;
+            r[0] = 3f64 * secants[0];
+            a[n - 1] = 1f64;
+            b[n - 1] = 2f64;
+            r[n - 1] = 3f64 * secants[n - 2];
+        },
+    }
+    tridiagonal(&a, &b, &c, &r)
+}
+
+/// Akima's slopes: averages of the secants on either side weighted by how
+/// much the secants beyond them differ, with two secants extrapolated
+/// linearly past each end
+fn akima_slopes(secants: &[f64]) -> Vec<f64>
+{
+    let k = secants.len();
+    if k == 1
+    {
+        return vec![secants[0]; 2];
+    }
+    let mut m = Vec::with_capacity(k + 4);
+    let left = 2f64 * secants[0] - secants[1];
+    let right = 2f64 * secants[k - 1] - secants[k - 2];
+    m.push(2f64 * left - secants[0]);
+    m.push(left);
+    m.extend_from_slice(secants);
+    m.push(right);
+    m.push(2f64 * right - secants[k - 1]);
+
+    let dm: Vec<f64> = (0..k + 3).map(|i| (m[i + 1] - m[i]).abs()).collect();
+    let largest = (0..k + 1).fold(0f64, |s, i| s.max(dm[i + 2] + dm[i]));
+    (0..k + 1).map(|i|
+    {
+        let (f1, f2) = (dm[i + 2], dm[i]);
+        if f1 + f2 > 1e-9 * largest
+        {
+            (f1 * m[i + 1] + f2 * m[i + 2]) / (f1 + f2)
+        } else {
+            (m[i + 1] + m[i + 2]) / 2f64
+        }
+    }).collect()
+}
+
+/// Steffen's slopes: the slope of the parabola through each point and its
+/// neighbours, limited so that the cubic stays monotone, with the secants
+/// at the ends as in GSL
+fn steffen_slopes(h: &[f64], secants: &[f64]) -> Vec<f64>
+{
+    let n = h.len() + 1;
+    let mut m = vec![0f64; n];
+    m[0] = secants[0];
+    m[n - 1] = secants[n - 2];
+    for i in 1..n - 1
+    {
+        let (s0, s1) = (secants[i - 1], secants[i]);
+        let p = (s0 * h[i] + s1 * h[i - 1]) / (h[i - 1] + h[i]);
+        m[i] = (s0.signum() + s1.signum()) * s0.abs().min(s1.abs()).min(p.abs() / 2f64);
+    }
+    m
+}
+
+/// Fritsch-Butland slopes: weighted harmonic means of the neighbouring
+/// secants, zero at extrema, with one-sided three point estimates at the
+/// ends kept from spoiling monotonicity
+fn pchip_slopes(h: &[f64], secants: &[f64]) -> Vec<f64>
+{
+    let n = h.len() + 1;
+    if n == 2
+    {
+        return vec![secants[0]; 2];
+    }
+    let mut m = vec![0f64; n];
+    for i in 1..n - 1
+    {
+        let (s0, s1) = (secants[i - 1], secants[i]);
+        if s0 * s1 > 0f64
+        {
+            let w1 = 2f64 * h[i] + h[i - 1];
+            let w2 = h[i] + 2f64 * h[i - 1];
+            m[i] = (w1 + w2) / (w1 / s0 + w2 / s1);
+        }
+    }
+    let edge = |h0: f64, h1: f64, s0: f64, s1: f64|
+    {
+        let d = ((2f64 * h0 + h1) * s0 - h0 * s1) / (h0 + h1);
+        if d * s0 <= 0f64
+        {
+            0f64
+        } else if s0 * s1 <= 0f64 && d.abs() > 3f64 * s0.abs() {
+            3f64 * s0
+        } else {
+            d
+        }
+    };
+    m[0] = edge(h[0], h[1], secants[0], secants[1]);
+    m[n - 1] = edge(h[n - 2], h[n - 3], secants[n - 2], secants[n - 3]);
+    m
+}
@@ -42,6 +42,39 @@ extern
                vt: *mut c_float, ldvt: *mut c_int, u: *mut c_float, ldu: *mut c_int,
                c: *mut c_float, ldc: *mut c_int, work: *mut c_float, info: *mut c_int);
 
+    /// DSTEV computes all eigenvalues and, optionally, eigenvectors of a
+    /// real symmetric tridiagonal matrix A.
+    fn dstev_(jobz: *mut c_char, n: *mut c_int, d: *mut c_double,
+              e: *mut c_double, z: *mut c_double, ldz: *mut c_int,
+              work: *mut c_double, info: *mut c_int);
+
+    /// DGELS solves overdetermined or underdetermined real linear systems
+    /// involving an M-by-N matrix A, or its transpose, using a QR or LQ
+    /// factorization of A.  It is assumed that A has full rank.
+    fn dgels_(trans: *mut c_char, m: *mut c_int, n: *mut c_int,
+              nrhs: *mut c_int, a: *mut c_double, lda: *mut c_int,
+              b: *mut c_double, ldb: *mut c_int, work: *mut c_double,
+              lwork: *mut c_int, info: *mut c_int);
+
+    /// DGEEV computes for an N-by-N real nonsymmetric matrix A, the
+    /// eigenvalues and, optionally, the left and/or right eigenvectors.
+    fn dgeev_(jobvl: *mut c_char, jobvr: *mut c_char, n: *mut c_int,
+              a: *mut c_double, lda: *mut c_int, wr: *mut c_double,
+              wi: *mut c_double, vl: *mut c_double, ldvl: *mut c_int,
+              vr: *mut c_double, ldvr: *mut c_int, work: *mut c_double,
+              lwork: *mut c_int, info: *mut c_int);
+
+    /// DGETRF computes an LU factorization of a general M-by-N matrix A
+    /// using partial pivoting with row interchanges.
+    fn dgetrf_(m: *mut c_int, n: *mut c_int, a: *mut c_double,
+               lda: *mut c_int, ipiv: *mut c_int, info: *mut c_int);
+
+    /// DGETRS solves a system of linear equations A * X = B or
+    /// A**T * X = B with a general N-by-N matrix A using the LU
+    /// factorization computed by DGETRF.
+    fn dgetrs_(trans: *mut c_char, n: *mut c_int, nrhs: *mut c_int,
+               a: *mut c_double, lda: *mut c_int, ipiv: *mut c_int,
+               b: *mut c_double, ldb: *mut c_int, info: *mut c_int);
 }
 
 /// Performs a Cholesky decomposition on a matrix in-place
@@ -104,6 +137,244 @@ pub fn bidiagonal_reduction(a: &mut Matrix<f32>)
     }
 }
 
+/// Computes all eigenvalues and eigenvectors of a real symmetric tridiagonal
+/// matrix given its diagonal and off-diagonal. Returns the eigenvalues in
+/// ascending order, and a matrix whose i-th row is the normalized eigenvector
+/// belonging to the i-th eigenvalue.
+pub fn symmetric_tridiagonal_eigen(diag: &Array<f64>, offdiag: &Array<f64>)
+    -> (Array<f64>, Matrix<f64>)
+{
+    let _n = diag.len();
+    if _n == 0 || offdiag.len() + 1 != _n
+    {
+        panic!("Off-diagonal must have exactly one element less than the diagonal.");
+    }
+    let mut jobz: c_char = 'V' as c_char;
+    let mut n = _n as c_int;
+    let mut ldz = n;
+    let mut info: c_int = 0;
+    // D is overwritten with the eigenvalues, E is destroyed. E must be
+    // allocated even for N = 1.
+    let mut d = Array::<f64>::new(diag.as_slice().to_vec(), Order::Row);
+    let mut e = Array::<f64>::new_filled(0f64, _n, Order::Row);
+    for i in 0.._n - 1
+    {
+        e[i] = offdiag[i];
+    }
+    // Z is column-major, so read as a row-major matrix it holds one
+    // eigenvector per row.
+    let mut z = vec![0f64; _n * _n];
+    // WORK is DOUBLE PRECISION array, dimension (max(1,2*N-2))
+    let mut work = Array::<f64>::new_filled(0f64, 2 * _n, Order::Row);
+
+    unsafe {
+        dstev_(&mut jobz, &mut n, d.as_mut_ptr(), e.as_mut_ptr(),
+               z.as_mut_ptr(), &mut ldz, work.as_mut_ptr(), &mut info);
+    }
+    if info != 0
+    {
+        panic!("Tridiagonal eigenvalue computation failed: {}", info);
+    }
+    (d, Matrix::new_from_vec(z, _n, _n))
+}
+
+/// Solves the linear least squares problem min ||A x - b|| for an M-by-N
+/// matrix A of full rank with M >= N, returning x.
+pub fn least_squares(a: &Matrix<f64>, b: &Array<f64>) -> Array<f64>
+{
+    let (_m, _n) = a.get_dims();
+    if _m < _n
+    {
+        panic!("Least squares requires at least as many rows as columns.");
+    }
+    if b.len() != _m
+    {
+        panic!("Right-hand side has length {}, expected {}.", b.len(), _m);
+    }
+    let mut trans: c_char = 'N' as c_char;
+    let mut m = _m as c_int;
+    let mut n = _n as c_int;
+    let mut nrhs: c_int = 1;
+    let mut lda = m;
+    let mut ldb = m;
+    let mut info: c_int = 0;
+    // A is column-major, and is overwritten by its QR factorization
+    let mut a_cm = vec![0f64; _m * _n];
+    for i in 0.._m
+    {
+        for j in 0.._n
+        {
+            a_cm[j * _m + i] = *a.get(i, j);
+        }
+    }
+    // B is overwritten by the solution in its first N elements
+    let mut x = Array::<f64>::new(b.as_slice().to_vec(), Order::Row);
+    // LWORK >= max(1, MN + max(MN, NRHS)), larger for blocked performance
+    let mut lwork = (_n + _m * 32) as c_int;
+    let mut work = Array::<f64>::new_filled(0f64, lwork as usize, Order::Row);
+
+    unsafe {
+        dgels_(&mut trans, &mut m, &mut n, &mut nrhs, a_cm.as_mut_ptr(), &mut lda,
+               x.as_mut_ptr(), &mut ldb, work.as_mut_ptr(), &mut lwork, &mut info);
+    }
+    if info > 0
+    {
+        panic!("Least squares matrix does not have full rank.");
+    } else if info < 0 {
+        panic!("Least squares computation failed: {}", info);
+    }
+    Array::<f64>::new(x.as_slice()[.._n].to_vec(), Order::Row)
+}
+
+/// Computes the eigenvalues of a general real square matrix, returned as
+/// [re, im] pairs in no particular order. Complex eigenvalues come in
+/// conjugate pairs with the positive imaginary part first.
+pub fn eigenvalues(a: &Matrix<f64>) -> Vec<[f64; 2]>
+{
+    if !a.is_square()
+    {
+        panic!("Cannot compute eigenvalues of a non-square matrix.");
+    }
+    let (_n, _) = a.get_dims();
+    let mut jobvl: c_char = 'N' as c_char;
+    let mut jobvr: c_char = 'N' as c_char;
+    let mut n = _n as c_int;
+    let mut lda = n;
+    let mut ldv: c_int = 1;
+    let mut info: c_int = 0;
+    // A is column-major, and is overwritten during the computation
+    let mut a_cm = vec![0f64; _n * _n];
+    for i in 0.._n
+    {
+        for j in 0.._n
+        {
+            a_cm[j * _n + i] = *a.get(i, j);
+        }
+    }
+    let mut wr = Array::<f64>::new_filled(0f64, _n, Order::Row);
+    let mut wi = Array::<f64>::new_filled(0f64, _n, Order::Row);
+    // Eigenvectors are not referenced as JOBVL = JOBVR = 'N'
+    let mut v = [0f64; 1];
+    // LWORK >= max(1, 3*N), larger for blocked performance
+    let mut lwork = (8 * _n + 1) as c_int;
+    let mut work = Array::<f64>::new_filled(0f64, lwork as usize, Order::Row);
+
+    unsafe {
+        dgeev_(&mut jobvl, &mut jobvr, &mut n, a_cm.as_mut_ptr(), &mut lda,
+               wr.as_mut_ptr(), wi.as_mut_ptr(), v.as_mut_ptr(), &mut ldv,
+               v.as_mut_ptr(), &mut ldv, work.as_mut_ptr(), &mut lwork, &mut info);
+    }
+    if info != 0
+    {
+        panic!("Eigenvalue computation failed: {}", info);
+    }
+    (0.._n).map(|i| [wr[i], wi[i]]).collect()
+}
+
+/// LU factorization with partial pivoting of a square matrix, P A = L U, as
+/// computed by DGETRF, for solving repeatedly with the same matrix.
+pub struct LuFactors
+{
+    /// L and U packed column-major, the unit diagonal of L not stored
+    lu: Vec<f64>,
+    /// Row interchanges, row i was swapped with row ipiv[i] - 1
+    ipiv: Vec<c_int>,
+    /// Order of the matrix
+    n: usize,
+    /// Zero if nonsingular, otherwise the 1-based index of a zero pivot
+    info: c_int,
+}
+
+impl LuFactors
+{
+    /// Order of the factorized matrix
+    pub fn dims(&self) -> usize
+    {
+        self.n
+    }
+
+    /// True if U has an exactly zero pivot, in which case solving with the
+    /// factors divides by zero
+    pub fn is_singular(&self) -> bool
+    {
+        self.info > 0
+    }
+}
+
+/// Computes the LU factorization of a square matrix. A singular matrix is
+/// not an error here; it can be detected with `LuFactors::is_singular`.
+pub fn lu_factor(a: &Matrix<f64>) -> LuFactors
+{
+    if !a.is_square()
+    {
+        panic!("Cannot LU factorize a non-square matrix.");
+    }
+    let (_n, _) = a.get_dims();
+    let mut m = _n as c_int;
+    let mut n = _n as c_int;
+    let mut lda = n;
+    let mut info: c_int = 0;
+    // A is column-major, and is overwritten by its factors
+    let mut lu = vec![0f64; _n * _n];
+    for i in 0.._n
+    {
+        for j in 0.._n
+        {
+            lu[j * _n + i] = *a.get(i, j);
+        }
+    }
+    let mut ipiv = vec![0 as c_int; _n];
+
+    unsafe {
+        dgetrf_(&mut m, &mut n, lu.as_mut_ptr(), &mut lda, ipiv.as_mut_ptr(), &mut info);
+    }
+    if info < 0
+    {
+        panic!("LU factorization failed: {}", info);
+    }
+    LuFactors{lu: lu, ipiv: ipiv, n: _n, info: info}
+}
+
+/// Solves A x = b given the LU factorization of A.
+pub fn lu_solve(factors: &LuFactors, b: &Array<f64>) -> Array<f64>
+{
+    if b.len() != factors.n
+    {
+        panic!("Right-hand side has length {}, expected {}.", b.len(), factors.n);
+    }
+    let mut trans: c_char = 'N' as c_char;
+    let mut n = factors.n as c_int;
+    let mut nrhs: c_int = 1;
+    let mut lda = n;
+    let mut ldb = n;
+    let mut info: c_int = 0;
+    // DGETRS does not modify the factors, but takes them by mutable pointer
+    let mut lu = factors.lu.clone();
+    let mut ipiv = factors.ipiv.clone();
+    let mut x = Array::<f64>::new(b.as_slice().to_vec(), Order::Row);
+
+    unsafe {
+        dgetrs_(&mut trans, &mut n, &mut nrhs, lu.as_mut_ptr(), &mut lda,
+                ipiv.as_mut_ptr(), x.as_mut_ptr(), &mut ldb, &mut info);
+    }
+    if info != 0
+    {
+        panic!("LU solve failed: {}", info);
+    }
+    x
+}
+
+/// Solves the square linear system A x = b by LU factorization.
+pub fn solve(a: &Matrix<f64>, b: &Array<f64>) -> Array<f64>
+{
+    let factors = lu_factor(a);
+    if factors.is_singular()
+    {
+        panic!("Matrix is singular.");
+    }
+    lu_solve(&factors, b)
+}
+
 // Computes the singular values and, optionally, the right and/or left singular
 // vectors from the singular value decomposition (SVD) of a real N-by-N (upper
 // or lower) bidiagonal matrix B using the implicit zero-shift QR algorithm.
@@ -7,13 +7,37 @@ extern crate libc;
 #[macro_use] pub mod array;
 #[macro_use] pub mod matrix;
 pub mod lapacke;
+mod dense;
 pub mod openblas;
 pub mod gsl_poly;
+pub mod poly_series;
 #[macro_use] pub mod gsl_math;
 pub mod gsl_sf;
+pub mod gsl_guard;
 pub mod gsl_airy;
 pub mod gsl_bessel;
 pub mod gsl_coulomb;
+pub mod gsl_zeta;
+pub mod gsl_hyperg;
+pub mod gsl_exp;
+pub mod gsl_expint;
+pub mod gsl_orthopoly;
+pub mod gsl_coupling;
+pub mod gsl_psi;
+pub mod gsl_lambert;
+pub mod gsl_fermi_dirac;
+pub mod gsl_debye;
+pub mod gsl_synchrotron;
+pub mod gsl_transport;
+pub mod gsl_mathieu;
+pub mod gsl_integration;
+pub mod integrate;
+pub mod ode;
+pub mod bvp;
+pub mod roots;
+pub mod optimize;
+pub mod global_optimize;
+pub mod interpolate;
 
 #[cfg(test)]
 mod test
@@ -21,6 +45,7 @@ mod test
     use array;
     use matrix;
     use lapacke;
+    use gsl_guard;
     use std::mem;
 
     /////////////////
@@ -325,6 +350,117 @@ mod test
         lapacke::bidiagonal_reduction(&mut m);
         // TODO: Finish testing bidiag_reduc
     }
+
+    #[test]
+    fn test_sym_tridiag_eigen()
+    {
+        // [[2, 1], [1, 2]] has eigenvalues 1 and 3
+        let (vals, vecs) = lapacke::symmetric_tridiagonal_eigen(&arr![2f64, 2f64],
+                                                                &arr![1f64]);
+        assert!((vals[0] - 1f64).abs() < 1e-12);
+        assert!((vals[1] - 3f64).abs() < 1e-12);
+        assert!((vecs.get(0, 0).abs() - 0.5f64.sqrt()).abs() < 1e-12);
+        assert!((*vecs.get(1, 0) - *vecs.get(1, 1)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_least_squares()
+    {
+        // Best fit of y = a + b x through (0, 1), (1, 3), (2, 2), (3, 4)
+        let a = mat![[1f64, 0f64], [1f64, 1f64], [1f64, 2f64], [1f64, 3f64]];
+        let x = lapacke::least_squares(&a, &arr![1f64, 3f64, 2f64, 4f64]);
+        assert_eq!(x.len(), 2);
+        assert!((x[0] - 1.3f64).abs() < 1e-12);
+        assert!((x[1] - 0.8f64).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_lu_solve()
+    {
+        // Needs a row interchange, as the leading entry is zero
+        let a = mat![[0f64, 2f64, 1f64], [1f64, 1f64, 0f64], [2f64, 0f64, 3f64]];
+        let factors = lapacke::lu_factor(&a);
+        assert_eq!(factors.dims(), 3);
+        assert!(!factors.is_singular());
+        // Solution is (1, 2, 3)
+        let x = lapacke::lu_solve(&factors, &arr![7f64, 3f64, 11f64]);
+        for (xi, ei) in x.as_slice().iter().zip([1f64, 2f64, 3f64].iter())
+        {
+            assert!((xi - ei).abs() < 1e-12);
+        }
+        let x = lapacke::solve(&a, &arr![0f64, 1f64, 2f64]);
+        for (xi, ei) in x.as_slice().iter().zip([1f64, 0f64, 0f64].iter())
+        {
+            assert!((xi - ei).abs() < 1e-12);
+        }
+
+        let a = mat![[1f64, 2f64], [2f64, 4f64]];
+        assert!(lapacke::lu_factor(&a).is_singular());
+    }
+
+    #[test]
+    fn test_eigenvalues()
+    {
+        // Rotation by 90 degrees has eigenvalues +i and -i
+        let a = mat![[0f64, -1f64], [1f64, 0f64]];
+        let mut vals = lapacke::eigenvalues(&a);
+        vals.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert!(vals[0][0].abs() < 1e-12 && (vals[0][1] + 1f64).abs() < 1e-12);
+        assert!(vals[1][0].abs() < 1e-12 && (vals[1][1] - 1f64).abs() < 1e-12);
+
+        let a = mat![[2f64, 1f64], [0f64, 3f64]];
+        let mut vals = lapacke::eigenvalues(&a);
+        vals.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert!((vals[0][0] - 2f64).abs() < 1e-12 && vals[0][1] == 0f64);
+        assert!((vals[1][0] - 3f64).abs() < 1e-12 && vals[1][1] == 0f64);
+    }
+
+    ////////////////////////
+    // Guard Buffer Tests //
+    ////////////////////////
+
+    #[test]
+    fn test_guard_untouched()
+    {
+        let mut b = gsl_guard::GuardedBuffer::new(3);
+        unsafe {
+            *b.as_mut_ptr() = 1f64;
+            *b.as_mut_ptr().offset(2) = 3f64;
+        }
+        assert!(!b.overrun());
+        assert_eq!(b.into_array(), arr![1f64, 0f64, 3f64]);
+    }
+
+    #[test]
+    fn test_guard_overrun()
+    {
+        let mut b = gsl_guard::GuardedBuffer::new(3);
+        unsafe {
+            *b.as_mut_ptr().offset(3) = 0f64;
+        }
+        assert!(b.overrun());
+    }
+
+    #[test]
+    fn test_guard_underrun()
+    {
+        let mut b = gsl_guard::GuardedBuffer::new(3);
+        unsafe {
+            *b.as_mut_ptr().offset(-1) = 0f64;
+        }
+        assert!(b.overrun());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_guard_overrun_panics()
+    {
+        let mut b = gsl_guard::GuardedBuffer::new(0);
+        unsafe {
+            *b.as_mut_ptr() = 0f64;
+        }
+        b.into_array();
+    }
 }
 
 
@@ -0,0 +1,2019 @@
+/// Initial value problems for systems of ordinary differential equations
+///     y' = f(t, y),  y(t0) = y0,
+/// in the manner of SciPy's solve_ivp. Explicit Runge-Kutta pairs (RK23,
+/// RK45 and DOP853) suit non-stiff problems; the implicit Radau IIA and
+/// variable order BDF methods suit stiff problems, and solve their Newton
+/// iterations with `lapacke` LU factorizations of a Jacobian which is either
+/// supplied or estimated by forward differences.
+/// All methods adapt their step size to keep the local error estimate within
+/// atol + rtol |y| in every component, measured in the root mean square norm.
+/// Optionally a continuous solution is returned, and events g(t, y) = 0 are
+/// located along the way, possibly terminating the integration.
+/// For further information see Hairer, Norsett & Wanner, Solving Ordinary
+/// Differential Equations I and II, and Shampine & Reichelt, The MATLAB ODE
+/// Suite.
+
+use std::f64;
+use array::Array;
+use array::Order;
+use matrix::Matrix;
+use lapacke;
+use lapacke::LuFactors;
+use dense;
+
+
+/////////////////////////
+// Options and results //
+/////////////////////////
+
+/// Integration method
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Method
+{
+    /// Explicit Runge-Kutta 5(4) pair of Dormand and Prince, with a quartic
+    /// interpolant
+    RK45,
+    /// Explicit Runge-Kutta 3(2) pair of Bogacki and Shampine, with a cubic
+    /// Hermite interpolant
+    RK23,
+    /// Explicit Runge-Kutta method of order 8 of Dormand and Prince with the
+    /// 5th and 3rd order error estimators of Hairer, and a 7th order
+    /// interpolant
+    DOP853,
+    /// Implicit Runge-Kutta method of the Radau IIA family of order 5, with
+    /// a cubic collocation polynomial as interpolant
+    Radau,
+    /// Implicit multistep method of variable order 1 to 5 based on backward
+    /// differentiation formulas, in the quasi-constant step size NDF form
+    BDF,
+}
+
+/// Absolute or relative error tolerance
+#[derive(Debug, Clone, PartialEq)]
+pub enum Tolerance
+{
+    /// The same tolerance for every component
+    Uniform(f64),
+    /// A tolerance for each component, as long as the state
+    PerComponent(Array<f64>),
+}
+
+/// An event function g(t, y) whose zeros are located during the
+/// integration. Events are only detected where g changes sign between
+/// steps, so zeros that come in close pairs may be missed.
+pub struct Event<'a>
+{
+    function: Box<Fn(f64, &Array<f64>) -> f64 + 'a>,
+    terminal: bool,
+    direction: f64,
+}
+
+impl<'a> Event<'a>
+{
+    /// A non-terminal event triggered by zeros of g in either direction
+    pub fn new<G>(g: G) -> Event<'a>
+        where G: Fn(f64, &Array<f64>) -> f64 + 'a
+    {
+        Event{function: Box::new(g), terminal: false, direction: 0f64}
+    }
+
+    /// Sets whether the integration stops at the first zero of the event
+    pub fn terminal(mut self, terminal: bool) -> Event<'a>
+    {
+        self.terminal = terminal;
+        self
+    }
+
+    /// Restricts the event to zeros where g is increasing (direction > 0) or
+    /// decreasing (direction < 0); zero accepts either
+    pub fn direction(mut self, direction: f64) -> Event<'a>
+    {
+        self.direction = direction;
+        self
+    }
+
+    fn eval(&self, t: f64, y: &[f64]) -> f64
+    {
+        (self.function)(t, &Array::new(y.to_vec(), Order::Row))
+    }
+}
+
+/// Options for `solve_ivp`
+pub struct IvpOptions<'a>
+{
+    /// Integration method, RK45 by default
+    pub method: Method,
+    /// Relative tolerance, 1e-3 by default. Values below 100 times machine
+    /// epsilon are raised to that.
+    pub rtol: Tolerance,
+    /// Absolute tolerance, 1e-6 by default
+    pub atol: Tolerance,
+    /// Size of the first step, chosen automatically if None
+    pub first_step: Option<f64>,
+    /// Largest step size allowed, unbounded by default
+    pub max_step: f64,
+    /// Times at which to store the solution, ordered in the direction of
+    /// integration. If None every step is stored.
+    pub t_eval: Option<Array<f64>>,
+    /// Whether to return a continuous solution
+    pub dense_output: bool,
+    /// Events to locate
+    pub events: Vec<Event<'a>>,
+    /// Jacobian df/dy of the right-hand side for the implicit methods,
+    /// estimated by forward differences if None
+    pub jac: Option<Box<Fn(f64, &Array<f64>) -> Matrix<f64> + 'a>>,
+}
+
+impl<'a> Default for IvpOptions<'a>
+{
+    fn default() -> IvpOptions<'a>
+    {
+        IvpOptions{
+            method: Method::RK45,
+            rtol: Tolerance::Uniform(1e-3),
+            atol: Tolerance::Uniform(1e-6),
+            first_step: None,
+            max_step: f64::INFINITY,
+            t_eval: None,
+            dense_output: false,
+            events: Vec::new(),
+            jac: None,
+        }
+    }
+}
+
+/// Solution returned by `solve_ivp`
+pub struct IvpResult
+{
+    /// Times at which the solution is stored
+    pub t: Array<f64>,
+    /// Solution at each of the times in t
+    pub y: Vec<Array<f64>>,
+    /// Continuous solution, if dense output was requested
+    pub sol: Option<OdeSolution>,
+    /// For each event, the times at which it occurred
+    pub t_events: Vec<Array<f64>>,
+    /// For each event, the solution at each of its occurrences
+    pub y_events: Vec<Vec<Array<f64>>>,
+    /// Number of evaluations of the right-hand side
+    pub nfev: usize,
+    /// Number of evaluations of the Jacobian
+    pub njev: usize,
+    /// Number of LU factorizations
+    pub nlu: usize,
+    /// 0 if the end of the interval was reached, 1 if a terminal event
+    /// occurred, -1 if the integration failed
+    pub status: i32,
+    /// Description of the reason the integration stopped
+    pub message: String,
+    /// True if status is 0 or 1
+    pub success: bool,
+}
+
+
+////////////////////
+// Vector helpers //
+////////////////////
+
+/// Root mean square norm
+fn rms(x: &[f64]) -> f64
+{
+    if x.is_empty()
+    {
+        return 0f64;
+    }
+    (x.iter().fold(0f64, |s, v| s + v * v) / x.len() as f64).sqrt()
+}
+
+/// Root mean square norm of x / scale
+fn scaled_rms(x: &[f64], scale: &[f64]) -> f64
+{
+    let r: Vec<f64> = x.iter().zip(scale.iter()).map(|(v, s)| v / s).collect();
+    rms(&r)
+}
+
+/// atol + rtol * |y|
+fn error_scale(y: &[f64], rtol: &[f64], atol: &[f64]) -> Vec<f64>
+{
+    (0..y.len()).map(|i| atol[i] + rtol[i] * y[i].abs()).collect()
+}
+
+/// atol + rtol * max(|y|, |y_new|)
+fn error_scale2(y: &[f64], y_new: &[f64], rtol: &[f64], atol: &[f64]) -> Vec<f64>
+{
+    (0..y.len()).map(|i| atol[i] + rtol[i] * y[i].abs().max(y_new[i].abs())).collect()
+}
+
+/// Sum of the rows of k weighted by w, for as many rows as w has weights
+fn combine(k: &[Vec<f64>], w: &[f64], n: usize) -> Vec<f64>
+{
+    let mut out = vec![0f64; n];
+    for (row, &c) in k.iter().zip(w.iter())
+    {
+        if c != 0f64
+        {
+            for i in 0..n
+            {
+                out[i] += c * row[i];
+            }
+        }
+    }
+    out
+}
+
+/// The next representable number after t in the given direction
+fn next_after(t: f64, direction: f64) -> f64
+{
+    if t == 0f64
+    {
+        return direction.signum() * f64::from_bits(1);
+    }
+    let bits = t.to_bits();
+    if (direction > 0f64) == (t > 0f64)
+    {
+        f64::from_bits(bits + 1)
+    } else {
+        f64::from_bits(bits - 1)
+    }
+}
+
+/// Smallest step allowed at t, ten units in the last place
+fn min_step(t: f64, direction: f64) -> f64
+{
+    10f64 * (next_after(t, direction) - t).abs()
+}
+
+/// Expands a tolerance to one value per component
+fn expand(tol: &Tolerance, n: usize, name: &str) -> Vec<f64>
+{
+    let v = match *tol
+    {
+        Tolerance::Uniform(v) => vec![v; n],
+        Tolerance::PerComponent(ref a) => {
+            if a.len() != n
+            {
+                panic!("{} has {} components, expected {}", name, a.len(), n);
+            }
+            a.as_slice().to_vec()
+        },
+    };
+    if v.iter().any(|&x| !(x >= 0f64))
+    {
+        panic!("Invalid value for {}: must be non-negative", name);
+    }
+    v
+}
+
+
+////////////
+// System //
+////////////
+
+/// The right-hand side and its Jacobian, counting evaluations
+struct System<'a>
+{
+    fun: &'a Fn(f64, &Array<f64>) -> Array<f64>,
+    jac: Option<&'a Fn(f64, &Array<f64>) -> Matrix<f64>>,
+    n: usize,
+    nfev: usize,
+    njev: usize,
+    nlu: usize,
+}
+
+impl<'a> System<'a>
+{
+    fn fun(&mut self, t: f64, y: &[f64]) -> Vec<f64>
+    {
+        self.nfev += 1;
+        let f = (self.fun)(t, &Array::new(y.to_vec(), Order::Row));
+        if f.len() != self.n
+        {
+            panic!("Right-hand side returned {} values, expected {}", f.len(), self.n);
+        }
+        f.as_slice().to_vec()
+    }
+
+    /// Jacobian at (t, y) as a row-major n * n vector. f is the right-hand
+    /// side at (t, y) if already known, used by the difference quotients.
+    fn jacobian(&mut self, t: f64, y: &[f64], f: Option<&[f64]>) -> Vec<f64>
+    {
+        let n = self.n;
+        self.njev += 1;
+        if let Some(jac) = self.jac
+        {
+            return dense::row_major(&jac(t, &Array::new(y.to_vec(), Order::Row)), n, n, "Jacobian");
+        }
+        let f0 = match f
+        {
+            Some(f) => f.to_vec(),
+            None => self.fun(t, y),
+        };
+        let h: Vec<f64> = y.iter().map(|&v| dense::fd_step(v)).collect();
+        dense::forward_jacobian(|yh| self.fun(t, yh), y, &f0, &h)
+    }
+
+    /// LU factorization of a row-major m * m matrix
+    fn lu(&mut self, a: Vec<f64>, m: usize) -> LuFactors
+    {
+        self.nlu += 1;
+        lapacke::lu_factor(&Matrix::new_from_vec(a, m, m))
+    }
+
+    fn solve(&self, lu: &LuFactors, b: &[f64]) -> Vec<f64>
+    {
+        lapacke::lu_solve(lu, &Array::new(b.to_vec(), Order::Row)).as_slice().to_vec()
+    }
+}
+
+
+///////////////////
+// Dense output  //
+///////////////////
+
+/// Interpolant over a single step
+enum Dense
+{
+    /// y_old + sum_k q[k] x^(k + 1) with x = (t - t_old) / h, used by the
+    /// Runge-Kutta methods and Radau
+    Power
+    {
+        t_old: f64,
+        h: f64,
+        y_old: Vec<f64>,
+        q: Vec<Vec<f64>>,
+    },
+    /// Hairer's nested form for DOP853
+    Dop853
+    {
+        t_old: f64,
+        h: f64,
+        y_old: Vec<f64>,
+        f: Vec<Vec<f64>>,
+    },
+    /// Newton form over the backward differences of BDF
+    Bdf
+    {
+        t_shift: Vec<f64>,
+        denom: Vec<f64>,
+        d: Vec<Vec<f64>>,
+    },
+}
+
+impl Dense
+{
+    fn eval(&self, t: f64) -> Vec<f64>
+    {
+        match *self
+        {
+            Dense::Power{t_old, h, ref y_old, ref q} => {
+                let x = (t - t_old) / h;
+                let mut y = y_old.clone();
+                let mut p = 1f64;
+                for col in q.iter()
+                {
+                    p *= x;
+                    for i in 0..y.len()
+                    {
+                        y[i] += p * col[i];
+                    }
+                }
+                y
+            },
+            Dense::Dop853{t_old, h, ref y_old, ref f} => {
+                let x = (t - t_old) / h;
+                let mut y = vec![0f64; y_old.len()];
+                for (k, fk) in f.iter().rev().enumerate()
+                {
+                    let w = if k % 2 == 0 { x } else { 1f64 - x };
+                    for i in 0..y.len()
+                    {
+                        y[i] = (y[i] + fk[i]) * w;
+                    }
+                }
+                for i in 0..y.len()
+                {
+                    y[i] += y_old[i];
+                }
+                y
+            },
+            Dense::Bdf{ref t_shift, ref denom, ref d} => {
+                let mut y = d[0].clone();
+                let mut p = 1f64;
+                for k in 0..t_shift.len()
+                {
+                    p *= (t - t_shift[k]) / denom[k];
+                    for i in 0..y.len()
+                    {
+                        y[i] += p * d[k + 1][i];
+                    }
+                }
+                y
+            },
+        }
+    }
+}
+
+/// Continuous solution made up of the interpolants of the individual steps
+pub struct OdeSolution
+{
+    /// Step boundaries, monotonic in the direction of integration
+    ts: Vec<f64>,
+    /// Interpolant for each step
+    segments: Vec<Dense>,
+}
+
+impl OdeSolution
+{
+    /// Interval of integration covered, as (t0, t_final)
+    pub fn span(&self) -> (f64, f64)
+    {
+        (self.ts[0], self.ts[self.ts.len() - 1])
+    }
+
+    /// Solution at t. Outside the interval of integration the interpolant
+    /// of the first or last step is extrapolated.
+    pub fn eval(&self, t: f64) -> Array<f64>
+    {
+        let ascending = self.ts[self.ts.len() - 1] >= self.ts[0];
+        // Number of step boundaries strictly before t in the direction of
+        // integration, so that t lies in segment count - 1
+        let count = self.ts.iter()
+            .take_while(|&&s| if ascending { s < t } else { s > t })
+            .count();
+        let seg = if count == 0 { 0 } else { (count - 1).min(self.segments.len() - 1) };
+        Array::new(self.segments[seg].eval(t), Order::Row)
+    }
+
+    /// Solution at each of the times in t
+    pub fn eval_array(&self, t: &Array<f64>) -> Vec<Array<f64>>
+    {
+        t.as_slice().iter().map(|&ti| self.eval(ti)).collect()
+    }
+}
+
+
+//////////////////////////
+// Step size controller //
+//////////////////////////
+
+/// Common state of every stepper
+struct Common
+{
+    t: f64,
+    y: Vec<f64>,
+    t_old: f64,
+    t_bound: f64,
+    direction: f64,
+    max_step: f64,
+    rtol: Vec<f64>,
+    atol: Vec<f64>,
+}
+
+/// A method advancing the solution one step at a time
+trait Stepper
+{
+    fn common(&self) -> &Common;
+
+    /// Takes one step, or returns why no step could be taken
+    fn step(&mut self, sys: &mut System) -> Result<(), String>;
+
+    /// Interpolant over the last step
+    fn dense(&mut self, sys: &mut System) -> Dense;
+}
+
+/// Initial step size from the first two derivatives, after Hairer, Norsett
+/// and Wanner, Section II.4, for a method of the given error order
+fn select_initial_step(sys: &mut System, t0: f64, y0: &[f64], f0: &[f64],
+                       t_bound: f64, max_step: f64, direction: f64,
+                       order: usize, rtol: &[f64], atol: &[f64]) -> f64
+{
+    let n = y0.len();
+    let interval = (t_bound - t0).abs();
+    if n == 0 || interval == 0f64
+    {
+        return interval;
+    }
+    let scale = error_scale(y0, rtol, atol);
+    let d0 = scaled_rms(y0, &scale);
+    let d1 = scaled_rms(f0, &scale);
+    let mut h0 = if d0 < 1e-5 || d1 < 1e-5 { 1e-6 } else { 0.01 * d0 / d1 };
+    h0 = h0.min(interval);
+    let y1: Vec<f64> = (0..n).map(|i| y0[i] + h0 * direction * f0[i]).collect();
+    let f1 = sys.fun(t0 + h0 * direction, &y1);
+    let df: Vec<f64> = (0..n).map(|i| f1[i] - f0[i]).collect();
+    let d2 = scaled_rms(&df, &scale) / h0;
+    let h1 = if d1 <= 1e-15 && d2 <= 1e-15
+    {
+        (h0 * 1e-3).max(1e-6)
+    } else {
+        (0.01 / d1.max(d2)).powf(1f64 / (order as f64 + 1f64))
+    };
+    (100f64 * h0).min(h1).min(interval).min(max_step)
+}
+
+/// Failure message when the step size underflows
+const TOO_SMALL_STEP: &'static str = "Required step size is less than spacing between numbers.";
+
+/// Safety factor on the predicted optimal step size
+const SAFETY: f64 = 0.9;
+/// Smallest factor by which a step size may shrink
+const MIN_FACTOR: f64 = 0.2;
+/// Largest factor by which a step size may grow
+const MAX_FACTOR: f64 = 10f64;
+
+
+//////////////////////////
+// Explicit Runge-Kutta //
+//////////////////////////
+
+/// Butcher tableau with embedded error estimator and interpolant
+struct Tableau
+{
+    /// Nodes
+    c: &'static [f64],
+    /// Lower triangle of the coefficient matrix, one row per stage
+    a: &'static [&'static [f64]],
+    /// Weights
+    b: &'static [f64],
+    /// Error estimator weights, including one for the derivative at the end
+    /// of the step
+    e: &'static [f64],
+    /// Interpolant coefficients, one row per stage plus the end derivative
+    p: &'static [&'static [f64]],
+    /// Number of stages
+    stages: usize,
+    /// Order of the error estimator
+    error_order: usize,
+}
+
+static RK23: Tableau = Tableau{
+    c: &[0.0, 1.0 / 2.0, 3.0 / 4.0],
+    a: &[&[], &[1.0 / 2.0], &[0.0, 3.0 / 4.0]],
+    b: &[2.0 / 9.0, 1.0 / 3.0, 4.0 / 9.0],
+    e: &[5.0 / 72.0, -1.0 / 12.0, -1.0 / 9.0, 1.0 / 8.0],
+    p: &[&[1.0, -4.0 / 3.0, 5.0 / 9.0],
+         &[0.0, 1.0, -2.0 / 3.0],
+         &[0.0, 4.0 / 3.0, -8.0 / 9.0],
+         &[0.0, -1.0, 1.0]],
+    stages: 3,
+    error_order: 2,
+};
+
+static RK45: Tableau = Tableau{
+    c: &[0.0, 1.0 / 5.0, 3.0 / 10.0, 4.0 / 5.0, 8.0 / 9.0, 1.0],
+    a: &[&[],
+         &[1.0 / 5.0],
+         &[3.0 / 40.0, 9.0 / 40.0],
+         &[44.0 / 45.0, -56.0 / 15.0, 32.0 / 9.0],
+         &[19372.0 / 6561.0, -25360.0 / 2187.0, 64448.0 / 6561.0, -212.0 / 729.0],
+         &[9017.0 / 3168.0, -355.0 / 33.0, 46732.0 / 5247.0, 49.0 / 176.0,
+           -5103.0 / 18656.0]],
+    b: &[35.0 / 384.0, 0.0, 500.0 / 1113.0, 125.0 / 192.0, -2187.0 / 6784.0,
+         11.0 / 84.0],
+    e: &[-71.0 / 57600.0, 0.0, 71.0 / 16695.0, -71.0 / 1920.0, 17253.0 / 339200.0,
+         -22.0 / 525.0, 1.0 / 40.0],
+    // Shampine's quartic interpolant, Math. Comp. 46 (1986)
+    p: &[&[1.0, -8048581381.0 / 2820520608.0, 8663915743.0 / 2820520608.0,
+           -12715105075.0 / 11282082432.0],
+         &[0.0, 0.0, 0.0, 0.0],
+         &[0.0, 131558114200.0 / 32700410799.0, -68118460800.0 / 10900136933.0,
+           87487479700.0 / 32700410799.0],
+         &[0.0, -1754552775.0 / 470086768.0, 14199869525.0 / 1410260304.0,
+           -10690763975.0 / 1880347072.0],
+         &[0.0, 127303824393.0 / 49829197408.0, -318862633887.0 / 49829197408.0,
+           701980252875.0 / 199316789632.0],
+         &[0.0, -282668133.0 / 205662961.0, 2019193451.0 / 616988883.0,
+           -1453857185.0 / 822651844.0],
+         &[0.0, 40617522.0 / 29380423.0, -110615467.0 / 29380423.0,
+           69997945.0 / 29380423.0]],
+    stages: 6,
+    error_order: 4,
+};
+
+/// Nodes of DOP853, including the three extra stages of the interpolant
+static DOP853_C: [f64; 16] = [
+    0.0,
+    0.05260015195876773,
+    0.0789002279381516,
+    0.1183503419072274,
+    0.2816496580927726,
+    0.3333333333333333,
+    0.25,
+    0.3076923076923077,
+    0.6512820512820513,
+    0.6,
+    0.8571428571428571,
+    1.0,
+    1.0,
+    0.1,
+    0.2,
+    0.7777777777777778,
+];
+
+/// Coefficients of DOP853, one row per stage. Row 12 holds the weights, so
+/// that the derivative at the end of the step is stage 12, and rows 13 to 15
+/// are the extra stages of the interpolant.
+static DOP853_A: &'static [&'static [f64]] = &[
+    &[],
+    &[0.05260015195876773],
+    &[0.0197250569845379, 0.0591751709536137],
+    &[0.02958758547680685, 0.0, 0.08876275643042054],
+    &[0.2413651341592667, 0.0, -0.8845494793282861, 0.924834003261792],
+    &[0.037037037037037035, 0.0, 0.0, 0.17082860872947386, 0.12546768756682242],
+    &[0.037109375, 0.0, 0.0, 0.17025221101954405, 0.06021653898045596, -0.017578125],
+    &[0.03709200011850479, 0.0, 0.0, 0.17038392571223998, 0.10726203044637328,
+      -0.015319437748624402, 0.008273789163814023],
+    &[0.6241109587160757, 0.0, 0.0, -3.3608926294469414, -0.868219346841726,
+      27.59209969944671, 20.154067550477894, -43.48988418106996],
+    &[0.47766253643826434, 0.0, 0.0, -2.4881146199716677, -0.590290826836843,
+      21.230051448181193, 15.279233632882423, -33.28821096898486, -0.020331201708508627],
+    &[-0.9371424300859873, 0.0, 0.0, 5.186372428844064, 1.0914373489967295,
+      -8.149787010746927, -18.52006565999696, 22.739487099350505, 2.4936055526796523,
+      -3.0467644718982196],
+    &[2.273310147516538, 0.0, 0.0, -10.53449546673725, -2.0008720582248625,
+      -17.9589318631188, 27.94888452941996, -2.8589982771350235, -8.87285693353063,
+      12.360567175794303, 0.6433927460157636],
+    &[0.054293734116568765, 0.0, 0.0, 0.0, 0.0, 4.450312892752409, 1.8915178993145003,
+      -5.801203960010585, 0.3111643669578199, -0.1521609496625161, 0.20136540080403034,
+      0.04471061572777259],
+    &[0.056167502283047954, 0.0, 0.0, 0.0, 0.0, 0.0, 0.25350021021662483,
+      -0.2462390374708025, -0.12419142326381637, 0.15329179827876568,
+      0.00820105229563469, 0.007567897660545699, -0.008298],
+    &[0.03183464816350214, 0.0, 0.0, 0.0, 0.0, 0.028300909672366776,
+      0.053541988307438566, -0.05492374857139099, 0.0, 0.0, -0.00010834732869724932,
+      0.0003825710908356584, -0.00034046500868740456, 0.1413124436746325],
+    &[-0.42889630158379194, 0.0, 0.0, 0.0, 0.0, -4.697621415361164, 7.683421196062599,
+      4.06898981839711, 0.3567271874552811, 0.0, 0.0, 0.0, -0.0013990241651590145,
+      2.9475147891527724, -9.15095847217987],
+];
+
+/// Weights of DOP853
+static DOP853_B: [f64; 12] = [
+    0.054293734116568765, 0.0, 0.0, 0.0, 0.0, 4.450312892752409, 1.8915178993145003,
+    -5.801203960010585, 0.3111643669578199, -0.1521609496625161,
+    0.20136540080403034, 0.04471061572777259
+];
+
+/// Weights of the 3rd order error estimator, against the stages and the
+/// derivative at the end of the step
+static DOP853_E3: [f64; 13] = [
+    -0.18980075407240762, 0.0, 0.0, 0.0, 0.0, 4.450312892752409, 1.8915178993145003,
+    -5.801203960010585, -0.4226823213237919, -0.1521609496625161,
+    0.20136540080403034, 0.02265179219836082, 0.0
+];
+
+/// Weights of the 5th order error estimator
+static DOP853_E5: [f64; 13] = [
+    0.01312004499419488, 0.0, 0.0, 0.0, 0.0, -1.2251564463762044,
+    -0.4957589496572502, 1.6643771824549864, -0.35032884874997366,
+    0.3341791187130175, 0.08192320648511571, -0.022355307863886294, 0.0
+];
+
+/// Coefficients of the 7th order interpolant, against all 16 stages
+static DOP853_D: [[f64; 16]; 4] = [
+    [-8.428938276109013, 0.0, 0.0, 0.0, 0.0, 0.5667149535193777, -3.0689499459498917,
+     2.38466765651207, 2.117034582445028, -0.871391583777973, 2.2404374302607883,
+     0.6315787787694688, -0.08899033645133331, 18.148505520854727, -9.194632392478356,
+     -4.436036387594894],
+    [10.427508642579134, 0.0, 0.0, 0.0, 0.0, 242.28349177525817, 165.20045171727028,
+     -374.5467547226902, -22.113666853125306, 7.733432668472264, -30.674084731089398,
+     -9.332130526430229, 15.697238121770845, -31.139403219565178, -9.35292435884448,
+     35.81684148639408],
+    [19.985053242002433, 0.0, 0.0, 0.0, 0.0, -387.0373087493518, -189.17813819516758,
+     527.8081592054236, -11.57390253995963, 6.8812326946963, -1.0006050966910838,
+     0.7777137798053443, -2.778205752353508, -60.19669523126412, 84.32040550667716,
+     11.99229113618279],
+    [-25.69393346270375, 0.0, 0.0, 0.0, 0.0, -154.18974869023643, -231.5293791760455,
+     357.6391179106141, 93.40532418362432, -37.45832313645163, 104.0996495089623,
+     29.8402934266605, -43.53345659001114, 96.32455395918828, -39.17726167561544,
+     -149.72683625798564],
+];
+
+static DOP853: Tableau = Tableau{
+    c: &DOP853_C,
+    a: DOP853_A,
+    b: &DOP853_B,
+    e: &DOP853_E5,
+    p: &[],
+    stages: 12,
+    error_order: 7,
+};
+
+/// Explicit Runge-Kutta stepper
+struct RungeKutta
+{
+    common: Common,
+    tableau: &'static Tableau,
+    dop853: bool,
+    f: Vec<f64>,
+    y_old: Vec<f64>,
+    h_abs: f64,
+    h_previous: f64,
+    /// Stage derivatives of the last step, the last row being f at its end
+    k: Vec<Vec<f64>>,
+}
+
+impl RungeKutta
+{
+    fn new(sys: &mut System, common: Common, method: Method,
+           first_step: Option<f64>) -> RungeKutta
+    {
+        let (tableau, dop853) = match method
+        {
+            Method::RK23 => (&RK23, false),
+            Method::DOP853 => (&DOP853, true),
+            _ => (&RK45, false),
+        };
+        let f = sys.fun(common.t, &common.y);
+        let h_abs = match first_step
+        {
+            Some(h) => h,
+            None => select_initial_step(sys, common.t, &common.y, &f, common.t_bound,
+                                        common.max_step, common.direction,
+                                        tableau.error_order, &common.rtol,
+                                        &common.atol),
+        };
+        let n = common.y.len();
+        let y_old = common.y.clone();
+        RungeKutta{
+            common: common,
+            tableau: tableau,
+            dop853: dop853,
+            f: f,
+            y_old: y_old,
+            h_abs: h_abs,
+            h_previous: 0f64,
+            k: vec![vec![0f64; n]; tableau.stages + 1],
+        }
+    }
+
+    /// Error norm of the step h, scaled by scale, from the stages in k
+    fn error_norm(&self, h: f64, scale: &[f64]) -> f64
+    {
+        let n = scale.len();
+        if self.dop853
+        {
+            let err5 = combine(&self.k, &DOP853_E5, n);
+            let err3 = combine(&self.k, &DOP853_E3, n);
+            let mut e5 = 0f64;
+            let mut e3 = 0f64;
+            for i in 0..n
+            {
+                e5 += (err5[i] / scale[i]).powi(2);
+                e3 += (err3[i] / scale[i]).powi(2);
+            }
+            if e5 == 0f64 && e3 == 0f64
+            {
+                return 0f64;
+            }
+            let denom = e5 + 0.01 * e3;
+            h.abs() * e5 / (denom * n as f64).sqrt()
+        } else {
+            let err = combine(&self.k, self.tableau.e, n);
+            h.abs() * scaled_rms(&err, scale)
+        }
+    }
+}
+
+impl Stepper for RungeKutta
+{
+    fn common(&self) -> &Common
+    {
+        &self.common
+    }
+
+    fn step(&mut self, sys: &mut System) -> Result<(), String>
+    {
+        let t = self.common.t;
+        let direction = self.common.direction;
+        let n = self.common.y.len();
+        let min_step = min_step(t, direction);
+        let exponent = -1f64 / (self.tableau.error_order as f64 + 1f64);
+        let mut h_abs = self.h_abs.min(self.common.max_step).max(min_step);
+        let mut rejected = false;
+        loop
+        {
+            if h_abs < min_step
+            {
+                return Err(String::from(TOO_SMALL_STEP));
+            }
+            let mut t_new = t + h_abs * direction;
+            if direction * (t_new - self.common.t_bound) > 0f64
+            {
+                t_new = self.common.t_bound;
+            }
+            let h = t_new - t;
+            h_abs = h.abs();
+
+            self.k[0] = self.f.clone();
+            for s in 1..self.tableau.stages
+            {
+                let dy = combine(&self.k[..s], self.tableau.a[s], n);
+                let ys: Vec<f64> = (0..n).map(|i| self.common.y[i] + h * dy[i]).collect();
+                self.k[s] = sys.fun(t + self.tableau.c[s] * h, &ys);
+            }
+            let dy = combine(&self.k[..self.tableau.stages], self.tableau.b, n);
+            let y_new: Vec<f64> = (0..n).map(|i| self.common.y[i] + h * dy[i]).collect();
+            let f_new = sys.fun(t_new, &y_new);
+            self.k[self.tableau.stages] = f_new.clone();
+
+            let scale = error_scale2(&self.common.y, &y_new,
+                                     &self.common.rtol, &self.common.atol);
+            let error_norm = self.error_norm(h, &scale);
+            if error_norm < 1f64
+            {
+                let mut factor = if error_norm == 0f64
+                {
+                    MAX_FACTOR
+                } else {
+                    MAX_FACTOR.min(SAFETY * error_norm.powf(exponent))
+                };
+                if rejected
+                {
+                    factor = factor.min(1f64);
+                }
+                self.h_previous = h;
+                self.y_old = ::std::mem::replace(&mut self.common.y, y_new);
+                self.common.t_old = t;
+                self.common.t = t_new;
+                self.h_abs = h_abs * factor;
+                self.f = f_new;
+                return Ok(());
+            }
+            h_abs *= MIN_FACTOR.max(SAFETY * error_norm.powf(exponent));
+            rejected = true;
+        }
+    }
+
+    fn dense(&mut self, sys: &mut System) -> Dense
+    {
+        let n = self.common.y.len();
+        let h = self.h_previous;
+        let t_old = self.common.t_old;
+        if !self.dop853
+        {
+            let cols = self.tableau.p[0].len();
+            let q = (0..cols)
+                .map(|j| {
+                    let w: Vec<f64> = self.tableau.p.iter().map(|row| row[j]).collect();
+                    combine(&self.k, &w, n).iter().map(|v| h * v).collect()
+                })
+                .collect();
+            return Dense::Power{t_old: t_old, h: h, y_old: self.y_old.clone(), q: q};
+        }
+        // Three extra stages for the interpolant
+        let mut k = self.k.clone();
+        for s in 13..16
+        {
+            let dy = combine(&k, DOP853_A[s], n);
+            let ys: Vec<f64> = (0..n).map(|i| self.y_old[i] + h * dy[i]).collect();
+            k.push(sys.fun(t_old + DOP853_C[s] * h, &ys));
+        }
+        let dy: Vec<f64> = (0..n).map(|i| self.common.y[i] - self.y_old[i]).collect();
+        let mut f = Vec::with_capacity(7);
+        f.push(dy.clone());
+        f.push((0..n).map(|i| h * k[0][i] - dy[i]).collect());
+        f.push((0..n).map(|i| 2f64 * dy[i] - h * (self.f[i] + k[0][i])).collect());
+        for d in DOP853_D.iter()
+        {
+            f.push(combine(&k, d, n).iter().map(|v| h * v).collect());
+        }
+        Dense::Dop853{t_old: t_old, h: h, y_old: self.y_old.clone(), f: f}
+    }
+}
+
+
+///////////
+// Radau //
+///////////
+
+/// Maximum Newton iterations per step of the implicit methods
+const RADAU_NEWTON_MAXITER: usize = 6;
+
+/// Nodes of the 3-stage Radau IIA method
+fn radau_c() -> [f64; 3]
+{
+    let s6 = 6f64.sqrt();
+    [(4f64 - s6) / 10f64, (4f64 + s6) / 10f64, 1f64]
+}
+
+/// Error estimator weights
+fn radau_e() -> [f64; 3]
+{
+    let s6 = 6f64.sqrt();
+    [(-13f64 - 7f64 * s6) / 3f64, (-13f64 + 7f64 * s6) / 3f64, -1f64 / 3f64]
+}
+
+/// Interpolant coefficients: the collocation polynomial in powers of x
+fn radau_p() -> [[f64; 3]; 3]
+{
+    let s6 = 6f64.sqrt();
+    [[13f64 / 3f64 + 7f64 * s6 / 3f64, -23f64 / 3f64 - 22f64 * s6 / 3f64, 10f64 / 3f64 +
+     5f64 * s6],
+     [13f64 / 3f64 - 7f64 * s6 / 3f64, -23f64 / 3f64 + 22f64 * s6 / 3f64, 10f64 / 3f64 - 5f64 * s6],
+     [1f64 / 3f64, -8f64 / 3f64, 10f64 / 3f64]]
+}
+
+/// Real eigenvalue of the inverse of the Radau IIA coefficient matrix
+fn radau_mu_real() -> f64
+{
+    3f64 + 3f64.powf(2f64 / 3f64) - 3f64.powf(1f64 / 3f64)
+}
+
+/// Complex eigenvalue of the inverse coefficient matrix, as (re, im)
+fn radau_mu_complex() -> (f64, f64)
+{
+    (3f64 + 0.5 * (3f64.powf(1f64 / 3f64) - 3f64.powf(2f64 / 3f64)),
+     -0.5 * (3f64.powf(5f64 / 6f64) + 3f64.powf(7f64 / 6f64)))
+}
+
+/// Eigenvectors of the inverse coefficient matrix, transforming the
+/// collocation system into a real and a complex system of size n
+const RADAU_T: [[f64; 3]; 3] = [
+    [0.09443876248897524, -0.14125529502095421, 0.03002919410514742],
+    [0.25021312296533332, 0.20412935229379994, -0.38294211275726192],
+    [1.0, 1.0, 0.0]];
+
+/// Inverse of RADAU_T
+const RADAU_TI: [[f64; 3]; 3] = [
+    [4.17871859155190428, 0.32768282076106237, 0.52337644549944951],
+    [-4.17871859155190428, -0.32768282076106237, 0.47662355450055044],
+    [0.50287263494578682, -2.57192694985560522, 0.59603920482822492]];
+
+/// Step size factor predicted from the last two error norms, after Hairer
+/// and Wanner, Section IV.8
+fn predict_factor(h_abs: f64, h_abs_old: Option<f64>, error_norm: f64,
+                  error_norm_old: Option<f64>) -> f64
+{
+    let multiplier = match (h_abs_old, error_norm_old)
+    {
+        (Some(h_old), Some(e_old)) if error_norm != 0f64 =>
+            h_abs / h_old * (e_old / error_norm).powf(0.25),
+        _ => 1f64,
+    };
+    multiplier.min(1f64) * error_norm.powf(-0.25)
+}
+
+/// Radau IIA stepper of order 5
+struct Radau
+{
+    common: Common,
+    f: Vec<f64>,
+    y_old: Vec<f64>,
+    h_abs: f64,
+    h_abs_old: Option<f64>,
+    error_norm_old: Option<f64>,
+    newton_tol: f64,
+    /// Jacobian, row-major
+    jac: Vec<f64>,
+    current_jac: bool,
+    lu_real: Option<LuFactors>,
+    lu_complex: Option<LuFactors>,
+    /// Stage increments of the last step
+    z: Vec<Vec<f64>>,
+    /// Interpolant of the last step, predicting the next stages
+    sol: Option<Dense>,
+}
+
+impl Radau
+{
+    fn new(sys: &mut System, common: Common, first_step: Option<f64>) -> Radau
+    {
+        let f = sys.fun(common.t, &common.y);
+        let h_abs = match first_step
+        {
+            Some(h) => h,
+            None => select_initial_step(sys, common.t, &common.y, &f, common.t_bound,
+                                        common.max_step, common.direction, 3,
+                                        &common.rtol, &common.atol),
+        };
+        let jac = sys.jacobian(common.t, &common.y, Some(&f));
+        let newton_tol = newton_tol(&common.rtol);
+        let y_old = common.y.clone();
+        Radau{
+            common: common,
+            f: f,
+            y_old: y_old,
+            h_abs: h_abs,
+            h_abs_old: None,
+            error_norm_old: None,
+            newton_tol: newton_tol,
+            jac: jac,
+            current_jac: true,
+            lu_real: None,
+            lu_complex: None,
+            z: Vec::new(),
+            sol: None,
+        }
+    }
+
+    /// Factorizes mu_real / h I - J and (mu_re + i mu_im) / h I - J, the
+    /// latter as the real system [[A, -B], [B, A]] of twice the size
+    fn factorize(&self, sys: &mut System, h: f64) -> (LuFactors, LuFactors)
+    {
+        let n = self.common.y.len();
+        let mr = radau_mu_real() / h;
+        let (cr, ci) = radau_mu_complex();
+        let (cr, ci) = (cr / h, ci / h);
+        let mut real = vec![0f64; n * n];
+        let mut complex = vec![0f64; 4 * n * n];
+        let m = 2 * n;
+        for i in 0..n
+        {
+            for j in 0..n
+            {
+                let jij = self.jac[i * n + j];
+                let d = if i == j { 1f64 } else { 0f64 };
+                real[i * n + j] = mr * d - jij;
+                complex[i * m + j] = cr * d - jij;
+                complex[(i + n) * m + j + n] = cr * d - jij;
+                complex[i * m + j + n] = -ci * d;
+                complex[(i + n) * m + j] = ci * d;
+            }
+        }
+        (sys.lu(real, n), sys.lu(complex, m))
+    }
+
+    /// Simplified Newton iterations for the collocation system of the step
+    /// h from z0. Returns whether they converged, the iteration count, the
+    /// stage increments and the last convergence rate.
+    fn solve_collocation(&self, sys: &mut System, h: f64, z0: Vec<Vec<f64>>,
+                         scale: &[f64], lu_real: &LuFactors, lu_complex: &LuFactors)
+        -> (bool, usize, Vec<Vec<f64>>, Option<f64>)
+    {
+        let n = self.common.y.len();
+        let t = self.common.t;
+        let c = radau_c();
+        let mr = radau_mu_real() / h;
+        let (cr, ci) = radau_mu_complex();
+        let (cr, ci) = (cr / h, ci / h);
+        let mut w: Vec<Vec<f64>> = (0..3)
+            .map(|r| combine(&z0, &RADAU_TI[r], n))
+            .collect();
+        let mut z = z0;
+        let mut dw_norm_old: Option<f64> = None;
+        let mut rate: Option<f64> = None;
+        let mut iterations = 0;
+        if lu_real.is_singular() || lu_complex.is_singular()
+        {
+            return (false, 1, z, rate);
+        }
+        for k in 0..RADAU_NEWTON_MAXITER
+        {
+            iterations = k + 1;
+            let mut f = Vec::with_capacity(3);
+            for r in 0..3
+            {
+                let yr: Vec<f64> = (0..n).map(|i| self.common.y[i] + z[r][i]).collect();
+                f.push(sys.fun(t + c[r] * h, &yr));
+            }
+            if f.iter().any(|fr| fr.iter().any(|v| !v.is_finite()))
+            {
+                break;
+            }
+            let tf: Vec<Vec<f64>> = (0..3).map(|r| combine(&f, &RADAU_TI[r], n)).collect();
+            let f_real: Vec<f64> = (0..n).map(|i| tf[0][i] - mr * w[0][i]).collect();
+            let mut f_complex = vec![0f64; 2 * n];
+            for i in 0..n
+            {
+                f_complex[i] = tf[1][i] - (cr * w[1][i] - ci * w[2][i]);
+                f_complex[i + n] = tf[2][i] - (ci * w[1][i] + cr * w[2][i]);
+            }
+            let dw_real = sys.solve(lu_real, &f_real);
+            let dw_complex = sys.solve(lu_complex, &f_complex);
+            let dw = vec![dw_real, dw_complex[..n].to_vec(), dw_complex[n..].to_vec()];
+            let flat: Vec<f64> = dw.iter().flat_map(|r| r.iter().cloned()).collect();
+            let scale3: Vec<f64> = (0..3).flat_map(|_| scale.iter().cloned()).collect();
+            let dw_norm = scaled_rms(&flat, &scale3);
+            if let Some(old) = dw_norm_old
+            {
+                rate = Some(dw_norm / old);
+            }
+            if let Some(r) = rate
+            {
+                if r >= 1f64
+                    || r.powi((RADAU_NEWTON_MAXITER - k) as i32) / (1f64 - r) * dw_norm
+                        > self.newton_tol
+                {
+                    break;
+                }
+            }
+            for r in 0..3
+            {
+                for i in 0..n
+                {
+                    w[r][i] += dw[r][i];
+                }
+            }
+            z = (0..3).map(|r| combine(&w, &RADAU_T[r], n)).collect();
+            let done = match rate
+            {
+                Some(r) => r / (1f64 - r) * dw_norm < self.newton_tol,
+                None => false,
+            };
+            if dw_norm == 0f64 || done
+            {
+                return (true, iterations, z, rate);
+            }
+            dw_norm_old = Some(dw_norm);
+        }
+        (false, iterations, z, rate)
+    }
+}
+
+/// Newton iteration tolerance for the implicit methods
+fn newton_tol(rtol: &[f64]) -> f64
+{
+    let rtol = rtol.iter().fold(f64::INFINITY, |m, &r| m.min(r));
+    (10f64 * f64::EPSILON / rtol).max(0.03f64.min(rtol.sqrt()))
+}
+
+impl Stepper for Radau
+{
+    fn common(&self) -> &Common
+    {
+        &self.common
+    }
+
+    fn step(&mut self, sys: &mut System) -> Result<(), String>
+    {
+        let t = self.common.t;
+        let n = self.common.y.len();
+        let direction = self.common.direction;
+        let min_step = min_step(t, direction);
+        let c = radau_c();
+        let e = radau_e();
+        let (mut h_abs, h_abs_old, error_norm_old) =
+            if self.h_abs > self.common.max_step
+            {
+                (self.common.max_step, None, None)
+            } else if self.h_abs < min_step {
+                (min_step, None, None)
+            } else {
+                (self.h_abs, self.h_abs_old, self.error_norm_old)
+            };
+        let mut lu_real = self.lu_real.take();
+        let mut lu_complex = self.lu_complex.take();
+        let mut current_jac = self.current_jac;
+        let mut rejected = false;
+        let (t_new, y_new, z, n_iter, rate, error_norm, safety);
+        loop
+        {
+            if h_abs < min_step
+            {
+                return Err(String::from(TOO_SMALL_STEP));
+            }
+            let mut tn = t + h_abs * direction;
+            if direction * (tn - self.common.t_bound) > 0f64
+            {
+                tn = self.common.t_bound;
+            }
+            let h = tn - t;
+            h_abs = h.abs();
+
+            let z0: Vec<Vec<f64>> = match self.sol
+            {
+                None => vec![vec![0f64; n]; 3],
+                Some(ref sol) => (0..3)
+                    .map(|r| {
+                        let yc = sol.eval(t + h * c[r]);
+                        (0..n).map(|i| yc[i] - self.common.y[i]).collect()
+                    })
+                    .collect(),
+            };
+            let scale = error_scale(&self.common.y, &self.common.rtol, &self.common.atol);
+
+            let mut result = None;
+            loop
+            {
+                if lu_real.is_none() || lu_complex.is_none()
+                {
+                    let (lr, lc) = self.factorize(sys, h);
+                    lu_real = Some(lr);
+                    lu_complex = Some(lc);
+                }
+                let (converged, iters, zs, r) = self.solve_collocation(
+                    sys, h, z0.clone(), &scale,
+                    lu_real.as_ref().unwrap(), lu_complex.as_ref().unwrap());
+                if converged
+                {
+                    result = Some((iters, zs, r));
+                    break;
+                }
+                if current_jac
+                {
+                    break;
+                }
+                self.jac = sys.jacobian(t, &self.common.y, Some(&self.f));
+                current_jac = true;
+                lu_real = None;
+                lu_complex = None;
+            }
+            let (iters, zs, r) = match result
+            {
+                Some(res) => res,
+                None => {
+                    h_abs *= 0.5;
+                    lu_real = None;
+                    lu_complex = None;
+                    continue;
+                },
+            };
+
+            let yn: Vec<f64> = (0..n).map(|i| self.common.y[i] + zs[2][i]).collect();
+            let ze: Vec<f64> = combine(&zs, &e, n).iter().map(|v| v / h).collect();
+            let rhs: Vec<f64> = (0..n).map(|i| self.f[i] + ze[i]).collect();
+            let mut error = sys.solve(lu_real.as_ref().unwrap(), &rhs);
+            let scale = error_scale2(&self.common.y, &yn, &self.common.rtol, &self.common.atol);
+            let mut norm = scaled_rms(&error, &scale);
+            let sf = SAFETY * (2 * RADAU_NEWTON_MAXITER + 1) as f64
+                / (2 * RADAU_NEWTON_MAXITER + iters) as f64;
+            if rejected && norm > 1f64
+            {
+                let ye: Vec<f64> = (0..n).map(|i| self.common.y[i] + error[i]).collect();
+                let fe = sys.fun(t, &ye);
+                let rhs: Vec<f64> = (0..n).map(|i| fe[i] + ze[i]).collect();
+                error = sys.solve(lu_real.as_ref().unwrap(), &rhs);
+                norm = scaled_rms(&error, &scale);
+            }
+            if norm > 1f64
+            {
+                let factor = predict_factor(h_abs, h_abs_old, norm, error_norm_old);
+                h_abs *= MIN_FACTOR.max(sf * factor);
+                lu_real = None;
+                lu_complex = None;
+                rejected = true;
+                continue;
+            }
+            t_new = tn;
+            y_new = yn;
+            z = zs;
+            n_iter = iters;
+            rate = r;
+            error_norm = norm;
+            safety = sf;
+            break;
+        }
+
+        let recompute_jac = n_iter > 2 && rate.map_or(false, |r| r > 1e-3);
+        let mut factor = predict_factor(h_abs, h_abs_old, error_norm, error_norm_old);
+        factor = MAX_FACTOR.min(safety * factor);
+        if !recompute_jac && factor < 1.2
+        {
+            factor = 1f64;
+        } else {
+            lu_real = None;
+            lu_complex = None;
+        }
+        let f_new = sys.fun(t_new, &y_new);
+        if recompute_jac
+        {
+            self.jac = sys.jacobian(t_new, &y_new, Some(&f_new));
+            current_jac = true;
+        } else {
+            current_jac = false;
+        }
+
+        self.h_abs_old = Some(self.h_abs);
+        self.error_norm_old = Some(error_norm);
+        self.h_abs = h_abs * factor;
+        self.y_old = ::std::mem::replace(&mut self.common.y, y_new);
+        self.common.t_old = t;
+        self.common.t = t_new;
+        self.f = f_new;
+        self.z = z;
+        self.lu_real = lu_real;
+        self.lu_complex = lu_complex;
+        self.current_jac = current_jac;
+        let sol = self.collocation_polynomial();
+        self.sol = Some(sol);
+        Ok(())
+    }
+
+    fn dense(&mut self, _sys: &mut System) -> Dense
+    {
+        self.collocation_polynomial()
+    }
+}
+
+impl Radau
+{
+    /// Collocation polynomial of the last step
+    fn collocation_polynomial(&self) -> Dense
+    {
+        let n = self.common.y.len();
+        let p = radau_p();
+        let q = (0..3)
+            .map(|j| {
+                let w: Vec<f64> = p.iter().map(|row| row[j]).collect();
+                combine(&self.z, &w, n)
+            })
+            .collect();
+        Dense::Power{
+            t_old: self.common.t_old,
+            h: self.common.t - self.common.t_old,
+            y_old: self.y_old.clone(),
+            q: q,
+        }
+    }
+}
+
+
+/////////
+// BDF //
+/////////
+
+/// Highest order of the BDF method
+const BDF_MAX_ORDER: usize = 5;
+
+/// Maximum Newton iterations per BDF step
+const BDF_NEWTON_MAXITER: usize = 4;
+
+/// Matrix R(order, factor) relating backward differences before and after
+/// the step size is multiplied by factor
+fn compute_r(order: usize, factor: f64) -> Vec<Vec<f64>>
+{
+    let mut m = vec![vec![0f64; order + 1]; order + 1];
+    for j in 0..order + 1
+    {
+        m[0][j] = 1f64;
+    }
+    for i in 1..order + 1
+    {
+        for j in 1..order + 1
+        {
+            m[i][j] = (i as f64 - 1f64 - factor * j as f64) / i as f64;
+        }
+    }
+    // Cumulative product down each column
+    for i in 1..order + 1
+    {
+        for j in 0..order + 1
+        {
+            m[i][j] *= m[i - 1][j];
+        }
+    }
+    m
+}
+
+/// Rescales the backward differences d for a step size multiplied by factor
+fn change_d(d: &mut Vec<Vec<f64>>, order: usize, factor: f64)
+{
+    let r = compute_r(order, factor);
+    let u = compute_r(order, 1f64);
+    let k = order + 1;
+    let n = d[0].len();
+    // RU = R U, and D[:k] = RU^T D[:k]
+    let mut ru = vec![vec![0f64; k]; k];
+    for i in 0..k
+    {
+        for j in 0..k
+        {
+            ru[i][j] = (0..k).fold(0f64, |s, l| s + r[i][l] * u[l][j]);
+        }
+    }
+    let old: Vec<Vec<f64>> = d[..k].to_vec();
+    for i in 0..k
+    {
+        let mut row = vec![0f64; n];
+        for l in 0..k
+        {
+            for c in 0..n
+            {
+                row[c] += ru[l][i] * old[l][c];
+            }
+        }
+        d[i] = row;
+    }
+}
+
+/// Variable order BDF stepper in the NDF form of Shampine and Reichelt
+struct Bdf
+{
+    common: Common,
+    h_abs: f64,
+    newton_tol: f64,
+    jac: Vec<f64>,
+    lu: Option<LuFactors>,
+    /// Backward differences of the solution, scaled by powers of h
+    d: Vec<Vec<f64>>,
+    order: usize,
+    n_equal_steps: usize,
+    gamma: [f64; BDF_MAX_ORDER + 1],
+    alpha: [f64; BDF_MAX_ORDER + 1],
+    error_const: [f64; BDF_MAX_ORDER + 1],
+}
+
+impl Bdf
+{
+    fn new(sys: &mut System, common: Common, first_step: Option<f64>) -> Bdf
+    {
+        let n = common.y.len();
+        let f = sys.fun(common.t, &common.y);
+        let h_abs = match first_step
+        {
+            Some(h) => h,
+            None => select_initial_step(sys, common.t, &common.y, &f, common.t_bound,
+                                        common.max_step, common.direction, 1,
+                                        &common.rtol, &common.atol),
+        };
+        let jac = sys.jacobian(common.t, &common.y, Some(&f));
+        // NDF coefficients of Shampine and Reichelt
+        let kappa = [0f64, -0.1850, -1f64 / 9f64, -0.0823, -0.0415, 0f64];
+        let mut gamma = [0f64; BDF_MAX_ORDER + 1];
+        let mut alpha = [0f64; BDF_MAX_ORDER + 1];
+        let mut error_const = [0f64; BDF_MAX_ORDER + 1];
+        for k in 0..BDF_MAX_ORDER + 1
+        {
+            if k > 0
+            {
+                gamma[k] = gamma[k - 1] + 1f64 / k as f64;
+            }
+            alpha[k] = (1f64 - kappa[k]) * gamma[k];
+            error_const[k] = kappa[k] * gamma[k] + 1f64 / (k as f64 + 1f64);
+        }
+        let mut d = vec![vec![0f64; n]; BDF_MAX_ORDER + 3];
+        d[0] = common.y.clone();
+        d[1] = f.iter().map(|v| v * h_abs * common.direction).collect();
+        let newton_tol = newton_tol(&common.rtol);
+        Bdf{
+            common: common,
+            h_abs: h_abs,
+            newton_tol: newton_tol,
+            jac: jac,
+            lu: None,
+            d: d,
+            order: 1,
+            n_equal_steps: 0,
+            gamma: gamma,
+            alpha: alpha,
+            error_const: error_const,
+        }
+    }
+
+    /// Simplified Newton iterations for the BDF system at t_new. Returns
+    /// whether they converged, the iteration count, the solution and its
+    /// correction from the predictor.
+    fn solve_system(&self, sys: &mut System, t_new: f64, y_predict: &[f64], c: f64,
+                    psi: &[f64], lu: &LuFactors, scale: &[f64])
+        -> (bool, usize, Vec<f64>, Vec<f64>)
+    {
+        let n = y_predict.len();
+        let mut d = vec![0f64; n];
+        let mut y = y_predict.to_vec();
+        let mut dy_norm_old: Option<f64> = None;
+        let mut iterations = 0;
+        if lu.is_singular()
+        {
+            return (false, 1, y, d);
+        }
+        for k in 0..BDF_NEWTON_MAXITER
+        {
+            iterations = k + 1;
+            let f = sys.fun(t_new, &y);
+            if f.iter().any(|v| !v.is_finite())
+            {
+                break;
+            }
+            let rhs: Vec<f64> = (0..n).map(|i| c * f[i] - psi[i] - d[i]).collect();
+            let dy = sys.solve(lu, &rhs);
+            let dy_norm = scaled_rms(&dy, scale);
+            let rate = dy_norm_old.map(|old| dy_norm / old);
+            if let Some(r) = rate
+            {
+                if r >= 1f64
+                    || r.powi((BDF_NEWTON_MAXITER - k) as i32) / (1f64 - r) * dy_norm
+                        > self.newton_tol
+                {
+                    break;
+                }
+            }
+            for i in 0..n
+            {
+                y[i] += dy[i];
+                d[i] += dy[i];
+            }
+            let done = match rate
+            {
+                Some(r) => r / (1f64 - r) * dy_norm < self.newton_tol,
+                None => false,
+            };
+            if dy_norm == 0f64 || done
+            {
+                return (true, iterations, y, d);
+            }
+            dy_norm_old = Some(dy_norm);
+        }
+        (false, iterations, y, d)
+    }
+}
+
+impl Stepper for Bdf
+{
+    fn common(&self) -> &Common
+    {
+        &self.common
+    }
+
+    fn step(&mut self, sys: &mut System) -> Result<(), String>
+    {
+        let t = self.common.t;
+        let n = self.common.y.len();
+        let direction = self.common.direction;
+        let min_step = min_step(t, direction);
+        let mut h_abs = self.h_abs;
+        if h_abs > self.common.max_step
+        {
+            h_abs = self.common.max_step;
+            change_d(&mut self.d, self.order, self.common.max_step / self.h_abs);
+            self.n_equal_steps = 0;
+        } else if h_abs < min_step {
+            h_abs = min_step;
+            change_d(&mut self.d, self.order, min_step / self.h_abs);
+            self.n_equal_steps = 0;
+        }
+        let order = self.order;
+        let mut lu = self.lu.take();
+        let mut current_jac = false;
+        let (t_new, y_new, d_corr, n_iter, scale, error_norm);
+        loop
+        {
+            if h_abs < min_step
+            {
+                return Err(String::from(TOO_SMALL_STEP));
+            }
+            let mut tn = t + h_abs * direction;
+            if direction * (tn - self.common.t_bound) > 0f64
+            {
+                tn = self.common.t_bound;
+                change_d(&mut self.d, order, (tn - t).abs() / h_abs);
+                self.n_equal_steps = 0;
+                lu = None;
+            }
+            let h = tn - t;
+            h_abs = h.abs();
+
+            let y_predict = combine(&self.d[..order + 1], &[1f64; BDF_MAX_ORDER + 1], n);
+            let sc = error_scale(&y_predict, &self.common.rtol, &self.common.atol);
+            let psi: Vec<f64> = combine(&self.d[1..order + 1], &self.gamma[1..order + 1], n)
+                .iter().map(|v| v / self.alpha[order]).collect();
+            let c = h / self.alpha[order];
+
+            let mut result = None;
+            loop
+            {
+                if lu.is_none()
+                {
+                    let mut m = vec![0f64; n * n];
+                    for i in 0..n
+                    {
+                        for j in 0..n
+                        {
+                            let delta = if i == j { 1f64 } else { 0f64 };
+                            m[i * n + j] = delta - c * self.jac[i * n + j];
+                        }
+                    }
+                    lu = Some(sys.lu(m, n));
+                }
+                let (converged, iters, yn, dn) = self.solve_system(
+                    sys, tn, &y_predict, c, &psi, lu.as_ref().unwrap(), &sc);
+                if converged
+                {
+                    result = Some((iters, yn, dn));
+                    break;
+                }
+                if current_jac
+                {
+                    break;
+                }
+                self.jac = sys.jacobian(tn, &y_predict, None);
+                lu = None;
+                current_jac = true;
+            }
+            let (iters, yn, dn) = match result
+            {
+                Some(res) => res,
+                None => {
+                    h_abs *= 0.5;
+                    change_d(&mut self.d, order, 0.5);
+                    self.n_equal_steps = 0;
+                    lu = None;
+                    continue;
+                },
+            };
+
+            let safety = SAFETY * (2 * BDF_NEWTON_MAXITER + 1) as f64
+                / (2 * BDF_NEWTON_MAXITER + iters) as f64;
+            let sc = error_scale(&yn, &self.common.rtol, &self.common.atol);
+            let error: Vec<f64> = dn.iter().map(|v| self.error_const[order] * v).collect();
+            let norm = scaled_rms(&error, &sc);
+            if norm > 1f64
+            {
+                let factor = MIN_FACTOR.max(safety * norm.powf(-1f64 / (order as f64 + 1f64)));
+                h_abs *= factor;
+                change_d(&mut self.d, order, factor);
+                self.n_equal_steps = 0;
+                // The Newton iterations converged, so the LU factors are kept
+                continue;
+            }
+            t_new = tn;
+            y_new = yn;
+            d_corr = dn;
+            n_iter = iters;
+            scale = sc;
+            error_norm = norm;
+            break;
+        }
+
+        self.n_equal_steps += 1;
+        self.common.t_old = t;
+        self.common.t = t_new;
+        self.common.y = y_new;
+        self.h_abs = h_abs;
+        self.lu = lu;
+
+        // Update the differences, D^(j+1) y_n = D^j y_n - D^j y_(n-1)
+        self.d[order + 2] = (0..n).map(|i| d_corr[i] - self.d[order + 1][i]).collect();
+        self.d[order + 1] = d_corr;
+        for i in (0..order + 1).rev()
+        {
+            for c in 0..n
+            {
+                self.d[i][c] += self.d[i + 1][c];
+            }
+        }
+
+        if self.n_equal_steps < order + 1
+        {
+            return Ok(());
+        }
+
+        // Consider changing the order
+        let safety = SAFETY * (2 * BDF_NEWTON_MAXITER + 1) as f64
+            / (2 * BDF_NEWTON_MAXITER + n_iter) as f64;
+        let error_m_norm = if order > 1
+        {
+            let e: Vec<f64> = self.d[order].iter()
+                .map(|v| self.error_const[order - 1] * v).collect();
+            scaled_rms(&e, &scale)
+        } else {
+            f64::INFINITY
+        };
+        let error_p_norm = if order < BDF_MAX_ORDER
+        {
+            let e: Vec<f64> = self.d[order + 2].iter()
+                .map(|v| self.error_const[order + 1] * v).collect();
+            scaled_rms(&e, &scale)
+        } else {
+            f64::INFINITY
+        };
+        let norms = [error_m_norm, error_norm, error_p_norm];
+        let mut best = 0;
+        let mut factors = [0f64; 3];
+        for k in 0..3
+        {
+            factors[k] = norms[k].powf(-1f64 / (order + k) as f64);
+            if factors[k] > factors[best]
+            {
+                best = k;
+            }
+        }
+        let new_order = order + best - 1;
+        self.order = new_order;
+        let factor = MAX_FACTOR.min(safety * factors[best]);
+        self.h_abs *= factor;
+        change_d(&mut self.d, new_order, factor);
+        self.n_equal_steps = 0;
+        self.lu = None;
+        Ok(())
+    }
+
+    fn dense(&mut self, _sys: &mut System) -> Dense
+    {
+        let h = self.h_abs * self.common.direction;
+        let t = self.common.t;
+        Dense::Bdf{
+            t_shift: (0..self.order).map(|k| t - h * k as f64).collect(),
+            denom: (0..self.order).map(|k| h * (k as f64 + 1f64)).collect(),
+            d: self.d[..self.order + 1].to_vec(),
+        }
+    }
+}
+
+
+////////////
+// Events //
+////////////
+
+/// Brent's method for a zero of g in the interval between a and b, where g
+/// changes sign, to a tolerance of four units in the last place
+fn event_root<G>(g: G, a: f64, b: f64) -> f64
+    where G: Fn(f64) -> f64
+{
+    let tol = 4f64 * f64::EPSILON;
+    let (mut xpre, mut xcur) = (a, b);
+    let (mut fpre, mut fcur) = (g(xpre), g(xcur));
+    let (mut xblk, mut fblk) = (0f64, 0f64);
+    let (mut spre, mut scur) = (0f64, 0f64);
+    if fpre == 0f64
+    {
+        return xpre;
+    }
+    if fcur == 0f64
+    {
+        return xcur;
+    }
+    for _ in 0..100
+    {
+        if fpre != 0f64 && fcur != 0f64 && (fpre < 0f64) != (fcur < 0f64)
+        {
+            xblk = xpre;
+            fblk = fpre;
+            spre = xcur - xpre;
+            scur = spre;
+        }
+        if fblk.abs() < fcur.abs()
+        {
+            xpre = xcur;
+            xcur = xblk;
+            xblk = xpre;
+            fpre = fcur;
+            fcur = fblk;
+            fblk = fpre;
+        }
+        let delta = (tol + tol * xcur.abs()) / 2f64;
+        let sbis = (xblk - xcur) / 2f64;
+        if fcur == 0f64 || sbis.abs() < delta
+        {
+            return xcur;
+        }
+        if spre.abs() > delta && fcur.abs() < fpre.abs()
+        {
+            let stry = if xpre == xblk
+            {
+                // Secant
+                -fcur * (xcur - xpre) / (fcur - fpre)
+            } else {
+                // Inverse quadratic interpolation
+                let dpre = (fpre - fcur) / (xpre - xcur);
+                let dblk = (fblk - fcur) / (xblk - xcur);
+                -fcur * (fblk * dblk - fpre * dpre) / (dblk * dpre * (fblk - fpre))
+            };
+            if 2f64 * stry.abs() < spre.abs().min(3f64 * sbis.abs() - delta)
+            {
+                spre = scur;
+                scur = stry;
+            } else {
+                spre = sbis;
+                scur = sbis;
+            }
+        } else {
+            spre = sbis;
+            scur = sbis;
+        }
+        xpre = xcur;
+        fpre = fcur;
+        if scur.abs() > delta
+        {
+            xcur += scur;
+        } else {
+            xcur += if sbis > 0f64 { delta } else { -delta };
+        }
+        fcur = g(xcur);
+    }
+    xcur
+}
+
+/// Indices of the events whose sign change between g and g_new matches
+/// their direction
+fn active_events(events: &[Event], g: &[f64], g_new: &[f64]) -> Vec<usize>
+{
+    (0..events.len())
+        .filter(|&i| {
+            let up = g[i] <= 0f64 && g_new[i] >= 0f64;
+            let down = g[i] >= 0f64 && g_new[i] <= 0f64;
+            let dir = events[i].direction;
+            (up && dir > 0f64) || (down && dir < 0f64) || ((up || down) && dir == 0f64)
+        })
+        .collect()
+}
+
+
+///////////////
+// solve_ivp //
+///////////////
+
+/// Integrates y' = f(t, y) from t0 to tf starting from y0, which may run
+/// backwards if tf < t0. f must return an array as long as y.
+/// The integration stops early at the first terminal event, or with a
+/// negative status if the step size falls below the resolution of t.
+pub fn solve_ivp<F>(f: F, t0: f64, tf: f64, y0: &Array<f64>,
+                    opts: &IvpOptions) -> IvpResult
+    where F: Fn(f64, &Array<f64>) -> Array<f64>
+{
+    if !t0.is_finite() || !tf.is_finite()
+    {
+        panic!("Invalid integration interval: {}, {}", t0, tf);
+    }
+    if !(opts.max_step > 0f64)
+    {
+        panic!("Invalid value for max_step: {}", opts.max_step);
+    }
+    if let Some(h) = opts.first_step
+    {
+        if !(h > 0f64) || h > (tf - t0).abs()
+        {
+            panic!("Invalid value for first_step: {}", h);
+        }
+    }
+    let n = y0.len();
+    let mut rtol = expand(&opts.rtol, n, "rtol");
+    for r in rtol.iter_mut()
+    {
+        *r = r.max(100f64 * f64::EPSILON);
+    }
+    let atol = expand(&opts.atol, n, "atol");
+    let direction = if tf >= t0 { 1f64 } else { -1f64 };
+    let t_eval: Option<Vec<f64>> = opts.t_eval.as_ref().map(|te| te.as_slice().to_vec());
+    if let Some(ref te) = t_eval
+    {
+        let (lo, hi) = if t0 <= tf { (t0, tf) } else { (tf, t0) };
+        if te.iter().any(|&s| s < lo || s > hi)
+        {
+            panic!("Values in t_eval are not within the integration interval.");
+        }
+        if te.windows(2).any(|w| direction * (w[1] - w[0]) < 0f64)
+        {
+            panic!("Values in t_eval are not ordered in the direction of integration.");
+        }
+    }
+
+    let jac = opts.jac.as_ref().map(|j| &**j);
+    let mut sys = System{fun: &f, jac: jac, n: n, nfev: 0, njev: 0, nlu: 0};
+    let common = Common{
+        t: t0,
+        y: y0.as_slice().to_vec(),
+        t_old: t0,
+        t_bound: tf,
+        direction: direction,
+        max_step: opts.max_step,
+        rtol: rtol,
+        atol: atol,
+    };
+    match opts.method
+    {
+        Method::RK45 | Method::RK23 | Method::DOP853 => {
+            let solver = RungeKutta::new(&mut sys, common, opts.method, opts.first_step);
+            integrate(solver, &mut sys, t_eval, opts)
+        },
+        Method::Radau => {
+            let solver = Radau::new(&mut sys, common, opts.first_step);
+            integrate(solver, &mut sys, t_eval, opts)
+        },
+        Method::BDF => {
+            let solver = Bdf::new(&mut sys, common, opts.first_step);
+            integrate(solver, &mut sys, t_eval, opts)
+        },
+    }
+}
+
+/// Drives a stepper to the end of the interval, recording the solution,
+/// the interpolants and any events
+fn integrate<S>(mut solver: S, sys: &mut System, t_eval: Option<Vec<f64>>,
+                opts: &IvpOptions) -> IvpResult
+    where S: Stepper
+{
+    let events = &opts.events;
+    let t0 = solver.common().t;
+    let tf = solver.common().t_bound;
+    let direction = solver.common().direction;
+    let y0 = solver.common().y.clone();
+
+    let mut ts: Vec<f64> = Vec::new();
+    let mut ys: Vec<Vec<f64>> = Vec::new();
+    let mut eval_index = 0;
+    match t_eval
+    {
+        None => {
+            ts.push(t0);
+            ys.push(y0.clone());
+        },
+        Some(ref te) => {
+            // Requested times at the very start
+            while eval_index < te.len() && te[eval_index] == t0
+            {
+                ts.push(t0);
+                ys.push(y0.clone());
+                eval_index += 1;
+            }
+        },
+    }
+    let mut boundaries = vec![t0];
+    let mut segments: Vec<Dense> = Vec::new();
+    let mut g: Vec<f64> = events.iter().map(|e| e.eval(t0, &y0)).collect();
+    let mut t_events: Vec<Vec<f64>> = vec![Vec::new(); events.len()];
+    let mut y_events: Vec<Vec<Array<f64>>> = (0..events.len()).map(|_| Vec::new()).collect();
+
+    let mut status = 0;
+    let mut message =
+        String::from("The solver successfully reached the end of the integration interval.");
+    while t0 != tf && solver.common().t != tf
+    {
+        if let Err(msg) = solver.step(sys)
+        {
+            status = -1;
+            message = msg;
+            break;
+        }
+        let t_old = solver.common().t_old;
+        let mut t = solver.common().t;
+        let mut y = solver.common().y.clone();
+        let mut sol = if opts.dense_output || !events.is_empty() || t_eval.is_some()
+        {
+            Some(solver.dense(sys))
+        } else {
+            None
+        };
+
+        if !events.is_empty()
+        {
+            let g_new: Vec<f64> = events.iter().map(|e| e.eval(t, &y)).collect();
+            let mut active = active_events(events, &g, &g_new);
+            if !active.is_empty()
+            {
+                let dense = sol.as_ref().unwrap();
+                let mut roots: Vec<(usize, f64)> = active.drain(..)
+                    .map(|i| {
+                        let root = event_root(|s| events[i].eval(s, &dense.eval(s)), t_old, t);
+                        (i, root)
+                    })
+                    .collect();
+                // Occurrences in order of time, up to the first terminal one
+                roots.sort_by(|a, b| (direction * a.1).partial_cmp(&(direction * b.1)).unwrap());
+                if let Some(first) = roots.iter().position(|&(i, _)| events[i].terminal)
+                {
+                    roots.truncate(first + 1);
+                    status = 1;
+                    message = String::from("A termination event occurred.");
+                }
+                for &(i, root) in roots.iter()
+                {
+                    t_events[i].push(root);
+                    y_events[i].push(Array::new(dense.eval(root), Order::Row));
+                }
+                if status == 1
+                {
+                    t = roots[roots.len() - 1].1;
+                    y = dense.eval(t);
+                }
+            }
+            g = g_new;
+        }
+
+        match t_eval
+        {
+            None => {
+                ts.push(t);
+                ys.push(y);
+            },
+            Some(ref te) => {
+                let dense = sol.as_ref().unwrap();
+                while eval_index < te.len() && direction * (te[eval_index] - t) <= 0f64
+                {
+                    ts.push(te[eval_index]);
+                    ys.push(dense.eval(te[eval_index]));
+                    eval_index += 1;
+                }
+            },
+        }
+        if opts.dense_output
+        {
+            boundaries.push(t);
+            segments.push(sol.take().unwrap());
+        }
+        if status == 1
+        {
+            break;
+        }
+    }
+
+    let sol = if opts.dense_output && !segments.is_empty()
+    {
+        Some(OdeSolution{ts: boundaries, segments: segments})
+    } else {
+        None
+    };
+    IvpResult{
+        t: Array::new(ts, Order::Row),
+        y: ys.into_iter().map(|y| Array::new(y, Order::Row)).collect(),
+        sol: sol,
+        t_events: t_events.into_iter().map(|t| Array::new(t, Order::Row)).collect(),
+        y_events: y_events,
+        nfev: sys.nfev,
+        njev: sys.njev,
+        nlu: sys.nlu,
+        status: status,
+        message: message,
+        success: status >= 0,
+    }
+}
@@ -0,0 +1,3540 @@
+/// Minimization of scalar functions of several variables, f: R^n -> R, over
+/// Rust closures in the manner of SciPy's minimize.
+/// Nelder-Mead and Powell's method need function values only; conjugate
+/// gradients, BFGS and L-BFGS-B use the gradient, and the Newton-CG trust
+/// region method also uses the Hessian or Hessian-vector products. Gradients
+/// which are not supplied are estimated by forward differences, Hessian-vector
+/// products by differences of gradients.
+/// L-BFGS-B handles box bounds l <= x <= u directly; Nelder-Mead keeps its
+/// simplex inside them.
+/// Nonlinear least squares problems are solved by `least_squares`, with
+/// Levenberg-Marquardt or a trust region reflective method supporting
+/// bounds and robust loss functions, and `curve_fit` fits models to data,
+/// estimating the covariance of the parameters.
+/// Linear programs are solved by `linprog`, with a dense revised simplex
+/// method or a primal-dual interior point method, and convex quadratic
+/// programs with a symmetric Hessian by `quadprog`; both take linear
+/// inequality and equality constraints and bounds.
+/// `minimize`, `least_squares`, `linprog` and `quadprog` return a result
+/// structure; running out of iterations or function evaluations is reported
+/// there rather than by a panic.
+/// For further information see Nocedal & Wright, Numerical Optimization,
+/// Byrd, Lu, Nocedal & Zhu, A Limited Memory Algorithm for Bound Constrained
+/// Optimization, SIAM J. Sci. Comput. 16 (1995), and Branch, Coleman & Li,
+/// A Subspace, Interior, and Conjugate Gradient Method for Large-Scale
+/// Bound-Constrained Minimization Problems, SIAM J. Sci. Comput. 21 (1999),
+/// and Andersen & Andersen, The MOSEK Interior Point Optimizer for Linear
+/// Programming, High Performance Optimization (2000).
+
+use std::f64;
+use std::cmp::Ordering;
+use array::Array;
+use array::Order;
+use matrix::Matrix;
+use matrix::SymMat;
+use lapacke;
+use dense;
+
+
+/////////////////////////
+// Options and results //
+/////////////////////////
+
+/// Minimization method
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Method
+{
+    /// Nelder-Mead downhill simplex, using function values only
+    NelderMead,
+    /// Powell's conjugate direction method with Brent line searches, using
+    /// function values only
+    Powell,
+    /// Polak-Ribiere nonlinear conjugate gradients with a Wolfe line search
+    CG,
+    /// Quasi-Newton method with a dense BFGS approximation to the inverse
+    /// Hessian and a Wolfe line search
+    BFGS,
+    /// Limited memory BFGS with box bounds, using generalized Cauchy points
+    /// and subspace minimization
+    LBFGSB,
+    /// Trust region method solving the Newton equations approximately by
+    /// Steihaug's truncated conjugate gradients; needs the gradient
+    TrustNCG,
+}
+
+/// Box bounds lower <= x <= upper. Infinite entries leave a side of a
+/// component unbounded.
+#[derive(Debug, Clone)]
+pub struct Bounds
+{
+    pub lower: Array<f64>,
+    pub upper: Array<f64>,
+}
+
+impl Bounds
+{
+    pub fn new(lower: Array<f64>, upper: Array<f64>) -> Bounds
+    {
+        if lower.len() != upper.len()
+        {
+            panic!("Lower bounds have {} components but upper bounds have {}",
+                   lower.len(), upper.len());
+        }
+        for i in 0..lower.len()
+        {
+            if !(lower[i] <= upper[i])
+            {
+                panic!("Lower bound {} exceeds upper bound {} for component {}",
+                       lower[i], upper[i], i);
+            }
+        }
+        Bounds{lower: lower, upper: upper}
+    }
+
+    fn check(&self, n: usize)
+    {
+        if self.lower.len() != n || self.upper.len() != n
+        {
+            panic!("Bounds have {} components, expected {}", self.lower.len(), n);
+        }
+    }
+
+    /// Moves x onto the nearest point within the bounds
+    fn clip(&self, x: &mut [f64])
+    {
+        for i in 0..x.len()
+        {
+            x[i] = x[i].max(self.lower[i]).min(self.upper[i]);
+        }
+    }
+}
+
+/// Options for `minimize`. The tolerances mean different things for
+/// different methods, as for SciPy.
+pub struct MinimizeOptions<'a>
+{
+    /// Minimization method, BFGS by default
+    pub method: Method,
+    /// Gradient of f, estimated by forward differences if None
+    pub jac: Option<Box<Fn(&Array<f64>) -> Array<f64> + 'a>>,
+    /// Hessian of f, used by TrustNCG only; Hessian-vector products are
+    /// estimated from differences of gradients if None
+    pub hess: Option<Box<Fn(&Array<f64>) -> Matrix<f64> + 'a>>,
+    /// Box bounds on x, supported by LBFGSB and NelderMead
+    pub bounds: Option<Bounds>,
+    /// The gradient based methods stop once the largest component of the
+    /// (projected) gradient, or for TrustNCG its norm, is below gtol;
+    /// 1e-5 by default
+    pub gtol: f64,
+    /// NelderMead stops once the simplex is within xtol of its best vertex
+    /// in every component; Powell uses xtol for its line searches; 1e-4 by
+    /// default
+    pub xtol: f64,
+    /// NelderMead stops once the function values at the vertices are within
+    /// ftol of each other, 1e-4 if None. Powell and LBFGSB stop once an
+    /// iteration reduces f by a relative amount of less than ftol, 1e-4 and
+    /// 1e7 times machine epsilon respectively if None
+    pub ftol: Option<f64>,
+    /// Maximum number of iterations; 200 n for n variables if None, except
+    /// for Powell (1000 n) and LBFGSB (15000)
+    pub maxiter: Option<usize>,
+    /// Maximum number of evaluations of f, checked by NelderMead, Powell
+    /// and LBFGSB between iterations; defaults as for maxiter
+    pub maxfev: Option<usize>,
+}
+
+impl<'a> Default for MinimizeOptions<'a>
+{
+    fn default() -> MinimizeOptions<'a>
+    {
+        MinimizeOptions{
+            method: Method::BFGS,
+            jac: None,
+            hess: None,
+            bounds: None,
+            gtol: 1e-5,
+            xtol: 1e-4,
+            ftol: None,
+            maxiter: None,
+            maxfev: None,
+        }
+    }
+}
+
+/// Outcome of `minimize`
+#[derive(Debug, Clone)]
+pub struct OptimizeResult
+{
+    /// Estimate of the minimizer
+    pub x: Array<f64>,
+    /// f at x
+    pub fun: f64,
+    /// Gradient at x, for the methods which use it
+    pub jac: Option<Array<f64>>,
+    /// Number of iterations done
+    pub nit: usize,
+    /// Number of evaluations of f, including those for difference quotients
+    pub nfev: usize,
+    /// Number of evaluations of the gradient
+    pub njev: usize,
+    /// Number of evaluations of the Hessian
+    pub nhev: usize,
+    /// 0 if the tolerances were met, 1 if the iteration or evaluation limit
+    /// was reached, 2 if no further progress could be made
+    pub status: i32,
+    /// Whether the tolerances were met
+    pub success: bool,
+    /// Description of the reason the method stopped
+    pub message: String,
+}
+
+const SUCCESS: &'static str = "Optimization terminated successfully.";
+const MAXITER: &'static str = "Maximum number of iterations has been exceeded.";
+const MAXFEV: &'static str = "Maximum number of function evaluations has been exceeded.";
+const PRECISION_LOSS: &'static str =
+    "Desired error not necessarily achieved due to precision loss.";
+
+/// Outcome of a method: x, f(x), gradient, iterations, status and message
+type Outcome = (Vec<f64>, f64, Option<Vec<f64>>, usize, i32, String);
+
+
+///////////////
+// Objective //
+///////////////
+
+/// f and its derivatives, counting evaluations
+struct Objective<'a>
+{
+    fun: &'a Fn(&Array<f64>) -> f64,
+    jac: Option<&'a Fn(&Array<f64>) -> Array<f64>>,
+    hess: Option<&'a Fn(&Array<f64>) -> Matrix<f64>>,
+    /// Bounds, so that difference quotients are taken inside them
+    lower: Vec<f64>,
+    upper: Vec<f64>,
+    nfev: usize,
+    njev: usize,
+    nhev: usize,
+}
+
+impl<'a> Objective<'a>
+{
+    fn fun(&mut self, x: &[f64]) -> f64
+    {
+        self.nfev += 1;
+        (self.fun)(&Array::new(x.to_vec(), Order::Row))
+    }
+
+    /// Gradient at x, given f(x)
+    fn grad(&mut self, x: &[f64], f: f64) -> Vec<f64>
+    {
+        if self.jac.is_some()
+        {
+            return self.user_grad(x);
+        }
+        self.njev += 1;
+        let h: Vec<f64> = (0..x.len())
+            .map(|i| dense::bounded_fd_step(x[i], self.lower[i], self.upper[i])).collect();
+        dense::forward_jacobian(|xh| vec![self.fun(xh)], x, &[f], &h)
+    }
+
+    fn user_grad(&mut self, x: &[f64]) -> Vec<f64>
+    {
+        let jac = self.jac.unwrap();
+        self.njev += 1;
+        let g = jac(&Array::new(x.to_vec(), Order::Row));
+        if g.len() != x.len()
+        {
+            panic!("Gradient has {} components, expected {}", g.len(), x.len());
+        }
+        g.as_slice().to_vec()
+    }
+
+    /// Hessian at x as a row-major n * n vector
+    fn hess(&mut self, x: &[f64]) -> Vec<f64>
+    {
+        let n = x.len();
+        let hess = self.hess.unwrap();
+        self.nhev += 1;
+        dense::row_major(&hess(&Array::new(x.to_vec(), Order::Row)), n, n, "Hessian")
+    }
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64
+{
+    a.iter().zip(b.iter()).fold(0f64, |s, (x, y)| s + x * y)
+}
+
+fn norm(x: &[f64]) -> f64
+{
+    dot(x, x).sqrt()
+}
+
+fn norm_inf(x: &[f64]) -> f64
+{
+    x.iter().fold(0f64, |m, v| m.max(v.abs()))
+}
+
+/// x + a p
+fn axpy(x: &[f64], a: f64, p: &[f64]) -> Vec<f64>
+{
+    x.iter().zip(p.iter()).map(|(xi, pi)| xi + a * pi).collect()
+}
+
+
+//////////////
+// minimize //
+//////////////
+
+/// Minimizes f starting from x0. Failure to converge is reported in the
+/// result.
+pub fn minimize<F>(f: F, x0: &Array<f64>, opts: &MinimizeOptions) -> OptimizeResult
+    where F: Fn(&Array<f64>) -> f64
+{
+    let n = x0.len();
+    if n == 0
+    {
+        panic!("Cannot minimize over no variables.");
+    }
+    if !(opts.gtol >= 0f64)
+    {
+        panic!("Invalid value for gtol: {}", opts.gtol);
+    }
+    if !(opts.xtol >= 0f64)
+    {
+        panic!("Invalid value for xtol: {}", opts.xtol);
+    }
+    if let Some(ftol) = opts.ftol
+    {
+        if !(ftol >= 0f64)
+        {
+            panic!("Invalid value for ftol: {}", ftol);
+        }
+    }
+    let bounds = opts.bounds.as_ref();
+    if let Some(b) = bounds
+    {
+        b.check(n);
+        if opts.method != Method::LBFGSB && opts.method != Method::NelderMead
+        {
+            panic!("Method {:?} cannot handle bounds", opts.method);
+        }
+    }
+    if opts.method == Method::TrustNCG && opts.jac.is_none()
+    {
+        panic!("Method TrustNCG requires the gradient jac");
+    }
+    let mut obj = Objective{
+        fun: &f,
+        jac: opts.jac.as_ref().map(|j| &**j),
+        hess: opts.hess.as_ref().map(|h| &**h),
+        lower: match bounds
+        {
+            Some(b) => b.lower.as_slice().to_vec(),
+            None => vec![f64::NEG_INFINITY; n],
+        },
+        upper: match bounds
+        {
+            Some(b) => b.upper.as_slice().to_vec(),
+            None => vec![f64::INFINITY; n],
+        },
+        nfev: 0,
+        njev: 0,
+        nhev: 0,
+    };
+    let x0 = x0.as_slice().to_vec();
+    let (x, fun, jac, nit, status, message) = match opts.method
+    {
+        Method::NelderMead => nelder_mead(&mut obj, x0, bounds, opts),
+        Method::Powell => powell(&mut obj, x0, opts),
+        Method::CG => conjugate_gradient(&mut obj, x0, opts),
+        Method::BFGS => bfgs(&mut obj, x0, opts),
+        Method::LBFGSB => lbfgsb(&mut obj, x0, bounds, opts),
+        Method::TrustNCG => trust_ncg(&mut obj, x0, opts),
+    };
+    OptimizeResult{
+        x: Array::new(x, Order::Row),
+        fun: fun,
+        jac: jac.map(|g| Array::new(g, Order::Row)),
+        nit: nit,
+        nfev: obj.nfev,
+        njev: obj.njev,
+        nhev: obj.nhev,
+        status: status,
+        success: status == 0,
+        message: message,
+    }
+}
+
+
+/////////////////
+// Nelder-Mead //
+/////////////////
+
+/// Orders the vertices of the simplex by function value
+fn sort_simplex(sim: &mut Vec<Vec<f64>>, fsim: &mut Vec<f64>)
+{
+    let mut idx: Vec<usize> = (0..fsim.len()).collect();
+    idx.sort_by(|&a, &b| fsim[a].partial_cmp(&fsim[b]).unwrap_or(Ordering::Equal));
+    *sim = idx.iter().map(|&i| sim[i].clone()).collect();
+    *fsim = idx.iter().map(|&i| fsim[i]).collect();
+}
+
+fn nelder_mead(obj: &mut Objective, x0: Vec<f64>, bounds: Option<&Bounds>,
+               opts: &MinimizeOptions) -> Outcome
+{
+    // Reflection, expansion, contraction and shrink coefficients
+    let (rho, chi, psi, sigma) = (1f64, 2f64, 0.5f64, 0.5f64);
+    let n = x0.len();
+    let maxiter = opts.maxiter.unwrap_or(200 * n);
+    let maxfev = opts.maxfev.unwrap_or(200 * n);
+    let fatol = opts.ftol.unwrap_or(1e-4);
+    let clip = |x: &mut Vec<f64>|
+    {
+        if let Some(b) = bounds
+        {
+            b.clip(x);
+        }
+    };
+
+    // Initial simplex of steps of 5% along each axis, reflected back into
+    // the bounds where they cross an upper bound
+    let mut x0 = x0;
+    clip(&mut x0);
+    let mut sim = vec![x0.clone()];
+    for k in 0..n
+    {
+        let mut y = x0.clone();
+        y[k] = if y[k] != 0f64 { 1.05 * y[k] } else { 0.00025 };
+        if let Some(b) = bounds
+        {
+            if y[k] > b.upper[k]
+            {
+                y[k] = 2f64 * b.upper[k] - y[k];
+            }
+        }
+        clip(&mut y);
+        sim.push(y);
+    }
+    let mut fsim: Vec<f64> = sim.iter().map(|x| obj.fun(x)).collect();
+    sort_simplex(&mut sim, &mut fsim);
+
+    let mut iterations = 1;
+    let mut converged = false;
+    while obj.nfev < maxfev && iterations < maxiter
+    {
+        let xdiff = sim[1..].iter().fold(0f64, |m, v| m.max(
+            v.iter().zip(sim[0].iter()).fold(0f64, |m, (a, b)| m.max((a - b).abs()))));
+        let fdiff = fsim[1..].iter().fold(0f64, |m, v| m.max((v - fsim[0]).abs()));
+        if xdiff <= opts.xtol && fdiff <= fatol
+        {
+            converged = true;
+            break;
+        }
+
+        // Points along the line from the worst vertex through the centroid
+        // of the others
+        let xbar: Vec<f64> = (0..n).map(|i| (0..n).fold(0f64, |s, j| s + sim[j][i]) / n as f64)
+                                   .collect();
+        let worst = sim[n].clone();
+        let along = |a: f64|
+        {
+            let mut x: Vec<f64> = (0..n).map(|i| (1f64 + a) * xbar[i] - a * worst[i]).collect();
+            clip(&mut x);
+            x
+        };
+
+        let xr = along(rho);
+        let fxr = obj.fun(&xr);
+        let mut shrink = false;
+        if fxr < fsim[0]
+        {
+            let xe = along(rho * chi);
+            let fxe = obj.fun(&xe);
+            if fxe < fxr
+            {
+                sim[n] = xe;
+                fsim[n] = fxe;
+            } else {
+                sim[n] = xr;
+                fsim[n] = fxr;
+            }
+        } else if fxr < fsim[n - 1] {
+            sim[n] = xr;
+            fsim[n] = fxr;
+        } else if fxr < fsim[n] {
+            // Contract outside
+            let xc = along(psi * rho);
+            let fxc = obj.fun(&xc);
+            if fxc <= fxr
+            {
+                sim[n] = xc;
+                fsim[n] = fxc;
+            } else {
+                shrink = true;
+            }
+        } else {
+            // Contract inside
+            let xcc = along(-psi);
+            let fxcc = obj.fun(&xcc);
+            if fxcc < fsim[n]
+            {
+                sim[n] = xcc;
+                fsim[n] = fxcc;
+            } else {
+                shrink = true;
+            }
+        }
+        if shrink
+        {
+            for j in 1..n + 1
+            {
+                let mut x: Vec<f64> = (0..n).map(|i| sim[0][i] + sigma * (sim[j][i] - sim[0][i]))
+                                            .collect();
+                clip(&mut x);
+                fsim[j] = obj.fun(&x);
+                sim[j] = x;
+            }
+        }
+        iterations += 1;
+        sort_simplex(&mut sim, &mut fsim);
+    }
+
+    let (status, message) = if converged
+    {
+        (0, SUCCESS)
+    } else if obj.nfev >= maxfev {
+        (1, MAXFEV)
+    } else {
+        (1, MAXITER)
+    };
+    (sim[0].clone(), fsim[0], None, iterations, status, String::from(message))
+}
+
+
+////////////
+// Powell //
+////////////
+
+/// Brackets a minimum of phi starting from the points a and b, returning
+/// (a, b, c) and the function values there with phi(b) below phi(a) and
+/// phi(c), as in Numerical Recipes' mnbrak
+fn bracket<F>(phi: &mut F, a: f64, b: f64) -> ([f64; 3], [f64; 3])
+    where F: FnMut(f64) -> f64
+{
+    const GOLD: f64 = 1.618034;
+    const GROW_LIMIT: f64 = 110f64;
+    const TINY: f64 = 1e-21;
+    let (mut xa, mut xb) = (a, b);
+    let (mut fa, mut fb) = (phi(xa), phi(xb));
+    if fa < fb
+    {
+        ::std::mem::swap(&mut xa, &mut xb);
+        ::std::mem::swap(&mut fa, &mut fb);
+    }
+    let mut xc = xb + GOLD * (xb - xa);
+    let mut fc = phi(xc);
+    let mut iter = 0;
+    while fc < fb && iter < 1000
+    {
+        iter += 1;
+        // Parabolic extrapolation through the three points
+        let tmp1 = (xb - xa) * (fb - fc);
+        let tmp2 = (xb - xc) * (fb - fa);
+        let val = tmp2 - tmp1;
+        let denom = if val.abs() < TINY { 2f64 * TINY } else { 2f64 * val };
+        let mut w = xb - ((xb - xc) * tmp2 - (xb - xa) * tmp1) / denom;
+        let wlim = xb + GROW_LIMIT * (xc - xb);
+        let mut fw;
+        if (w - xc) * (xb - w) > 0f64
+        {
+            fw = phi(w);
+            if fw < fc
+            {
+                return ([xb, w, xc], [fb, fw, fc]);
+            } else if fw > fb {
+                return ([xa, xb, w], [fa, fb, fw]);
+            }
+            w = xc + GOLD * (xc - xb);
+            fw = phi(w);
+        } else if (w - wlim) * (wlim - xc) >= 0f64 {
+            w = wlim;
+            fw = phi(w);
+        } else if (w - wlim) * (xc - w) > 0f64 {
+            fw = phi(w);
+            if fw < fc
+            {
+                xb = xc;
+                xc = w;
+                w = xc + GOLD * (xc - xb);
+                fb = fc;
+                fc = fw;
+                fw = phi(w);
+            }
+        } else {
+            w = xc + GOLD * (xc - xb);
+            fw = phi(w);
+        }
+        xa = xb;
+        xb = xc;
+        xc = w;
+        fa = fb;
+        fb = fc;
+        fc = fw;
+    }
+    ([xa, xb, xc], [fa, fb, fc])
+}
+
+/// Minimum of phi to a relative tolerance tol by Brent's method, returning
+/// the minimizer and the value there
+fn brent_min<F>(phi: &mut F, tol: f64) -> (f64, f64)
+    where F: FnMut(f64) -> f64
+{
+    const CGOLD: f64 = 0.3819660;
+    const MINTOL: f64 = 1e-11;
+    let (xs, fs) = bracket(phi, 0f64, 1f64);
+    let (mut a, mut b) = if xs[0] < xs[2] { (xs[0], xs[2]) } else { (xs[2], xs[0]) };
+    let (mut x, mut w, mut v) = (xs[1], xs[1], xs[1]);
+    let (mut fx, mut fw, mut fv) = (fs[1], fs[1], fs[1]);
+    let mut deltax = 0f64;
+    let mut rat = 0f64;
+    for _ in 0..500
+    {
+        let tol1 = tol * x.abs() + MINTOL;
+        let tol2 = 2f64 * tol1;
+        let xmid = 0.5 * (a + b);
+        if (x - xmid).abs() < tol2 - 0.5 * (b - a)
+        {
+            break;
+        }
+        let golden = if deltax.abs() <= tol1
+        {
+            true
+        } else {
+            // Parabolic step through x, w and v
+            let tmp1 = (x - w) * (fx - fv);
+            let mut tmp2 = (x - v) * (fx - fw);
+            let mut p = (x - v) * tmp2 - (x - w) * tmp1;
+            tmp2 = 2f64 * (tmp2 - tmp1);
+            if tmp2 > 0f64
+            {
+                p = -p;
+            }
+            tmp2 = tmp2.abs();
+            let dx_temp = deltax;
+            deltax = rat;
+            if p > tmp2 * (a - x) && p < tmp2 * (b - x) && p.abs() < (0.5 * tmp2 * dx_temp).abs()
+            {
+                rat = p / tmp2;
+                let u = x + rat;
+                if u - a < tol2 || b - u < tol2
+                {
+                    rat = if xmid - x >= 0f64 { tol1 } else { -tol1 };
+                }
+                false
+            } else {
+                true
+            }
+        };
+        if golden
+        {
+            deltax = if x >= xmid { a - x } else { b - x };
+            rat = CGOLD * deltax;
+        }
+        let u = if rat.abs() < tol1
+        {
+            if rat >= 0f64 { x + tol1 } else { x - tol1 }
+        } else {
+            x + rat
+        };
+        let fu = phi(u);
+        if fu > fx
+        {
+            if u < x
+            {
+                a = u;
+            } else {
+                b = u;
+            }
+            if fu <= fw || w == x
+            {
+                v = w;
+                w = u;
+                fv = fw;
+                fw = fu;
+            } else if fu <= fv || v == x || v == w {
+                v = u;
+                fv = fu;
+            }
+        } else {
+            if u >= x
+            {
+                a = x;
+            } else {
+                b = x;
+            }
+            v = w;
+            w = x;
+            x = u;
+            fv = fw;
+            fw = fx;
+            fx = fu;
+        }
+    }
+    (x, fx)
+}
+
+/// Minimizes f along the direction d from x, returning the new value of f,
+/// the new point and the step taken
+fn powell_line_search(obj: &mut Objective, x: &[f64], d: &[f64], fx: f64, tol: f64)
+    -> (f64, Vec<f64>, Vec<f64>)
+{
+    if d.iter().all(|&v| v == 0f64)
+    {
+        return (fx, x.to_vec(), d.to_vec());
+    }
+    let (alpha, fmin) = brent_min(&mut |a: f64| obj.fun(&axpy(x, a, d)), tol);
+    (fmin, axpy(x, alpha, d), d.iter().map(|v| alpha * v).collect())
+}
+
+fn powell(obj: &mut Objective, x0: Vec<f64>, opts: &MinimizeOptions) -> Outcome
+{
+    let n = x0.len();
+    let maxiter = opts.maxiter.unwrap_or(1000 * n);
+    let maxfev = opts.maxfev.unwrap_or(1000 * n);
+    let ftol = opts.ftol.unwrap_or(1e-4);
+    let tol = opts.xtol * 100f64;
+
+    let mut direc: Vec<Vec<f64>> = (0..n).map(|i|
+    {
+        let mut e = vec![0f64; n];
+        e[i] = 1f64;
+        e
+    }).collect();
+    let mut x = x0;
+    let mut fval = obj.fun(&x);
+    let mut x1 = x.clone();
+    let mut iter = 0;
+    let mut converged = false;
+    loop
+    {
+        // Line searches along each direction, noting the largest decrease
+        let fx = fval;
+        let mut bigind = 0;
+        let mut delta = 0f64;
+        for i in 0..n
+        {
+            let fx2 = fval;
+            let (f_new, x_new, _) = powell_line_search(obj, &x, &direc[i], fval, tol);
+            fval = f_new;
+            x = x_new;
+            if fx2 - fval > delta
+            {
+                delta = fx2 - fval;
+                bigind = i;
+            }
+        }
+        iter += 1;
+        if 2f64 * (fx - fval) <= ftol * (fx.abs() + fval.abs()) + 1e-20
+        {
+            converged = true;
+            break;
+        }
+        if obj.nfev >= maxfev || iter >= maxiter
+        {
+            break;
+        }
+
+        // Replace the direction of largest decrease by the overall one if
+        // the extrapolated point shows it is worthwhile
+        let d: Vec<f64> = x.iter().zip(x1.iter()).map(|(a, b)| a - b).collect();
+        let x2: Vec<f64> = x.iter().zip(x1.iter()).map(|(a, b)| 2f64 * a - b).collect();
+        x1 = x.clone();
+        let fx2 = obj.fun(&x2);
+        if fx > fx2
+        {
+            let mut t = 2f64 * (fx + fx2 - 2f64 * fval);
+            let temp = fx - fval - delta;
+            t *= temp * temp;
+            let temp = fx - fx2;
+            t -= delta * temp * temp;
+            if t < 0f64
+            {
+                let (f_new, x_new, d) = powell_line_search(obj, &x, &d, fval, tol);
+                fval = f_new;
+                x = x_new;
+                if d.iter().any(|&v| v != 0f64)
+                {
+                    direc[bigind] = direc[n - 1].clone();
+                    direc[n - 1] = d;
+                }
+            }
+        }
+    }
+
+    let (status, message) = if converged
+    {
+        (0, SUCCESS)
+    } else if obj.nfev >= maxfev {
+        (1, MAXFEV)
+    } else {
+        (1, MAXITER)
+    };
+    (x, fval, None, iter, status, String::from(message))
+}
+
+
+/////////////////
+// Line search //
+/////////////////
+
+/// Minimizer of the cubic interpolating phi and phi' at a and b, or the
+/// midpoint where that is not safely inside the interval
+fn cubic_min(a: f64, fa: f64, da: f64, b: f64, fb: f64, db: f64) -> f64
+{
+    let (lo, hi) = if a < b { (a, b) } else { (b, a) };
+    let width = hi - lo;
+    let d1 = da + db - 3f64 * (fa - fb) / (a - b);
+    let disc = d1 * d1 - da * db;
+    if disc >= 0f64
+    {
+        let d2 = (b - a).signum() * disc.sqrt();
+        let x = b - (b - a) * (db + d2 - d1) / (db - da + 2f64 * d2);
+        if x.is_finite() && x > lo + 0.1 * width && x < hi - 0.1 * width
+        {
+            return x;
+        }
+    }
+    0.5 * (a + b)
+}
+
+/// Initial trial step for a line search, from the decrease in f over the
+/// previous iteration
+fn initial_step(f: f64, f_prev: f64, slope: f64) -> f64
+{
+    let alpha = 1.01 * 2f64 * (f - f_prev) / slope;
+    if alpha > 0f64 && alpha.is_finite() { alpha.min(1f64) } else { 1f64 }
+}
+
+/// Step length along the descent direction p from x satisfying the strong
+/// Wolfe conditions with parameters c1 and c2, given f and its gradient g at
+/// x, a trial step and the largest step allowed (Nocedal & Wright,
+/// algorithms 3.5 and 3.6). Returns the step and f and its gradient there,
+/// or None if no step reducing f sufficiently was found.
+fn wolfe_search(obj: &mut Objective, x: &[f64], p: &[f64], f0: f64, g0: &[f64], alpha1: f64,
+                amax: f64, c1: f64, c2: f64) -> Option<(f64, f64, Vec<f64>)>
+{
+    let slope0 = dot(g0, p);
+    if !(slope0 < 0f64)
+    {
+        return None;
+    }
+    let mut eval = |a: f64|
+    {
+        let xa = axpy(x, a, p);
+        let fa = obj.fun(&xa);
+        let ga = obj.grad(&xa, fa);
+        let da = dot(&ga, p);
+        (fa, ga, da)
+    };
+    let armijo = |a: f64, fa: f64| fa > f0 + c1 * a * slope0 || !fa.is_finite();
+
+    // Expand the step until it brackets an acceptable one
+    let (mut a_lo, mut f_lo, mut d_lo, mut g_lo) = (0f64, f0, slope0, g0.to_vec());
+    let (mut a_hi, mut f_hi, mut d_hi);
+    let mut a = alpha1.min(amax);
+    let mut i = 0;
+    loop
+    {
+        let (fa, ga, da) = eval(a);
+        if armijo(a, fa) || (i > 0 && fa >= f_lo)
+        {
+            a_hi = a;
+            f_hi = fa;
+            d_hi = da;
+            break;
+        }
+        if da.abs() <= -c2 * slope0
+        {
+            return Some((a, fa, ga));
+        }
+        if da >= 0f64
+        {
+            a_hi = a_lo;
+            f_hi = f_lo;
+            d_hi = d_lo;
+            a_lo = a;
+            f_lo = fa;
+            d_lo = da;
+            g_lo = ga;
+            break;
+        }
+        if a >= amax || i == 20
+        {
+            // Still descending, but f has decreased sufficiently
+            return Some((a, fa, ga));
+        }
+        a_lo = a;
+        f_lo = fa;
+        d_lo = da;
+        g_lo = ga;
+        a = (2f64 * a).min(amax);
+        i += 1;
+    }
+
+    // Shrink the bracket [a_lo, a_hi], where a_lo is the best step so far
+    for _ in 0..30
+    {
+        if (a_hi - a_lo).abs() <= f64::EPSILON * a_lo.abs().max(a_hi.abs())
+        {
+            break;
+        }
+        let a = cubic_min(a_lo, f_lo, d_lo, a_hi, f_hi, d_hi);
+        let (fa, ga, da) = eval(a);
+        if armijo(a, fa) || fa >= f_lo
+        {
+            a_hi = a;
+            f_hi = fa;
+            d_hi = da;
+        } else {
+            if da.abs() <= -c2 * slope0
+            {
+                return Some((a, fa, ga));
+            }
+            if da * (a_hi - a_lo) >= 0f64
+            {
+                a_hi = a_lo;
+                f_hi = f_lo;
+                d_hi = d_lo;
+            }
+            a_lo = a;
+            f_lo = fa;
+            d_lo = da;
+            g_lo = ga;
+        }
+    }
+    if a_lo > 0f64 { Some((a_lo, f_lo, g_lo)) } else { None }
+}
+
+
+/////////////////////////
+// Conjugate gradients //
+/////////////////////////
+
+fn conjugate_gradient(obj: &mut Objective, x0: Vec<f64>, opts: &MinimizeOptions) -> Outcome
+{
+    let maxiter = opts.maxiter.unwrap_or(200 * x0.len());
+    let mut x = x0;
+    let mut f = obj.fun(&x);
+    let mut g = obj.grad(&x, f);
+    let mut f_prev = f + norm(&g) / 2f64;
+    let mut p: Vec<f64> = g.iter().map(|v| -v).collect();
+    let mut k = 0;
+    let mut status = 0;
+    while norm_inf(&g) > opts.gtol
+    {
+        if k >= maxiter
+        {
+            status = 1;
+            break;
+        }
+        let alpha1 = initial_step(f, f_prev, dot(&g, &p));
+        let (alpha, f_new, g_new) = match wolfe_search(obj, &x, &p, f, &g, alpha1, 1e100,
+                                                       1e-4, 0.4)
+        {
+            Some(step) => step,
+            None =>
+            {
+                status = 2;
+                break;
+            }
+        };
+        x = axpy(&x, alpha, &p);
+        f_prev = f;
+        f = f_new;
+        // Polak-Ribiere, restarting with steepest descent when negative
+        let gg = dot(&g, &g);
+        let beta = (dot(&g_new, &g_new) - dot(&g_new, &g)) / gg;
+        let beta = if beta > 0f64 { beta } else { 0f64 };
+        p = g_new.iter().zip(p.iter()).map(|(gi, pi)| -gi + beta * pi).collect();
+        if dot(&p, &g_new) >= 0f64
+        {
+            p = g_new.iter().map(|v| -v).collect();
+        }
+        g = g_new;
+        k += 1;
+    }
+    let message = match status
+    {
+        0 => SUCCESS,
+        1 => MAXITER,
+        _ => PRECISION_LOSS,
+    };
+    (x, f, Some(g), k, status, String::from(message))
+}
+
+
+//////////
+// BFGS //
+//////////
+
+fn bfgs(obj: &mut Objective, x0: Vec<f64>, opts: &MinimizeOptions) -> Outcome
+{
+    let n = x0.len();
+    let maxiter = opts.maxiter.unwrap_or(200 * n);
+    let mut x = x0;
+    let mut f = obj.fun(&x);
+    let mut g = obj.grad(&x, f);
+    let mut f_prev = f + norm(&g) / 2f64;
+    // Inverse Hessian approximation, row-major
+    let mut h = vec![0f64; n * n];
+    for i in 0..n
+    {
+        h[i * n + i] = 1f64;
+    }
+    let mut k = 0;
+    let mut status = 0;
+    while norm_inf(&g) > opts.gtol
+    {
+        if k >= maxiter
+        {
+            status = 1;
+            break;
+        }
+        let p: Vec<f64> = dense::mat_vec(&h, &g).iter().map(|v| -v).collect();
+        let alpha1 = initial_step(f, f_prev, dot(&g, &p));
+        let (alpha, f_new, g_new) = match wolfe_search(obj, &x, &p, f, &g, alpha1, 1e100,
+                                                       1e-4, 0.9)
+        {
+            Some(step) => step,
+            None =>
+            {
+                status = 2;
+                break;
+            }
+        };
+        let s: Vec<f64> = p.iter().map(|v| alpha * v).collect();
+        let y: Vec<f64> = g_new.iter().zip(g.iter()).map(|(a, b)| a - b).collect();
+        x = axpy(&x, 1f64, &s);
+        f_prev = f;
+        f = f_new;
+        g = g_new;
+        k += 1;
+
+        // H <- (I - rho s y') H (I - rho y s') + rho s s'
+        let ys = dot(&y, &s);
+        if ys > 0f64
+        {
+            let rho = 1f64 / ys;
+            let hy = dense::mat_vec(&h, &y);
+            let yhy = dot(&y, &hy);
+            for r in 0..n
+            {
+                for c in 0..n
+                {
+                    h[r * n + c] += rho * ((1f64 + rho * yhy) * s[r] * s[c]
+                                           - hy[r] * s[c] - s[r] * hy[c]);
+                }
+            }
+        }
+    }
+    let message = match status
+    {
+        0 => SUCCESS,
+        1 => MAXITER,
+        _ => PRECISION_LOSS,
+    };
+    (x, f, Some(g), k, status, String::from(message))
+}
+
+
+//////////////
+// L-BFGS-B //
+//////////////
+
+/// Number of correction pairs kept by L-BFGS-B
+const LBFGSB_MEMORY: usize = 10;
+
+/// Compact representation B = theta I - W M W' of the limited memory BFGS
+/// matrix, where W = [Y, theta S] holds the correction pairs
+struct CompactBfgs
+{
+    theta: f64,
+    /// Rows of W, each of length 2 k for k correction pairs
+    w: Vec<Vec<f64>>,
+    /// The 2 k * 2 k middle matrix M, row-major
+    m: Vec<f64>,
+}
+
+impl CompactBfgs
+{
+    fn new(s: &[Vec<f64>], y: &[Vec<f64>], theta: f64, n: usize) -> Option<CompactBfgs>
+    {
+        let k = s.len();
+        let w = (0..n).map(|r|
+        {
+            y.iter().map(|v| v[r]).chain(s.iter().map(|v| theta * v[r])).collect()
+        }).collect();
+        if k == 0
+        {
+            return Some(CompactBfgs{theta: theta, w: w, m: vec![]});
+        }
+        // M is the inverse of [[-D, L'], [L, theta S'S]], where D is the
+        // diagonal and L the strictly lower triangle of S'Y
+        let k2 = 2 * k;
+        let mut kmat = vec![0f64; k2 * k2];
+        for i in 0..k
+        {
+            for j in 0..k
+            {
+                let sy = dot(&s[i], &y[j]);
+                if i == j
+                {
+                    kmat[i * k2 + i] = -sy;
+                } else if i > j {
+                    kmat[(k + i) * k2 + j] = sy;
+                    kmat[j * k2 + k + i] = sy;
+                }
+                kmat[(k + i) * k2 + k + j] = theta * dot(&s[i], &s[j]);
+            }
+        }
+        dense::inverse(&kmat, k2).map(|m| CompactBfgs{theta: theta, w: w, m: m})
+    }
+
+    /// M v
+    fn m_vec(&self, v: &[f64]) -> Vec<f64>
+    {
+        if v.is_empty() { vec![] } else { dense::mat_vec(&self.m, v) }
+    }
+}
+
+/// Largest component of the projected gradient
+fn projected_gradient_norm(x: &[f64], g: &[f64], lower: &[f64], upper: &[f64]) -> f64
+{
+    (0..x.len()).fold(0f64, |m, i|
+    {
+        m.max(((x[i] - g[i]).max(lower[i]).min(upper[i]) - x[i]).abs())
+    })
+}
+
+/// Generalized Cauchy point, the first local minimizer of the quadratic
+/// model along the projected steepest descent path. Returns the point and
+/// c = W'(xc - x).
+fn cauchy_point(x: &[f64], g: &[f64], lower: &[f64], upper: &[f64], bm: &CompactBfgs)
+    -> (Vec<f64>, Vec<f64>)
+{
+    let n = x.len();
+    let theta = bm.theta;
+    let mut xc = x.to_vec();
+    let mut d = vec![0f64; n];
+    let mut t = vec![f64::INFINITY; n];
+    for i in 0..n
+    {
+        if g[i] < 0f64
+        {
+            t[i] = (x[i] - upper[i]) / g[i];
+        } else if g[i] > 0f64 {
+            t[i] = (x[i] - lower[i]) / g[i];
+        }
+        if t[i] > 0f64
+        {
+            d[i] = -g[i];
+        }
+    }
+    let mut breaks: Vec<usize> = (0..n).filter(|&i| t[i] > 0f64 && t[i].is_finite()).collect();
+    breaks.sort_by(|&a, &b| t[a].partial_cmp(&t[b]).unwrap_or(Ordering::Equal));
+
+    let k2 = bm.w.first().map_or(0, |w| w.len());
+    let mut p = vec![0f64; k2];
+    for i in 0..n
+    {
+        for j in 0..k2
+        {
+            p[j] += bm.w[i][j] * d[i];
+        }
+    }
+    let mut c = vec![0f64; k2];
+    let mut fp = -dot(&d, &d);
+    let fpp0 = -theta * fp;
+    let mut fpp = (fpp0 - dot(&p, &bm.m_vec(&p))).max(f64::EPSILON * fpp0);
+    let mut dt_min = if fp == 0f64 { 0f64 } else { -fp / fpp };
+    let mut t_old = 0f64;
+    for &b in breaks.iter()
+    {
+        let dt = t[b] - t_old;
+        if dt_min < dt
+        {
+            break;
+        }
+        // Fix variable b at its bound and update the slope and curvature
+        xc[b] = if d[b] > 0f64 { upper[b] } else { lower[b] };
+        let zb = xc[b] - x[b];
+        let gb = g[b];
+        let wb = &bm.w[b];
+        for j in 0..k2
+        {
+            c[j] += dt * p[j];
+        }
+        let mwb = bm.m_vec(wb);
+        fp += dt * fpp + gb * gb + theta * gb * zb - gb * dot(&mwb, &c);
+        fpp -= theta * gb * gb + 2f64 * gb * dot(&mwb, &p) + gb * gb * dot(&mwb, wb);
+        fpp = fpp.max(f64::EPSILON * fpp0);
+        for j in 0..k2
+        {
+            p[j] += gb * wb[j];
+        }
+        d[b] = 0f64;
+        dt_min = -fp / fpp;
+        t_old = t[b];
+    }
+    let dt_min = dt_min.max(0f64);
+    t_old += dt_min;
+    for i in 0..n
+    {
+        if d[i] != 0f64
+        {
+            xc[i] = x[i] + t_old * d[i];
+        }
+    }
+    for j in 0..k2
+    {
+        c[j] += dt_min * p[j];
+    }
+    (xc, c)
+}
+
+/// Minimizes the quadratic model over the variables which are free at the
+/// Cauchy point xc, then backtracks into the bounds (direct primal method)
+fn subspace_min(x: &[f64], g: &[f64], xc: &[f64], c: &[f64], lower: &[f64], upper: &[f64],
+                bm: &CompactBfgs) -> Vec<f64>
+{
+    let theta = bm.theta;
+    let free: Vec<usize> = (0..x.len()).filter(|&i| xc[i] > lower[i] && xc[i] < upper[i])
+                                       .collect();
+    if free.is_empty()
+    {
+        return xc.to_vec();
+    }
+    let k2 = c.len();
+    // Reduced gradient of the model at xc
+    let mc = bm.m_vec(c);
+    let r: Vec<f64> = free.iter().map(|&i|
+    {
+        g[i] + theta * (xc[i] - x[i]) - dot(&bm.w[i], &mc)
+    }).collect();
+    // Newton step -B^-1 r on the free variables by Sherman-Morrison-Woodbury
+    let mut du: Vec<f64> = r.iter().map(|v| -v / theta).collect();
+    if k2 > 0
+    {
+        let mut wr = vec![0f64; k2];
+        let mut wtw = vec![0f64; k2 * k2];
+        for (fi, &i) in free.iter().enumerate()
+        {
+            let wi = &bm.w[i];
+            for a in 0..k2
+            {
+                wr[a] += wi[a] * r[fi];
+                for b in 0..k2
+                {
+                    wtw[a * k2 + b] += wi[a] * wi[b];
+                }
+            }
+        }
+        let v = bm.m_vec(&wr);
+        let mut nmat = vec![0f64; k2 * k2];
+        for a in 0..k2
+        {
+            for b in 0..k2
+            {
+                let mw = (0..k2).fold(0f64, |s, l| s + bm.m[a * k2 + l] * wtw[l * k2 + b]);
+                nmat[a * k2 + b] = if a == b { 1f64 } else { 0f64 } - mw / theta;
+            }
+        }
+        let v = match dense::inverse(&nmat, k2)
+        {
+            Some(ninv) => dense::mat_vec(&ninv, &v),
+            None => return xc.to_vec(),
+        };
+        for (fi, &i) in free.iter().enumerate()
+        {
+            du[fi] -= dot(&bm.w[i], &v) / (theta * theta);
+        }
+    }
+    let mut alpha = 1f64;
+    for (fi, &i) in free.iter().enumerate()
+    {
+        if du[fi] > 0f64
+        {
+            alpha = alpha.min((upper[i] - xc[i]) / du[fi]);
+        } else if du[fi] < 0f64 {
+            alpha = alpha.min((lower[i] - xc[i]) / du[fi]);
+        }
+    }
+    let mut xbar = xc.to_vec();
+    for (fi, &i) in free.iter().enumerate()
+    {
+        xbar[i] += alpha * du[fi];
+    }
+    xbar
+}
+
+fn lbfgsb(obj: &mut Objective, x0: Vec<f64>, bounds: Option<&Bounds>,
+          opts: &MinimizeOptions) -> Outcome
+{
+    let n = x0.len();
+    let maxiter = opts.maxiter.unwrap_or(15000);
+    let maxfev = opts.maxfev.unwrap_or(15000);
+    let ftol = opts.ftol.unwrap_or(1e7 * f64::EPSILON);
+    let (lower, upper) = match bounds
+    {
+        Some(b) => (b.lower.as_slice().to_vec(), b.upper.as_slice().to_vec()),
+        None => (vec![f64::NEG_INFINITY; n], vec![f64::INFINITY; n]),
+    };
+    let bounded = lower.iter().chain(upper.iter()).any(|v| v.is_finite());
+
+    let mut x = x0;
+    if let Some(b) = bounds
+    {
+        b.clip(&mut x);
+    }
+    let mut f = obj.fun(&x);
+    let mut g = obj.grad(&x, f);
+    let mut s_hist: Vec<Vec<f64>> = vec![];
+    let mut y_hist: Vec<Vec<f64>> = vec![];
+    let mut theta = 1f64;
+    let mut k = 0;
+    let (status, message);
+    loop
+    {
+        if projected_gradient_norm(&x, &g, &lower, &upper) <= opts.gtol
+        {
+            status = 0;
+            message = "CONVERGENCE: NORM_OF_PROJECTED_GRADIENT_<=_PGTOL";
+            break;
+        }
+        if k >= maxiter
+        {
+            status = 1;
+            message = "STOP: TOTAL NO. of ITERATIONS REACHED LIMIT";
+            break;
+        }
+        if obj.nfev >= maxfev
+        {
+            status = 1;
+            message = "STOP: TOTAL NO. of f AND g EVALUATIONS EXCEEDS LIMIT";
+            break;
+        }
+
+        // Search direction towards the minimizer of the quadratic model in
+        // the box; start again from B = I if it is not a descent direction
+        let restart = s_hist.is_empty();
+        let step = CompactBfgs::new(&s_hist, &y_hist, theta, n).and_then(|bm|
+        {
+            let (xc, c) = cauchy_point(&x, &g, &lower, &upper, &bm);
+            let d: Vec<f64> = subspace_min(&x, &g, &xc, &c, &lower, &upper, &bm)
+                .iter().zip(x.iter()).map(|(a, b)| a - b).collect();
+            if !(dot(&d, &g) < 0f64)
+            {
+                return None;
+            }
+            let amax = (0..n).fold(1e10f64, |m, i|
+            {
+                if d[i] > 0f64
+                {
+                    m.min((upper[i] - x[i]) / d[i])
+                } else if d[i] < 0f64 {
+                    m.min((lower[i] - x[i]) / d[i])
+                } else {
+                    m
+                }
+            });
+            let alpha1 = if k == 0 && !bounded { (1f64 / norm(&d)).min(amax) } else { 1f64 };
+            wolfe_search(obj, &x, &d, f, &g, alpha1, amax, 1e-3, 0.9)
+                .map(|(alpha, f_new, g_new)| (axpy(&x, alpha, &d), f_new, g_new))
+        });
+        let (mut x_new, f_new, g_new) = match step
+        {
+            Some(step) => step,
+            None =>
+            {
+                if restart
+                {
+                    status = 2;
+                    message = "ABNORMAL_TERMINATION_IN_LNSRCH";
+                    break;
+                }
+                s_hist.clear();
+                y_hist.clear();
+                theta = 1f64;
+                continue;
+            }
+        };
+        if let Some(b) = bounds
+        {
+            b.clip(&mut x_new);
+        }
+        let s: Vec<f64> = x_new.iter().zip(x.iter()).map(|(a, b)| a - b).collect();
+        let y: Vec<f64> = g_new.iter().zip(g.iter()).map(|(a, b)| a - b).collect();
+        let f_old = f;
+        x = x_new;
+        f = f_new;
+        g = g_new;
+        k += 1;
+
+        // Keep the correction pair only if it preserves positive definiteness
+        let (sy, yy) = (dot(&s, &y), dot(&y, &y));
+        if sy > f64::EPSILON * yy
+        {
+            s_hist.push(s);
+            y_hist.push(y);
+            if s_hist.len() > LBFGSB_MEMORY
+            {
+                s_hist.remove(0);
+                y_hist.remove(0);
+            }
+            theta = yy / sy;
+        }
+        if (f_old - f) / f_old.abs().max(f.abs()).max(1f64) <= ftol
+        {
+            status = 0;
+            message = "CONVERGENCE: REL_REDUCTION_OF_F_<=_FACTR*EPSMCH";
+            break;
+        }
+    }
+    (x, f, Some(g), k, status, String::from(message))
+}
+
+
+////////////////////////////
+// Newton-CG trust region //
+////////////////////////////
+
+/// Hessian times v at x, from the Hessian h if there is one and otherwise
+/// from a difference of gradients, given the gradient g at x
+fn hess_vec(obj: &mut Objective, x: &[f64], g: &[f64], h: Option<&Vec<f64>>, v: &[f64])
+    -> Vec<f64>
+{
+    if let Some(h) = h
+    {
+        return dense::mat_vec(h, v);
+    }
+    let vnorm = norm(v);
+    if vnorm == 0f64
+    {
+        return vec![0f64; v.len()];
+    }
+    let eps = f64::EPSILON.sqrt() * (1f64 + norm(x)) / vnorm;
+    let gh = obj.user_grad(&axpy(x, eps, v));
+    gh.iter().zip(g.iter()).map(|(a, b)| (a - b) / eps).collect()
+}
+
+/// Values of t at which |z + t d| = delta, in increasing order
+fn boundary_intersections(z: &[f64], d: &[f64], delta: f64) -> (f64, f64)
+{
+    let a = dot(d, d);
+    let b = 2f64 * dot(z, d);
+    let c = dot(z, z) - delta * delta;
+    let sqrt_disc = (b * b - 4f64 * a * c).max(0f64).sqrt();
+    let aux = b + if b >= 0f64 { sqrt_disc } else { -sqrt_disc };
+    let (ta, tb) = (-aux / (2f64 * a), -2f64 * c / aux);
+    if ta < tb { (ta, tb) } else { (tb, ta) }
+}
+
+/// Approximate minimizer of the quadratic model within the trust region
+/// |p| <= delta by Steihaug's conjugate gradients, and whether it lies on
+/// the boundary
+fn steihaug(obj: &mut Objective, x: &[f64], f: f64, g: &[f64], h: Option<&Vec<f64>>,
+            delta: f64) -> (Vec<f64>, bool)
+{
+    let n = x.len();
+    let gnorm = norm(g);
+    let tolerance = gnorm.sqrt().min(0.5) * gnorm;
+    let mut z = vec![0f64; n];
+    let mut r = g.to_vec();
+    let mut d: Vec<f64> = g.iter().map(|v| -v).collect();
+    loop
+    {
+        let bd = hess_vec(obj, x, g, h, &d);
+        let dbd = dot(&d, &bd);
+        if dbd <= 0f64
+        {
+            // Negative curvature, go to whichever end on the boundary is
+            // lower in the model
+            let (ta, tb) = boundary_intersections(&z, &d, delta);
+            let pa = axpy(&z, ta, &d);
+            let pb = axpy(&z, tb, &d);
+            let ma = model(obj, x, f, g, h, &pa);
+            let mb = model(obj, x, f, g, h, &pb);
+            return (if ma < mb { pa } else { pb }, true);
+        }
+        let r_sq = dot(&r, &r);
+        let alpha = r_sq / dbd;
+        let z_next = axpy(&z, alpha, &d);
+        if norm(&z_next) >= delta
+        {
+            let (_, tb) = boundary_intersections(&z, &d, delta);
+            return (axpy(&z, tb, &d), true);
+        }
+        let r_next = axpy(&r, alpha, &bd);
+        let r_next_sq = dot(&r_next, &r_next);
+        if r_next_sq.sqrt() < tolerance
+        {
+            return (z_next, false);
+        }
+        let beta = r_next_sq / r_sq;
+        d = r_next.iter().zip(d.iter()).map(|(ri, di)| -ri + beta * di).collect();
+        z = z_next;
+        r = r_next;
+    }
+}
+
+/// Quadratic model f + g'p + p'Hp / 2
+fn model(obj: &mut Objective, x: &[f64], f: f64, g: &[f64], h: Option<&Vec<f64>>, p: &[f64])
+    -> f64
+{
+    let hp = hess_vec(obj, x, g, h, p);
+    f + dot(g, p) + 0.5 * dot(p, &hp)
+}
+
+fn trust_ncg(obj: &mut Objective, x0: Vec<f64>, opts: &MinimizeOptions) -> Outcome
+{
+    const MAX_RADIUS: f64 = 1000f64;
+    const ETA: f64 = 0.15;
+    let maxiter = opts.maxiter.unwrap_or(200 * x0.len());
+    let mut x = x0;
+    let mut f = obj.fun(&x);
+    let mut g = obj.grad(&x, f);
+    let mut h = if obj.hess.is_some() { Some(obj.hess(&x)) } else { None };
+    let mut radius = 1f64;
+    let mut k = 0;
+    let mut status = 0;
+    while norm(&g) >= opts.gtol
+    {
+        let (p, hits_boundary) = steihaug(obj, &x, f, &g, h.as_ref(), radius);
+        let predicted = f - model(obj, &x, f, &g, h.as_ref(), &p);
+        if predicted <= 0f64
+        {
+            status = 2;
+            break;
+        }
+        let x_new = axpy(&x, 1f64, &p);
+        let f_new = obj.fun(&x_new);
+        let rho = (f - f_new) / predicted;
+        if !(rho >= 0.25)
+        {
+            radius *= 0.25;
+        } else if rho > 0.75 && hits_boundary {
+            radius = (2f64 * radius).min(MAX_RADIUS);
+        }
+        if rho > ETA
+        {
+            g = obj.grad(&x_new, f_new);
+            if obj.hess.is_some()
+            {
+                h = Some(obj.hess(&x_new));
+            }
+            x = x_new;
+            f = f_new;
+        }
+        k += 1;
+        if k >= maxiter
+        {
+            status = 1;
+            break;
+        }
+    }
+    let message = match status
+    {
+        0 => SUCCESS,
+        1 => MAXITER,
+        _ => "A bad approximation caused failure to predict improvement.",
+    };
+    (x, f, Some(g), k, status, String::from(message))
+}
+
+
+///////////////////
+// Least squares //
+///////////////////
+
+/// Method for `least_squares`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum LeastSquaresMethod
+{
+    /// Trust region reflective method, which handles bounds and robust loss
+    /// functions
+    TRF,
+    /// Levenberg-Marquardt with Nielsen's damping strategy, for problems
+    /// without bounds and with the linear loss only
+    LM,
+}
+
+/// Loss function rho applied to the squared scaled residuals z = (f / C)^2,
+/// where C is the soft margin f_scale between inliers and outliers
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Loss
+{
+    /// rho(z) = z, ordinary least squares
+    Linear,
+    /// rho(z) = 2 (sqrt(1 + z) - 1), a smooth approximation of the absolute
+    /// value
+    SoftL1,
+    /// rho(z) = z for z <= 1, 2 sqrt(z) - 1 otherwise
+    Huber,
+    /// rho(z) = ln(1 + z), severely weakening outliers
+    Cauchy,
+    /// rho(z) = arctan(z), limiting the loss of any single residual
+    Arctan,
+}
+
+impl Loss
+{
+    /// rho(z) and its first two derivatives
+    fn rho(&self, z: f64) -> [f64; 3]
+    {
+        match *self
+        {
+            Loss::Linear => [z, 1f64, 0f64],
+            Loss::SoftL1 =>
+            {
+                let t = 1f64 + z;
+                [2f64 * (t.sqrt() - 1f64), 1f64 / t.sqrt(), -0.5 / (t * t.sqrt())]
+            },
+            Loss::Huber =>
+            {
+                if z <= 1f64
+                {
+                    [z, 1f64, 0f64]
+                } else {
+                    [2f64 * z.sqrt() - 1f64, 1f64 / z.sqrt(), -0.5 / (z * z.sqrt())]
+                }
+            },
+            Loss::Cauchy =>
+            {
+                let t = 1f64 + z;
+                [t.ln(), 1f64 / t, -1f64 / (t * t)]
+            },
+            Loss::Arctan =>
+            {
+                let t = 1f64 + z * z;
+                [z.atan(), 1f64 / t, -2f64 * z / (t * t)]
+            },
+        }
+    }
+}
+
+/// Options for `least_squares`
+pub struct LeastSquaresOptions<'a>
+{
+    /// Solution method, TRF by default
+    pub method: LeastSquaresMethod,
+    /// Jacobian of the residuals, an m * n matrix for m residuals and n
+    /// variables, estimated by forward differences if None
+    pub jac: Option<Box<Fn(&Array<f64>) -> Matrix<f64> + 'a>>,
+    /// Box bounds on x, which must be strict; TRF only
+    pub bounds: Option<Bounds>,
+    /// Loss function, Linear by default; TRF only
+    pub loss: Loss,
+    /// Soft margin between inlier and outlier residuals, 1 by default
+    pub f_scale: f64,
+    /// Stop once an iteration reduces the cost by a relative amount of less
+    /// than ftol, 1e-8 by default
+    pub ftol: f64,
+    /// Stop once the step is smaller than xtol relative to x, 1e-8 by default
+    pub xtol: f64,
+    /// Stop once the scaled gradient is below gtol, 1e-8 by default
+    pub gtol: f64,
+    /// Maximum number of evaluations of the residuals, excluding those for
+    /// difference quotients; 100 n for n variables if None
+    pub max_nfev: Option<usize>,
+}
+
+impl<'a> Default for LeastSquaresOptions<'a>
+{
+    fn default() -> LeastSquaresOptions<'a>
+    {
+        LeastSquaresOptions{
+            method: LeastSquaresMethod::TRF,
+            jac: None,
+            bounds: None,
+            loss: Loss::Linear,
+            f_scale: 1f64,
+            ftol: 1e-8,
+            xtol: 1e-8,
+            gtol: 1e-8,
+            max_nfev: None,
+        }
+    }
+}
+
+/// Outcome of `least_squares`
+#[derive(Debug, Clone)]
+pub struct LeastSquaresResult
+{
+    /// Estimate of the solution
+    pub x: Array<f64>,
+    /// Value of the cost function, half the sum of the losses, at x
+    pub cost: f64,
+    /// Residuals at x
+    pub fun: Array<f64>,
+    /// Jacobian at x, scaled by the loss function so that J'J approximates
+    /// the Hessian of the cost
+    pub jac: Matrix<f64>,
+    /// Gradient of the cost at x
+    pub grad: Array<f64>,
+    /// First order optimality measure, the largest component of the
+    /// gradient scaled for the bounds
+    pub optimality: f64,
+    /// For each variable -1 if it is at its lower bound, 1 if at its upper
+    /// bound and 0 otherwise
+    pub active_mask: Vec<i32>,
+    /// Number of evaluations of the residuals, excluding those for
+    /// difference quotients
+    pub nfev: usize,
+    /// Number of evaluations of the Jacobian
+    pub njev: usize,
+    /// -1 if the Jacobian or the Levenberg-Marquardt damping stopped being
+    /// finite, 0 if max_nfev was reached, 1 if the gtol condition was met,
+    /// 2 for ftol, 3 for xtol and 4 for both ftol and xtol
+    pub status: i32,
+    /// Whether a tolerance was met
+    pub success: bool,
+    /// Description of the reason the method stopped
+    pub message: String,
+}
+
+/// Outcome of a least squares method: x, residuals, scaled Jacobian,
+/// gradient, cost, optimality and status
+type LsqOutcome = (Vec<f64>, Vec<f64>, Vec<f64>, Vec<f64>, f64, f64, i32);
+
+/// Residuals and their Jacobian, counting evaluations
+struct Residuals<'a>
+{
+    fun: &'a Fn(&Array<f64>) -> Array<f64>,
+    jac: Option<&'a Fn(&Array<f64>) -> Matrix<f64>>,
+    /// Bounds, so that difference quotients are taken inside them
+    lower: Vec<f64>,
+    upper: Vec<f64>,
+    m: usize,
+    nfev: usize,
+    njev: usize,
+}
+
+impl<'a> Residuals<'a>
+{
+    fn fun(&mut self, x: &[f64]) -> Vec<f64>
+    {
+        self.nfev += 1;
+        let f = (self.fun)(&Array::new(x.to_vec(), Order::Row));
+        if f.len() != self.m
+        {
+            panic!("Residuals have {} components, expected {}", f.len(), self.m);
+        }
+        f.as_slice().to_vec()
+    }
+
+    /// Jacobian at x as a row-major m * n vector, given the residuals f
+    fn jacobian(&mut self, x: &[f64], f: &[f64]) -> Vec<f64>
+    {
+        let (m, n) = (self.m, x.len());
+        self.njev += 1;
+        if let Some(jac) = self.jac
+        {
+            return dense::row_major(&jac(&Array::new(x.to_vec(), Order::Row)), m, n, "Jacobian");
+        }
+        let h: Vec<f64> = (0..n)
+            .map(|c| dense::bounded_fd_step(x[c], self.lower[c], self.upper[c])).collect();
+        let fun = self.fun;
+        dense::forward_jacobian(|xh| fun(&Array::new(xh.to_vec(), Order::Row)).as_slice().to_vec(),
+                                x, f, &h)
+    }
+}
+
+/// J'J and J'f for a row-major m * n matrix J
+fn normal_equations(j: &[f64], f: &[f64], n: usize) -> (Vec<f64>, Vec<f64>)
+{
+    let m = f.len();
+    let mut a = vec![0f64; n * n];
+    let mut g = vec![0f64; n];
+    for k in 0..m
+    {
+        let row = &j[k * n..(k + 1) * n];
+        for r in 0..n
+        {
+            g[r] += row[r] * f[k];
+            for c in 0..n
+            {
+                a[r * n + c] += row[r] * row[c];
+            }
+        }
+    }
+    (a, g)
+}
+
+/// Cost, half the sum of the losses of the residuals
+fn lsq_cost(f: &[f64], loss: Loss, f_scale: f64) -> f64
+{
+    if loss == Loss::Linear
+    {
+        return 0.5 * dot(f, f);
+    }
+    let c2 = f_scale * f_scale;
+    0.5 * c2 * f.iter().fold(0f64, |s, v| s + loss.rho(v * v / c2)[0])
+}
+
+/// Scales the residuals and Jacobian so that the Gauss-Newton model of the
+/// scaled problem matches the second order model of the robust cost
+fn scale_for_loss(f: &mut [f64], j: &mut [f64], n: usize, loss: Loss, f_scale: f64)
+{
+    if loss == Loss::Linear
+    {
+        return;
+    }
+    let c2 = f_scale * f_scale;
+    for k in 0..f.len()
+    {
+        let rho = loss.rho(f[k] * f[k] / c2);
+        let j_scale = (rho[1] + 2f64 * rho[2] * f[k] * f[k] / c2).max(f64::EPSILON).sqrt();
+        f[k] *= rho[1] / j_scale;
+        for c in 0..n
+        {
+            j[k * n + c] *= j_scale;
+        }
+    }
+}
+
+const LSQ_MESSAGES: [&'static str; 5] = [
+    "The maximum number of function evaluations is exceeded.",
+    "`gtol` termination condition is satisfied.",
+    "`ftol` termination condition is satisfied.",
+    "`xtol` termination condition is satisfied.",
+    "Both `ftol` and `xtol` termination conditions are satisfied.",
+];
+const LSQ_NOT_FINITE: &'static str = "The Jacobian or the damping parameter is not finite.";
+
+/// Finds a local minimizer of the cost 1/2 sum rho(f_i(x)^2) of the
+/// residuals f(x) from the estimate x0, subject to optional bounds.
+/// Failure to converge is reported in the result.
+pub fn least_squares<F>(f: F, x0: &Array<f64>, opts: &LeastSquaresOptions) -> LeastSquaresResult
+    where F: Fn(&Array<f64>) -> Array<f64>
+{
+    let n = x0.len();
+    if n == 0
+    {
+        panic!("Cannot minimize over no variables.");
+    }
+    for &(name, tol) in [("ftol", opts.ftol), ("xtol", opts.xtol), ("gtol", opts.gtol)].iter()
+    {
+        if !(tol >= 0f64)
+        {
+            panic!("Invalid value for {}: {}", name, tol);
+        }
+    }
+    if !(opts.f_scale > 0f64)
+    {
+        panic!("Invalid value for f_scale: {}", opts.f_scale);
+    }
+    let (lower, upper) = match opts.bounds
+    {
+        Some(ref b) =>
+        {
+            b.check(n);
+            (b.lower.as_slice().to_vec(), b.upper.as_slice().to_vec())
+        },
+        None => (vec![f64::NEG_INFINITY; n], vec![f64::INFINITY; n]),
+    };
+    if opts.method == LeastSquaresMethod::LM
+        && (opts.bounds.is_some() || opts.loss != Loss::Linear)
+    {
+        panic!("Method LM supports neither bounds nor robust loss functions");
+    }
+    for i in 0..n
+    {
+        if !(lower[i] < upper[i])
+        {
+            panic!("Lower bound {} must be strictly less than upper bound {} for component {}",
+                   lower[i], upper[i], i);
+        }
+        if !(x0[i] >= lower[i] && x0[i] <= upper[i])
+        {
+            panic!("Initial guess {} for component {} is outside the bounds", x0[i], i);
+        }
+    }
+
+    let mut res = Residuals{
+        fun: &f,
+        jac: opts.jac.as_ref().map(|j| &**j),
+        lower: lower.clone(),
+        upper: upper.clone(),
+        m: 0,
+        nfev: 0,
+        njev: 0,
+    };
+    let mut x = x0.as_slice().to_vec();
+    if opts.method == LeastSquaresMethod::TRF
+    {
+        // Start strictly inside the bounds
+        for i in 0..n
+        {
+            let step = 1e-10;
+            if x[i] <= lower[i]
+            {
+                x[i] = lower[i] + step * lower[i].abs().max(1f64);
+            } else if x[i] >= upper[i] {
+                x[i] = upper[i] - step * upper[i].abs().max(1f64);
+            }
+            if !(x[i] > lower[i] && x[i] < upper[i])
+            {
+                x[i] = 0.5 * (lower[i] + upper[i]);
+            }
+        }
+    }
+    let f0 = (res.fun)(&Array::new(x.clone(), Order::Row));
+    res.nfev = 1;
+    res.m = f0.len();
+    if res.m == 0
+    {
+        panic!("Residuals must have at least one component.");
+    }
+    if !f0.as_slice().iter().all(|v| v.is_finite())
+    {
+        panic!("Residuals are not finite in the initial point.");
+    }
+    if opts.method == LeastSquaresMethod::LM && res.m < n
+    {
+        panic!("Method LM requires at least as many residuals ({}) as variables ({})", res.m, n);
+    }
+    let max_nfev = opts.max_nfev.unwrap_or(100 * n);
+    let f0 = f0.as_slice().to_vec();
+    let (x, fx, j, g, cost, optimality, status) = match opts.method
+    {
+        LeastSquaresMethod::TRF => trf(&mut res, x, f0, &lower, &upper, opts, max_nfev),
+        LeastSquaresMethod::LM => lm(&mut res, x, f0, opts, max_nfev),
+    };
+
+    // Variables within xtol of a bound are reported as active
+    let active_mask = (0..n).map(|i|
+    {
+        if lower[i].is_finite() && x[i] - lower[i] <= opts.xtol * lower[i].abs().max(1f64)
+        {
+            -1
+        } else if upper[i].is_finite()
+            && upper[i] - x[i] <= opts.xtol * upper[i].abs().max(1f64) {
+            1
+        } else {
+            0
+        }
+    }).collect();
+    let m = res.m;
+    let message = if status < 0 { LSQ_NOT_FINITE } else { LSQ_MESSAGES[status as usize] };
+    LeastSquaresResult{
+        x: Array::new(x, Order::Row),
+        cost: cost,
+        fun: Array::new(fx, Order::Row),
+        jac: Matrix::new_from_vec(j, m, n),
+        grad: Array::new(g, Order::Row),
+        optimality: optimality,
+        active_mask: active_mask,
+        nfev: res.nfev,
+        njev: res.njev,
+        status: status,
+        success: status > 0,
+        message: String::from(message),
+    }
+}
+
+fn all_finite(v: &[f64]) -> bool
+{
+    v.iter().all(|x| x.is_finite())
+}
+
+/// Levenberg-Marquardt with the MINPACK termination tests, damping J'J by
+/// mu D^2 where D holds the largest column norms of J seen so far
+fn lm(res: &mut Residuals, mut x: Vec<f64>, mut f: Vec<f64>, opts: &LeastSquaresOptions,
+      max_nfev: usize) -> LsqOutcome
+{
+    let n = x.len();
+    let mut j = res.jacobian(&x, &f);
+    let (mut a, mut g) = normal_equations(&j, &f, n);
+    let mut dsq: Vec<f64> = (0..n).map(|i| if a[i * n + i] > 0f64 { a[i * n + i] } else { 1f64 })
+                                  .collect();
+    let mut mu = 1e-3f64;
+    let mut nu = 2f64;
+    let status;
+    loop
+    {
+        // A non-finite Jacobian makes every damped system singular, and
+        // repeated failures drive mu to infinity
+        if !(mu.is_finite() && all_finite(&j) && all_finite(&a) && all_finite(&g))
+        {
+            status = -1;
+            break;
+        }
+        // Largest cosine of the angle between f and a column of J
+        let fnorm = norm(&f);
+        let gnorm = if fnorm == 0f64
+        {
+            0f64
+        } else {
+            (0..n).filter(|&i| a[i * n + i] > 0f64)
+                  .fold(0f64, |m, i| m.max(g[i].abs() / (a[i * n + i].sqrt() * fnorm)))
+        };
+        if gnorm <= opts.gtol
+        {
+            status = 1;
+            break;
+        }
+        if res.nfev >= max_nfev
+        {
+            status = 0;
+            break;
+        }
+
+        let mut damped = a.clone();
+        for i in 0..n
+        {
+            damped[i * n + i] += mu * dsq[i];
+        }
+        let neg_g: Vec<f64> = g.iter().map(|v| -v).collect();
+        let p = match dense::lin_solve(&damped, &neg_g)
+        {
+            Some(p) => p,
+            None =>
+            {
+                mu *= nu;
+                nu *= 2f64;
+                continue;
+            },
+        };
+        let x_new = axpy(&x, 1f64, &p);
+        let f_new = res.fun(&x_new);
+        let fnorm_new = norm(&f_new);
+        let actual = if fnorm_new.is_finite() { 1f64 - (fnorm_new / fnorm).powi(2) } else { -1f64 };
+        let dp: f64 = (0..n).fold(0f64, |s, i| s + dsq[i] * p[i] * p[i]);
+        let predicted = (dot(&p, &dense::mat_vec(&a, &p)) + 2f64 * mu * dp) / (fnorm * fnorm);
+        let rho = actual / predicted;
+        let dpnorm = dp.sqrt();
+        let dxnorm = (0..n).fold(0f64, |s, i| s + dsq[i] * x[i] * x[i]).sqrt();
+
+        if rho > 1e-4
+        {
+            x = x_new;
+            f = f_new;
+            j = res.jacobian(&x, &f);
+            let (a_new, g_new) = normal_equations(&j, &f, n);
+            a = a_new;
+            g = g_new;
+            for i in 0..n
+            {
+                dsq[i] = dsq[i].max(a[i * n + i]);
+            }
+            mu *= (1f64 / 3f64).max(1f64 - (2f64 * rho - 1f64).powi(3));
+            nu = 2f64;
+        } else {
+            mu *= nu;
+            nu *= 2f64;
+        }
+
+        let ftol_met = actual.abs() <= opts.ftol && predicted <= opts.ftol && 0.5 * rho <= 1f64;
+        let xtol_met = dpnorm <= opts.xtol * (opts.xtol + dxnorm);
+        if ftol_met || xtol_met
+        {
+            status = if ftol_met && xtol_met { 4 } else if ftol_met { 2 } else { 3 };
+            break;
+        }
+    }
+    let cost = 0.5 * dot(&f, &f);
+    let optimality = norm_inf(&g);
+    (x, f, j, g, cost, optimality, status)
+}
+
+
+/////////////////////////////
+// Trust region reflective //
+/////////////////////////////
+
+/// The next floating point number after x in the direction of y
+fn next_toward(x: f64, y: f64) -> f64
+{
+    if x == y || x.is_nan() || y.is_nan() || x.is_infinite()
+    {
+        return x;
+    }
+    if x == 0f64
+    {
+        let tiny = f64::from_bits(1);
+        return if y > 0f64 { tiny } else { -tiny };
+    }
+    let bits = x.to_bits();
+    f64::from_bits(if (y > x) == (x > 0f64) { bits + 1 } else { bits - 1 })
+}
+
+/// Coleman-Li scaling vector v and its derivative dv: the distance to the
+/// bound the anti-gradient points towards, or 1 if there is none
+fn cl_scaling(x: &[f64], g: &[f64], lower: &[f64], upper: &[f64]) -> (Vec<f64>, Vec<f64>)
+{
+    let n = x.len();
+    let mut v = vec![1f64; n];
+    let mut dv = vec![0f64; n];
+    for i in 0..n
+    {
+        if g[i] < 0f64 && upper[i].is_finite()
+        {
+            v[i] = upper[i] - x[i];
+            dv[i] = -1f64;
+        } else if g[i] > 0f64 && lower[i].is_finite() {
+            v[i] = x[i] - lower[i];
+            dv[i] = 1f64;
+        }
+    }
+    (v, dv)
+}
+
+/// Largest step t with lower <= x + t s <= upper, and for each component
+/// the sign of s if that step takes it to a bound
+fn step_to_bound(x: &[f64], s: &[f64], lower: &[f64], upper: &[f64]) -> (f64, Vec<f64>)
+{
+    let steps: Vec<f64> = (0..x.len()).map(|i|
+    {
+        if s[i] == 0f64
+        {
+            f64::INFINITY
+        } else {
+            ((lower[i] - x[i]) / s[i]).max((upper[i] - x[i]) / s[i])
+        }
+    }).collect();
+    let min_step = steps.iter().fold(f64::INFINITY, |m, &t| m.min(t));
+    let hits = (0..x.len()).map(|i| if steps[i] == min_step { s[i].signum() } else { 0f64 })
+                           .collect();
+    (min_step, hits)
+}
+
+/// Values of t at which |x + t s| = delta, in increasing order
+fn trust_region_intersections(x: &[f64], s: &[f64], delta: f64) -> (f64, f64)
+{
+    let a = dot(s, s);
+    let b = dot(x, s);
+    let c = dot(x, x) - delta * delta;
+    let d = (b * b - a * c).max(0f64).sqrt();
+    let q = -(b + if b >= 0f64 { d } else { -d });
+    let (t1, t2) = (q / a, c / q);
+    if t1 < t2 { (t1, t2) } else { (t2, t1) }
+}
+
+/// s'(J'J + diag(d))s / 2 + g's, with a = J'J + diag(d)
+fn quadratic(a: &[f64], g: &[f64], s: &[f64]) -> f64
+{
+    0.5 * dot(s, &dense::mat_vec(a, s)) + dot(g, s)
+}
+
+/// Minimum of the quadratic q(t) = a t^2 + b t + c over [lo, hi], returning
+/// t and q(t), with the quadratic that of q(s0 + t s)
+fn minimize_along(a_mat: &[f64], g: &[f64], s: &[f64], s0: Option<&[f64]>, lo: f64, hi: f64)
+    -> (f64, f64)
+{
+    let as_ = dense::mat_vec(a_mat, s);
+    let a = 0.5 * dot(s, &as_);
+    let (b, c) = match s0
+    {
+        Some(s0) => (dot(g, s) + dot(s0, &as_), quadratic(a_mat, g, s0)),
+        None => (dot(g, s), 0f64),
+    };
+    let mut ts = vec![lo, hi];
+    if a != 0f64
+    {
+        let extremum = -0.5 * b / a;
+        if lo < extremum && extremum < hi
+        {
+            ts.push(extremum);
+        }
+    }
+    ts.iter().map(|&t| (t, t * (a * t + b) + c))
+      .fold((lo, f64::INFINITY), |best, tv| if tv.1 < best.1 { tv } else { best })
+}
+
+/// Minimizer of s'As / 2 + g's subject to |s| <= delta for positive
+/// semidefinite A, by Newton iterations on the secular equation for the
+/// Levenberg-Marquardt parameter (Nocedal & Wright, algorithm 4.3)
+fn trust_region_step(a: &[f64], g: &[f64], delta: f64) -> Vec<f64>
+{
+    let n = g.len();
+    let solve = |alpha: f64| -> Option<(Vec<f64>, Vec<f64>)>
+    {
+        let mut shifted = a.to_vec();
+        for i in 0..n
+        {
+            shifted[i * n + i] += alpha;
+        }
+        let factors = lapacke::lu_factor(&Matrix::new_from_vec(shifted, n, n));
+        if factors.is_singular()
+        {
+            return None;
+        }
+        let p = lapacke::lu_solve(&factors, &Array::new(g.iter().map(|v| -v).collect(),
+                                                        Order::Row));
+        let w = lapacke::lu_solve(&factors, &p);
+        let (p, w) = (p.as_slice().to_vec(), w.as_slice().to_vec());
+        if p.iter().chain(w.iter()).all(|v| v.is_finite()) { Some((p, w)) } else { None }
+    };
+    if let Some((p, _)) = solve(0f64)
+    {
+        if norm(&p) <= delta
+        {
+            return p;
+        }
+    }
+    let mut alpha_lower = 0f64;
+    let mut alpha_upper = norm(g) / delta;
+    let mut alpha = 0f64;
+    let mut p = g.iter().map(|v| -v).collect();
+    for _ in 0..10
+    {
+        if alpha <= alpha_lower || alpha >= alpha_upper
+        {
+            alpha = (0.001 * alpha_upper).max((alpha_lower * alpha_upper).sqrt());
+        }
+        let (pa, w) = match solve(alpha)
+        {
+            Some(pw) => pw,
+            None =>
+            {
+                alpha_lower = alpha;
+                continue;
+            }
+        };
+        p = pa;
+        let pnorm = norm(&p);
+        let phi = pnorm - delta;
+        if phi < 0f64
+        {
+            alpha_upper = alpha;
+        }
+        let phi_prime = -dot(&p, &w) / pnorm;
+        alpha_lower = alpha_lower.max(alpha - phi / phi_prime);
+        alpha -= (phi + delta) / delta * phi / phi_prime;
+        if phi.abs() < 0.01 * delta
+        {
+            break;
+        }
+    }
+    let scale = delta / norm(&p);
+    p.iter().map(|v| v * scale).collect()
+}
+
+/// Chooses between the trust region step p, its reflection at the first
+/// bound it crosses and a step along the anti-gradient, each kept inside
+/// the bounds. Returns the step, the step in scaled variables and the
+/// predicted reduction of the cost.
+fn select_step(x: &[f64], a_h: &[f64], g_h: &[f64], p_h: Vec<f64>, d: &[f64], delta: f64,
+               lower: &[f64], upper: &[f64], theta: f64) -> (Vec<f64>, Vec<f64>, f64)
+{
+    let n = x.len();
+    let scale = |s: &[f64]| -> Vec<f64> { s.iter().zip(d.iter()).map(|(a, b)| a * b).collect() };
+    let mut p = scale(&p_h);
+    if (0..n).all(|i| x[i] + p[i] >= lower[i] && x[i] + p[i] <= upper[i])
+    {
+        let value = quadratic(a_h, g_h, &p_h);
+        return (p, p_h, -value);
+    }
+    let mut p_h = p_h;
+
+    // Reflect the step at the bound it hits first
+    let (p_stride, hits) = step_to_bound(x, &p, lower, upper);
+    let mut r_h = p_h.clone();
+    for i in 0..n
+    {
+        if hits[i] != 0f64
+        {
+            r_h[i] = -r_h[i];
+        }
+    }
+    let r = scale(&r_h);
+    for i in 0..n
+    {
+        p[i] *= p_stride;
+        p_h[i] *= p_stride;
+    }
+    let x_on_bound = axpy(x, 1f64, &p);
+    let (_, to_tr) = trust_region_intersections(&p_h, &r_h, delta);
+    let (to_bound, _) = step_to_bound(&x_on_bound, &r, lower, upper);
+    let r_stride = to_bound.min(to_tr);
+    let (r_lo, r_hi) = if r_stride > 0f64
+    {
+        ((1f64 - theta) * p_stride / r_stride,
+         if r_stride == to_bound { theta * to_bound } else { to_tr })
+    } else {
+        (0f64, -1f64)
+    };
+    let (r, r_h, r_value) = if r_lo <= r_hi
+    {
+        let (t, value) = minimize_along(a_h, g_h, &r_h, Some(&p_h), r_lo, r_hi);
+        let r_h = axpy(&p_h, t, &r_h);
+        (scale(&r_h), r_h, value)
+    } else {
+        (r, r_h, f64::INFINITY)
+    };
+
+    // Step back from the bound
+    for i in 0..n
+    {
+        p[i] *= theta;
+        p_h[i] *= theta;
+    }
+    let p_value = quadratic(a_h, g_h, &p_h);
+
+    // Anti-gradient direction
+    let ag_h: Vec<f64> = g_h.iter().map(|v| -v).collect();
+    let ag = scale(&ag_h);
+    let to_tr = delta / norm(&ag_h);
+    let (to_bound, _) = step_to_bound(x, &ag, lower, upper);
+    let ag_max = if to_bound < to_tr { theta * to_bound } else { to_tr };
+    let (t, ag_value) = minimize_along(a_h, g_h, &ag_h, None, 0f64, ag_max);
+    let ag_h: Vec<f64> = ag_h.iter().map(|v| v * t).collect();
+    let ag: Vec<f64> = ag.iter().map(|v| v * t).collect();
+
+    if p_value < r_value && p_value < ag_value
+    {
+        (p, p_h, -p_value)
+    } else if r_value < p_value && r_value < ag_value {
+        (r, r_h, -r_value)
+    } else {
+        (ag, ag_h, -ag_value)
+    }
+}
+
+/// Trust region reflective method of Branch, Coleman & Li as in SciPy,
+/// working in variables scaled by the Coleman-Li vector so that the bounds
+/// recede as they are approached
+fn trf(res: &mut Residuals, mut x: Vec<f64>, f0: Vec<f64>, lower: &[f64], upper: &[f64],
+       opts: &LeastSquaresOptions, max_nfev: usize) -> LsqOutcome
+{
+    let n = x.len();
+    let (loss, f_scale) = (opts.loss, opts.f_scale);
+    let mut f_true = f0;
+    let mut cost = lsq_cost(&f_true, loss, f_scale);
+    let mut f = f_true.clone();
+    let mut j = res.jacobian(&x, &f);
+    scale_for_loss(&mut f, &mut j, n, loss, f_scale);
+    let (mut jtj, mut g) = normal_equations(&j, &f, n);
+
+    let (v, _) = cl_scaling(&x, &g, lower, upper);
+    let mut delta = (0..n).fold(0f64, |s, i| s + x[i] * x[i] / v[i]).sqrt();
+    if delta == 0f64
+    {
+        delta = 1f64;
+    }
+    let mut g_norm;
+    let mut status = -1;
+    loop
+    {
+        let (v, dv) = cl_scaling(&x, &g, lower, upper);
+        g_norm = (0..n).fold(0f64, |m, i| m.max((g[i] * v[i]).abs()));
+        if g_norm < opts.gtol
+        {
+            status = 1;
+        }
+        if status >= 0
+        {
+            break;
+        }
+        if res.nfev >= max_nfev
+        {
+            status = 0;
+            break;
+        }
+
+        // Quadratic model in the scaled variables x = d x_h
+        let d: Vec<f64> = v.iter().map(|vi| vi.sqrt()).collect();
+        let g_h: Vec<f64> = (0..n).map(|i| d[i] * g[i]).collect();
+        let mut a_h = vec![0f64; n * n];
+        for r in 0..n
+        {
+            for c in 0..n
+            {
+                a_h[r * n + c] = d[r] * jtj[r * n + c] * d[c];
+            }
+            a_h[r * n + r] += g[r] * dv[r] * d[r] * d[r] / v[r];
+        }
+        let theta = (1f64 - g_norm).max(0.995);
+
+        let mut actual = -1f64;
+        let mut accepted = None;
+        while actual <= 0f64 && res.nfev < max_nfev
+        {
+            let p_h = trust_region_step(&a_h, &g_h, delta);
+            let (step, step_h, predicted) = select_step(&x, &a_h, &g_h, p_h, &d, delta,
+                                                        lower, upper, theta);
+            let mut x_new = axpy(&x, 1f64, &step);
+            for i in 0..n
+            {
+                if x_new[i] <= lower[i]
+                {
+                    x_new[i] = next_toward(lower[i], upper[i]);
+                } else if x_new[i] >= upper[i] {
+                    x_new[i] = next_toward(upper[i], lower[i]);
+                }
+            }
+            let f_new = res.fun(&x_new);
+            let step_h_norm = norm(&step_h);
+            if !f_new.iter().all(|v| v.is_finite())
+            {
+                delta = 0.25 * step_h_norm;
+                continue;
+            }
+            let cost_new = lsq_cost(&f_new, loss, f_scale);
+            actual = cost - cost_new;
+
+            // Update the trust radius from the agreement with the model
+            let ratio = if predicted > 0f64
+            {
+                actual / predicted
+            } else if predicted == 0f64 && actual == 0f64 {
+                1f64
+            } else {
+                0f64
+            };
+            if ratio < 0.25
+            {
+                delta = 0.25 * step_h_norm;
+            } else if ratio > 0.75 && step_h_norm > 0.95 * delta {
+                delta *= 2f64;
+            }
+
+            let step_norm = norm(&step);
+            let ftol_met = actual < opts.ftol * cost && ratio > 0.25;
+            let xtol_met = step_norm < opts.xtol * (opts.xtol + norm(&x));
+            if ftol_met || xtol_met
+            {
+                status = if ftol_met && xtol_met { 4 } else if ftol_met { 2 } else { 3 };
+            }
+            if actual > 0f64
+            {
+                accepted = Some((x_new, f_new, cost_new));
+            }
+            if status >= 0
+            {
+                break;
+            }
+        }
+        if let Some((x_new, f_new, cost_new)) = accepted
+        {
+            x = x_new;
+            f_true = f_new;
+            cost = cost_new;
+            f = f_true.clone();
+            j = res.jacobian(&x, &f);
+            scale_for_loss(&mut f, &mut j, n, loss, f_scale);
+            let (jtj_new, g_new) = normal_equations(&j, &f, n);
+            jtj = jtj_new;
+            g = g_new;
+        }
+    }
+    (x, f_true, j, g, cost, g_norm, status)
+}
+
+
+///////////////
+// curve_fit //
+///////////////
+
+/// Fits the parameters p of model(x, p) to the data points (xdata, ydata)
+/// by least squares from the estimate p0, using Levenberg-Marquardt.
+/// Returns the parameters and their estimated covariance, which is infinite
+/// if it cannot be determined. Panics if the fit fails to converge.
+pub fn curve_fit<F>(model: F, xdata: &Array<f64>, ydata: &Array<f64>, p0: &Array<f64>)
+    -> (Array<f64>, Matrix<f64>)
+    where F: Fn(f64, &Array<f64>) -> f64
+{
+    let opts = LeastSquaresOptions{method: LeastSquaresMethod::LM,
+                                   ..LeastSquaresOptions::default()};
+    curve_fit_with(model, xdata, ydata, p0, None, &opts)
+}
+
+/// As `curve_fit`, weighting the residuals by the standard deviations sigma
+/// of the data if given, with the method, bounds and loss function of opts.
+/// A Jacobian in opts is that of the weighted residuals with respect to p.
+/// The covariance is scaled by the reduced chi-squared of the fit.
+pub fn curve_fit_with<F>(model: F, xdata: &Array<f64>, ydata: &Array<f64>, p0: &Array<f64>,
+                         sigma: Option<&Array<f64>>, opts: &LeastSquaresOptions)
+    -> (Array<f64>, Matrix<f64>)
+    where F: Fn(f64, &Array<f64>) -> f64
+{
+    let m = xdata.len();
+    if ydata.len() != m
+    {
+        panic!("ydata has {} points, expected {}", ydata.len(), m);
+    }
+    if let Some(s) = sigma
+    {
+        if s.len() != m
+        {
+            panic!("sigma has {} components, expected {}", s.len(), m);
+        }
+    }
+    let weight = |i: usize| sigma.map_or(1f64, |s| 1f64 / s[i]);
+    let residuals = |p: &Array<f64>|
+    {
+        Array::new((0..m).map(|i| (model(xdata[i], p) - ydata[i]) * weight(i)).collect(),
+                   Order::Row)
+    };
+    let res = least_squares(residuals, p0, opts);
+    if !res.success
+    {
+        panic!("Optimal parameters not found: {}", res.message);
+    }
+
+    // Covariance from the inverse of J'J, scaled by the residual variance
+    let n = p0.len();
+    let mut j = vec![0f64; m * n];
+    for r in 0..m
+    {
+        for c in 0..n
+        {
+            j[r * n + c] = *res.jac.get(r, c);
+        }
+    }
+    let (jtj, _) = normal_equations(&j, &vec![0f64; m], n);
+    let pcov = match dense::inverse(&jtj, n)
+    {
+        Some(inv) if m > n =>
+        {
+            let s_sq = 2f64 * res.cost / (m - n) as f64;
+            inv.iter().map(|v| v * s_sq).collect()
+        },
+        _ => vec![f64::INFINITY; n * n],
+    };
+    (res.x, Matrix::new_from_vec(pcov, n, n))
+}
+
+
+////////////////////////
+// Linear constraints //
+////////////////////////
+
+/// Linear constraints a_ub x <= b_ub and a_eq x = b_eq together with box
+/// bounds on x, for `linprog` and `quadprog`
+#[derive(Clone)]
+pub struct Constraints
+{
+    a_ub: Option<Matrix<f64>>,
+    b_ub: Option<Array<f64>>,
+    a_eq: Option<Matrix<f64>>,
+    b_eq: Option<Array<f64>>,
+    bounds: Option<Bounds>,
+}
+
+impl Constraints
+{
+    /// No constraints besides the default bounds of the solver
+    pub fn new() -> Constraints
+    {
+        Constraints{a_ub: None, b_ub: None, a_eq: None, b_eq: None, bounds: None}
+    }
+
+    /// Sets the inequality constraints a x <= b
+    pub fn inequality(mut self, a: Matrix<f64>, b: Array<f64>) -> Constraints
+    {
+        if a.get_dims().0 != b.len()
+        {
+            panic!("A_ub has {} rows but b_ub has {} components", a.get_dims().0, b.len());
+        }
+        self.a_ub = Some(a);
+        self.b_ub = Some(b);
+        self
+    }
+
+    /// Sets the equality constraints a x = b
+    pub fn equality(mut self, a: Matrix<f64>, b: Array<f64>) -> Constraints
+    {
+        if a.get_dims().0 != b.len()
+        {
+            panic!("A_eq has {} rows but b_eq has {} components", a.get_dims().0, b.len());
+        }
+        self.a_eq = Some(a);
+        self.b_eq = Some(b);
+        self
+    }
+
+    /// Sets the bounds on x, replacing the default bounds of the solver
+    pub fn bounds(mut self, bounds: Bounds) -> Constraints
+    {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    /// Rows and right hand sides of the inequality and equality constraints
+    /// on n variables
+    fn rows(&self, n: usize) -> (Vec<Vec<f64>>, Vec<f64>, Vec<Vec<f64>>, Vec<f64>)
+    {
+        let dense = |a: &Option<Matrix<f64>>, b: &Option<Array<f64>>, name: &str|
+        {
+            match (a, b)
+            {
+                (&Some(ref a), &Some(ref b)) =>
+                {
+                    let (rows, cols) = a.get_dims();
+                    if cols != n
+                    {
+                        panic!("{} has {} columns, expected {}", name, cols, n);
+                    }
+                    let a: Vec<Vec<f64>> = (0..rows)
+                        .map(|r| (0..n).map(|c| *a.get(r, c)).collect())
+                        .collect();
+                    (a, b.as_slice().to_vec())
+                },
+                _ => (vec![], vec![]),
+            }
+        };
+        let (a_ub, b_ub) = dense(&self.a_ub, &self.b_ub, "A_ub");
+        let (a_eq, b_eq) = dense(&self.a_eq, &self.b_eq, "A_eq");
+        (a_ub, b_ub, a_eq, b_eq)
+    }
+
+    /// Lower and upper bounds on n variables, lower <= x by default
+    fn limits(&self, n: usize, lower: f64) -> (Vec<f64>, Vec<f64>)
+    {
+        match self.bounds
+        {
+            Some(ref bounds) =>
+            {
+                bounds.check(n);
+                for i in 0..n
+                {
+                    if bounds.lower[i] == f64::INFINITY || bounds.upper[i] == -f64::INFINITY
+                    {
+                        panic!("Bounds for component {} admit no finite value", i);
+                    }
+                }
+                (bounds.lower.as_slice().to_vec(), bounds.upper.as_slice().to_vec())
+            },
+            None => (vec![lower; n], vec![f64::INFINITY; n]),
+        }
+    }
+}
+
+/// Solution method for `linprog`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum LinprogMethod
+{
+    /// Dense revised simplex method with two phases, refactoring the basis
+    /// at every iteration
+    Simplex,
+    /// Primal-dual interior point method on the homogeneous self-dual
+    /// embedding of the problem
+    InteriorPoint,
+}
+
+/// Options for `linprog`
+pub struct LinprogOptions
+{
+    /// Solution method, InteriorPoint by default
+    pub method: LinprogMethod,
+    /// Simplex treats reduced costs and pivots below tol as zero;
+    /// InteriorPoint stops once the relative residuals and duality gap are
+    /// below tol. 1e-9 and 1e-8 respectively if None
+    pub tol: Option<f64>,
+    /// Maximum number of iterations, counted over both phases of Simplex;
+    /// 1000 by default
+    pub maxiter: usize,
+}
+
+impl Default for LinprogOptions
+{
+    fn default() -> LinprogOptions
+    {
+        LinprogOptions{method: LinprogMethod::InteriorPoint, tol: None, maxiter: 1000}
+    }
+}
+
+/// Options for `quadprog`
+pub struct QuadprogOptions
+{
+    /// The method stops once the relative residuals and the average
+    /// complementarity are below tol, 1e-8 by default
+    pub tol: f64,
+    /// Maximum number of iterations, 100 by default
+    pub maxiter: usize,
+}
+
+impl Default for QuadprogOptions
+{
+    fn default() -> QuadprogOptions
+    {
+        QuadprogOptions{tol: 1e-8, maxiter: 100}
+    }
+}
+
+/// Outcome of `linprog` and `quadprog`
+#[derive(Debug, Clone)]
+pub struct ProgramResult
+{
+    /// Estimate of the minimizer
+    pub x: Array<f64>,
+    /// Objective at x
+    pub fun: f64,
+    /// b_ub - A_ub x, nonnegative where the inequalities hold
+    pub slack: Array<f64>,
+    /// b_eq - A_eq x, the residuals of the equalities
+    pub con: Array<f64>,
+    /// Number of iterations done
+    pub nit: usize,
+    /// 0 if the problem was solved, 1 if the iteration limit was reached,
+    /// 2 if it is infeasible, 3 if it is unbounded, 4 on numerical
+    /// difficulties
+    pub status: i32,
+    /// Whether the problem was solved
+    pub success: bool,
+    /// Description of the reason the method stopped
+    pub message: String,
+}
+
+const PROGRAM_MESSAGES: [&'static str; 5] = [
+    "Optimization terminated successfully.",
+    "The iteration limit was reached before the algorithm converged.",
+    "The problem appears to be infeasible.",
+    "The problem appears to be unbounded.",
+    "Numerical difficulties encountered.",
+];
+
+fn program_result(x: Vec<f64>, fun: f64, a_ub: &[Vec<f64>], b_ub: &[f64], a_eq: &[Vec<f64>],
+                  b_eq: &[f64], nit: usize, status: i32) -> ProgramResult
+{
+    let slack = a_ub.iter().zip(b_ub.iter()).map(|(a, b)| b - dot(a, &x)).collect();
+    let con = a_eq.iter().zip(b_eq.iter()).map(|(a, b)| b - dot(a, &x)).collect();
+    ProgramResult{
+        x: Array::new(x, Order::Row),
+        fun: fun,
+        slack: Array::new(slack, Order::Row),
+        con: Array::new(con, Order::Row),
+        nit: nit,
+        status: status,
+        success: status == 0,
+        message: PROGRAM_MESSAGES[status as usize].to_string(),
+    }
+}
+
+
+///////////////////
+// Standard form //
+///////////////////
+
+/// How a variable of the original problem is expressed through the
+/// nonnegative variables of the standard form
+#[derive(Copy, Clone)]
+enum Substitution
+{
+    /// x = l + z[k]
+    Shift(usize, f64),
+    /// x = u - z[k]
+    Flip(usize, f64),
+    /// x = z[k] - z[j]
+    Split(usize, usize),
+}
+
+impl Substitution
+{
+    /// Adds the coefficient a of x to a row over z, returning the constant
+    /// part of a x
+    fn expand(&self, a: f64, row: &mut [f64]) -> f64
+    {
+        match *self
+        {
+            Substitution::Shift(k, l) => { row[k] += a; a * l },
+            Substitution::Flip(k, u) => { row[k] -= a; a * u },
+            Substitution::Split(k, j) => { row[k] += a; row[j] -= a; 0f64 },
+        }
+    }
+
+    fn value(&self, z: &[f64]) -> f64
+    {
+        match *self
+        {
+            Substitution::Shift(k, l) => l + z[k],
+            Substitution::Flip(k, u) => u - z[k],
+            Substitution::Split(k, j) => z[k] - z[j],
+        }
+    }
+}
+
+/// The linear program min c'z + c0 subject to a z = b, z >= 0
+struct StandardForm
+{
+    a: Vec<Vec<f64>>,
+    b: Vec<f64>,
+    c: Vec<f64>,
+    c0: f64,
+    subs: Vec<Substitution>,
+}
+
+impl StandardForm
+{
+    /// Brings min c'x subject to the constraints and bounds into standard
+    /// form, with a slack variable for each inequality and for each finite
+    /// upper bound of a variable which is also bounded below
+    fn new(c: &[f64], a_ub: &[Vec<f64>], b_ub: &[f64], a_eq: &[Vec<f64>], b_eq: &[f64],
+           lower: &[f64], upper: &[f64]) -> StandardForm
+    {
+        let n = c.len();
+        let mut subs = Vec::with_capacity(n);
+        let mut cols = 0;
+        for i in 0..n
+        {
+            if lower[i].is_finite()
+            {
+                subs.push(Substitution::Shift(cols, lower[i]));
+                cols += 1;
+            } else if upper[i].is_finite() {
+                subs.push(Substitution::Flip(cols, upper[i]));
+                cols += 1;
+            } else {
+                subs.push(Substitution::Split(cols, cols + 1));
+                cols += 2;
+            }
+        }
+        let expand = |coeffs: &[f64], rhs: f64|
+        {
+            let mut row = vec![0f64; cols];
+            let mut rhs = rhs;
+            for i in 0..n
+            {
+                rhs -= subs[i].expand(coeffs[i], &mut row);
+            }
+            (row, rhs)
+        };
+
+        let mut ub: Vec<(Vec<f64>, f64)> = a_ub.iter().zip(b_ub.iter())
+            .map(|(a, &b)| expand(a, b))
+            .collect();
+        for i in 0..n
+        {
+            if let Substitution::Shift(k, l) = subs[i]
+            {
+                if upper[i].is_finite()
+                {
+                    let mut row = vec![0f64; cols];
+                    row[k] = 1f64;
+                    ub.push((row, upper[i] - l));
+                }
+            }
+        }
+        let m_ub = ub.len();
+        let mut a = Vec::with_capacity(m_ub + a_eq.len());
+        let mut b = Vec::with_capacity(m_ub + a_eq.len());
+        for (k, (mut row, rhs)) in ub.into_iter().enumerate()
+        {
+            row.extend((0..m_ub).map(|j| if j == k { 1f64 } else { 0f64 }));
+            a.push(row);
+            b.push(rhs);
+        }
+        for (coeffs, &rhs) in a_eq.iter().zip(b_eq.iter())
+        {
+            let (mut row, rhs) = expand(coeffs, rhs);
+            row.extend(vec![0f64; m_ub]);
+            a.push(row);
+            b.push(rhs);
+        }
+
+        let mut cz = vec![0f64; cols + m_ub];
+        let mut c0 = 0f64;
+        for i in 0..n
+        {
+            c0 += subs[i].expand(c[i], &mut cz);
+        }
+        StandardForm{a: a, b: b, c: cz, c0: c0, subs: subs}
+    }
+
+    /// The original variables at z
+    fn recover(&self, z: &[f64]) -> Vec<f64>
+    {
+        self.subs.iter().map(|s| s.value(z)).collect()
+    }
+}
+
+/// Removes the rows of a z = b which are linear combinations of the others,
+/// by elimination with the largest remaining entries as pivots. None if
+/// such a row contradicts the others.
+fn independent_rows(a: &[Vec<f64>], b: &[f64]) -> Option<(Vec<Vec<f64>>, Vec<f64>)>
+{
+    // Reduced rows, normalized at their pivot column
+    let mut reduced: Vec<(usize, Vec<f64>, f64)> = vec![];
+    let mut keep = vec![];
+    for r in 0..a.len()
+    {
+        let scale = norm_inf(&a[r]).max(b[r].abs()).max(1f64);
+        let mut row = a[r].clone();
+        let mut rhs = b[r];
+        for &(p, ref e, e_rhs) in reduced.iter()
+        {
+            let factor = row[p];
+            if factor != 0f64
+            {
+                for j in 0..row.len()
+                {
+                    row[j] -= factor * e[j];
+                }
+                rhs -= factor * e_rhs;
+            }
+        }
+        let (p, pivot) = row.iter().enumerate()
+            .fold((0, 0f64), |best, (j, &v)| if v.abs() > best.1.abs() { (j, v) } else { best });
+        if pivot.abs() > 1e-10 * scale
+        {
+            let e = row.iter().map(|v| v / pivot).collect();
+            reduced.push((p, e, rhs / pivot));
+            keep.push(r);
+        } else if rhs.abs() > 1e-10 * scale {
+            return None;
+        }
+    }
+    Some((keep.iter().map(|&r| a[r].clone()).collect(), keep.iter().map(|&r| b[r]).collect()))
+}
+
+
+/////////////
+// Simplex //
+/////////////
+
+/// LU factors of the basis matrix formed by the columns `basis` of a, or of
+/// its transpose, None if singular
+fn basis_factors(a: &[Vec<f64>], basis: &[usize], transpose: bool) -> Option<lapacke::LuFactors>
+{
+    let m = basis.len();
+    let mut bmat = vec![0f64; m * m];
+    for r in 0..m
+    {
+        for k in 0..m
+        {
+            if transpose
+            {
+                bmat[k * m + r] = a[r][basis[k]];
+            } else {
+                bmat[r * m + k] = a[r][basis[k]];
+            }
+        }
+    }
+    let factors = lapacke::lu_factor(&Matrix::new_from_vec(bmat, m, m));
+    if factors.is_singular() { None } else { Some(factors) }
+}
+
+fn lu_apply(factors: &lapacke::LuFactors, b: &[f64]) -> Vec<f64>
+{
+    lapacke::lu_solve(factors, &Array::new(b.to_vec(), Order::Row)).as_slice().to_vec()
+}
+
+/// The basic solution of a z = b for the basis, None if it is singular
+fn basic_solution(a: &[Vec<f64>], b: &[f64], basis: &[usize], cols: usize) -> Option<Vec<f64>>
+{
+    let factors = match basis_factors(a, basis, false)
+    {
+        Some(factors) => factors,
+        None => return None,
+    };
+    let xb = lu_apply(&factors, b);
+    let mut z = vec![0f64; cols];
+    for k in 0..basis.len()
+    {
+        z[basis[k]] = xb[k].max(0f64);
+    }
+    Some(z)
+}
+
+/// Revised simplex iterations on min c'z, a z = b, z >= 0 from a feasible
+/// basis, letting only the first `enter` columns into it. Dantzig's rule
+/// picks the entering column, and Bland's rule after a degenerate step so
+/// that the method cannot cycle. Returns 0 at an optimum, 1 at the iteration
+/// limit, 3 if the objective is unbounded and 4 if the basis is singular.
+fn simplex_iterations(a: &[Vec<f64>], b: &[f64], c: &[f64], enter: usize,
+                      basis: &mut Vec<usize>, tol: f64, maxiter: usize, nit: &mut usize) -> i32
+{
+    let m = b.len();
+    let mut bland = false;
+    loop
+    {
+        let (factors, factors_t) = match (basis_factors(a, basis, false),
+                                          basis_factors(a, basis, true))
+        {
+            (Some(f), Some(ft)) => (f, ft),
+            _ => return 4,
+        };
+        let xb = lu_apply(&factors, b);
+        let cb: Vec<f64> = basis.iter().map(|&k| c[k]).collect();
+        let y = lu_apply(&factors_t, &cb);
+
+        // Entering column by its reduced cost
+        let mut entering = None;
+        let mut best = -tol;
+        for j in 0..enter
+        {
+            if basis.contains(&j)
+            {
+                continue;
+            }
+            let reduced = c[j] - (0..m).fold(0f64, |s, r| s + a[r][j] * y[r]);
+            if reduced < best
+            {
+                best = reduced;
+                entering = Some(j);
+                if bland
+                {
+                    break;
+                }
+            }
+        }
+        let j = match entering
+        {
+            Some(j) => j,
+            None => return 0,
+        };
+        if *nit >= maxiter
+        {
+            return 1;
+        }
+
+        // Leaving column by the ratio test, ties going to the smallest index
+        let col: Vec<f64> = (0..m).map(|r| a[r][j]).collect();
+        let d = lu_apply(&factors, &col);
+        let mut leave: Option<usize> = None;
+        let mut ratio = f64::INFINITY;
+        for k in 0..m
+        {
+            if d[k] > tol
+            {
+                let t = xb[k].max(0f64) / d[k];
+                if t < ratio || (t == ratio && basis[k] < basis[leave.unwrap()])
+                {
+                    ratio = t;
+                    leave = Some(k);
+                }
+            }
+        }
+        let k = match leave
+        {
+            Some(k) => k,
+            None => return 3,
+        };
+        bland = ratio <= tol;
+        basis[k] = j;
+        *nit += 1;
+    }
+}
+
+/// Two phase simplex method. The first phase minimizes the sum of artificial
+/// variables, one for each row, starting from the basis they form; the
+/// artificial variables left in the basis at zero are then exchanged for
+/// original columns, except in redundant rows where no such column exists.
+/// Returns the status, z and the number of iterations.
+fn simplex(sf: &StandardForm, tol: f64, maxiter: usize) -> (i32, Vec<f64>, usize)
+{
+    let m = sf.b.len();
+    let n = sf.c.len();
+    if m == 0
+    {
+        let status = if sf.c.iter().any(|&v| v < 0f64) { 3 } else { 0 };
+        return (status, vec![0f64; n], 0);
+    }
+    let mut a = Vec::with_capacity(m);
+    let mut b = Vec::with_capacity(m);
+    for r in 0..m
+    {
+        let sign = if sf.b[r] < 0f64 { -1f64 } else { 1f64 };
+        let mut row: Vec<f64> = sf.a[r].iter().map(|v| sign * v).collect();
+        row.extend((0..m).map(|k| if k == r { 1f64 } else { 0f64 }));
+        a.push(row);
+        b.push(sign * sf.b[r]);
+    }
+    let mut basis: Vec<usize> = (n..n + m).collect();
+    let mut nit = 0;
+
+    // Phase one
+    let mut c = vec![0f64; n];
+    c.extend(vec![1f64; m]);
+    let status = simplex_iterations(&a, &b, &c, n + m, &mut basis, tol, maxiter, &mut nit);
+    let z = match basic_solution(&a, &b, &basis, n + m)
+    {
+        Some(z) => z,
+        None => return (4, vec![0f64; n], nit),
+    };
+    if status != 0
+    {
+        return (status, z[..n].to_vec(), nit);
+    }
+    let infeasibility = z[n..].iter().fold(0f64, |s, v| s + v);
+    if infeasibility > 100f64 * tol * norm_inf(&b).max(1f64)
+    {
+        return (2, z[..n].to_vec(), nit);
+    }
+    for k in 0..m
+    {
+        if basis[k] < n
+        {
+            continue;
+        }
+        // Row k of the basis inverse times a, over the original columns
+        let factors_t = match basis_factors(&a, &basis, true)
+        {
+            Some(f) => f,
+            None => return (4, z[..n].to_vec(), nit),
+        };
+        let mut e = vec![0f64; m];
+        e[k] = 1f64;
+        let w = lu_apply(&factors_t, &e);
+        let mut pivot = (0, 0f64);
+        for j in 0..n
+        {
+            if !basis.contains(&j)
+            {
+                let v = (0..m).fold(0f64, |s, r| s + w[r] * a[r][j]);
+                if v.abs() > pivot.1.abs()
+                {
+                    pivot = (j, v);
+                }
+            }
+        }
+        if pivot.1.abs() > tol
+        {
+            basis[k] = pivot.0;
+        }
+    }
+
+    // Phase two, keeping the artificial variables out of the basis
+    let mut c = sf.c.clone();
+    c.extend(vec![0f64; m]);
+    let status = simplex_iterations(&a, &b, &c, n, &mut basis, tol, maxiter, &mut nit);
+    match basic_solution(&a, &b, &basis, n + m)
+    {
+        Some(z) => (status, z[..n].to_vec(), nit),
+        None => (4, vec![0f64; n], nit),
+    }
+}
+
+
+////////////////////
+// Interior point //
+////////////////////
+
+/// Largest step alpha <= 1 keeping v + alpha dv nonnegative, for each pair
+/// of v and dv, reduced by the factor alpha0
+fn fraction_to_boundary(pairs: &[(&[f64], &[f64])], alpha0: f64) -> f64
+{
+    let mut alpha = 1f64;
+    for &(v, dv) in pairs
+    {
+        for i in 0..v.len()
+        {
+            if dv[i] < 0f64
+            {
+                alpha = alpha.min(alpha0 * v[i] / -dv[i]);
+            }
+        }
+    }
+    alpha
+}
+
+/// Primal-dual interior point method of Andersen & Andersen on the
+/// homogeneous self-dual embedding of the standard form, with Mehrotra's
+/// predictor-corrector steps, as in SciPy. The embedding variables tau and
+/// kappa certify infeasibility or unboundedness when tau tends to zero.
+/// Returns the status, z and the number of iterations.
+fn interior_point(a: &[Vec<f64>], b: &[f64], c: &[f64], tol: f64, maxiter: usize)
+    -> (i32, Vec<f64>, usize)
+{
+    let m = b.len();
+    let n = c.len();
+    if m == 0
+    {
+        let status = if c.iter().any(|&v| v < 0f64) { 3 } else { 0 };
+        return (status, vec![0f64; n], 0);
+    }
+    let a_mul = |x: &[f64]| -> Vec<f64> { a.iter().map(|row| dot(row, x)).collect() };
+    let at_mul = |y: &[f64]| -> Vec<f64>
+    {
+        (0..n).map(|j| (0..m).fold(0f64, |s, r| s + a[r][j] * y[r])).collect()
+    };
+    // Primal, dual and gap residuals and the complementarity
+    let residuals = |x: &[f64], y: &[f64], z: &[f64], tau: f64, kappa: f64|
+    {
+        let ax = a_mul(x);
+        let aty = at_mul(y);
+        let r_p: Vec<f64> = (0..m).map(|r| b[r] * tau - ax[r]).collect();
+        let r_d: Vec<f64> = (0..n).map(|j| c[j] * tau - aty[j] - z[j]).collect();
+        let r_g = dot(c, x) - dot(b, y) + kappa;
+        let mu = (dot(x, z) + tau * kappa) / (n + 1) as f64;
+        (r_p, r_d, r_g, mu)
+    };
+
+    let mut x = vec![1f64; n];
+    let mut y = vec![0f64; m];
+    let mut z = vec![1f64; n];
+    let mut tau = 1f64;
+    let mut kappa = 1f64;
+    let (r_p0, r_d0, r_g0, mu0) = residuals(&x, &y, &z, tau, kappa);
+    let (r_p0, r_d0, r_g0) = (norm(&r_p0).max(1f64), norm(&r_d0).max(1f64), r_g0.abs().max(1f64));
+    let mut nit = 0;
+    let status;
+    loop
+    {
+        let (r_p, r_d, r_g, mu) = residuals(&x, &y, &z, tau, kappa);
+        let rho_p = norm(&r_p) / r_p0;
+        let rho_d = norm(&r_d) / r_d0;
+        let rho_g = r_g.abs() / r_g0;
+        let rho_mu = mu / mu0;
+        let bty = dot(b, &y);
+        let rho_a = (dot(c, &x) - bty).abs() / (tau + bty.abs());
+        if !(rho_p.is_finite() && rho_d.is_finite() && rho_a.is_finite())
+        {
+            status = 4;
+            break;
+        }
+        if rho_p <= tol && rho_d <= tol && rho_a <= tol
+        {
+            status = 0;
+            break;
+        }
+        if (rho_p < tol && rho_d < tol && rho_g < tol && tau < tol * kappa.max(1f64))
+            || (rho_mu < tol && tau < tol * kappa.min(1f64))
+        {
+            status = if bty > tol { 2 } else { 3 };
+            break;
+        }
+        if nit >= maxiter
+        {
+            status = 1;
+            break;
+        }
+        nit += 1;
+
+        // Normal equations a D a' with D = X / Z
+        let dinv: Vec<f64> = (0..n).map(|j| x[j] / z[j]).collect();
+        let mut mat = vec![0f64; m * m];
+        for r in 0..m
+        {
+            for k in r..m
+            {
+                let v = (0..n).fold(0f64, |s, j| s + a[r][j] * dinv[j] * a[k][j]);
+                mat[r * m + k] = v;
+                mat[k * m + r] = v;
+            }
+        }
+        let factors = lapacke::lu_factor(&Matrix::new_from_vec(mat, m, m));
+        if factors.is_singular()
+        {
+            status = 4;
+            break;
+        }
+        let sym_solve = |r1: &[f64], r2: &[f64]|
+        {
+            let scaled: Vec<f64> = (0..n).map(|j| dinv[j] * r1[j]).collect();
+            let r = axpy(r2, 1f64, &a_mul(&scaled));
+            let v = lu_apply(&factors, &r);
+            let atv = at_mul(&v);
+            let u: Vec<f64> = (0..n).map(|j| dinv[j] * (atv[j] - r1[j])).collect();
+            (u, v)
+        };
+        let (p, q) = sym_solve(c, b);
+
+        // Predictor, then corrector with Mehrotra's centering parameter
+        let mut gamma = 0f64;
+        let mut d_x = vec![0f64; n];
+        let mut d_y = vec![0f64; m];
+        let mut d_z = vec![0f64; n];
+        let mut d_tau = 0f64;
+        let mut d_kappa = 0f64;
+        for &corrector in [false, true].iter()
+        {
+            let eta = 1f64 - gamma;
+            let rhat_p: Vec<f64> = r_p.iter().map(|v| eta * v).collect();
+            let rhat_g = eta * r_g;
+            let mut rhat_xs: Vec<f64> = (0..n).map(|j| gamma * mu - x[j] * z[j]).collect();
+            let mut rhat_tk = gamma * mu - tau * kappa;
+            if corrector
+            {
+                for j in 0..n
+                {
+                    rhat_xs[j] -= d_x[j] * d_z[j];
+                }
+                rhat_tk -= d_tau * d_kappa;
+            }
+            let r1: Vec<f64> = (0..n).map(|j| eta * r_d[j] - rhat_xs[j] / x[j]).collect();
+            let (u, v) = sym_solve(&r1, &rhat_p);
+            d_tau = (rhat_g + rhat_tk / tau - (dot(b, &v) - dot(c, &u)))
+                / (kappa / tau + dot(b, &q) - dot(c, &p));
+            d_x = axpy(&u, d_tau, &p);
+            d_y = axpy(&v, d_tau, &q);
+            d_z = (0..n).map(|j| (rhat_xs[j] - z[j] * d_x[j]) / x[j]).collect();
+            d_kappa = (rhat_tk - kappa * d_tau) / tau;
+            if !corrector
+            {
+                let alpha = fraction_to_boundary(&[(&x, &d_x), (&z, &d_z), (&[tau], &[d_tau]),
+                                                   (&[kappa], &[d_kappa])], 1f64);
+                gamma = (1f64 - alpha).powi(2) * (1f64 - alpha).min(0.1);
+            }
+        }
+        let alpha = fraction_to_boundary(&[(&x, &d_x), (&z, &d_z), (&[tau], &[d_tau]),
+                                           (&[kappa], &[d_kappa])], 0.99995);
+        x = axpy(&x, alpha, &d_x);
+        y = axpy(&y, alpha, &d_y);
+        z = axpy(&z, alpha, &d_z);
+        tau += alpha * d_tau;
+        kappa += alpha * d_kappa;
+    }
+    // tau tends to zero on infeasible and unbounded problems, where x / tau
+    // means nothing
+    if status == 0 || status == 1
+    {
+        (status, x.iter().map(|v| v / tau).collect(), nit)
+    } else {
+        (status, x, nit)
+    }
+}
+
+
+/////////////
+// linprog //
+/////////////
+
+/// Minimizes the linear objective c'x subject to the constraints. Unless
+/// bounds are given the variables are nonnegative, as for SciPy.
+/// Infeasible and unbounded problems and failures to converge are reported
+/// in the result.
+pub fn linprog(c: &Array<f64>, constraints: &Constraints, opts: &LinprogOptions)
+    -> ProgramResult
+{
+    let n = c.len();
+    if n == 0
+    {
+        panic!("Cannot optimize over no variables.");
+    }
+    let tol = opts.tol.unwrap_or(match opts.method
+    {
+        LinprogMethod::Simplex => 1e-9,
+        LinprogMethod::InteriorPoint => 1e-8,
+    });
+    if !(tol > 0f64)
+    {
+        panic!("Invalid value for tol");
+    }
+    let (a_ub, b_ub, a_eq, b_eq) = constraints.rows(n);
+    let (lower, upper) = constraints.limits(n, 0f64);
+    let c = c.as_slice();
+    let sf = StandardForm::new(c, &a_ub, &b_ub, &a_eq, &b_eq, &lower, &upper);
+
+    let (status, z, nit) = match opts.method
+    {
+        LinprogMethod::Simplex => simplex(&sf, tol, opts.maxiter),
+        LinprogMethod::InteriorPoint =>
+        {
+            match independent_rows(&sf.a, &sf.b)
+            {
+                Some((a, b)) => interior_point(&a, &b, &sf.c, tol, opts.maxiter),
+                None => (2, vec![0f64; sf.c.len()], 0),
+            }
+        },
+    };
+    let x = sf.recover(&z);
+    let fun = dot(c, &x);
+    program_result(x, fun, &a_ub, &b_ub, &a_eq, &b_eq, nit, status)
+}
+
+
+//////////////
+// quadprog //
+//////////////
+
+/// Primal-dual interior point method with Mehrotra's predictor-corrector
+/// steps for min x'Hx / 2 + c'x subject to a_eq x = b_eq and g x <= h,
+/// eliminating the inequality slacks and multipliers from the Newton
+/// equations. Returns the status, x and the number of iterations.
+fn qp_interior_point(hess: &[f64], c: &[f64], a_eq: &[Vec<f64>], b_eq: &[f64],
+                     g: &[Vec<f64>], h: &[f64], tol: f64, maxiter: usize)
+    -> (i32, Vec<f64>, usize)
+{
+    let n = c.len();
+    let me = b_eq.len();
+    let mi = h.len();
+    let k = n + me;
+    let mut x = vec![0f64; n];
+    let mut y = vec![0f64; me];
+    let mut s: Vec<f64> = (0..mi).map(|i| (h[i] - dot(&g[i], &x)).max(1f64)).collect();
+    let mut z = vec![1f64; mi];
+    let scale_c = 1f64 + norm_inf(c);
+    let scale_b = 1f64 + norm_inf(b_eq);
+    let scale_h = 1f64 + norm_inf(h);
+    let gt_mul = |v: &[f64]| -> Vec<f64>
+    {
+        (0..n).map(|j| (0..mi).fold(0f64, |sum, i| sum + g[i][j] * v[i])).collect()
+    };
+    let mut nit = 0;
+    let status;
+    loop
+    {
+        let mut r_d = axpy(&dense::mat_vec(hess, &x), 1f64, c);
+        for r in 0..me
+        {
+            for j in 0..n
+            {
+                r_d[j] += a_eq[r][j] * y[r];
+            }
+        }
+        r_d = axpy(&r_d, 1f64, &gt_mul(&z));
+        let r_eq: Vec<f64> = (0..me).map(|r| dot(&a_eq[r], &x) - b_eq[r]).collect();
+        let r_in: Vec<f64> = (0..mi).map(|i| dot(&g[i], &x) + s[i] - h[i]).collect();
+        let mu = if mi > 0 { dot(&s, &z) / mi as f64 } else { 0f64 };
+        if !(norm_inf(&r_d).is_finite() && mu.is_finite())
+        {
+            status = 4;
+            break;
+        }
+        if norm_inf(&r_d) <= tol * scale_c && norm_inf(&r_eq) <= tol * scale_b
+            && norm_inf(&r_in) <= tol * scale_h && mu <= tol
+        {
+            status = 0;
+            break;
+        }
+        if nit >= maxiter
+        {
+            status = 1;
+            break;
+        }
+        nit += 1;
+
+        // Newton matrix [H + G'WG, A'; A, 0] with W = Z / S
+        let w: Vec<f64> = (0..mi).map(|i| z[i] / s[i]).collect();
+        let mut kkt = vec![0f64; k * k];
+        for r in 0..n
+        {
+            for j in 0..n
+            {
+                kkt[r * k + j] = hess[r * n + j]
+                    + (0..mi).fold(0f64, |sum, i| sum + g[i][r] * w[i] * g[i][j]);
+            }
+        }
+        for r in 0..me
+        {
+            for j in 0..n
+            {
+                kkt[(n + r) * k + j] = a_eq[r][j];
+                kkt[j * k + n + r] = a_eq[r][j];
+            }
+        }
+        let factors = lapacke::lu_factor(&Matrix::new_from_vec(kkt, k, k));
+        if factors.is_singular()
+        {
+            status = 4;
+            break;
+        }
+        // Steps for the complementarity right hand side r_sz
+        let newton = |r_sz: &[f64]|
+        {
+            let t: Vec<f64> = (0..mi).map(|i| w[i] * r_in[i] + r_sz[i] / s[i]).collect();
+            let mut rhs: Vec<f64> = axpy(&r_d, 1f64, &gt_mul(&t)).iter().map(|v| -v).collect();
+            rhs.extend(r_eq.iter().map(|v| -v));
+            let sol = lu_apply(&factors, &rhs);
+            let dx = sol[..n].to_vec();
+            let dy = sol[n..].to_vec();
+            let dz: Vec<f64> = (0..mi)
+                .map(|i| w[i] * (dot(&g[i], &dx) + r_in[i]) + r_sz[i] / s[i])
+                .collect();
+            let ds: Vec<f64> = (0..mi).map(|i| (r_sz[i] - s[i] * dz[i]) / z[i]).collect();
+            (dx, dy, dz, ds)
+        };
+
+        let r_sz: Vec<f64> = (0..mi).map(|i| -s[i] * z[i]).collect();
+        let (_, _, dz_aff, ds_aff) = newton(&r_sz);
+        let alpha = fraction_to_boundary(&[(&s, &ds_aff), (&z, &dz_aff)], 1f64);
+        let sigma = if mi > 0
+        {
+            let mu_aff = (0..mi).fold(0f64, |sum, i| {
+                sum + (s[i] + alpha * ds_aff[i]) * (z[i] + alpha * dz_aff[i])
+            }) / mi as f64;
+            (mu_aff / mu).powi(3)
+        } else {
+            0f64
+        };
+        let r_sz: Vec<f64> = (0..mi)
+            .map(|i| sigma * mu - s[i] * z[i] - ds_aff[i] * dz_aff[i])
+            .collect();
+        let (dx, dy, dz, ds) = newton(&r_sz);
+        let alpha = fraction_to_boundary(&[(&s, &ds), (&z, &dz)], 0.995);
+        x = axpy(&x, alpha, &dx);
+        y = axpy(&y, alpha, &dy);
+        z = axpy(&z, alpha, &dz);
+        s = axpy(&s, alpha, &ds);
+    }
+    (status, x, nit)
+}
+
+/// Minimizes the convex quadratic x'Hx / 2 + c'x subject to the
+/// constraints, for positive semidefinite H. Unlike for `linprog` the
+/// variables are free unless bounds are given. Feasibility is checked first
+/// by the simplex method, so infeasible problems are reported in the result;
+/// an objective unbounded below shows up as a failure to converge.
+pub fn quadprog(hess: &SymMat<f64>, c: &Array<f64>, constraints: &Constraints,
+                opts: &QuadprogOptions) -> ProgramResult
+{
+    let n = c.len();
+    if n == 0
+    {
+        panic!("Cannot optimize over no variables.");
+    }
+    if hess.get_dims() != (n, n)
+    {
+        panic!("Hessian has dimensions {:?}, expected ({}, {})", hess.get_dims(), n, n);
+    }
+    if !(opts.tol > 0f64)
+    {
+        panic!("Invalid value for tol");
+    }
+    let (a_ub, b_ub, a_eq, b_eq) = constraints.rows(n);
+    let (lower, upper) = constraints.limits(n, -f64::INFINITY);
+    let c = c.as_slice();
+    let mut h = vec![0f64; n * n];
+    for r in 0..n
+    {
+        for j in 0..n
+        {
+            h[r * n + j] = *hess.get(r, j);
+        }
+    }
+    let objective = |x: &[f64]| dot(x, &dense::mat_vec(&h, x)) / 2f64 + dot(c, x);
+
+    let sf = StandardForm::new(&vec![0f64; n], &a_ub, &b_ub, &a_eq, &b_eq, &lower, &upper);
+    // Without constraint rows any point within the bounds is feasible
+    let (status, z) = if sf.b.is_empty()
+    {
+        (0, vec![0f64; sf.c.len()])
+    } else {
+        let (status, z, _) = simplex(&sf, 1e-9, 1000 * (n + sf.b.len()));
+        (status, z)
+    };
+    let reduced = independent_rows(&a_eq, &b_eq);
+    if status == 2 || reduced.is_none()
+    {
+        let x = sf.recover(&z);
+        let fun = objective(&x);
+        return program_result(x, fun, &a_ub, &b_ub, &a_eq, &b_eq, 0, 2);
+    }
+    let (a_red, b_red) = reduced.unwrap();
+
+    // Inequalities g x <= h_in, including the finite bounds
+    let mut g = a_ub.clone();
+    let mut h_in = b_ub.clone();
+    for i in 0..n
+    {
+        let mut e = vec![0f64; n];
+        if upper[i].is_finite()
+        {
+            e[i] = 1f64;
+            g.push(e.clone());
+            h_in.push(upper[i]);
+        }
+        if lower[i].is_finite()
+        {
+            e[i] = -1f64;
+            g.push(e);
+            h_in.push(-lower[i]);
+        }
+    }
+    let (status, x, nit) = qp_interior_point(&h, c, &a_red, &b_red, &g, &h_in, opts.tol,
+                                             opts.maxiter);
+    let fun = objective(&x);
+    program_result(x, fun, &a_ub, &b_ub, &a_eq, &b_eq, nit, status)
+}
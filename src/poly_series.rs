@@ -0,0 +1,536 @@
+/// Series in the classical orthogonal polynomials, after numpy.polynomial.
+/// A series c[0] P_0(u) + c[1] P_1(u) + ... + c[n] P_n(u) is stored with its
+/// coefficients in ascending order, the same convention as gsl_poly. Each
+/// series carries a domain and a window: an argument x in the domain is
+/// mapped linearly onto u in the window before the polynomials are
+/// evaluated, so that a fit over an arbitrary interval is expressed in the
+/// range where the basis is well conditioned.
+/// Supported bases are the Chebyshev polynomials of the first kind T_n, the
+/// Legendre polynomials P_n, the Laguerre polynomials L_n and the
+/// physicists' Hermite polynomials H_n.
+
+use array::Array;
+use array::Order;
+use matrix::Matrix;
+use lapacke;
+use gsl_poly::Polynomial;
+use std::f64;
+use std::cmp::Ordering;
+
+/// Family of orthogonal polynomials a series is expanded in
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Basis { Chebyshev, Legendre, Laguerre, Hermite }
+
+impl Basis
+{
+    /// The window a series is mapped onto by default: [0, 1] for Laguerre
+    /// series and [-1, 1] otherwise
+    pub fn default_window(&self) -> [f64; 2]
+    {
+        match *self {
+            Basis::Laguerre => [0f64, 1f64],
+            _ => [-1f64, 1f64],
+        }
+    }
+
+    /// Coefficients (a, b, c) of the three-term recurrence
+    ///   P_{n+1}(u) = (a u + b) P_n(u) - c P_{n-1}(u)
+    fn recurrence(&self, n: usize) -> (f64, f64, f64)
+    {
+        let nf = n as f64;
+        match *self {
+            Basis::Chebyshev => if n == 0 { (1f64, 0f64, 0f64) } else { (2f64, 0f64, 1f64) },
+            Basis::Legendre => ((2f64 * nf + 1f64) / (nf + 1f64), 0f64, nf / (nf + 1f64)),
+            Basis::Laguerre => (-1f64 / (nf + 1f64), (2f64 * nf + 1f64) / (nf + 1f64),
+                                nf / (nf + 1f64)),
+            Basis::Hermite => (2f64, 0f64, 2f64 * nf),
+        }
+    }
+
+    /// Values P_0(u), P_1(u), ..., P_deg(u)
+    fn vander(&self, u: f64, deg: usize) -> Vec<f64>
+    {
+        let mut p = vec![0f64; deg + 1];
+        p[0] = 1f64;
+        for n in 0..deg
+        {
+            let (a, b, c) = self.recurrence(n);
+            let prev = if n == 0 { 0f64 } else { p[n - 1] };
+            p[n + 1] = (a * u + b) * p[n] - c * prev;
+        }
+        p
+    }
+
+    /// Sum of c[k] P_k(u) by Clenshaw's algorithm
+    fn clenshaw(&self, c: &[f64], u: f64) -> f64
+    {
+        let (mut b1, mut b2) = (0f64, 0f64);
+        for k in (0..c.len()).rev()
+        {
+            let (a, b, _) = self.recurrence(k);
+            let (_, _, g) = self.recurrence(k + 1);
+            let bk = c[k] + (a * u + b) * b1 - g * b2;
+            b2 = b1;
+            b1 = bk;
+        }
+        b1
+    }
+
+    /// P_0, P_1, ..., P_deg in the power basis
+    fn power_forms(&self, deg: usize) -> Vec<Polynomial<f64>>
+    {
+        let mut forms = vec![Polynomial::new(vec![1f64])];
+        for n in 0..deg
+        {
+            let (a, b, c) = self.recurrence(n);
+            let mut next = Polynomial::new(vec![b, a]) * forms[n].clone();
+            if n > 0
+            {
+                next = next - Polynomial::new(vec![c]) * forms[n - 1].clone();
+            }
+            forms.push(next);
+        }
+        forms
+    }
+
+    /// Coefficients of the derivative d/du of a series
+    fn derivative(&self, c: &[f64]) -> Vec<f64>
+    {
+        let n = c.len() - 1;
+        if n == 0
+        {
+            return vec![0f64];
+        }
+        let mut c = c.to_vec();
+        let mut der = vec![0f64; n];
+        match *self {
+            Basis::Chebyshev => {
+                for j in (3..n + 1).rev()
+                {
+                    der[j - 1] = 2f64 * j as f64 * c[j];
+                    c[j - 2] += j as f64 * c[j] / (j - 2) as f64;
+                }
+                if n > 1
+                {
+                    der[1] = 4f64 * c[2];
+                }
+                der[0] = c[1];
+            },
+            Basis::Legendre => {
+                for j in (3..n + 1).rev()
+                {
+                    der[j - 1] = (2 * j - 1) as f64 * c[j];
+                    c[j - 2] += c[j];
+                }
+                if n > 1
+                {
+                    der[1] = 3f64 * c[2];
+                }
+                der[0] = c[1];
+            },
+            Basis::Laguerre => {
+                for j in (2..n + 1).rev()
+                {
+                    der[j - 1] = -c[j];
+                    c[j - 1] += c[j];
+                }
+                der[0] = -c[1];
+            },
+            Basis::Hermite => {
+                for j in 1..n + 1
+                {
+                    der[j - 1] = 2f64 * j as f64 * c[j];
+                }
+            },
+        }
+        der
+    }
+
+    /// Coefficients of the antiderivative in u of a series, vanishing at u0
+    fn integral(&self, c: &[f64], u0: f64) -> Vec<f64>
+    {
+        let n = c.len();
+        let mut int = vec![0f64; n + 1];
+        match *self {
+            Basis::Chebyshev => {
+                int[1] = c[0];
+                if n > 1
+                {
+                    int[2] = c[1] / 4f64;
+                }
+                for j in 2..n
+                {
+                    int[j + 1] = c[j] / (2 * (j + 1)) as f64;
+                    int[j - 1] -= c[j] / (2 * (j - 1)) as f64;
+                }
+            },
+            Basis::Legendre => {
+                int[1] = c[0];
+                if n > 1
+                {
+                    int[2] = c[1] / 3f64;
+                }
+                for j in 2..n
+                {
+                    let t = c[j] / (2 * j + 1) as f64;
+                    int[j + 1] = t;
+                    int[j - 1] -= t;
+                }
+            },
+            Basis::Laguerre => {
+                int[0] = c[0];
+                int[1] = -c[0];
+                for j in 1..n
+                {
+                    int[j] += c[j];
+                    int[j + 1] = -c[j];
+                }
+            },
+            Basis::Hermite => {
+                int[1] = c[0] / 2f64;
+                for j in 1..n
+                {
+                    int[j + 1] = c[j] / (2 * (j + 1)) as f64;
+                }
+            },
+        }
+        int[0] -= self.clenshaw(&int, u0);
+        int
+    }
+
+    /// Companion matrix of a series of degree n >= 1 with c[n] != 0, whose
+    /// eigenvalues are the roots of the series. It is scaled so that, apart
+    /// from its last column, it is the symmetric Jacobi matrix of the basis.
+    fn companion(&self, c: &[f64]) -> Matrix<f64>
+    {
+        let n = c.len() - 1;
+        let mut mat = Matrix::<f64>::new_filled(0f64, n, n);
+        if n == 1
+        {
+            let root = match *self {
+                Basis::Laguerre => 1f64 + c[0] / c[1],
+                Basis::Hermite => -0.5f64 * c[0] / c[1],
+                _ => -c[0] / c[1],
+            };
+            mat.set(0, 0, root);
+            return mat;
+        }
+        fn set_offdiag(mat: &mut Matrix<f64>, i: usize, v: f64)
+        {
+            mat.set(i, i + 1, v);
+            mat.set(i + 1, i, v);
+        }
+        match *self {
+            Basis::Chebyshev => {
+                let scl: Vec<f64> = (0..n).map(|k| if k == 0 { 1f64 } else { 0.5f64.sqrt() })
+                    .collect();
+                for i in 0..n - 1
+                {
+                    set_offdiag(&mut mat, i, if i == 0 { 0.5f64.sqrt() } else { 0.5f64 });
+                }
+                for i in 0..n
+                {
+                    let v = *mat.get(i, n - 1) - c[i] / c[n] * scl[i] / scl[n - 1] * 0.5f64;
+                    mat.set(i, n - 1, v);
+                }
+            },
+            Basis::Legendre => {
+                let scl: Vec<f64> = (0..n).map(|k| 1f64 / ((2 * k + 1) as f64).sqrt()).collect();
+                for i in 0..n - 1
+                {
+                    set_offdiag(&mut mat, i, (i + 1) as f64 * scl[i] * scl[i + 1]);
+                }
+                let factor = n as f64 / (2 * n - 1) as f64;
+                for i in 0..n
+                {
+                    let v = *mat.get(i, n - 1) - c[i] / c[n] * scl[i] / scl[n - 1] * factor;
+                    mat.set(i, n - 1, v);
+                }
+            },
+            Basis::Laguerre => {
+                for i in 0..n
+                {
+                    mat.set(i, i, (2 * i + 1) as f64);
+                }
+                for i in 0..n - 1
+                {
+                    set_offdiag(&mut mat, i, -((i + 1) as f64));
+                }
+                for i in 0..n
+                {
+                    let v = *mat.get(i, n - 1) + c[i] / c[n] * n as f64;
+                    mat.set(i, n - 1, v);
+                }
+            },
+            Basis::Hermite => {
+                // scl[i] = 1/sqrt(2^(n-1-i) (n-1)!/i!)
+                let mut scl = vec![1f64; n];
+                for i in (0..n - 1).rev()
+                {
+                    scl[i] = scl[i + 1] / (2f64 * (i + 1) as f64).sqrt();
+                }
+                for i in 0..n - 1
+                {
+                    set_offdiag(&mut mat, i, (0.5f64 * (i + 1) as f64).sqrt());
+                }
+                for i in 0..n
+                {
+                    let v = *mat.get(i, n - 1) - scl[i] * c[i] / (2f64 * c[n]);
+                    mat.set(i, n - 1, v);
+                }
+            },
+        }
+        mat
+    }
+}
+
+/// A finite series in one of the classical orthogonal polynomial bases
+#[derive(Debug, Clone, PartialEq)]
+pub struct Series
+{
+    /// Polynomial family of the expansion
+    basis: Basis,
+    /// Coefficients in ascending order
+    coeffs: Vec<f64>,
+    /// Interval of the argument x
+    domain: [f64; 2],
+    /// Interval the domain is mapped onto before evaluating the polynomials
+    window: [f64; 2],
+}
+
+impl Series
+{
+    /// Creates a series from its coefficients, with both the domain and the
+    /// window set to the default window of the basis
+    pub fn new(basis: Basis, coeffs: Vec<f64>) -> Series
+    {
+        let window = basis.default_window();
+        Series::new_mapped(basis, coeffs, window, window)
+    }
+
+    /// Creates a series from its coefficients, mapping the given domain onto
+    /// the given window
+    pub fn new_mapped(basis: Basis, coeffs: Vec<f64>, domain: [f64; 2], window: [f64; 2])
+        -> Series
+    {
+        if coeffs.is_empty()
+        {
+            panic!("Series must have at least one coefficient");
+        }
+        if domain[0] == domain[1] || window[0] == window[1]
+        {
+            panic!("Domain and window must have non-zero length");
+        }
+        Series{basis: basis, coeffs: coeffs, domain: domain, window: window}
+    }
+
+    /// Polynomial family of the expansion
+    pub fn basis(&self) -> Basis
+    {
+        self.basis
+    }
+
+    /// Coefficients in ascending order
+    pub fn coeffs(&self) -> &[f64]
+    {
+        &self.coeffs
+    }
+
+    /// Degree of the series, the number of coefficients less one
+    pub fn degree(&self) -> usize
+    {
+        self.coeffs.len() - 1
+    }
+
+    /// Interval of the argument x
+    pub fn domain(&self) -> [f64; 2]
+    {
+        self.domain
+    }
+
+    /// Interval the domain is mapped onto
+    pub fn window(&self) -> [f64; 2]
+    {
+        self.window
+    }
+
+    /// Offset and scale (off, scl) of the linear map u = off + scl x taking
+    /// the domain onto the window
+    pub fn map_parameters(&self) -> (f64, f64)
+    {
+        let ([d0, d1], [w0, w1]) = (self.domain, self.window);
+        let scl = (w1 - w0) / (d1 - d0);
+        let off = (w0 * d1 - w1 * d0) / (d1 - d0);
+        (off, scl)
+    }
+
+    /// Evaluates the series at x
+    pub fn eval(&self, x: f64) -> f64
+    {
+        let (off, scl) = self.map_parameters();
+        self.basis.clenshaw(&self.coeffs, off + scl * x)
+    }
+
+    /// Evaluates the series at each element of xs
+    pub fn eval_array(&self, xs: &Array<f64>) -> Array<f64>
+    {
+        let values = xs.as_slice().iter().map(|&x| self.eval(x)).collect();
+        Array::new(values, Order::Row)
+    }
+
+    /// Converts the series to a polynomial in x in the power basis.
+    /// High degree series lose accuracy in the conversion, as the power basis
+    /// is badly conditioned.
+    pub fn to_polynomial(&self) -> Polynomial<f64>
+    {
+        let forms = self.basis.power_forms(self.degree());
+        let mut sum = Polynomial::new(vec![]);
+        for (k, form) in forms.into_iter().enumerate()
+        {
+            sum = sum + Polynomial::new(vec![self.coeffs[k]]) * form;
+        }
+        let (off, scl) = self.map_parameters();
+        sum.compose(&Polynomial::new(vec![off, scl]))
+    }
+
+    /// Expresses a polynomial in x as a series in the given basis, with the
+    /// given domain mapped onto the default window of the basis
+    pub fn from_polynomial(basis: Basis, p: &Polynomial<f64>, domain: [f64; 2]) -> Series
+    {
+        let mut series = Series::new_mapped(basis, vec![0f64], domain, basis.default_window());
+        let (off, scl) = series.map_parameters();
+        // Rewrite p in terms of u, then peel off the basis polynomials from
+        // the highest degree down
+        let mut q = p.compose(&Polynomial::new(vec![-off / scl, 1f64 / scl]));
+        let n = q.degree();
+        let forms = basis.power_forms(n);
+        let mut coeffs = vec![0f64; n + 1];
+        for k in (0..n + 1).rev()
+        {
+            let qk = if k < q.coeffs().len() { q.coeffs()[k] } else { 0f64 };
+            coeffs[k] = qk / forms[k].coeffs()[k];
+            q = q - Polynomial::new(vec![coeffs[k]]) * forms[k].clone();
+        }
+        series.coeffs = coeffs;
+        series
+    }
+
+    /// Least squares fit of a series of degree deg to the points (x, y).
+    /// The domain is set to the range of x.
+    pub fn fit(basis: Basis, x: &Array<f64>, y: &Array<f64>, deg: usize) -> Series
+    {
+        let w = Array::<f64>::new_filled(1f64, x.len(), Order::Row);
+        Series::fit_weighted(basis, x, y, deg, &w)
+    }
+
+    /// Weighted least squares fit of a series of degree deg to the points
+    /// (x, y), minimizing Sum[(w_i (y_i - s(x_i)))^2]. For y_i with standard
+    /// deviations sigma_i, w_i = 1/sigma_i. The domain is set to the range of x.
+    pub fn fit_weighted(basis: Basis, x: &Array<f64>, y: &Array<f64>, deg: usize,
+                        w: &Array<f64>) -> Series
+    {
+        if x.len() != y.len() || x.len() != w.len()
+        {
+            panic!("Inconsistent sizes for x, y, w");
+        }
+        if x.len() < deg + 1
+        {
+            panic!("Fitting a degree {} series needs at least {} points", deg, deg + 1);
+        }
+        let xs = x.as_slice();
+        let lo = xs.iter().fold(f64::INFINITY, |a, &b| a.min(b));
+        let hi = xs.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+        let mut series = Series::new_mapped(basis, vec![0f64], [lo, hi], basis.default_window());
+        let (off, scl) = series.map_parameters();
+
+        let (m, n) = (x.len(), deg + 1);
+        let mut lhs = Matrix::<f64>::new_filled(0f64, m, n);
+        let mut rhs = Array::<f64>::new_filled(0f64, m, Order::Row);
+        for i in 0..m
+        {
+            let row = basis.vander(off + scl * x[i], deg);
+            for j in 0..n
+            {
+                lhs.set(i, j, w[i] * row[j]);
+            }
+            rhs[i] = w[i] * y[i];
+        }
+        // Scale the columns to unit norm to improve the conditioning
+        let mut col_scale = vec![1f64; n];
+        for j in 0..n
+        {
+            let norm = (0..m).map(|i| lhs.get(i, j).powi(2)).sum::<f64>().sqrt();
+            if norm != 0f64
+            {
+                col_scale[j] = norm;
+            }
+            for i in 0..m
+            {
+                let v = *lhs.get(i, j) / col_scale[j];
+                lhs.set(i, j, v);
+            }
+        }
+        let solution = lapacke::least_squares(&lhs, &rhs);
+        series.coeffs = (0..n).map(|j| solution[j] / col_scale[j]).collect();
+        series
+    }
+
+    /// m'th derivative of the series with respect to x
+    pub fn derivative(&self, m: usize) -> Series
+    {
+        let (_, scl) = self.map_parameters();
+        let mut coeffs = self.coeffs.clone();
+        for _ in 0..m
+        {
+            coeffs = self.basis.derivative(&coeffs).iter().map(|c| c * scl).collect();
+        }
+        Series{coeffs: coeffs, ..self.clone()}
+    }
+
+    /// m'th antiderivative of the series with respect to x, with every
+    /// integration constant chosen so that the result and its first m-1
+    /// derivatives vanish at the lower end of the domain
+    pub fn integral(&self, m: usize) -> Series
+    {
+        let (off, scl) = self.map_parameters();
+        let u0 = off + scl * self.domain[0];
+        let mut coeffs = self.coeffs.clone();
+        for _ in 0..m
+        {
+            let scaled: Vec<f64> = coeffs.iter().map(|c| c / scl).collect();
+            coeffs = self.basis.integral(&scaled, u0);
+        }
+        Series{coeffs: coeffs, ..self.clone()}
+    }
+
+    /// Definite integral of the series from a to b
+    pub fn integrate(&self, a: f64, b: f64) -> f64
+    {
+        let s = self.integral(1);
+        s.eval(b) - s.eval(a)
+    }
+
+    /// All complex roots of the series in x, as [re, im] pairs sorted by real
+    /// and then imaginary part, from the eigenvalues of the companion matrix
+    /// of the basis. Trailing zero coefficients are ignored, so a constant
+    /// series has no roots.
+    pub fn roots(&self) -> Vec<[f64; 2]>
+    {
+        let mut n = self.coeffs.len() - 1;
+        while n > 0 && self.coeffs[n] == 0f64
+        {
+            n -= 1;
+        }
+        if n == 0
+        {
+            return vec![];
+        }
+        let companion = self.basis.companion(&self.coeffs[..n + 1]);
+        let (off, scl) = self.map_parameters();
+        let mut roots: Vec<[f64; 2]> = lapacke::eigenvalues(&companion).iter()
+            .map(|u| [(u[0] - off) / scl, u[1] / scl])
+            .collect();
+        roots.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        roots
+    }
+}
@@ -0,0 +1,1202 @@
+/// Roots of scalar functions f(x) = 0 over Rust closures.
+/// The bracketing methods (bisection, false position, Ridder, Brent and
+/// TOMS 748) need an interval [a, b] on which f changes sign and always
+/// converge; the derivative-based methods (Newton, Halley and secant) start
+/// from a single point, converge faster near a simple root but may diverge.
+/// Every scalar solver returns a `RootResult`; failing to converge within maxiter
+/// iterations is reported there rather than by a panic.
+/// Systems of n nonlinear equations in n unknowns, F(x) = 0, are solved by
+/// `root` with Powell's hybrid method, Levenberg-Marquardt or Broyden's
+/// method, using `lapacke` for the linear algebra.
+/// For roots of polynomials see `gsl_poly`.
+
+use std::f64;
+use array::Array;
+use array::Order;
+use matrix::Matrix;
+use dense;
+
+/// Tolerances and iteration limit for the root finders. The root is
+/// accepted once it is known to within xtol + rtol * |x|.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RootOptions
+{
+    /// Absolute tolerance, 2e-12 by default
+    pub xtol: f64,
+    /// Relative tolerance, 4 times machine epsilon by default, which is also
+    /// the smallest value the bracketing methods accept
+    pub rtol: f64,
+    /// Maximum number of iterations, 100 by default
+    pub maxiter: usize,
+}
+
+impl Default for RootOptions
+{
+    fn default() -> RootOptions
+    {
+        RootOptions{xtol: 2e-12, rtol: 4f64 * f64::EPSILON, maxiter: 100}
+    }
+}
+
+/// Outcome of a root finder
+#[derive(Debug, Clone, PartialEq)]
+pub struct RootResult
+{
+    /// Estimate of the root
+    pub root: f64,
+    /// Number of iterations done
+    pub iterations: usize,
+    /// Number of evaluations of the function and its derivatives
+    pub function_calls: usize,
+    /// Whether the tolerances were met
+    pub converged: bool,
+    /// Description of the reason the solver stopped
+    pub message: String,
+}
+
+/// A function counting its evaluations
+struct Counter<F>
+{
+    f: F,
+    calls: usize,
+}
+
+impl<F> Counter<F>
+    where F: Fn(f64) -> f64
+{
+    fn new(f: F) -> Counter<F>
+    {
+        Counter{f: f, calls: 0}
+    }
+
+    fn call(&mut self, x: f64) -> f64
+    {
+        self.calls += 1;
+        (self.f)(x)
+    }
+}
+
+fn result(root: f64, iterations: usize, calls: usize, converged: bool) -> RootResult
+{
+    let message = if converged
+    {
+        String::from("converged")
+    } else {
+        format!("Failed to converge after {} iterations, value is {}", iterations, root)
+    };
+    RootResult{root: root, iterations: iterations, function_calls: calls,
+               converged: converged, message: message}
+}
+
+fn check_options(opts: &RootOptions, min_rtol: f64)
+{
+    if !(opts.xtol > 0f64)
+    {
+        panic!("Invalid value for xtol: {}", opts.xtol);
+    }
+    if !(opts.rtol >= min_rtol)
+    {
+        panic!("Invalid value for rtol: {} (must be at least {})", opts.rtol, min_rtol);
+    }
+    if opts.maxiter == 0
+    {
+        panic!("Invalid value for maxiter: 0");
+    }
+}
+
+/// Evaluates f at both ends of the bracket, checking for a sign change.
+/// Returns the result if either end is already a root.
+fn start_bracket<F>(f: &mut Counter<F>, a: f64, b: f64, opts: &RootOptions)
+    -> Result<(f64, f64), RootResult>
+    where F: Fn(f64) -> f64
+{
+    check_options(opts, 4f64 * f64::EPSILON);
+    if !a.is_finite() || !b.is_finite()
+    {
+        panic!("Invalid bracket: [{}, {}]", a, b);
+    }
+    let fa = f.call(a);
+    let fb = f.call(b);
+    if fa * fb > 0f64
+    {
+        panic!("f(a) and f(b) must have different signs: f({}) = {}, f({}) = {}", a, fa, b, fb);
+    }
+    if fa == 0f64
+    {
+        return Err(result(a, 0, f.calls, true));
+    }
+    if fb == 0f64
+    {
+        return Err(result(b, 0, f.calls, true));
+    }
+    Ok((fa, fb))
+}
+
+
+////////////////
+// Bracketing //
+////////////////
+
+/// Bisection on the bracket [a, b]: slow, but needs nothing but a sign
+/// change of f
+pub fn bisect<F>(f: F, a: f64, b: f64, opts: &RootOptions) -> RootResult
+    where F: Fn(f64) -> f64
+{
+    let mut f = Counter::new(f);
+    let (fa, _) = match start_bracket(&mut f, a, b, opts)
+    {
+        Ok(v) => v,
+        Err(r) => return r,
+    };
+    let (mut xa, mut fa) = (a, fa);
+    let mut dm = b - a;
+    for i in 0..opts.maxiter
+    {
+        dm *= 0.5;
+        let xm = xa + dm;
+        let fm = f.call(xm);
+        if fm * fa >= 0f64
+        {
+            xa = xm;
+            fa = fm;
+        }
+        if fm == 0f64 || dm.abs() < opts.xtol + opts.rtol * xm.abs()
+        {
+            return result(xm, i + 1, f.calls, true);
+        }
+    }
+    result(xa, opts.maxiter, f.calls, false)
+}
+
+/// False position on the bracket [a, b] with the Illinois modification,
+/// which halves the retained function value whenever the same end point is
+/// kept twice, giving superlinear convergence
+pub fn false_position<F>(f: F, a: f64, b: f64, opts: &RootOptions) -> RootResult
+    where F: Fn(f64) -> f64
+{
+    let mut f = Counter::new(f);
+    let (fa, fb) = match start_bracket(&mut f, a, b, opts)
+    {
+        Ok(v) => v,
+        Err(r) => return r,
+    };
+    // b is always the latest estimate, and the root lies between a and b
+    let (mut a, mut fa, mut b, mut fb) = (a, fa, b, fb);
+    for i in 0..opts.maxiter
+    {
+        let c = b - fb * (b - a) / (fb - fa);
+        let fc = f.call(c);
+        if fc == 0f64
+        {
+            return result(c, i + 1, f.calls, true);
+        }
+        if fc * fb < 0f64
+        {
+            a = b;
+            fa = fb;
+        } else {
+            fa *= 0.5;
+        }
+        b = c;
+        fb = fc;
+        if (b - a).abs() < opts.xtol + opts.rtol * b.abs()
+        {
+            return result(b, i + 1, f.calls, true);
+        }
+    }
+    result(b, opts.maxiter, f.calls, false)
+}
+
+/// Ridder's method on the bracket [a, b], which fits an exponential through
+/// the ends and midpoint at each step and converges quadratically
+pub fn ridder<F>(f: F, a: f64, b: f64, opts: &RootOptions) -> RootResult
+    where F: Fn(f64) -> f64
+{
+    let mut f = Counter::new(f);
+    let (fa, fb) = match start_bracket(&mut f, a, b, opts)
+    {
+        Ok(v) => v,
+        Err(r) => return r,
+    };
+    let (mut xa, mut fa, mut xb, mut fb) = (a, fa, b, fb);
+    let mut tol = opts.xtol;
+    let mut xn = xa;
+    for i in 0..opts.maxiter
+    {
+        let dm = 0.5 * (xb - xa);
+        let xm = xa + dm;
+        let fm = f.call(xm);
+        let dn = (fb - fa).signum() * dm * fm / (fm * fm - fa * fb).sqrt();
+        xn = xm - dn.signum() * dn.abs().min(dm.abs() - 0.5 * tol);
+        let fnew = f.call(xn);
+        if fnew * fm < 0f64
+        {
+            xa = xn;
+            fa = fnew;
+            xb = xm;
+            fb = fm;
+        } else if fnew * fa < 0f64 {
+            xb = xn;
+            fb = fnew;
+        } else {
+            xa = xn;
+            fa = fnew;
+        }
+        tol = opts.xtol + opts.rtol * xn.abs();
+        if fnew == 0f64 || (xb - xa).abs() < tol
+        {
+            return result(xn, i + 1, f.calls, true);
+        }
+    }
+    result(xn, opts.maxiter, f.calls, false)
+}
+
+/// Brent's method on the bracket [a, b], combining bisection with secant
+/// and inverse quadratic interpolation steps. This is the method of choice
+/// for a bracketed root.
+pub fn brentq<F>(f: F, a: f64, b: f64, opts: &RootOptions) -> RootResult
+    where F: Fn(f64) -> f64
+{
+    let mut f = Counter::new(f);
+    let (fa, fb) = match start_bracket(&mut f, a, b, opts)
+    {
+        Ok(v) => v,
+        Err(r) => return r,
+    };
+    let (mut xpre, mut xcur) = (a, b);
+    let (mut fpre, mut fcur) = (fa, fb);
+    let (mut xblk, mut fblk) = (0f64, 0f64);
+    let (mut spre, mut scur) = (0f64, 0f64);
+    for i in 0..opts.maxiter
+    {
+        if fpre != 0f64 && fcur != 0f64 && (fpre < 0f64) != (fcur < 0f64)
+        {
+            xblk = xpre;
+            fblk = fpre;
+            spre = xcur - xpre;
+            scur = spre;
+        }
+        if fblk.abs() < fcur.abs()
+        {
+            xpre = xcur;
+            xcur = xblk;
+            xblk = xpre;
+            fpre = fcur;
+            fcur = fblk;
+            fblk = fpre;
+        }
+        let delta = (opts.xtol + opts.rtol * xcur.abs()) / 2f64;
+        let sbis = (xblk - xcur) / 2f64;
+        if fcur == 0f64 || sbis.abs() < delta
+        {
+            return result(xcur, i + 1, f.calls, true);
+        }
+        if spre.abs() > delta && fcur.abs() < fpre.abs()
+        {
+            let stry = if xpre == xblk
+            {
+                // Secant
+                -fcur * (xcur - xpre) / (fcur - fpre)
+            } else {
+                // Inverse quadratic interpolation
+                let dpre = (fpre - fcur) / (xpre - xcur);
+                let dblk = (fblk - fcur) / (xblk - xcur);
+                -fcur * (fblk * dblk - fpre * dpre) / (dblk * dpre * (fblk - fpre))
+            };
+            if 2f64 * stry.abs() < spre.abs().min(3f64 * sbis.abs() - delta)
+            {
+                spre = scur;
+                scur = stry;
+            } else {
+                spre = sbis;
+                scur = sbis;
+            }
+        } else {
+            spre = sbis;
+            scur = sbis;
+        }
+        xpre = xcur;
+        fpre = fcur;
+        if scur.abs() > delta
+        {
+            xcur += scur;
+        } else {
+            xcur += if sbis > 0f64 { delta } else { -delta };
+        }
+        fcur = f.call(xcur);
+    }
+    result(xcur, opts.maxiter, f.calls, false)
+}
+
+/// |a - b| <= atol + rtol * |b|
+fn is_close(a: f64, b: f64, rtol: f64, atol: f64) -> bool
+{
+    (a - b).abs() <= atol + rtol * b.abs()
+}
+
+/// Binary exponent of x, as returned by frexp
+fn exponent(x: f64) -> i32
+{
+    if x == 0f64 || !x.is_finite()
+    {
+        0
+    } else {
+        x.abs().log2().floor() as i32 + 1
+    }
+}
+
+/// Zero of the cubic through (f_i, x_i), interpolating x as a function of f
+fn inverse_cubic_zero(x: [f64; 4], fx: [f64; 4]) -> f64
+{
+    let mut sum = 0f64;
+    for i in 0..4
+    {
+        let mut term = x[i];
+        for j in 0..4
+        {
+            if j != i
+            {
+                term *= fx[j] / (fx[j] - fx[i]);
+            }
+        }
+        sum += term;
+    }
+    sum
+}
+
+/// Two Newton steps towards the zero of the quadratic through (a, fa),
+/// (b, fb) and (d, fd), staying inside the bracket [a, b]
+fn newton_quadratic(a: f64, b: f64, fa: f64, fb: f64, d: f64, fd: f64) -> f64
+{
+    let b1 = (fb - fa) / (b - a);
+    let a2 = ((fd - fb) / (d - b) - b1) / (d - a);
+    if a2 == 0f64
+    {
+        return a - fa / b1;
+    }
+    let p = |x: f64| (a2 * (x - b) + b1) * (x - a) + fa;
+    let mut r = if a2.signum() * fa.signum() > 0f64 { a } else { b };
+    for _ in 0..2
+    {
+        let r1 = r - p(r) / (b1 + a2 * (2f64 * r - a - b));
+        if !(a < r1 && r1 < b)
+        {
+            if a < r && r < b
+            {
+                return r;
+            }
+            return 0.5 * (a + b);
+        }
+        r = r1;
+    }
+    r
+}
+
+/// Bracket [a, b] with values fab, narrowed to the side of c on which f
+/// changes sign. Returns the end point discarded.
+fn update_bracket(ab: &mut [f64; 2], fab: &mut [f64; 2], c: f64, fc: f64) -> (f64, f64)
+{
+    let i = if fab[0].signum() * fc.signum() > 0f64 { 0 } else { 1 };
+    let old = (ab[i], fab[i]);
+    ab[i] = c;
+    fab[i] = fc;
+    old
+}
+
+/// Algorithm 748 of Alefeld, Potra and Shi on the bracket [a, b]: inverse
+/// cubic interpolation safeguarded by Newton quadratic steps, a double
+/// secant step and bisection. It needs about as few function evaluations as
+/// any bracketing method while the width of the bracket is guaranteed to
+/// shrink. ACM TOMS 21 (1995).
+pub fn toms748<F>(f: F, a: f64, b: f64, opts: &RootOptions) -> RootResult
+    where F: Fn(f64) -> f64
+{
+    let mut f = Counter::new(f);
+    let (a, b) = if a <= b { (a, b) } else { (b, a) };
+    if a == b
+    {
+        panic!("Invalid bracket: [{}, {}]", a, b);
+    }
+    let (fa, fb) = match start_bracket(&mut f, a, b, opts)
+    {
+        Ok(v) => v,
+        Err(r) => return r,
+    };
+    let eps = f64::EPSILON;
+    let mut ab = [a, b];
+    let mut fab = [fa, fb];
+    let mid = |ab: &[f64; 2]| 0.5 * (ab[0] + ab[1]);
+
+    // The first step is a secant step, having only two points
+    let mut c = if fab[0].abs() < fab[1].abs()
+    {
+        a - fa * (b - a) / (fb - fa)
+    } else {
+        b - fb * (b - a) / (fb - fa)
+    };
+    if !(ab[0] < c && c < ab[1])
+    {
+        c = mid(&ab);
+    }
+    let fc = f.call(c);
+    if fc == 0f64
+    {
+        return result(c, 1, f.calls, true);
+    }
+    let (mut d, mut fd) = update_bracket(&mut ab, &mut fab, c, fc);
+    let mut e: Option<(f64, f64)> = None;
+    let mut iterations = 1;
+
+    while iterations < opts.maxiter
+    {
+        iterations += 1;
+        let width = ab[1] - ab[0];
+
+        // Inverse cubic interpolation if the four function values are
+        // distinct, otherwise a Newton quadratic step
+        let mut c = None;
+        if let Some((ev, fe)) = e
+        {
+            let fs = [fab[0], fab[1], fd, fe];
+            let separated = fs.iter().all(|v| *v != 0f64 && v.is_finite())
+                && (0..4).all(|i| (i + 1..4).all(|j| !is_close(fs[i], fs[j], 0f64, 32f64 * eps)));
+            if separated
+            {
+                let c0 = inverse_cubic_zero([ab[0], ab[1], d, ev], fs);
+                if ab[0] < c0 && c0 < ab[1]
+                {
+                    c = Some(c0);
+                }
+            }
+        }
+        let c = c.unwrap_or_else(|| newton_quadratic(ab[0], ab[1], fab[0], fab[1], d, fd));
+        let fc = f.call(c);
+        if fc == 0f64
+        {
+            return result(c, iterations, f.calls, true);
+        }
+        let old = update_bracket(&mut ab, &mut fab, c, fc);
+        d = old.0;
+        fd = old.1;
+
+        // Double length secant step from the end point with the smaller
+        // function value
+        let u_index = if fab[0].abs() < fab[1].abs() { 0 } else { 1 };
+        let (u, fu) = (ab[u_index], fab[u_index]);
+        let slope = (fab[1] - fab[0]) / (ab[1] - ab[0]);
+        let mut c = u - 2f64 * fu / slope;
+        if (c - u).abs() > 0.5 * (ab[1] - ab[0])
+        {
+            c = mid(&ab);
+        } else if is_close(c, u, eps, 0f64) {
+            // The step vanished, because the function values differ
+            // greatly in magnitude or the root is very close to u
+            if exponent(fab[u_index]) < exponent(fab[1 - u_index]) - 50
+            {
+                c = (31f64 * ab[u_index] + ab[1 - u_index]) / 32f64;
+            } else {
+                let sign = if u_index == 0 { 1f64 } else { -1f64 };
+                c = u + sign * (c.abs() * opts.rtol + opts.xtol);
+            }
+            if !(ab[0] < c && c < ab[1])
+            {
+                c = mid(&ab);
+            }
+        }
+        let fc = f.call(c);
+        if fc == 0f64
+        {
+            return result(c, iterations, f.calls, true);
+        }
+        e = Some((d, fd));
+        let old = update_bracket(&mut ab, &mut fab, c, fc);
+        d = old.0;
+        fd = old.1;
+
+        // Bisect if the bracket did not shrink enough
+        if ab[1] - ab[0] > 0.5 * width
+        {
+            e = Some((d, fd));
+            let z = mid(&ab);
+            let fz = f.call(z);
+            if fz == 0f64
+            {
+                return result(z, iterations, f.calls, true);
+            }
+            let old = update_bracket(&mut ab, &mut fab, z, fz);
+            d = old.0;
+            fd = old.1;
+        }
+
+        if is_close(ab[0], ab[1], opts.rtol, opts.xtol)
+        {
+            return result(mid(&ab), iterations, f.calls, true);
+        }
+    }
+    result(mid(&ab), iterations, f.calls, false)
+}
+
+
+//////////////////////
+// Derivative-based //
+//////////////////////
+
+/// Newton's method from x0, or Halley's if the second derivative is given
+fn newton_halley<F, D, D2>(f: F, fprime: D, fprime2: Option<D2>, x0: f64,
+                           opts: &RootOptions) -> RootResult
+    where F: Fn(f64) -> f64,
+          D: Fn(f64) -> f64,
+          D2: Fn(f64) -> f64
+{
+    check_options(opts, 0f64);
+    let mut calls = 0;
+    let mut p0 = x0;
+    for i in 0..opts.maxiter
+    {
+        let fval = f(p0);
+        calls += 1;
+        if fval == 0f64
+        {
+            return result(p0, i + 1, calls, true);
+        }
+        let fder = fprime(p0);
+        calls += 1;
+        if fder == 0f64
+        {
+            let mut r = result(p0, i + 1, calls, false);
+            r.message = String::from("Derivative was zero.");
+            return r;
+        }
+        let mut step = fval / fder;
+        if let Some(ref fprime2) = fprime2
+        {
+            let fder2 = fprime2(p0);
+            calls += 1;
+            // Halley's correction, unless it would overshoot
+            let adj = step * fder2 / fder / 2f64;
+            if adj.abs() < 1f64
+            {
+                step /= 1f64 - adj;
+            }
+        }
+        let p = p0 - step;
+        if (p - p0).abs() < opts.xtol + opts.rtol * p.abs()
+        {
+            return result(p, i + 1, calls, true);
+        }
+        p0 = p;
+    }
+    result(p0, opts.maxiter, calls, false)
+}
+
+/// Newton's method from x0, given the derivative of f. Converges
+/// quadratically near a simple root.
+pub fn newton<F, D>(f: F, fprime: D, x0: f64, opts: &RootOptions) -> RootResult
+    where F: Fn(f64) -> f64,
+          D: Fn(f64) -> f64
+{
+    newton_halley(f, fprime, None::<fn(f64) -> f64>, x0, opts)
+}
+
+/// Halley's method from x0, given the first and second derivatives of f.
+/// Converges cubically near a simple root.
+pub fn halley<F, D, D2>(f: F, fprime: D, fprime2: D2, x0: f64,
+                        opts: &RootOptions) -> RootResult
+    where F: Fn(f64) -> f64,
+          D: Fn(f64) -> f64,
+          D2: Fn(f64) -> f64
+{
+    newton_halley(f, fprime, Some(fprime2), x0, opts)
+}
+
+/// The secant method from x0 and a second point a relative distance of
+/// 1e-4 away, needing no derivative
+pub fn secant<F>(f: F, x0: f64, opts: &RootOptions) -> RootResult
+    where F: Fn(f64) -> f64
+{
+    check_options(opts, 0f64);
+    let mut f = Counter::new(f);
+    let eps = 1e-4;
+    let mut p0 = x0;
+    let mut p1 = x0 * (1f64 + eps);
+    p1 += if p1 >= 0f64 { eps } else { -eps };
+    let mut q0 = f.call(p0);
+    let mut q1 = f.call(p1);
+    if q1.abs() < q0.abs()
+    {
+        ::std::mem::swap(&mut p0, &mut p1);
+        ::std::mem::swap(&mut q0, &mut q1);
+    }
+    for i in 0..opts.maxiter
+    {
+        if q1 == q0
+        {
+            // The secant is flat; the best estimate is the midpoint
+            let p = 0.5 * (p1 + p0);
+            let converged = (p1 - p0).abs() < opts.xtol + opts.rtol * p.abs();
+            let mut r = result(p, i + 1, f.calls, converged);
+            if !converged
+            {
+                r.message = format!("Tolerance of {} reached.", p1 - p0);
+            }
+            return r;
+        }
+        let p = if q1.abs() > q0.abs()
+        {
+            (-q0 / q1 * p1 + p0) / (1f64 - q0 / q1)
+        } else {
+            (-q1 / q0 * p0 + p1) / (1f64 - q1 / q0)
+        };
+        if is_close(p, p1, opts.rtol, opts.xtol)
+        {
+            return result(p, i + 1, f.calls, true);
+        }
+        p0 = p1;
+        q0 = q1;
+        p1 = p;
+        q1 = f.call(p1);
+    }
+    result(p1, opts.maxiter, f.calls, false)
+}
+
+
+//////////////////////////
+// Systems of equations //
+//////////////////////////
+
+/// Method for solving a system of nonlinear equations F(x) = 0
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SystemMethod
+{
+    /// Powell's hybrid method as in MINPACK's hybrd: a dogleg trust region
+    /// combining Gauss-Newton and steepest descent steps, with rank one
+    /// Broyden updates of the Jacobian between evaluations
+    Hybrid,
+    /// Levenberg-Marquardt minimization of |F(x)|^2 with Nielsen's damping
+    /// strategy, which also finds least squares solutions where there is no
+    /// root
+    LevenbergMarquardt,
+    /// Broyden's good method with a backtracking line search, evaluating
+    /// the Jacobian only at the start and when the line search fails
+    Broyden,
+}
+
+/// Options for `root`
+pub struct SystemOptions<'a>
+{
+    /// Solution method, Hybrid by default
+    pub method: SystemMethod,
+    /// Relative tolerance on x, 1.49012e-8 by default
+    pub xtol: f64,
+    /// Maximum number of evaluations of F, excluding those for difference
+    /// quotients; 200 (n + 1) for n unknowns if None
+    pub maxfev: Option<usize>,
+    /// Jacobian dF/dx, estimated by forward differences if None
+    pub jac: Option<Box<Fn(&Array<f64>) -> Matrix<f64> + 'a>>,
+}
+
+impl<'a> Default for SystemOptions<'a>
+{
+    fn default() -> SystemOptions<'a>
+    {
+        SystemOptions{
+            method: SystemMethod::Hybrid,
+            xtol: 1.49012e-8,
+            maxfev: None,
+            jac: None,
+        }
+    }
+}
+
+/// Outcome of `root`
+pub struct SystemResult
+{
+    /// Estimate of the solution
+    pub x: Array<f64>,
+    /// F at x
+    pub fun: Array<f64>,
+    /// Whether the tolerance was met
+    pub success: bool,
+    /// Description of the reason the solver stopped
+    pub message: String,
+    /// Number of evaluations of F, excluding those for difference quotients
+    pub nfev: usize,
+    /// Number of evaluations of the Jacobian
+    pub njev: usize,
+    /// Number of iterations done
+    pub nit: usize,
+}
+
+/// F and its Jacobian, counting evaluations
+struct System<'a>
+{
+    fun: &'a Fn(&Array<f64>) -> Array<f64>,
+    jac: Option<&'a Fn(&Array<f64>) -> Matrix<f64>>,
+    n: usize,
+    nfev: usize,
+    njev: usize,
+}
+
+impl<'a> System<'a>
+{
+    fn fun(&mut self, x: &[f64]) -> Vec<f64>
+    {
+        self.nfev += 1;
+        let f = (self.fun)(&Array::new(x.to_vec(), Order::Row));
+        if f.len() != self.n
+        {
+            panic!("System returned {} values, expected {}", f.len(), self.n);
+        }
+        f.as_slice().to_vec()
+    }
+
+    /// Jacobian at x as a row-major n * n vector, given F(x)
+    fn jacobian(&mut self, x: &[f64], f: &[f64]) -> Vec<f64>
+    {
+        let n = self.n;
+        self.njev += 1;
+        if let Some(jac) = self.jac
+        {
+            return dense::row_major(&jac(&Array::new(x.to_vec(), Order::Row)), n, n, "Jacobian");
+        }
+        let fun = self.fun;
+        let h: Vec<f64> = x.iter().map(|&v| dense::fd_step(v)).collect();
+        dense::forward_jacobian(|xh| fun(&Array::new(xh.to_vec(), Order::Row)).as_slice().to_vec(),
+                                x, f, &h)
+    }
+}
+
+/// Euclidean norm
+fn enorm(x: &[f64]) -> f64
+{
+    x.iter().fold(0f64, |s, v| s + v * v).sqrt()
+}
+
+/// Euclidean norm of d * x, elementwise
+fn scaled_norm(d: &[f64], x: &[f64]) -> f64
+{
+    d.iter().zip(x.iter()).fold(0f64, |s, (a, b)| s + (a * b) * (a * b)).sqrt()
+}
+
+/// Finds a root of the system F(x) = 0 of n equations in n unknowns from
+/// the estimate x0. Failure to converge is reported in the result.
+pub fn root<F>(f: F, x0: &Array<f64>, opts: &SystemOptions) -> SystemResult
+    where F: Fn(&Array<f64>) -> Array<f64>
+{
+    let n = x0.len();
+    if n == 0
+    {
+        panic!("Cannot solve a system of no equations.");
+    }
+    if !(opts.xtol >= 0f64)
+    {
+        panic!("Invalid value for xtol: {}", opts.xtol);
+    }
+    let maxfev = opts.maxfev.unwrap_or(200 * (n + 1));
+    let mut sys = System{
+        fun: &f,
+        jac: opts.jac.as_ref().map(|j| &**j),
+        n: n,
+        nfev: 0,
+        njev: 0,
+    };
+    let x0 = x0.as_slice().to_vec();
+    let (x, fx, success, message, nit) = match opts.method
+    {
+        SystemMethod::Hybrid => hybrid(&mut sys, x0, opts.xtol, maxfev),
+        SystemMethod::LevenbergMarquardt => levenberg_marquardt(&mut sys, x0, opts.xtol, maxfev),
+        SystemMethod::Broyden => broyden(&mut sys, x0, opts.xtol, maxfev),
+    };
+    SystemResult{
+        x: Array::new(x, Order::Row),
+        fun: Array::new(fx, Order::Row),
+        success: success,
+        message: message,
+        nfev: sys.nfev,
+        njev: sys.njev,
+        nit: nit,
+    }
+}
+
+/// Root of the system F(x) = 0 from the estimate x0 by Powell's hybrid
+/// method with default options. Panics if it fails to converge.
+pub fn fsolve<F>(f: F, x0: &Array<f64>) -> Array<f64>
+    where F: Fn(&Array<f64>) -> Array<f64>
+{
+    let res = root(f, x0, &SystemOptions::default());
+    if !res.success
+    {
+        panic!("fsolve failed to converge: {}", res.message);
+    }
+    res.x
+}
+
+/// Outcome of a system solver: x, F(x), success, message and iterations
+type SystemOutcome = (Vec<f64>, Vec<f64>, bool, String, usize);
+
+fn maxfev_message(maxfev: usize) -> String
+{
+    format!("The number of calls to function has reached maxfev = {}.", maxfev)
+}
+
+/// Dogleg step within the trust region |diag p| <= delta, for Jacobian j
+/// and residual f
+fn dogleg(j: &[f64], f: &[f64], diag: &[f64], delta: f64) -> Vec<f64>
+{
+    let n = f.len();
+    let neg_f: Vec<f64> = f.iter().map(|v| -v).collect();
+    let gauss_newton = dense::lin_solve(j, &neg_f);
+    let qnorm = match gauss_newton
+    {
+        Some(ref p) => {
+            let q = scaled_norm(diag, p);
+            if q <= delta
+            {
+                return p.clone();
+            }
+            q
+        },
+        None => f64::INFINITY,
+    };
+
+    // Scaled steepest descent direction
+    let jtf = dense::mat_t_vec(j, f);
+    let mut g: Vec<f64> = (0..n).map(|i| -jtf[i] / diag[i]).collect();
+    let gnorm = enorm(&g);
+    let mut sgnorm = 0f64;
+    if gnorm != 0f64
+    {
+        for i in 0..n
+        {
+            g[i] = g[i] / gnorm / diag[i];
+        }
+        let temp = enorm(&dense::mat_vec(j, &g));
+        sgnorm = gnorm / temp / temp;
+    }
+
+    // The minimizer along the steepest descent direction lies outside the
+    // trust region, or there is no Gauss-Newton step
+    if sgnorm >= delta || gauss_newton.is_none()
+    {
+        let t = sgnorm.min(delta);
+        return g.iter().map(|v| t * v).collect();
+    }
+
+    // Otherwise the point on the dogleg path at distance delta
+    let bnorm = enorm(f);
+    let dq = delta / qnorm;
+    let sd = sgnorm / delta;
+    let mut temp = bnorm / gnorm * (bnorm / qnorm) * sd;
+    temp = temp - dq * sd * sd
+        + ((temp - dq).powi(2) + (1f64 - dq * dq) * (1f64 - sd * sd)).sqrt();
+    let alpha = dq * (1f64 - sd * sd) / temp;
+    let t = (1f64 - alpha) * sgnorm.min(delta);
+    let p = gauss_newton.unwrap();
+    (0..n).map(|i| t * g[i] + alpha * p[i]).collect()
+}
+
+/// Powell's hybrid method, following MINPACK's hybrd
+fn hybrid(sys: &mut System, mut x: Vec<f64>, xtol: f64, maxfev: usize) -> SystemOutcome
+{
+    let n = x.len();
+    let mut fx = sys.fun(&x);
+    let mut fnorm = enorm(&fx);
+    let mut diag = vec![0f64; n];
+    let mut delta = 0f64;
+    let mut xnorm = 0f64;
+    let (mut ncsuc, mut ncfail, mut nslow1, mut nslow2) = (0, 0, 0, 0);
+    let mut iter = 1;
+    if fnorm == 0f64
+    {
+        return (x, fx, true, String::from("The solution converged."), 0);
+    }
+    loop
+    {
+        let mut j = sys.jacobian(&x, &fx);
+        let mut jeval = true;
+        for c in 0..n
+        {
+            let norm = (0..n).fold(0f64, |s, r| s + j[r * n + c] * j[r * n + c]).sqrt();
+            if iter == 1
+            {
+                diag[c] = if norm == 0f64 { 1f64 } else { norm };
+            } else {
+                diag[c] = diag[c].max(norm);
+            }
+        }
+        if iter == 1
+        {
+            xnorm = scaled_norm(&diag, &x);
+            delta = if xnorm == 0f64 { 100f64 } else { 100f64 * xnorm };
+        }
+
+        loop
+        {
+            let p = dogleg(&j, &fx, &diag, delta);
+            let pnorm = scaled_norm(&diag, &p);
+            if iter == 1
+            {
+                delta = delta.min(pnorm);
+            }
+            let x1: Vec<f64> = (0..n).map(|i| x[i] + p[i]).collect();
+            let f1 = sys.fun(&x1);
+            let fnorm1 = enorm(&f1);
+
+            // Ratio of the actual to the predicted reduction
+            let actred = if fnorm1 < fnorm { 1f64 - (fnorm1 / fnorm).powi(2) } else { -1f64 };
+            let jp = dense::mat_vec(&j, &p);
+            let predicted: Vec<f64> = (0..n).map(|i| fx[i] + jp[i]).collect();
+            let fnorm_pred = enorm(&predicted);
+            let prered = if fnorm_pred < fnorm
+            {
+                1f64 - (fnorm_pred / fnorm).powi(2)
+            } else {
+                0f64
+            };
+            let ratio = if prered > 0f64 { actred / prered } else { 0f64 };
+
+            if ratio < 0.1
+            {
+                ncsuc = 0;
+                ncfail += 1;
+                delta *= 0.5;
+            } else {
+                ncfail = 0;
+                ncsuc += 1;
+                if ratio >= 0.5 || ncsuc > 1
+                {
+                    delta = delta.max(pnorm / 0.5);
+                }
+                if (ratio - 1f64).abs() <= 0.1
+                {
+                    delta = pnorm / 0.5;
+                }
+            }
+
+            // Broyden update of the Jacobian along p, made before moving
+            let update: Vec<f64> = (0..n).map(|i| (f1[i] - fx[i] - jp[i]) / pnorm).collect();
+            let dp: Vec<f64> = (0..n).map(|i| diag[i] * diag[i] * p[i] / pnorm).collect();
+
+            if ratio >= 1e-4
+            {
+                x = x1;
+                fx = f1;
+                xnorm = scaled_norm(&diag, &x);
+                fnorm = fnorm1;
+                iter += 1;
+            }
+
+            nslow1 = if actred >= 0.001 { 0 } else { nslow1 + 1 };
+            if jeval
+            {
+                nslow2 += 1;
+            }
+            if actred >= 0.1
+            {
+                nslow2 = 0;
+            }
+
+            if delta <= xtol * xnorm || fnorm == 0f64
+            {
+                return (x, fx, true, String::from("The solution converged."), iter - 1);
+            }
+            if sys.nfev >= maxfev
+            {
+                return (x, fx, false, maxfev_message(maxfev), iter - 1);
+            }
+            if 0.1 * (0.1 * delta).max(pnorm) <= f64::EPSILON * xnorm
+            {
+                let msg = format!("xtol={} is too small, no further improvement in the \
+                                   approximate solution is possible.", xtol);
+                return (x, fx, false, msg, iter - 1);
+            }
+            if nslow2 == 5
+            {
+                let msg = String::from("The iteration is not making good progress, as measured \
+                                        by the improvement from the last five Jacobian \
+                                        evaluations.");
+                return (x, fx, false, msg, iter - 1);
+            }
+            if nslow1 == 10
+            {
+                let msg = String::from("The iteration is not making good progress, as measured \
+                                        by the improvement from the last ten iterations.");
+                return (x, fx, false, msg, iter - 1);
+            }
+            if ncfail == 2
+            {
+                break;
+            }
+
+            for r in 0..n
+            {
+                for c in 0..n
+                {
+                    j[r * n + c] += update[r] * dp[c];
+                }
+            }
+            jeval = false;
+        }
+    }
+}
+
+/// Levenberg-Marquardt with Nielsen's update of the damping parameter
+fn levenberg_marquardt(sys: &mut System, mut x: Vec<f64>, xtol: f64,
+                       maxfev: usize) -> SystemOutcome
+{
+    let n = x.len();
+    let mut fx = sys.fun(&x);
+    let normal = |sys: &mut System, x: &[f64], fx: &[f64]| {
+        let j = sys.jacobian(x, fx);
+        let mut a = vec![0f64; n * n];
+        for r in 0..n
+        {
+            for c in 0..n
+            {
+                a[r * n + c] = (0..n).fold(0f64, |s, k| s + j[k * n + r] * j[k * n + c]);
+            }
+        }
+        (a, dense::mat_t_vec(&j, fx))
+    };
+    let (mut a, mut g) = normal(sys, &x, &fx);
+    let mut mu = 1e-3 * (0..n).fold(0f64, |m, i| m.max(a[i * n + i]));
+    let mut nu = 2f64;
+    let mut nit = 0;
+    while sys.nfev < maxfev
+    {
+        if enorm(&fx) == 0f64 || g.iter().all(|v| *v == 0f64)
+        {
+            return (x, fx, true, String::from("The solution converged."), nit);
+        }
+        nit += 1;
+        let mut damped = a.clone();
+        for i in 0..n
+        {
+            damped[i * n + i] += mu;
+        }
+        let neg_g: Vec<f64> = g.iter().map(|v| -v).collect();
+        let p = match dense::lin_solve(&damped, &neg_g)
+        {
+            Some(p) => p,
+            None => {
+                mu *= nu;
+                nu *= 2f64;
+                if !mu.is_finite()
+                {
+                    return (x, fx, false, String::from("The damped system is singular."), nit);
+                }
+                continue;
+            },
+        };
+        if enorm(&p) <= xtol * (enorm(&x) + xtol)
+        {
+            return (x, fx, true, String::from("The solution converged."), nit);
+        }
+        let x1: Vec<f64> = (0..n).map(|i| x[i] + p[i]).collect();
+        let f1 = sys.fun(&x1);
+        let predicted = (0..n).fold(0f64, |s, i| s + p[i] * (mu * p[i] - g[i]));
+        let rho = (enorm(&fx).powi(2) - enorm(&f1).powi(2)) / predicted;
+        if rho > 0f64
+        {
+            x = x1;
+            fx = f1;
+            let (a1, g1) = normal(sys, &x, &fx);
+            a = a1;
+            g = g1;
+            mu *= (1f64 / 3f64).max(1f64 - (2f64 * rho - 1f64).powi(3));
+            nu = 2f64;
+        } else {
+            mu *= nu;
+            nu *= 2f64;
+        }
+    }
+    (x, fx, false, maxfev_message(maxfev), nit)
+}
+
+/// Broyden's good method, updating the inverse Jacobian by the Sherman-
+/// Morrison formula
+fn broyden(sys: &mut System, mut x: Vec<f64>, xtol: f64, maxfev: usize) -> SystemOutcome
+{
+    const MAX_HALVINGS: usize = 10;
+    let n = x.len();
+    let mut fx = sys.fun(&x);
+    let mut h: Option<Vec<f64>> = None;
+    let mut fresh = false;
+    let mut nit = 0;
+    while sys.nfev < maxfev
+    {
+        if enorm(&fx) == 0f64
+        {
+            return (x, fx, true, String::from("The solution converged."), nit);
+        }
+        if h.is_none()
+        {
+            let j = sys.jacobian(&x, &fx);
+            h = dense::inverse(&j, n);
+            if h.is_none()
+            {
+                return (x, fx, false, String::from("The Jacobian is singular."), nit);
+            }
+            fresh = true;
+        }
+        nit += 1;
+        let hm = h.take().unwrap();
+        let p: Vec<f64> = dense::mat_vec(&hm, &fx).iter().map(|v| -v).collect();
+
+        // Backtrack until |F| decreases sufficiently
+        let fnorm = enorm(&fx);
+        let mut alpha = 1f64;
+        let mut accepted = None;
+        for _ in 0..MAX_HALVINGS
+        {
+            let x1: Vec<f64> = (0..n).map(|i| x[i] + alpha * p[i]).collect();
+            let f1 = sys.fun(&x1);
+            if enorm(&f1) <= (1f64 - 1e-4 * alpha) * fnorm
+            {
+                accepted = Some((x1, f1));
+                break;
+            }
+            alpha *= 0.5;
+        }
+        let (x1, f1) = match accepted
+        {
+            Some(v) => v,
+            None => {
+                if fresh
+                {
+                    let msg = String::from("The iteration is not making good progress; the \
+                                            line search failed.");
+                    return (x, fx, false, msg, nit);
+                }
+                // Start again from the true Jacobian
+                continue;
+            },
+        };
+
+        let s: Vec<f64> = (0..n).map(|i| x1[i] - x[i]).collect();
+        let y: Vec<f64> = (0..n).map(|i| f1[i] - fx[i]).collect();
+        let hy = dense::mat_vec(&hm, &y);
+        let sh = dense::mat_t_vec(&hm, &s);
+        let denom = (0..n).fold(0f64, |acc, i| acc + s[i] * hy[i]);
+        let mut hm = hm;
+        if denom != 0f64
+        {
+            for r in 0..n
+            {
+                for c in 0..n
+                {
+                    hm[r * n + c] += (s[r] - hy[r]) * sh[c] / denom;
+                }
+            }
+        }
+        h = Some(hm);
+        fresh = false;
+        x = x1;
+        fx = f1;
+        if enorm(&s) <= xtol * (enorm(&x) + xtol)
+        {
+            return (x, fx, true, String::from("The solution converged."), nit);
+        }
+    }
+    (x, fx, false, maxfev_message(maxfev), nit)
+}
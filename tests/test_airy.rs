@@ -6,6 +6,7 @@ extern crate rustsci;
 
 use rustsci::gsl_airy;
 use rustsci::gsl_math;
+use rustsci::gsl_sf;
 
 const EPS: f64 = 0.00000000001;
 
@@ -152,3 +153,22 @@ fn test_airy_zero_bi_deriv()
     assert_epeq!(gsl_airy::airy_zero_bi_deriv(200).0, -96.04731050310324450, EPS);
     assert_epeq!(gsl_airy::airy_zero_bi_deriv(1000).0, -281.0315164471118527, EPS);
 }
+
+#[test]
+fn test_airy_precision_modes()
+{
+    // Reduced precision modes must stay within their documented accuracy
+    assert_epeq!(gsl_airy::airy_ai_mode(-5.0, gsl_sf::Precision::Single).0,
+                 0.3507610090241142, 1e-6);
+    assert_epeq!(gsl_airy::airy_bi_mode(1.649999999999991, gsl_sf::Precision::Single).0,
+                 2.196407956850028, 1e-6);
+    assert_epeq!(gsl_airy::airy_ai_mode(0.6999999999999907, gsl_sf::Precision::Approx).0,
+                 0.1891624003981519, 1e-3);
+    assert_epeq!(gsl_airy::airy_bi_deriv_mode(3.249999999999988, gsl_sf::Precision::Approx).0,
+                 36.55485149250338, 1e-3);
+
+    // Double precision is the default and matches the unsuffixed functions
+    assert_eq!(gsl_sf::Precision::default(), gsl_sf::Precision::Double);
+    assert_eq!(gsl_airy::airy_ai_mode(2.54999999999999, gsl_sf::Precision::Double),
+               gsl_airy::airy_ai(2.54999999999999));
+}
@@ -0,0 +1,149 @@
+////////////////////////////////////////
+// Output Array Sizes of GSL Wrappers //
+////////////////////////////////////////
+// Every wrapper below passes a guard-padded buffer to the GSL and panics if
+// anything is written outside it, so these tests exercise each wrapper over
+// a range of sizes (including a single element) and check the result length
+// and contents against the scalar functions.
+#[macro_use]
+extern crate rustsci;
+
+use rustsci::array;
+use rustsci::gsl_bessel;
+use rustsci::gsl_coulomb;
+use rustsci::gsl_poly;
+use rustsci::gsl_math;
+
+const EPS: f64 = 0.00000000001;
+
+#[test]
+fn test_bessel_integer_order_arrays()
+{
+    let x = 1.5;
+    for &(nmin, nmax) in [(0, 0), (3, 3), (0, 4), (2, 7)].iter()
+    {
+        let size = (nmax - nmin + 1) as usize;
+        let j = gsl_bessel::bessel_jnr_array(nmin, nmax, x);
+        let y = gsl_bessel::bessel_ynr_array(nmin, nmax, x);
+        let i = gsl_bessel::bessel_inr_array(nmin, nmax, x);
+        let is = gsl_bessel::bessel_inr_scaled_array(nmin, nmax, x);
+        let k = gsl_bessel::bessel_nn_array(nmin, nmax, x);
+        let ks = gsl_bessel::bessel_kni_scaled_array(nmin, nmax, x);
+        for a in [&j, &y, &i, &is, &k, &ks].iter()
+        {
+            assert_eq!(a.len(), size);
+        }
+        for n in nmin..nmax + 1
+        {
+            let idx = (n - nmin) as usize;
+            assert_epeq!(j[idx],  gsl_bessel::bessel_jnr(n, x).0,        EPS);
+            assert_epeq!(y[idx],  gsl_bessel::bessel_ynr(n, x).0,        EPS);
+            assert_epeq!(i[idx],  gsl_bessel::bessel_inr(n, x).0,        EPS);
+            assert_epeq!(is[idx], gsl_bessel::bessel_inr_scaled(n, x).0, EPS);
+            assert_epeq!(k[idx],  gsl_bessel::bessel_kni(n, x).0,        EPS);
+            assert_epeq!(ks[idx], gsl_bessel::bessel_kni_scaled(n, x).0, EPS);
+        }
+    }
+}
+
+#[test]
+fn test_bessel_spherical_arrays()
+{
+    let x = 1.5;
+    for &lmax in [0usize, 1, 2, 6].iter()
+    {
+        let j = gsl_bessel::bessel_jl_array(lmax, x);
+        let js = gsl_bessel::bessel_jlr_steed_array(lmax, x);
+        let y = gsl_bessel::bessel_yli_array(lmax, x);
+        let i = gsl_bessel::bessel_il_scaled_array(lmax, x);
+        let k = gsl_bessel::bessel_kl_scaled_array(lmax, x);
+        for a in [&j, &js, &y, &i, &k].iter()
+        {
+            assert_eq!(a.len(), lmax + 1);
+        }
+        for l in 0..lmax + 1
+        {
+            assert_epeq!(j[l], js[l], 1e-9);
+            assert_epeq!(y[l], gsl_bessel::bessel_yli(l as i32, x).0,        EPS);
+            assert_epeq!(i[l], gsl_bessel::bessel_il_scaled(l as i32, x).0, EPS);
+            assert_epeq!(k[l], gsl_bessel::bessel_kl_scaled(l as i32, x).0, EPS);
+        }
+        assert_epeq!(j[0], gsl_bessel::bessel_j0i(x).0, EPS);
+    }
+}
+
+#[test]
+fn test_bessel_sequence_array()
+{
+    let v = arr![0.5, 1.0, 2.0, 4.0];
+    let j = gsl_bessel::bessel_sequence_jnur(1.5, &v);
+    assert_eq!(j.len(), v.len());
+    for i in 0..v.len()
+    {
+        assert_epeq!(j[i], gsl_bessel::bessel_jnur(1.5, v[i]).0, EPS);
+    }
+}
+
+#[test]
+fn test_coulomb_arrays()
+{
+    let (eta, x) = (1.0, 5.0);
+    for &kmax in [0usize, 1, 4].iter()
+    {
+        let f = gsl_coulomb::coulomb_wave_f_array(0.0, kmax, eta, x);
+        let (f2, g2) = gsl_coulomb::coulomb_wave_fg_array(0.0, kmax, eta, x);
+        let (f3, g3, fp3, gp3) = gsl_coulomb::coulomb_wave_fgp_array(0.0, kmax, eta, x);
+        let sphf = gsl_coulomb::coulomb_wave_sphf_array(0.0, kmax, eta, x);
+        let cl = gsl_coulomb::coulomb_cl_array(0.0, kmax, eta);
+        for a in [&f, &f2, &g2, &f3, &g3, &fp3, &gp3, &sphf, &cl].iter()
+        {
+            assert_eq!(a.len(), kmax + 1);
+        }
+        for l in 0..kmax + 1
+        {
+            let (fg, _) = gsl_coulomb::coulomb_wave_fg(eta, x, l as f64, 0);
+            assert_epeq!(f[l],   fg[0], EPS);
+            assert_epeq!(f2[l],  fg[0], EPS);
+            assert_epeq!(g2[l],  fg[2], EPS);
+            assert_epeq!(f3[l],  fg[0], EPS);
+            assert_epeq!(fp3[l], fg[1], EPS);
+            assert_epeq!(g3[l],  fg[2], EPS);
+            assert_epeq!(gp3[l], fg[3], EPS);
+            assert_epeq!(sphf[l], fg[0] / x, EPS);
+            assert_epeq!(cl[l], gsl_coulomb::coulomb_cl_e(l as f64, eta).0, EPS);
+        }
+    }
+}
+
+#[test]
+fn test_poly_arrays()
+{
+    let c = arr![1.0, -2.0, 0.5, 3.0];
+    let d = gsl_poly::poly_eval_derivs(&c, 2.0, 1);
+    assert_eq!(d.len(), 1);
+    let d = gsl_poly::poly_eval_derivs(&c, 2.0, 6);
+    assert_eq!(d.len(), 6);
+    assert_epeq!(d[0], 23.0, EPS);
+    assert_epeq!(d[1], 36.0, EPS);
+    assert_epeq!(d[3], 18.0, EPS);
+    assert_eq!(d[5], 0.0);
+
+    let xa = arr![0.0, 1.0, 2.0, 3.0];
+    let ya = arr![1.0, 2.0, 5.0, 10.0];
+    let dd = gsl_poly::poly_divdiff_init(&xa, &ya);
+    assert_eq!(dd.len(), 4);
+    let taylor = gsl_poly::poly_divdiff_to_taylor(0.0, &dd, &xa);
+    assert_eq!(taylor.len(), 4);
+    assert_epeq!(taylor[0], 1.0, EPS);
+    assert_epeq!(taylor[2], 1.0, EPS);
+
+    // Hermite representation of x^3 from values and slopes at 1 and 2
+    let xa = arr![1.0, 2.0];
+    let ya = arr![1.0, 8.0];
+    let dya = arr![3.0, 12.0];
+    let (dd, za) = gsl_poly::poly_divdiff_to_hermite(&xa, &ya, &dya);
+    assert_eq!(dd.len(), 4);
+    assert_eq!(za.len(), 4);
+    assert_eq!(za, arr![1.0, 1.0, 2.0, 2.0]);
+    assert_epeq!(gsl_poly::poly_divdiff_eval(&dd, &za, 1.5), 3.375, EPS);
+}
@@ -574,3 +574,162 @@ fn test_bessel_ix()
     assert_epeq!(gsl_bessel::bessel_zero_jnu(500.0, 22,  &r), 649.34132440891735, EPS);
 }
 */
+
+#[test]
+fn test_bessel_knu_scaled_e10()
+{
+    let r = gsl_bessel::bessel_knu_scaled_e10(2.5, 1.0);
+    assert_epeq!(r.to_f64().unwrap(), 8.773198961208501758455179, EPS);
+    // K_1000(0.001) ~ 10^5865 is far outside the range of a double
+    let r = gsl_bessel::bessel_knu_scaled_e10(1000.0, 0.001);
+    assert_epeq!(r.log10_abs(), 5865.334044184823274698436, 1e-9);
+}
+
+#[test]
+fn test_bessel_fractional_arrays()
+{
+    let j = gsl_bessel::bessel_jnu_array(0.3, 4, 2.5);
+    assert_eq!(j.len(), 5);
+    assert_epeq!(j[0], 0.17564108274377366,  EPS);
+    assert_epeq!(j[1], 0.53081912366779366,  EPS);
+    assert_epeq!(j[2], 0.37641080587073178,  EPS);
+    assert_epeq!(j[3], 0.16177675913435277,  EPS);
+    assert_epeq!(j[4], 0.050679838243959505, EPS);
+
+    let y = gsl_bessel::bessel_ynu_array(0.3, 4, 2.5);
+    assert_eq!(y.len(), 5);
+    assert_epeq!(y[0],  0.47018102218197988,  EPS);
+    assert_epeq!(y[1], -0.028847640358480683, EPS);
+    assert_epeq!(y[2], -0.50018256815479969,  EPS);
+    assert_epeq!(y[3], -0.89148828504635081,  EPS);
+    assert_epeq!(y[4], -1.8533465043675663,   EPS);
+
+    let i = gsl_bessel::bessel_inu_array(1.7, 3, 3.0);
+    assert_epeq!(i[0], 2.7490535717647506,  EPS);
+    assert_epeq!(i[1], 1.2715236097070627,  EPS);
+    assert_epeq!(i[2], 0.46031107429203724, EPS);
+    assert_epeq!(i[3], 0.13608962645337076, EPS);
+
+    let i = gsl_bessel::bessel_inu_scaled_array(1.7, 3, 3.0);
+    assert_epeq!(i[0], 0.13686731812437220,   EPS);
+    assert_epeq!(i[1], 0.063305432887838678,  EPS);
+    assert_epeq!(i[2], 0.022917538926262556,  EPS);
+    assert_epeq!(i[3], 0.0067755035363910356, EPS);
+
+    let k = gsl_bessel::bessel_knu_array(1.7, 3, 3.0);
+    assert_epeq!(k[0], 0.052605504084725399, EPS);
+    assert_epeq!(k[1], 0.096922153727990200, EPS);
+    assert_epeq!(k[2], 0.22706538079510778,  EPS);
+    assert_epeq!(k[3], 0.65701675968925607,  EPS);
+
+    let k = gsl_bessel::bessel_knu_scaled_array(1.7, 3, 3.0);
+    assert_epeq!(k[0], 1.0566097946566517, EPS);
+    assert_epeq!(k[1], 1.9467334973784184, EPS);
+    assert_epeq!(k[2], 4.5607300899378052, EPS);
+    assert_epeq!(k[3], 13.196534385891672, EPS);
+
+    // A single order matches the scalar function
+    let j = gsl_bessel::bessel_jnu_array(1.5, 0, 4.0);
+    assert_eq!(j.len(), 1);
+    assert_epeq!(j[0], 0.18528594835426895, EPS);
+    let j = gsl_bessel::bessel_jnu_array(0.0, 2, 0.0);
+    assert_eq!(j[0], 1.0);
+    assert_eq!(j[1], 0.0);
+}
+
+#[test]
+fn test_hankel()
+{
+    let (h1, _) = gsl_bessel::hankel1(1.5, 4.0);
+    assert_epeq!(h1[0], 0.18528594835426895, EPS);
+    assert_epeq!(h1[1], 0.36711203246093415, EPS);
+    let (h2, _) = gsl_bessel::hankel2(1.5, 4.0);
+    assert_epeq!(h2[0],  0.18528594835426895, EPS);
+    assert_epeq!(h2[1], -0.36711203246093415, EPS);
+}
+
+#[test]
+fn test_bessel_spherical_deriv()
+{
+    assert_epeq!(gsl_bessel::bessel_jl_deriv(0, 1.7), -0.41892749161067840,  EPS);
+    assert_epeq!(gsl_bessel::bessel_jl_deriv(3, 1.7),  0.062413711680247913, EPS);
+    assert_epeq!(gsl_bessel::bessel_jl_deriv(2, 0.4),  0.052122386479825880, EPS);
+
+    assert_epeq!(gsl_bessel::bessel_yl_deriv(0, 1.7), 0.53874937144417718, EPS);
+    assert_epeq!(gsl_bessel::bessel_yl_deriv(3, 1.7), 4.8098090371934369,  EPS);
+
+    assert_epeq!(gsl_bessel::bessel_il_deriv(0, 1.7), 0.74827139950716665, EPS);
+    assert_epeq!(gsl_bessel::bessel_il_deriv(3, 1.7), 0.10679840893024033, EPS);
+    assert_epeq!(gsl_bessel::bessel_il_deriv(1, 0.5), 0.35870777723640623, EPS);
+
+    assert_epeq!(gsl_bessel::bessel_kl_deriv(0, 1.7), -0.26809281767458597, EPS);
+    assert_epeq!(gsl_bessel::bessel_kl_deriv(3, 1.7), -5.7149540630358964,  EPS);
+}
+
+#[test]
+fn test_riccati_bessel()
+{
+    let x = 1.7;
+    let (s, ds) = gsl_bessel::riccati_jn(3, x);
+    assert_eq!(s.len(), 4);
+    assert_epeq!(s[0], f64::sin(x), EPS);
+    assert_epeq!(ds[0], f64::cos(x), EPS);
+    // (x j_3)' = j_3 + x j_3'
+    assert_epeq!(s[3], 0.067581100672336681, EPS);
+    assert_epeq!(ds[3], s[3] / x + x * 0.062413711680247913, EPS);
+
+    let (c, dc) = gsl_bessel::riccati_yn(3, x);
+    assert_epeq!(c[0], -f64::cos(x), EPS);
+    assert_epeq!(dc[0], f64::sin(x), EPS);
+    assert_epeq!(c[3], -4.2167513880391816, EPS);
+    assert_epeq!(dc[3], c[3] / x + x * 4.8098090371934369, EPS);
+}
+
+#[test]
+fn test_bessel_zeros_extra()
+{
+    assert_epeq!(gsl_bessel::bessel_zero_ynu(0.0, 1).0,  0.89357696627916752, EPS);
+    assert_epeq!(gsl_bessel::bessel_zero_ynu(0.0, 2).0,  3.9576784193148579,  EPS);
+    assert_epeq!(gsl_bessel::bessel_zero_ynu(0.0, 3).0,  7.0860510603017727,  EPS);
+    assert_epeq!(gsl_bessel::bessel_zero_ynu(2.5, 1).0,  3.9595279165010954,  EPS);
+    assert_epeq!(gsl_bessel::bessel_zero_ynu(10.0, 1).0, 12.128927704415439,  EPS);
+
+    assert_epeq!(gsl_bessel::bessel_zero_jnu_deriv(0.0, 1).0,  3.8317059702075123, EPS);
+    assert_epeq!(gsl_bessel::bessel_zero_jnu_deriv(1.0, 1).0,  1.8411837813406593, EPS);
+    assert_epeq!(gsl_bessel::bessel_zero_jnu_deriv(1.0, 2).0,  5.3314427735250326, EPS);
+    assert_epeq!(gsl_bessel::bessel_zero_jnu_deriv(2.5, 1).0,  3.6327973198317625, EPS);
+    assert_epeq!(gsl_bessel::bessel_zero_jnu_deriv(10.0, 1).0, 11.770876674955582, EPS);
+}
+
+#[test]
+fn test_kelvin()
+{
+    assert_epeq!(gsl_bessel::kelvin_ber(2.5),  0.39996841712953134,  1e-9);
+    assert_epeq!(gsl_bessel::kelvin_bei(2.5),  1.4571820441598042,   1e-9);
+    assert_epeq!(gsl_bessel::kelvin_ker(2.5), -0.069687972589045344, 1e-9);
+    assert_epeq!(gsl_bessel::kelvin_kei(2.5), -0.11069609915567485,  1e-9);
+    assert_epeq!(gsl_bessel::kelvin_ber(20.0), 47489.370265061760,   1e-9);
+    assert_epeq!(gsl_bessel::kelvin_ker(20.0), -7.7152331098609615e-8, 1e-9);
+    assert_epeq!(gsl_bessel::kelvin_kei(0.3), -0.73310191229486361,  1e-9);
+    assert_eq!(gsl_bessel::kelvin_ber(0.0), 1.0);
+    assert_eq!(gsl_bessel::kelvin_bei(0.0), 0.0);
+
+    assert_epeq!(gsl_bessel::kelvin_ber_deriv(2.5), -0.94358340860481295,  1e-9);
+    assert_epeq!(gsl_bessel::kelvin_bei_deriv(2.5),  0.99826884649732420,  1e-9);
+    assert_epeq!(gsl_bessel::kelvin_ker_deriv(2.5), -0.016929796433529482, 1e-9);
+    assert_epeq!(gsl_bessel::kelvin_kei_deriv(2.5),  0.14889542117096166,  1e-9);
+}
+
+#[test]
+fn test_struve()
+{
+    assert_epeq!(gsl_bessel::struve_h(0.0, 1.5),   0.73672346560439987, 1e-9);
+    assert_epeq!(gsl_bessel::struve_h(1.0, 10.0),  0.89183249209453811, 1e-9);
+    assert_epeq!(gsl_bessel::struve_h(2.5, 30.0),  16.498336952968129,  1e-9);
+    assert_epeq!(gsl_bessel::struve_h(-0.3, 5.0), -0.32043952017673390, 1e-9);
+    assert_eq!(gsl_bessel::struve_h(1.0, 0.0), 0.0);
+
+    assert_epeq!(gsl_bessel::struve_l(0.0, 1.5),  1.2161625107171819,  1e-9);
+    assert_epeq!(gsl_bessel::struve_l(1.0, 10.0), 2670.3582852084830,  1e-9);
+    assert_epeq!(gsl_bessel::struve_l(2.5, 30.0), 703124015502.88738,  1e-9);
+}
@@ -0,0 +1,129 @@
+/////////////////////////////
+// Boundary Value Problems //
+/////////////////////////////
+#[macro_use]
+extern crate rustsci;
+
+use std::f64::consts::PI;
+use rustsci::array;
+use rustsci::array::Array;
+use rustsci::matrix;
+use rustsci::bvp;
+use rustsci::bvp::BvpOptions;
+
+fn mesh(a: f64, b: f64, m: usize) -> Array<f64>
+{
+    Array::new((0..m).map(|i| a + (b - a) * i as f64 / (m - 1) as f64).collect(),
+               array::Order::Row)
+}
+
+fn zeros(m: usize, n: usize) -> Vec<Array<f64>>
+{
+    (0..m).map(|_| Array::new_filled(0.0, n, array::Order::Row)).collect()
+}
+
+#[test]
+fn test_bratu()
+{
+    // y'' + exp(y) = 0, y(0) = y(1) = 0; a zero guess finds the lower solution
+    let opts = BvpOptions{tol: 1e-6, ..BvpOptions::default()};
+    let res = bvp::solve_bvp(|_, y| arr![y[1], -y[0].exp()],
+                             |ya, yb| arr![ya[0], yb[0]],
+                             &mesh(0.0, 1.0, 5), &zeros(5, 2), &opts);
+    assert!(res.success, "{}", res.message);
+    assert_eq!(res.status, 0);
+    assert!(res.rms_residuals.as_slice().iter().all(|&r| r <= 1e-6));
+    assert!((res.y[0][1] - 0.549352728775270819).abs() < 1e-6);
+    assert!((res.sol.eval(0.5)[0] - 0.140539214400471798).abs() < 1e-6);
+    assert_eq!(res.sol.span(), (0.0, 1.0));
+    assert_eq!(res.x.len(), res.y.len());
+    assert_eq!(res.x.len(), res.yp.len());
+}
+
+#[test]
+fn test_linear()
+{
+    // y'' = -y, y(0) = 0, y(pi / 2) = 1 has the solution sin x
+    let opts = BvpOptions{tol: 1e-8, ..BvpOptions::default()};
+    let res = bvp::solve_bvp(|_, y| arr![y[1], -y[0]],
+                             |ya, yb| arr![ya[0], yb[0] - 1.0],
+                             &mesh(0.0, PI / 2.0, 4), &zeros(4, 2), &opts);
+    assert!(res.success);
+    for i in 0..21
+    {
+        let x = PI / 2.0 * i as f64 / 20.0;
+        assert!((res.sol.eval(x)[0] - x.sin()).abs() < 1e-8);
+        assert!((res.sol.derivative(x)[0] - x.cos()).abs() < 1e-6);
+    }
+    let ys = res.sol.eval_array(&arr![0.0, PI / 2.0]);
+    assert!(ys[0][0].abs() < 1e-12);
+    assert!((ys[1][0] - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_beam_deflection()
+{
+    // Simply supported beam under a uniform load, EI y'''' = q with
+    // y = y'' = 0 at both ends, deflection q x (L^3 - 2 L x^2 + x^3) / 24 EI
+    let (l, q, ei) = (2.0, -1000.0, 5e4);
+    let mut opts = BvpOptions{tol: 1e-6, ..BvpOptions::default()};
+    opts.jac = Some(Box::new(|_, _| mat![[0.0, 1.0, 0.0, 0.0],
+                                         [0.0, 0.0, 1.0, 0.0],
+                                         [0.0, 0.0, 0.0, 1.0],
+                                         [0.0, 0.0, 0.0, 0.0]]));
+    let res = bvp::solve_bvp(|_, y| arr![y[1], y[2], y[3], q / ei],
+                             |ya, yb| arr![ya[0], ya[2], yb[0], yb[2]],
+                             &mesh(0.0, l, 5), &zeros(5, 4), &opts);
+    assert!(res.success, "{}", res.message);
+    let exact = |x: f64| q * x * (l * l * l - 2.0 * l * x * x + x * x * x) / (24.0 * ei);
+    // The cubic spline interpolates the quartic deflection between nodes
+    for i in 0..11
+    {
+        let x = l * i as f64 / 10.0;
+        assert!((res.sol.eval(x)[0] - exact(x)).abs() < 1e-7);
+    }
+    // Maximum deflection 5 q L^4 / 384 EI at midspan
+    assert!((res.sol.eval(1.0)[0] - 5.0 * q * 16.0 / (384.0 * ei)).abs() < 1e-7);
+}
+
+#[test]
+fn test_boundary_jacobian()
+{
+    // y' = y with a condition coupling both ends, y(0) + y(1) = 1 + e
+    let e = 1f64.exp();
+    let mut opts = BvpOptions{tol: 1e-8, ..BvpOptions::default()};
+    opts.bc_jac = Some(Box::new(|_, _| (mat![[1.0]], mat![[1.0]])));
+    let res = bvp::solve_bvp(|_, y| arr![y[0]], |ya, yb| arr![ya[0] + yb[0] - 1.0 - e],
+                             &mesh(0.0, 1.0, 3), &zeros(3, 1), &opts);
+    assert!(res.success);
+    assert!((res.sol.eval(0.5)[0] - 0.5f64.exp()).abs() < 1e-8);
+}
+
+#[test]
+fn test_max_nodes_exceeded()
+{
+    // A sharp boundary layer cannot be resolved on 20 nodes
+    let opts = BvpOptions{tol: 1e-8, max_nodes: 20, ..BvpOptions::default()};
+    let res = bvp::solve_bvp(|_, y| arr![y[1], 1e4 * y[0]],
+                             |ya, yb| arr![ya[0] - 1.0, yb[0]],
+                             &mesh(0.0, 1.0, 5), &zeros(5, 2), &opts);
+    assert!(!res.success);
+    assert_eq!(res.status, 1);
+    assert!(res.x.len() <= 20);
+}
+
+#[test]
+#[should_panic(expected = "strictly increasing")]
+fn test_mesh_order()
+{
+    bvp::solve_bvp(|_, y| arr![y[0]], |ya, _| arr![ya[0]],
+                   &arr![0.0, 1.0, 1.0], &zeros(3, 1), &BvpOptions::default());
+}
+
+#[test]
+#[should_panic(expected = "Boundary conditions returned 1 residuals, expected 2")]
+fn test_bc_length()
+{
+    bvp::solve_bvp(|_, y| arr![y[1], -y[0]], |ya, _| arr![ya[0]],
+                   &mesh(0.0, 1.0, 3), &zeros(3, 2), &BvpOptions::default());
+}
@@ -439,3 +439,17 @@ pub fn test_coulomb_wave_fg_23()
     assert_epeq!(  g,  x.cos(), EPS);
     assert_epeq!( gp,  -x.sin(), EPS);
 }
+
+#[test]
+pub fn test_coloumb_wave_fg_e10()
+{
+    // In-range values agree with the unscaled function
+    let vals = gsl_coulomb::coulomb_wave_fg_e10(1f64, 5f64, 0f64, 0);
+    assert_epeq!(vals[0].to_f64().unwrap(),  0.6849374120059439677, EPS);
+    assert_epeq!(vals[3].to_f64().unwrap(), -0.5108047585190350106, EPS);
+
+    // For eta = 300, x = 1 both F and G fall outside the range of a double
+    let vals = gsl_coulomb::coulomb_wave_fg_e10(300f64, 1f64, 0f64, 0);
+    assert_epeq!(vals[0].log10_abs(), -389.04156663221222176, 1e-6);
+    assert_epeq!(vals[2].log10_abs(),  387.35175529762309023, 1e-6);
+}
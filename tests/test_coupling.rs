@@ -0,0 +1,112 @@
+//////////////////////////////////////////////
+// Special Functions: Coupling Coefficients //
+//////////////////////////////////////////////
+#[macro_use]
+extern crate rustsci;
+
+use rustsci::gsl_coupling;
+use rustsci::gsl_coupling::Spin;
+use rustsci::gsl_math;
+
+const EPS: f64 = 0.00000000001;
+
+#[test]
+fn test_spin()
+{
+    let half = Spin::new(0.5);
+    assert_eq!(half, Spin::from_twice(1));
+    assert_eq!(half.twice(), 1);
+    assert_eq!(half.value(), 0.5);
+    assert!(!half.is_integer());
+    assert!(Spin::new(-2.0).is_integer());
+}
+
+#[test]
+#[should_panic]
+fn test_spin_invalid()
+{
+    Spin::new(0.75);
+}
+
+#[test]
+fn test_coupling_3j()
+{
+    let h = Spin::new(0.5);
+    assert_epeq!(gsl_coupling::coupling_3j(h, h, Spin::new(1.0),
+                                           h, Spin::new(-0.5), Spin::new(0.0)).0,
+                 0.40824829046386301637, EPS);
+    assert_epeq!(gsl_coupling::coupling_3j(Spin::new(2.0), Spin::new(3.0), Spin::new(4.0),
+                                           Spin::new(1.0), Spin::new(-2.0), Spin::new(1.0)).0,
+                 0.19720265943665386809, EPS);
+    assert_epeq!(gsl_coupling::coupling_3j(Spin::new(2.5), Spin::new(1.5), Spin::new(2.0),
+                                           Spin::new(1.5), Spin::new(-0.5), Spin::new(-1.0)).0,
+                 0.069006555934235421780, EPS);
+}
+
+#[test]
+fn test_coupling_3j_selection_rules()
+{
+    let one = Spin::new(1.0);
+    let zero = Spin::new(0.0);
+    // Triangle condition violated
+    assert_eq!(gsl_coupling::coupling_3j(one, one, Spin::new(3.0), zero, zero, zero),
+               (0.0, 0.0));
+    // Projections do not sum to zero
+    assert_eq!(gsl_coupling::coupling_3j(one, one, one, one, zero, zero), (0.0, 0.0));
+    // |m| > j
+    assert_eq!(gsl_coupling::coupling_3j(one, one, zero, Spin::new(2.0), Spin::new(-2.0), zero),
+               (0.0, 0.0));
+    // j - m not an integer
+    assert_eq!(gsl_coupling::coupling_3j(one, one, zero,
+                                         Spin::new(0.5), Spin::new(-0.5), zero),
+               (0.0, 0.0));
+}
+
+#[test]
+#[should_panic]
+fn test_coupling_3j_negative_j()
+{
+    let one = Spin::new(1.0);
+    let zero = Spin::new(0.0);
+    gsl_coupling::coupling_3j(Spin::new(-1.0), one, zero, zero, zero, zero);
+}
+
+#[test]
+fn test_coupling_6j()
+{
+    let one = Spin::new(1.0);
+    assert_epeq!(gsl_coupling::coupling_6j(one, one, one, one, one, one).0,
+                 1.0 / 6.0, EPS);
+    assert_epeq!(gsl_coupling::coupling_6j(Spin::new(2.5), Spin::new(1.5), Spin::new(2.0),
+                                           Spin::new(1.5), Spin::new(2.5), one).0,
+                 -0.11581320482871723336, EPS);
+    // (jd, jb, jf) = (5/2, 1/2, 3/2) is not a triangle
+    assert_eq!(gsl_coupling::coupling_6j(Spin::new(1.5), Spin::new(0.5), Spin::new(2.0),
+                                         Spin::new(2.5), Spin::new(3.0), Spin::new(1.5)),
+               (0.0, 0.0));
+}
+
+#[test]
+fn test_coupling_9j()
+{
+    let h = Spin::new(0.5);
+    let one = Spin::new(1.0);
+    let two = Spin::new(2.0);
+    assert_epeq!(gsl_coupling::coupling_9j(one, one, one,
+                                           one, one, one,
+                                           one, one, Spin::new(0.0)).0,
+                 1.0 / 18.0, EPS);
+    assert_epeq!(gsl_coupling::coupling_9j(h, h, one,
+                                           h, h, one,
+                                           one, one, two).0,
+                 1.0 / 9.0, EPS);
+    assert_epeq!(gsl_coupling::coupling_9j(one, two, Spin::new(3.0),
+                                           two, Spin::new(1.5), Spin::new(2.5),
+                                           one, Spin::new(1.5), Spin::new(1.5)).0,
+                 0.013093073414159542876, EPS);
+    // First column (1/2, 1/2, 2) is not a triangle
+    assert_eq!(gsl_coupling::coupling_9j(h, h, one,
+                                         h, h, one,
+                                         two, two, two),
+               (0.0, 0.0));
+}
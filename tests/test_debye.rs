@@ -0,0 +1,37 @@
+////////////////////////////////////////
+// Special Functions: Debye Functions //
+////////////////////////////////////////
+#[macro_use]
+extern crate rustsci;
+
+use rustsci::gsl_debye;
+use rustsci::gsl_math;
+
+const EPS: f64 = 0.00000000001;
+
+#[test]
+fn test_debye()
+{
+    assert_epeq!(gsl_debye::debye_1(1.5).0, 0.68614531078940204, EPS);
+    assert_epeq!(gsl_debye::debye_2(1.5).0, 0.59149637225671283, EPS);
+    assert_epeq!(gsl_debye::debye_3(1.5).0, 0.54710665141286285, EPS);
+    assert_epeq!(gsl_debye::debye_4(1.5).0, 0.52162830964878715, EPS);
+    assert_epeq!(gsl_debye::debye_5(1.5).0, 0.50518576983159048, EPS);
+    assert_epeq!(gsl_debye::debye_6(1.5).0, 0.49372879336788530, EPS);
+    assert_epeq!(gsl_debye::debye_3(20.0).0, 0.0024352200674805480, EPS);
+}
+
+#[test]
+fn test_debye_zero()
+{
+    // D_n(0) = 1 for every order
+    assert_epeq!(gsl_debye::debye_1(0.0).0, 1.0, EPS);
+    assert_epeq!(gsl_debye::debye_6(0.0).0, 1.0, EPS);
+}
+
+#[test]
+#[should_panic]
+fn test_debye_negative()
+{
+    gsl_debye::debye_2(-1.0);
+}
@@ -0,0 +1,105 @@
+/////////////////////////////////////////////////
+// Special Functions: Exponentials and Scaling //
+/////////////////////////////////////////////////
+#[macro_use]
+extern crate rustsci;
+
+use rustsci::gsl_exp;
+use rustsci::gsl_math;
+use rustsci::gsl_sf::ScaledResult;
+
+const EPS: f64 = 0.00000000001;
+
+#[test]
+fn test_scaled_result_to_f64()
+{
+    let r = ScaledResult::new(1.5, 0.1, 3);
+    assert_epeq!(r.to_f64().unwrap(), 1500.0, EPS);
+    let (val, err) = r.to_f64_err().unwrap();
+    assert_epeq!(val, 1500.0, EPS);
+    assert_epeq!(err, 100.0, EPS);
+
+    // 10^-300 * 10^600 is representable even though 10^600 is not
+    assert_epeq!(ScaledResult::new(1e-300, 0.0, 600).to_f64().unwrap(), 1e300, EPS);
+    assert_eq!(ScaledResult::new(0.0, 0.0, 500).to_f64(), Some(0.0));
+}
+
+#[test]
+fn test_scaled_result_out_of_range()
+{
+    assert!(ScaledResult::new(2.0, 0.0, 308).to_f64().is_none());
+    assert!(ScaledResult::new(-1.0, 0.0, 400).to_f64().is_none());
+    assert!(ScaledResult::new(1.0, 0.0, -400).to_f64().is_none());
+}
+
+#[test]
+fn test_scaled_result_logs()
+{
+    let r = ScaledResult::new(-2.0, 0.0, 500);
+    assert_epeq!(r.log10_abs(), 500.30102999566398120, EPS);
+    assert_epeq!(r.ln_abs(), 1151.9856936775827873, EPS);
+    assert_eq!(r.signum(), -1.0);
+    assert_eq!(ScaledResult::new(0.0, 0.0, 0).signum(), 0.0);
+}
+
+#[test]
+fn test_scaled_result_from_ln_scale()
+{
+    let r = ScaledResult::from_ln_scale(3.0, 0.0, 1000.0);
+    assert_epeq!(r.ln_abs(), 1001.0986122886681098, EPS);
+    assert!(r.val.abs() >= 1.0 && r.val.abs() < 30.0);
+}
+
+#[test]
+fn test_exp()
+{
+    assert_epeq!(gsl_exp::exp(1.0).0, 2.7182818284590452354, EPS);
+    assert_epeq!(gsl_exp::exp(-10.0).0, 4.539992976248485154e-05, EPS);
+}
+
+#[test]
+fn test_exp_e10()
+{
+    let r = gsl_exp::exp_e10(1000.0);
+    assert_epeq!(r.log10_abs(), 434.2944819032518276511289, EPS);
+    assert!(r.to_f64().is_none());
+
+    let r = gsl_exp::exp_e10(-1000.0);
+    assert_epeq!(r.log10_abs(), -434.2944819032518276511289, EPS);
+
+    let r = gsl_exp::exp_e10(1.0);
+    assert_epeq!(r.to_f64().unwrap(), 2.7182818284590452354, EPS);
+}
+
+#[test]
+fn test_exp_mult_e10()
+{
+    let r = gsl_exp::exp_mult_e10(1000.0, 2.0);
+    assert_epeq!(r.log10_abs(), 434.5955118989158088463427, EPS);
+    let r = gsl_exp::exp_mult_err_e10(1000.0, 0.0, 2.0, 0.0);
+    assert_epeq!(r.log10_abs(), 434.5955118989158088463427, EPS);
+    let r = gsl_exp::exp_err_e10(1000.0, 1e-10);
+    assert_epeq!(r.log10_abs(), 434.2944819032518276511289, EPS);
+}
+
+#[test]
+fn test_exp_helpers()
+{
+    assert_epeq!(gsl_exp::exp_mult(1.0, 2.0).0, 5.4365636569180904707, EPS);
+    assert_epeq!(gsl_exp::exp_err(2.0, 0.0).0,  7.3890560989306502272, EPS);
+    assert_epeq!(gsl_exp::exp_mult_err(1.0, 0.0, 2.0, 0.0).0, 5.4365636569180904707, EPS);
+    assert_epeq!(gsl_exp::expm1(1e-5).0, 0.000010000050000166667901, EPS);
+
+    // exp_err propagates the error in the argument
+    assert!(gsl_exp::exp_err(2.0, 1e-3).1 > 7e-3);
+}
+
+#[test]
+fn test_exprel()
+{
+    assert_epeq!(gsl_exp::exprel(0.1).0,        1.0517091807564762511, EPS);
+    assert_epeq!(gsl_exp::exprel_2(0.1).0,      1.0341836151295250576, EPS);
+    assert_epeq!(gsl_exp::exprel_n(1, 0.1).0,   1.0517091807564762511, EPS);
+    assert_epeq!(gsl_exp::exprel_n(3, 0.1).0,   1.0255084538857488702, EPS);
+    assert_epeq!(gsl_exp::exprel_n(3, 5.0).0,   6.2358316369236769642, EPS);
+}
@@ -0,0 +1,78 @@
+//////////////////////////////////////////////
+// Special Functions: Exponential Integrals //
+//////////////////////////////////////////////
+#[macro_use]
+extern crate rustsci;
+
+use rustsci::gsl_expint;
+use rustsci::gsl_math;
+
+const EPS: f64 = 0.00000000001;
+
+#[test]
+fn test_expint_en()
+{
+    assert_epeq!(gsl_expint::expint_e1(1.0).0,   0.21938393439552027368,  EPS);
+    assert_epeq!(gsl_expint::expint_e1(-1.0).0, -1.8951178163559367555,   EPS);
+    assert_epeq!(gsl_expint::expint_e1(10.0).0,  4.1569689296853242774e-6, EPS);
+
+    assert_epeq!(gsl_expint::expint_e2(1.0).0, 0.14849550677592204792, EPS);
+    assert_epeq!(gsl_expint::expint_e2(0.1).0, 0.72254502219402049644, EPS);
+
+    assert_epeq!(gsl_expint::expint_en(2, 1.0).0, 0.14849550677592204792,  EPS);
+    assert_epeq!(gsl_expint::expint_en(3, 2.0).0, 0.030133379797815893187, EPS);
+}
+
+#[test]
+fn test_expint_scaled()
+{
+    assert_epeq!(gsl_expint::expint_e1_scaled(10.0).0,    0.091563333939788081876, EPS);
+    assert_epeq!(gsl_expint::expint_e2_scaled(5.0).0,     0.14788911857633899094,  EPS);
+    assert_epeq!(gsl_expint::expint_en_scaled(10, 1.0).0, 0.098929132640646155219, EPS);
+    assert_epeq!(gsl_expint::expint_ei_scaled(10.0).0,    0.11314702047341077803,  EPS);
+}
+
+#[test]
+fn test_expint_ei()
+{
+    assert_epeq!(gsl_expint::expint_ei(1.0).0,   1.8951178163559367555,  EPS);
+    assert_epeq!(gsl_expint::expint_ei(-1.0).0, -0.21938393439552027368, EPS);
+}
+
+#[test]
+#[should_panic]
+fn test_expint_ei_zero()
+{
+    gsl_expint::expint_ei(0.0);
+}
+
+#[test]
+fn test_hyperbolic_integrals()
+{
+    assert_epeq!(gsl_expint::shi(1.0).0, 1.0572508753757285146,  EPS);
+    assert_epeq!(gsl_expint::chi(1.0).0, 0.83786694098020824089, EPS);
+}
+
+#[test]
+fn test_expint_3()
+{
+    assert_epeq!(gsl_expint::expint_3(1.0).0, 0.80751118213967145286, EPS);
+    assert_epeq!(gsl_expint::expint_3(3.0).0, 0.89297951156918122102, EPS);
+}
+
+#[test]
+fn test_trig_integrals()
+{
+    assert_epeq!(gsl_expint::si(1.0).0,  0.94608307036718301494, EPS);
+    assert_epeq!(gsl_expint::si(20.0).0, 1.5482417010434398402,  EPS);
+    assert_epeq!(gsl_expint::ci(1.0).0,  0.33740392290096813466, EPS);
+    assert_epeq!(gsl_expint::ci(20.0).0, 0.04441982084535331654, EPS);
+}
+
+#[test]
+fn test_atanint()
+{
+    // AtanInt(1) is Catalan's constant
+    assert_epeq!(gsl_expint::atanint(1.0).0, 0.91596559417721901505, EPS);
+    assert_epeq!(gsl_expint::atanint(4.0).0, 2.4258878412859089996,  EPS);
+}
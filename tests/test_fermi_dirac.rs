@@ -0,0 +1,53 @@
+//////////////////////////////////////////////
+// Special Functions: Fermi-Dirac Integrals //
+//////////////////////////////////////////////
+#[macro_use]
+extern crate rustsci;
+
+use rustsci::gsl_fermi_dirac;
+use rustsci::gsl_math;
+
+const EPS: f64 = 0.00000000001;
+
+#[test]
+fn test_fermi_dirac_small_orders()
+{
+    assert_epeq!(gsl_fermi_dirac::fermi_dirac_m1(1.5).0, 0.81757447619364366, EPS);
+    assert_epeq!(gsl_fermi_dirac::fermi_dirac_0(-2.0).0, 0.12692801104297250, EPS);
+    assert_epeq!(gsl_fermi_dirac::fermi_dirac_1(2.5).0, 4.6894747975997617, EPS);
+    assert_epeq!(gsl_fermi_dirac::fermi_dirac_2(-1.0).0, 0.35256487929780776, EPS);
+}
+
+#[test]
+fn test_fermi_dirac_int()
+{
+    assert_epeq!(gsl_fermi_dirac::fermi_dirac_int(3, 0.0).0, 0.94703282949724592, EPS);
+    assert_epeq!(gsl_fermi_dirac::fermi_dirac_int(5, 10.0).0, 2170.9524231414051, EPS);
+    assert_epeq!(gsl_fermi_dirac::fermi_dirac_int(1, 2.5).0,
+                 gsl_fermi_dirac::fermi_dirac_1(2.5).0, EPS);
+}
+
+#[test]
+fn test_fermi_dirac_half_orders()
+{
+    assert_epeq!(gsl_fermi_dirac::fermi_dirac_mhalf(0.7).0, 0.89428771216273468, EPS);
+    assert_epeq!(gsl_fermi_dirac::fermi_dirac_half(3.0).0, 4.4875474213517089, EPS);
+    assert_epeq!(gsl_fermi_dirac::fermi_dirac_3half(-0.4).0, 0.60561202133050408, EPS);
+}
+
+#[test]
+fn test_fermi_dirac_inc_0()
+{
+    assert_epeq!(gsl_fermi_dirac::fermi_dirac_inc_0(0.5, 2.0).0, 0.20141327798275241, EPS);
+    assert_epeq!(gsl_fermi_dirac::fermi_dirac_inc_0(3.0, 1.0).0, 2.1269280110429725, EPS);
+    // Starting the integration at b = 0 gives the complete integral
+    assert_epeq!(gsl_fermi_dirac::fermi_dirac_inc_0(-2.0, 0.0).0,
+                 gsl_fermi_dirac::fermi_dirac_0(-2.0).0, EPS);
+}
+
+#[test]
+#[should_panic]
+fn test_fermi_dirac_inc_0_negative_b()
+{
+    gsl_fermi_dirac::fermi_dirac_inc_0(1.0, -0.5);
+}
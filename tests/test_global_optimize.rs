@@ -0,0 +1,180 @@
+/////////////////////////
+// Global Optimization //
+/////////////////////////
+#[macro_use]
+extern crate rustsci;
+
+use rustsci::array;
+use rustsci::array::Array;
+use rustsci::global_optimize;
+use rustsci::global_optimize::{BasinHoppingOptions, DifferentialEvolutionOptions};
+use rustsci::global_optimize::{DualAnnealingOptions, Strategy};
+use rustsci::optimize::Bounds;
+
+const STRATEGIES: [Strategy; 12] = [Strategy::Best1Bin, Strategy::Best1Exp,
+                                    Strategy::Rand1Bin, Strategy::Rand1Exp,
+                                    Strategy::RandToBest1Bin, Strategy::RandToBest1Exp,
+                                    Strategy::CurrentToBest1Bin, Strategy::CurrentToBest1Exp,
+                                    Strategy::Best2Bin, Strategy::Best2Exp,
+                                    Strategy::Rand2Bin, Strategy::Rand2Exp];
+
+fn rastrigin(x: &Array<f64>) -> f64
+{
+    (0..x.len()).fold(10.0 * x.len() as f64, |s, i| {
+        s + x[i] * x[i] - 10.0 * (2.0 * std::f64::consts::PI * x[i]).cos()
+    })
+}
+
+fn rosen(x: &Array<f64>) -> f64
+{
+    (0..x.len() - 1).fold(0.0, |s, i| {
+        s + 100.0 * (x[i + 1] - x[i] * x[i]).powi(2) + (1.0 - x[i]).powi(2)
+    })
+}
+
+fn cube(n: usize, a: f64) -> Bounds
+{
+    Bounds::new(Array::new(vec![-a; n], array::Order::Row),
+                Array::new(vec![a; n], array::Order::Row))
+}
+
+#[test]
+fn test_differential_evolution()
+{
+    let res = global_optimize::differential_evolution(rastrigin, &cube(2, 5.12),
+                                                      &DifferentialEvolutionOptions::default());
+    assert!(res.success, "{}", res.message);
+    assert!(res.fun < 1e-12);
+    assert!(res.x[0].abs() < 1e-8 && res.x[1].abs() < 1e-8);
+    assert!(res.nfev > res.nit * 30);
+}
+
+#[test]
+fn test_differential_evolution_polish()
+{
+    // The gradient is reported only when polishing moves the result
+    let opts = |polish| DifferentialEvolutionOptions{maxiter: 5, polish: polish,
+                                                     ..DifferentialEvolutionOptions::default()};
+    let rough = global_optimize::differential_evolution(rosen, &cube(2, 2.0), &opts(false));
+    let polished = global_optimize::differential_evolution(rosen, &cube(2, 2.0), &opts(true));
+    assert!(rough.jac.is_none());
+    assert!(polished.fun < rough.fun);
+    assert!(polished.fun < 1e-10);
+    assert!(polished.jac.unwrap().as_slice().iter().all(|g| g.abs() < 1e-4));
+}
+
+#[test]
+fn test_strategies()
+{
+    for &strategy in STRATEGIES.iter()
+    {
+        let opts = DifferentialEvolutionOptions{strategy: strategy, tol: 1e-10, polish: false,
+                                                ..DifferentialEvolutionOptions::default()};
+        let res = global_optimize::differential_evolution(rosen, &cube(3, 2.0), &opts);
+        assert!(res.success, "{:?}: {}", strategy, res.message);
+        assert!(res.jac.is_none());
+        for i in 0..3
+        {
+            assert!((res.x[i] - 1.0).abs() < 1e-5, "{:?}: {:?}", strategy, res.x);
+        }
+    }
+}
+
+#[test]
+fn test_differential_evolution_seed()
+{
+    let opts = |seed| DifferentialEvolutionOptions{seed: seed, polish: false,
+                                                   ..DifferentialEvolutionOptions::default()};
+    let a = global_optimize::differential_evolution(rastrigin, &cube(2, 5.12), &opts(3));
+    let b = global_optimize::differential_evolution(rastrigin, &cube(2, 5.12), &opts(3));
+    let c = global_optimize::differential_evolution(rastrigin, &cube(2, 5.12), &opts(4));
+    assert_eq!(a.x, b.x);
+    assert_eq!(a.nfev, b.nfev);
+    assert!(a.x != c.x);
+}
+
+#[test]
+fn test_differential_evolution_maxiter()
+{
+    let opts = DifferentialEvolutionOptions{maxiter: 2, polish: false,
+                                            ..DifferentialEvolutionOptions::default()};
+    let res = global_optimize::differential_evolution(rastrigin, &cube(2, 5.12), &opts);
+    assert!(!res.success);
+    assert_eq!(res.status, 1);
+    assert_eq!(res.nit, 2);
+    assert_eq!(res.nfev, 3 * 30);
+}
+
+#[test]
+#[should_panic(expected = "Bounds for component 1 must be finite")]
+fn test_differential_evolution_unbounded()
+{
+    let bounds = Bounds::new(arr![-1.0, -1.0], arr![1.0, std::f64::INFINITY]);
+    global_optimize::differential_evolution(rosen, &bounds,
+                                            &DifferentialEvolutionOptions::default());
+}
+
+#[test]
+fn test_basin_hopping()
+{
+    // Example from the SciPy documentation, with many local minima along x0
+    let f = |x: &Array<f64>| {
+        (14.5 * x[0] - 0.3).cos() + (x[1] + 0.2) * x[1] + (x[0] + 0.2) * x[0]
+    };
+    let res = global_optimize::basin_hopping(f, &arr![1.0, 1.0],
+                                             &BasinHoppingOptions::default());
+    assert!(res.success, "{}", res.message);
+    assert_eq!(res.nit, 100);
+    assert!((res.x[0] + 0.195067554).abs() < 1e-6 && (res.x[1] + 0.1).abs() < 1e-6);
+    assert!((res.fun + 1.010876184442655).abs() < 1e-10);
+
+    let opts = BasinHoppingOptions{niter_success: Some(10), ..BasinHoppingOptions::default()};
+    let early = global_optimize::basin_hopping(f, &arr![1.0, 1.0], &opts);
+    assert_eq!(early.message, "success condition satisfied");
+    assert!(early.nit < 100 && early.nfev < res.nfev);
+    assert!((early.fun - res.fun).abs() < 1e-10);
+}
+
+#[test]
+fn test_dual_annealing()
+{
+    let bounds = cube(4, 5.12);
+    let opts = DualAnnealingOptions{maxiter: 200, x0: Some(arr![4.0, 4.0, -4.0, 3.0]),
+                                    seed: 7, ..DualAnnealingOptions::default()};
+    let res = global_optimize::dual_annealing(rastrigin, &bounds, &opts);
+    assert!(res.success, "{}", res.message);
+    assert_eq!(res.nit, 200);
+    assert!(res.fun < 1e-10);
+    for i in 0..4
+    {
+        assert!(res.x[i].abs() < 1e-6);
+    }
+    let again = global_optimize::dual_annealing(rastrigin, &bounds, &opts);
+    assert_eq!(res.x, again.x);
+}
+
+#[test]
+fn test_dual_annealing_maxfun()
+{
+    let opts = DualAnnealingOptions{maxfun: 500, ..DualAnnealingOptions::default()};
+    let res = global_optimize::dual_annealing(rastrigin, &cube(4, 5.12), &opts);
+    assert!(!res.success);
+    assert_eq!(res.status, 1);
+    assert_eq!(res.nfev, 500);
+}
+
+#[test]
+#[should_panic(expected = "Invalid value for visit")]
+fn test_dual_annealing_visit()
+{
+    let opts = DualAnnealingOptions{visit: 3.5, ..DualAnnealingOptions::default()};
+    global_optimize::dual_annealing(rastrigin, &cube(2, 5.12), &opts);
+}
+
+#[test]
+#[should_panic(expected = "Lower bound 2 must be less than upper bound 2 for component 1")]
+fn test_dual_annealing_empty_interval()
+{
+    let bounds = Bounds::new(arr![-1.0, 2.0], arr![1.0, 2.0]);
+    global_optimize::dual_annealing(rastrigin, &bounds, &DualAnnealingOptions::default());
+}
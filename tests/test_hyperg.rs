@@ -0,0 +1,95 @@
+/////////////////////////////////////////////////
+// Special Functions: Hypergeometric Functions //
+/////////////////////////////////////////////////
+#[macro_use]
+extern crate rustsci;
+
+use rustsci::gsl_hyperg;
+use rustsci::gsl_math;
+
+const EPS: f64 = 0.00000000001;
+
+#[test]
+fn test_hyperg_0f1()
+{
+    assert_epeq!(gsl_hyperg::hyperg_0f1(1.0, 0.5).0,   1.5660829297563505373,  EPS);
+    assert_epeq!(gsl_hyperg::hyperg_0f1(-0.5, 3.0).0, -39.291379975434342763,  EPS);
+    // 0F1(1; -x^2/4) = J_0(x)
+    assert_epeq!(gsl_hyperg::hyperg_0f1(1.0, -1.0).0,  0.22389077914123566805, EPS);
+}
+
+#[test]
+fn test_hyperg_1f1()
+{
+    assert_epeq!(gsl_hyperg::hyperg_1f1_int(2, 3, -1.0).0, 0.52848223531423071362, EPS);
+    assert_epeq!(gsl_hyperg::hyperg_1f1_int(1, 2, 10.0).0, 2202.5465794806716517,  EPS);
+
+    assert_epeq!(gsl_hyperg::hyperg_1f1(1.0, 1.5, 1.0).0,  2.0300784692787049755,  EPS);
+    assert_epeq!(gsl_hyperg::hyperg_1f1(0.5, 1.5, -2.0).0, 0.59814400666130410147, EPS);
+}
+
+#[test]
+fn test_hyperg_u()
+{
+    assert_epeq!(gsl_hyperg::hyperg_u_int(1, 1, 0.1).0,    2.0146425447084516348,  EPS);
+    assert_epeq!(gsl_hyperg::hyperg_u_int(1, 2, 1.0).0,    1.0,                    EPS);
+    assert_epeq!(gsl_hyperg::hyperg_u_int(1, 1, 1000.0).0, 0.000999001994023880715, EPS);
+
+    assert_epeq!(gsl_hyperg::hyperg_u(0.5, 1.5, 3.0).0, 0.57735026918962576451, EPS);
+    assert_epeq!(gsl_hyperg::hyperg_u(1.0, 1.0, 0.1).0, 2.0146425447084516348,  EPS);
+}
+
+#[test]
+fn test_hyperg_u_e10()
+{
+    // U(1, 300, 1) ~ 9.27e609 overflows a double, but not its e10 form
+    let r = gsl_hyperg::hyperg_u_int_e10(1, 300, 1.0);
+    assert_epeq!(r.log10_abs(), 609.96730508263263526, 1e-8);
+    assert!(r.to_f64().is_none());
+
+    let r = gsl_hyperg::hyperg_u_e10(1.0, 300.0, 1.0);
+    assert_epeq!(r.log10_abs(), 609.96730508263263526, 1e-8);
+
+    // In range values are returned unscaled
+    let r = gsl_hyperg::hyperg_u_e10(0.5, 1.5, 3.0);
+    assert_eq!(r.e10, 0);
+    assert_epeq!(r.to_f64().unwrap(), 0.57735026918962576451, EPS);
+}
+
+#[test]
+fn test_hyperg_2f1()
+{
+    assert_epeq!(gsl_hyperg::hyperg_2f1(1.0, 1.0, 1.0, 0.5).0,   2.0,                    EPS);
+    assert_epeq!(gsl_hyperg::hyperg_2f1(8.0, 8.0, 1.0, 0.5).0,   12451584.0,             EPS);
+    assert_epeq!(gsl_hyperg::hyperg_2f1(0.5, 0.5, 1.5, 0.25).0,  1.0471975511965977462,  EPS);
+    assert_epeq!(gsl_hyperg::hyperg_2f1(1.0, 1.0, 2.0, -0.5).0,  0.81093021621632876396, EPS);
+}
+
+#[test]
+#[should_panic]
+fn test_hyperg_2f1_out_of_range()
+{
+    gsl_hyperg::hyperg_2f1(1.0, 1.0, 1.0, 1.5);
+}
+
+#[test]
+fn test_hyperg_2f1_conj()
+{
+    assert_epeq!(gsl_hyperg::hyperg_2f1_conj(1.0, 0.5, 1.0, 0.5).0, 2.3023067566564289378, EPS);
+}
+
+#[test]
+fn test_hyperg_2f1_renorm()
+{
+    assert_epeq!(gsl_hyperg::hyperg_2f1_renorm(1.0, 1.0, 2.0, -0.5).0,  0.81093021621632876396, EPS);
+    assert_epeq!(gsl_hyperg::hyperg_2f1_renorm(1.0, 1.0, -1.5, 0.5).0,  6.9905832635082584163,  EPS);
+    assert_epeq!(gsl_hyperg::hyperg_2f1_conj_renorm(1.0, 0.5, -1.5, 0.5).0,
+                 9.6421705250080206418, EPS);
+}
+
+#[test]
+fn test_hyperg_2f0()
+{
+    assert_epeq!(gsl_hyperg::hyperg_2f0(0.01, 1.0, -0.02).0, 0.999803886655117309,   EPS);
+    assert_epeq!(gsl_hyperg::hyperg_2f0(1.0, 2.0, -0.5).0,   0.55468553244710966121, EPS);
+}
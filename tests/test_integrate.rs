@@ -0,0 +1,114 @@
+/////////////////////////////////
+// Integration of Sampled Data //
+/////////////////////////////////
+#[macro_use]
+extern crate rustsci;
+
+use rustsci::array;
+use rustsci::matrix;
+use rustsci::gsl_math;
+use rustsci::integrate;
+use rustsci::integrate::{Axis, Spacing};
+
+const EPS: f64 = 0.00000000001;
+
+#[test]
+fn test_trapezoid()
+{
+    let y = arr![0.0, 0.0625, 0.25, 0.5625, 1.0];
+    assert_epeq!(integrate::trapezoid(&y, Spacing::Uniform(0.25)), 0.34375, EPS);
+    let x = arr![0.0, 0.25, 0.5, 0.75, 1.0];
+    assert_epeq!(integrate::trapezoid(&y, Spacing::Points(&x)), 0.34375, EPS);
+    // Decreasing abscissae give the negated integral
+    let x = arr![1.0, 0.75, 0.5, 0.25, 0.0];
+    let y = arr![1.0, 0.5625, 0.25, 0.0625, 0.0];
+    assert_epeq!(integrate::trapezoid(&y, Spacing::Points(&x)), -0.34375, EPS);
+    assert_eq!(integrate::trapezoid(&arr![3.0], Spacing::Uniform(1.0)), 0.0);
+}
+
+#[test]
+fn test_simpson()
+{
+    // Exact for a cubic on an odd number of equally spaced samples
+    let y = arr![0.0, 0.125, 1.0, 3.375, 8.0];
+    assert_epeq!(integrate::simpson(&y, Spacing::Uniform(0.5)), 4.0, EPS);
+    // Exact for quadratics with uneven spacing, odd and even sample counts
+    let x = arr![0.0, 0.3, 1.0, 1.2, 2.0];
+    let y = arr![0.0, 0.09, 1.0, 1.44, 4.0];
+    assert_epeq!(integrate::simpson(&y, Spacing::Points(&x)), 8.0 / 3.0, EPS);
+    let x = arr![0.0, 0.5, 1.25, 2.0];
+    let y = arr![0.0, 0.25, 1.5625, 4.0];
+    assert_epeq!(integrate::simpson(&y, Spacing::Points(&x)), 8.0 / 3.0, EPS);
+    // Two samples reduce to the trapezoidal rule
+    assert_epeq!(integrate::simpson(&arr![1.0, 3.0], Spacing::Uniform(2.0)), 4.0, EPS);
+}
+
+#[test]
+fn test_romb()
+{
+    let dx = 1.0 / 16.0;
+    let y = array::Array::new((0..17).map(|i| (i as f64 * dx).exp()).collect(),
+                              array::Order::Row);
+    assert_epeq!(integrate::romb(&y, dx), 1f64.exp() - 1.0, EPS);
+    assert_epeq!(integrate::romb(&arr![1.0, 3.0], 0.5), 1.0, EPS);
+}
+
+#[test]
+#[should_panic(expected = "2^k + 1 samples")]
+fn test_romb_sample_count()
+{
+    integrate::romb(&arr![1.0, 2.0, 3.0, 4.0], 1.0);
+}
+
+#[test]
+fn test_cumulative_trapezoid()
+{
+    let y = arr![0.0, 2.0, 4.0, 6.0];
+    let c = integrate::cumulative_trapezoid(&y, Spacing::Uniform(1.0), None);
+    assert_eq!(c, arr![1.0, 4.0, 9.0]);
+    let x = arr![0.0, 1.0, 2.0, 3.0];
+    let c = integrate::cumulative_trapezoid(&y, Spacing::Points(&x), Some(0.0));
+    assert_eq!(c, arr![0.0, 1.0, 4.0, 9.0]);
+    let c = integrate::cumulative_trapezoid(&y, Spacing::Uniform(1.0), Some(2.0));
+    assert_eq!(c, arr![2.0, 3.0, 6.0, 11.0]);
+}
+
+#[test]
+#[should_panic(expected = "differ in length")]
+fn test_spacing_length_mismatch()
+{
+    integrate::trapezoid(&arr![1.0, 2.0, 3.0], Spacing::Points(&arr![0.0, 1.0]));
+}
+
+#[test]
+fn test_axis_integrals()
+{
+    let m = mat![[0.0, 1.0, 2.0, 3.0, 4.0],
+                 [0.0, 1.0, 4.0, 9.0, 16.0]];
+    let t = integrate::trapezoid_axis(&m, Spacing::Uniform(1.0), Axis::Row);
+    assert_eq!(t, arr![8.0, 22.0]);
+    let t = integrate::trapezoid_axis(&m, Spacing::Uniform(1.0), Axis::Column);
+    assert_eq!(t, arr![0.0, 1.0, 3.0, 6.0, 10.0]);
+    let s = integrate::simpson_axis(&m, Spacing::Uniform(1.0), Axis::Row);
+    assert_epeq!(s[0], 8.0, EPS);
+    assert_epeq!(s[1], 64.0 / 3.0, EPS);
+    let r = integrate::romb_axis(&m, 1.0, Axis::Row);
+    assert_epeq!(r[0], 8.0, EPS);
+    assert_epeq!(r[1], 64.0 / 3.0, EPS);
+}
+
+#[test]
+fn test_cumulative_trapezoid_axis()
+{
+    let m = mat![[0.0, 1.0, 2.0, 3.0, 4.0],
+                 [0.0, 1.0, 4.0, 9.0, 16.0]];
+    let c = integrate::cumulative_trapezoid_axis(&m, Spacing::Uniform(1.0),
+                                                 Axis::Row, Some(0.0));
+    assert_eq!(c, mat![[0.0, 0.5, 2.0, 4.5, 8.0],
+                       [0.0, 0.5, 3.0, 9.5, 22.0]]);
+    let x = arr![0.0, 2.0];
+    let c = integrate::cumulative_trapezoid_axis(&m, Spacing::Points(&x),
+                                                 Axis::Column, None);
+    assert_eq!(c.get_dims(), (1, 5));
+    assert_eq!(c, mat![[0.0, 2.0, 6.0, 12.0, 20.0]]);
+}
@@ -0,0 +1,221 @@
+///////////////////////////
+// Numerical Integration //
+///////////////////////////
+#[macro_use]
+extern crate rustsci;
+
+use std::f64::consts::PI;
+use rustsci::array;
+use rustsci::gsl_math;
+use rustsci::gsl_integration;
+use rustsci::gsl_integration::{QuadOptions, Rule, Weight};
+
+const EPS: f64 = 0.00000000001;
+
+fn tight() -> QuadOptions
+{
+    QuadOptions{epsabs: 1e-12, epsrel: 1e-12, limit: 200}
+}
+
+fn nested() -> QuadOptions
+{
+    QuadOptions{epsabs: 1e-10, epsrel: 1e-10, limit: 200}
+}
+
+#[test]
+fn test_quad_finite()
+{
+    let (v, e) = gsl_integration::quad(|x| x * x, 0.0, 1.0);
+    assert_epeq!(v, 1.0 / 3.0, EPS);
+    assert!(e < 1e-8);
+    // Integrable end point singularity, handled by the extrapolation of QAGS
+    let (v, _) = gsl_integration::quad_with(|x| 1.0 / x.sqrt(), 0.0, 1.0, &tight());
+    assert_epeq!(v, 2.0, EPS);
+    let (v, _) = gsl_integration::quad_with(|x| x.ln(), 0.0, 1.0, &tight());
+    assert_epeq!(v, -1.0, EPS);
+}
+
+#[test]
+fn test_quad_reversed_and_empty()
+{
+    let (v, _) = gsl_integration::quad(|x| x, 1.0, 0.0);
+    assert_epeq!(v, -0.5, EPS);
+    assert_eq!(gsl_integration::quad(|x| x, 2.0, 2.0), (0.0, 0.0));
+    let (v, _) = gsl_integration::quad_with(|x| (-x).exp(), PI, 0.0, &tight());
+    assert_epeq!(v, (-PI).exp() - 1.0, EPS);
+}
+
+#[test]
+fn test_quad_infinite()
+{
+    let opts = tight();
+    let (v, _) = gsl_integration::quad_with(|x| (-x * x).exp(),
+                                            std::f64::NEG_INFINITY, std::f64::INFINITY, &opts);
+    assert_epeq!(v, PI.sqrt(), EPS);
+    let (v, _) = gsl_integration::quad_with(|x| (-x).exp(), 0.0, std::f64::INFINITY, &opts);
+    assert_epeq!(v, 1.0, EPS);
+    let (v, _) = gsl_integration::quad_with(|x| x.exp(), std::f64::NEG_INFINITY, 1.0, &opts);
+    assert_epeq!(v, 1f64.exp(), EPS);
+    let (v, _) = gsl_integration::quad_with(|x| 1.0 / (1.0 + x * x),
+                                            std::f64::INFINITY, 0.0, &opts);
+    assert_epeq!(v, -PI / 2.0, EPS);
+}
+
+#[test]
+fn test_quad_rule()
+{
+    for &rule in [Rule::GK15, Rule::GK21, Rule::GK31,
+                  Rule::GK41, Rule::GK51, Rule::GK61].iter()
+    {
+        let (v, _) = gsl_integration::quad_rule(|x| x.sin(), 0.0, PI, rule, &tight());
+        assert_epeq!(v, 2.0, EPS);
+    }
+}
+
+#[test]
+fn test_quad_points()
+{
+    // |x - 1|^(-1/2) over (0, 2) with the singularity inside the interval
+    let pts = arr![1.0, 5.0];
+    let (v, _) = gsl_integration::quad_points(|x| 1.0 / (x - 1.0).abs().sqrt(),
+                                              0.0, 2.0, &pts, &tight());
+    assert_epeq!(v, 4.0, EPS);
+    // Step discontinuities at 0.5 and 1.5, given out of order
+    let pts = arr![1.5, 0.5];
+    let step = |x: f64| if x < 0.5 { 0.0 } else if x < 1.5 { 1.0 } else { 3.0 };
+    let (v, _) = gsl_integration::quad_points(step, 0.0, 2.0, &pts, &tight());
+    assert_epeq!(v, 2.5, EPS);
+}
+
+#[test]
+fn test_quad_cauchy()
+{
+    // PV of the integral of 1/x over (-1, 2) is ln 2
+    let (v, _) = gsl_integration::quad_cauchy(|_| 1.0, -1.0, 2.0, 0.0, &tight());
+    assert_epeq!(v, 2f64.ln(), EPS);
+    // PV of the integral of x^2 / (x - 1) over (0, 3) is 15/2 + ln 2
+    let (v, _) = gsl_integration::quad_cauchy(|x| x * x, 0.0, 3.0, 1.0, &tight());
+    assert_epeq!(v, 7.5 + 2f64.ln(), EPS);
+}
+
+#[test]
+fn test_quad_oscillatory()
+{
+    let opts = tight();
+    let (v, _) = gsl_integration::quad_oscillatory(|_| 1.0, 0.0, PI / 2.0, 1.0,
+                                                   Weight::Cos, &opts);
+    assert_epeq!(v, 1.0, EPS);
+    let (v, _) = gsl_integration::quad_oscillatory(|x| x, 0.0, PI, 1.0,
+                                                   Weight::Sin, &opts);
+    assert_epeq!(v, PI, EPS);
+    // Integral of sin(50 x) over (0, 1)
+    let (v, _) = gsl_integration::quad_oscillatory(|_| 1.0, 0.0, 1.0, 50.0,
+                                                   Weight::Sin, &opts);
+    assert_epeq!(v, (1.0 - 50f64.cos()) / 50.0, EPS);
+}
+
+#[test]
+fn test_quad_fourier()
+{
+    let opts = QuadOptions{epsabs: 1e-12, epsrel: 0.0, limit: 200};
+    // Integrals of exp(-x) cos(2x) and exp(-x) sin(2x) over (0, inf)
+    let (v, _) = gsl_integration::quad_fourier(|x| (-x).exp(), 0.0, 2.0, Weight::Cos, &opts);
+    assert!((v - 0.2).abs() < 1e-10);
+    let (v, _) = gsl_integration::quad_fourier(|x| (-x).exp(), 0.0, 2.0, Weight::Sin, &opts);
+    assert!((v - 0.4).abs() < 1e-10);
+}
+
+#[test]
+fn test_dblquad()
+{
+    // x y over the triangle 0 < y < x < 1
+    let (v, _) = gsl_integration::dblquad(|x, y| x * y, 0.0, 1.0,
+                                          |_| 0.0, |x| x, &nested());
+    assert_epeq!(v, 0.125, 1e-9);
+    // Area of the unit disc
+    let (v, _) = gsl_integration::dblquad(|_, _| 1.0, -1.0, 1.0,
+                                          |x| -(1.0 - x * x).sqrt(),
+                                          |x| (1.0 - x * x).sqrt(), &nested());
+    assert_epeq!(v, PI, 1e-9);
+    // Gaussian over the whole plane
+    let (v, _) = gsl_integration::dblquad(|x, y| (-x * x - y * y).exp(),
+                                          std::f64::NEG_INFINITY, std::f64::INFINITY,
+                                          |_| std::f64::NEG_INFINITY,
+                                          |_| std::f64::INFINITY, &nested());
+    assert_epeq!(v, PI, 1e-9);
+}
+
+#[test]
+fn test_tplquad()
+{
+    // Volume of the unit tetrahedron and the integral of x y z over it
+    let (v, _) = gsl_integration::tplquad(|_, _, _| 1.0, 0.0, 1.0,
+                                          |_| 0.0, |x| 1.0 - x,
+                                          |_, _| 0.0, |x, y| 1.0 - x - y, &nested());
+    assert_epeq!(v, 1.0 / 6.0, 1e-9);
+    let (v, _) = gsl_integration::tplquad(|x, y, z| x * y * z, 0.0, 1.0,
+                                          |_| 0.0, |x| 1.0 - x,
+                                          |_, _| 0.0, |x, y| 1.0 - x - y, &nested());
+    assert_epeq!(v, 1.0 / 720.0, 1e-9);
+}
+
+#[test]
+fn test_quad_gk()
+{
+    let opts = QuadOptions{epsabs: 1e-13, epsrel: 1e-13, limit: 1000};
+    let (v, _) = gsl_integration::quad_gk(|x| x * x, 0.0, 1.0, &opts);
+    assert_epeq!(v, 1.0 / 3.0, EPS);
+    let (v, _) = gsl_integration::quad_gk(|x| x.sin(), PI, 0.0, &opts);
+    assert_epeq!(v, -2.0, EPS);
+    let (v, _) = gsl_integration::quad_gk(|x| (-x * x).exp(),
+                                          std::f64::NEG_INFINITY, std::f64::INFINITY, &opts);
+    assert_epeq!(v, PI.sqrt(), EPS);
+    let (v, _) = gsl_integration::quad_gk(|x| (-x).exp(), 0.0, std::f64::INFINITY, &opts);
+    assert_epeq!(v, 1.0, EPS);
+    let (v, _) = gsl_integration::quad_gk(|x| x.exp(), std::f64::NEG_INFINITY, 0.0, &opts);
+    assert_epeq!(v, 1.0, EPS);
+    let (v, _) = gsl_integration::quad_gk(|x| 1.0 / x.sqrt(), 0.0, 1.0, &opts);
+    assert_epeq!(v, 2.0, 1e-9);
+}
+
+#[test]
+fn test_quad_matches_quad_gk()
+{
+    let f = |x: f64| (x * x).cos() * (-x / 4.0).exp();
+    let opts = tight();
+    let (v, _) = gsl_integration::quad_with(&f, 0.0, 10.0, &opts);
+    let (w, _) = gsl_integration::quad_gk(&f, 0.0, 10.0,
+                                          &QuadOptions{limit: 1000, ..opts});
+    assert_epeq!(v, w, 1e-10);
+}
+
+#[test]
+#[should_panic(expected = "integrand blew up")]
+fn test_quad_integrand_panic()
+{
+    gsl_integration::quad(|x| if x > 0.5 { panic!("integrand blew up") } else { x },
+                          0.0, 1.0);
+}
+
+#[test]
+#[should_panic(expected = "integrand blew up")]
+fn test_dblquad_integrand_panic()
+{
+    gsl_integration::dblquad(|x, y| if x * y > 0.25 { panic!("integrand blew up") } else { 1.0 },
+                             0.0, 1.0, |_| 0.0, |_| 1.0, &QuadOptions::default());
+}
+
+#[test]
+#[should_panic(expected = "Integration failed")]
+fn test_quad_divergent()
+{
+    gsl_integration::quad(|x| 1.0 / x, 0.0, 1.0);
+}
+
+#[test]
+#[should_panic(expected = "maximum number of subdivisions")]
+fn test_quad_gk_limit()
+{
+    let opts = QuadOptions{epsabs: 1e-14, epsrel: 1e-14, limit: 3};
+    gsl_integration::quad_gk(|x| (1.0 / x).sin(), 0.001, 1.0, &opts);
+}
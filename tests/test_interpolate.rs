@@ -0,0 +1,216 @@
+///////////////////
+// Interpolation //
+///////////////////
+#[macro_use]
+extern crate rustsci;
+
+use rustsci::array;
+use rustsci::array::Array;
+use rustsci::interpolate::{Extrapolate, Interp1d, Kind};
+
+const EPS: f64 = 0.00000000001;
+
+fn data() -> (Array<f64>, Array<f64>)
+{
+    (arr![0.0, 1.0, 2.5, 3.0, 4.5, 6.0, 7.0], arr![0.0, 2.0, 1.0, 3.0, 3.0, 0.5, 4.0])
+}
+
+fn assert_values(kind: Kind, expected: [f64; 6])
+{
+    let (x, y) = data();
+    let f = Interp1d::new(&x, &y, kind);
+    let v = f.eval_array(&arr![0.3, 1.7, 2.9, 4.0, 5.2, 6.8]);
+    for i in 0..6
+    {
+        assert!((v[i] - expected[i]).abs() < EPS, "{:?}: {} != {}", kind, v[i], expected[i]);
+    }
+    for i in 0..x.len()
+    {
+        assert!((f.eval(x[i]) - y[i]).abs() < EPS, "{:?}", kind);
+    }
+}
+
+fn cubic(x: f64) -> f64
+{
+    x * x * x - 2.0 * x * x + 0.5 * x + 1.0
+}
+
+#[test]
+fn test_linear()
+{
+    let (x, y) = data();
+    let f = Interp1d::new(&x, &y, Kind::Linear);
+    assert!((f.eval(0.3) - 0.6).abs() < EPS);
+    assert!((f.eval(5.2) - 11.0 / 6.0).abs() < EPS);
+    assert!((f.derivative(1.7, 1) + 2.0 / 3.0).abs() < EPS);
+    assert_eq!(f.derivative(1.7, 2), 0.0);
+    // Trapezoidal rule over the data
+    let trapz = (0..x.len() - 1).fold(0.0, |s, i| s + (x[i + 1] - x[i]) * (y[i] + y[i + 1]) / 2.0);
+    assert!((f.integral(0.0, 7.0) - trapz).abs() < EPS);
+    assert!((f.integral(7.0, 0.0) + trapz).abs() < EPS);
+    assert!((f.integral(0.0, 0.5) - 0.25).abs() < EPS);
+    assert_eq!(f.range(), (0.0, 7.0));
+}
+
+#[test]
+fn test_step_kinds()
+{
+    let x = arr![0.0, 1.0, 3.0];
+    let y = arr![1.0, 2.0, 4.0];
+    let nearest = Interp1d::new(&x, &y, Kind::Nearest);
+    let previous = Interp1d::new(&x, &y, Kind::Previous);
+    let next = Interp1d::new(&x, &y, Kind::Next);
+    for i in 0..3
+    {
+        assert_eq!(nearest.eval(x[i]), y[i]);
+        assert_eq!(previous.eval(x[i]), y[i]);
+        assert_eq!(next.eval(x[i]), y[i]);
+    }
+    // Midpoints go to the lower neighbour
+    assert_eq!(nearest.eval(0.5), 1.0);
+    assert_eq!(nearest.eval(2.0), 2.0);
+    assert_eq!(nearest.eval(2.1), 4.0);
+    assert_eq!(previous.eval(2.9), 2.0);
+    assert_eq!(next.eval(1.1), 4.0);
+    assert_eq!(nearest.derivative(0.7, 1), 0.0);
+    assert!((nearest.integral(0.0, 3.0) - (0.5 + 1.0 + 2.0 + 4.0)).abs() < EPS);
+    assert!((previous.integral(0.0, 3.0) - 5.0).abs() < EPS);
+    assert!((next.integral(0.5, 3.0) - 9.0).abs() < EPS);
+}
+
+#[test]
+fn test_natural_cubic()
+{
+    // Reference values from the second derivative formulation
+    assert_values(Kind::NaturalCubic, [0.8844855800388853, 1.0353129305585553,
+                                       2.5982920717217537, 3.971574134082235,
+                                       1.1563275965531306, 3.0854465327284504]);
+    let (x, y) = data();
+    let f = Interp1d::new(&x, &y, Kind::NaturalCubic);
+    assert!(f.derivative(0.0, 2).abs() < EPS && f.derivative(7.0, 2).abs() < EPS);
+    // Second derivatives are continuous at the interior points
+    for i in 1..x.len() - 1
+    {
+        let left = f.derivative(x[i] - 1e-9, 2);
+        assert!((left - f.derivative(x[i], 2)).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_cubic_exactness()
+{
+    // Not-a-knot splines, and clamped ones with the right end slopes,
+    // reproduce cubics
+    let x = arr![-1.0, -0.2, 0.5, 1.1, 2.0, 2.4, 3.0];
+    let y = Array::new(x.as_slice().iter().map(|&v| cubic(v)).collect(), array::Order::Row);
+    let slope = |x: f64| 3.0 * x * x - 4.0 * x + 0.5;
+    let kinds = [Kind::NotAKnotCubic, Kind::ClampedCubic(slope(-1.0), slope(3.0))];
+    for &kind in kinds.iter()
+    {
+        let f = Interp1d::new(&x, &y, kind);
+        for &t in [-0.9, 0.0, 0.7, 1.5, 2.2, 2.9].iter()
+        {
+            assert!((f.eval(t) - cubic(t)).abs() < 1e-10, "{:?}", kind);
+            assert!((f.derivative(t, 1) - slope(t)).abs() < 1e-9, "{:?}", kind);
+            assert!((f.derivative(t, 2) - (6.0 * t - 4.0)).abs() < 1e-8, "{:?}", kind);
+            assert!((f.derivative(t, 3) - 6.0).abs() < 1e-7, "{:?}", kind);
+            assert_eq!(f.derivative(t, 4), 0.0);
+        }
+        let antiderivative = |x: f64| x.powi(4) / 4.0 - 2.0 * x.powi(3) / 3.0 + x * x / 4.0 + x;
+        let exact = antiderivative(2.7) - antiderivative(-0.5);
+        assert!((f.integral(-0.5, 2.7) - exact).abs() < 1e-10, "{:?}", kind);
+    }
+
+    // Through three points not-a-knot gives the parabola
+    let f = Interp1d::new(&arr![0.0, 1.0, 3.0], &arr![1.0, 0.0, 4.0], Kind::NotAKnotCubic);
+    assert!((f.eval(2.0) - 1.0).abs() < EPS);
+    assert!(f.derivative(1.5, 3).abs() < EPS);
+}
+
+#[test]
+fn test_akima()
+{
+    assert_values(Kind::Akima, [0.857090909090909, 1.5460040404040405, 2.743831578947368,
+                                3.278575225943647, 1.8008287755604822, 2.844325203252031]);
+    // Straight lines are reproduced
+    let x = arr![0.0, 0.5, 2.0, 3.0];
+    let f = Interp1d::new(&x, &arr![1.0, 2.0, 5.0, 7.0], Kind::Akima);
+    assert!((f.eval(1.3) - 3.6).abs() < EPS);
+    assert!((f.derivative(2.5, 1) - 2.0).abs() < EPS);
+}
+
+#[test]
+fn test_monotone()
+{
+    assert_values(Kind::Pchip, [0.8828, 1.549925925925926, 2.792, 3.0, 1.8748148148148145,
+                                2.9234666666666658]);
+    assert_values(Kind::Steffen, [0.726, 1.549925925925926, 2.792, 3.0, 1.8748148148148145,
+                                  3.188]);
+    // No overshoot between the points of a step, unlike the spline
+    let x = arr![0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+    let y = arr![0.0, 0.0, 0.1, 1.0, 1.0, 1.0];
+    for &kind in [Kind::Pchip, Kind::Steffen].iter()
+    {
+        let f = Interp1d::new(&x, &y, kind);
+        let mut last = 0.0;
+        for i in 0..101
+        {
+            let v = f.eval(0.05 * i as f64);
+            assert!(v >= last - EPS && v <= 1.0 + EPS, "{:?}", kind);
+            last = v;
+        }
+    }
+    let spline = Interp1d::new(&x, &y, Kind::NaturalCubic);
+    assert!(spline.eval(3.5) > 1.0);
+}
+
+#[test]
+fn test_integral()
+{
+    let n = 41;
+    let pi = std::f64::consts::PI;
+    let x = Array::new((0..n).map(|i| pi * i as f64 / (n - 1) as f64).collect(), array::Order::Row);
+    let y = Array::new(x.as_slice().iter().map(|v| v.sin()).collect(), array::Order::Row);
+    for &kind in [Kind::NotAKnotCubic, Kind::Akima, Kind::Pchip].iter()
+    {
+        let f = Interp1d::new(&x, &y, kind);
+        assert!((f.integral(0.0, pi) - 2.0).abs() < 1e-4, "{:?}", kind);
+        assert!((f.integral(0.3, 1.2) - (0.3f64.cos() - 1.2f64.cos())).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn test_extrapolate()
+{
+    let (x, y) = data();
+    let fill = Interp1d::new(&x, &y, Kind::Linear).extrapolate(Extrapolate::Fill(-1.0));
+    assert_eq!(fill.eval(-0.5), -1.0);
+    assert_eq!(fill.eval(8.0), -1.0);
+    assert!((fill.integral(-1.0, 0.5) - (-1.0 + 0.25)).abs() < EPS);
+
+    let hold = Interp1d::new(&x, &y, Kind::Akima).extrapolate(Extrapolate::Hold);
+    assert_eq!(hold.eval(-3.0), 0.0);
+    assert_eq!(hold.eval(9.0), 4.0);
+    assert_eq!(hold.derivative(9.0, 1), 0.0);
+
+    let extend = Interp1d::new(&x, &y, Kind::Linear).extrapolate(Extrapolate::Extend);
+    assert!((extend.eval(-1.0) + 2.0).abs() < EPS);
+    assert!((extend.eval(8.0) - 7.5).abs() < EPS);
+    let previous = Interp1d::new(&x, &y, Kind::Previous).extrapolate(Extrapolate::Extend);
+    assert_eq!(previous.eval(8.0), 4.0);
+}
+
+#[test]
+#[should_panic(expected = "x = 7.5 is outside the interpolation range [0, 7]")]
+fn test_out_of_range()
+{
+    let (x, y) = data();
+    Interp1d::new(&x, &y, Kind::Pchip).eval(7.5);
+}
+
+#[test]
+#[should_panic(expected = "x must be strictly increasing")]
+fn test_unsorted()
+{
+    Interp1d::new(&arr![0.0, 2.0, 1.0], &arr![1.0, 2.0, 3.0], Kind::Linear);
+}
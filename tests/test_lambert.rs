@@ -0,0 +1,53 @@
+///////////////////////////////////////////
+// Special Functions: Lambert W Function //
+///////////////////////////////////////////
+#[macro_use]
+extern crate rustsci;
+
+use rustsci::gsl_lambert;
+use rustsci::gsl_math;
+
+const EPS: f64 = 0.00000000001;
+
+#[test]
+fn test_lambert_w0()
+{
+    assert_epeq!(gsl_lambert::lambert_w0(0.0).0, 0.0, EPS);
+    assert_epeq!(gsl_lambert::lambert_w0(1.0).0, 0.56714329040978387, EPS);
+    assert_epeq!(gsl_lambert::lambert_w0(-0.2).0, -0.25917110181907376, EPS);
+    assert_epeq!(gsl_lambert::lambert_w0(1000.0).0, 5.2496028524015962, EPS);
+}
+
+#[test]
+fn test_lambert_wm1()
+{
+    assert_epeq!(gsl_lambert::lambert_wm1(-0.2).0, -2.5426413577735263, EPS);
+    assert_epeq!(gsl_lambert::lambert_wm1(-1e-4).0, -11.667114532566354, EPS);
+}
+
+#[test]
+fn test_lambert_inverse()
+{
+    // W(x) exp(W(x)) = x on both branches
+    for &x in [-0.3, -0.05, -1e-6].iter()
+    {
+        let w0 = gsl_lambert::lambert_w0(x).0;
+        let wm1 = gsl_lambert::lambert_wm1(x).0;
+        assert_epeq!(w0 * w0.exp(), x, EPS);
+        assert_epeq!(wm1 * wm1.exp(), x, EPS);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_lambert_below_branch_point()
+{
+    gsl_lambert::lambert_w0(-0.5);
+}
+
+#[test]
+#[should_panic]
+fn test_lambert_wm1_positive()
+{
+    gsl_lambert::lambert_wm1(1.0);
+}
@@ -0,0 +1,145 @@
+//////////////////////////////////////////
+// Special Functions: Mathieu Functions //
+//////////////////////////////////////////
+#[macro_use]
+extern crate rustsci;
+
+use rustsci::gsl_mathieu;
+use rustsci::gsl_mathieu::MathieuWorkspace;
+use rustsci::gsl_math;
+
+const EPS: f64 = 0.00000000001;
+
+#[test]
+fn test_mathieu_characteristic()
+{
+    assert_epeq!(gsl_mathieu::mathieu_a(0, 1.0).0, -0.45513860410741355, EPS);
+    assert_epeq!(gsl_mathieu::mathieu_a(2, 1.0).0, 4.3713009827350857, EPS);
+    assert_epeq!(gsl_mathieu::mathieu_a(3, 5.0).0, 11.548832036343401, EPS);
+    assert_epeq!(gsl_mathieu::mathieu_b(1, 1.0).0, -0.11024881699209517, EPS);
+    assert_epeq!(gsl_mathieu::mathieu_b(2, 1.0).0, 3.9170247729984712, EPS);
+    assert_epeq!(gsl_mathieu::mathieu_b(4, 5.0).0, 16.648219937169777, EPS);
+}
+
+#[test]
+fn test_mathieu_characteristic_arrays()
+{
+    let mut work = MathieuWorkspace::new(10, 5.0);
+    let a = gsl_mathieu::mathieu_a_array(0, 4, 2.0, &mut work);
+    assert_eq!(a.len(), 5);
+    assert_epeq!(a[0], -1.5139568850565203, EPS);
+    assert_epeq!(a[1],  2.3791998804886860, EPS);
+    assert_epeq!(a[4], 16.141203785584937, EPS);
+
+    // The same workspace serves further calls
+    let b = gsl_mathieu::mathieu_b_array(1, 4, 2.0, &mut work);
+    assert_eq!(b.len(), 4);
+    assert_epeq!(b[0], -1.3906765012253229, EPS);
+    assert_epeq!(b[3], 16.127687952522628, EPS);
+
+    let single = gsl_mathieu::mathieu_a_array(3, 3, 2.0, &mut work);
+    assert_eq!(single.len(), 1);
+    assert_epeq!(single[0], 9.3703224836211040, EPS);
+}
+
+#[test]
+#[should_panic]
+fn test_mathieu_workspace_order_too_large()
+{
+    let mut work = MathieuWorkspace::new(5, 5.0);
+    gsl_mathieu::mathieu_a_array(0, 6, 2.0, &mut work);
+}
+
+#[test]
+#[should_panic]
+fn test_mathieu_workspace_q_too_large()
+{
+    let mut work = MathieuWorkspace::new(5, 1.0);
+    gsl_mathieu::mathieu_ce_array(0, 3, 2.0, 0.5, &mut work);
+}
+
+#[test]
+fn test_mathieu_workspace_reuse()
+{
+    // Allocating and dropping many workspaces must not leak or crash
+    for n in 1..50
+    {
+        let work = MathieuWorkspace::new(n, 10.0);
+        assert_eq!(work.nmax(), n);
+        assert_eq!(work.qmax(), 10.0);
+    }
+}
+
+#[test]
+fn test_mathieu_angular()
+{
+    assert_epeq!(gsl_mathieu::mathieu_ce(0, 1.0, 0.6).0, 0.54870855237248557, EPS);
+    assert_epeq!(gsl_mathieu::mathieu_ce(2, 1.0, 0.6).0, 0.61850792142225827, EPS);
+    assert_epeq!(gsl_mathieu::mathieu_ce(1, 1.0, 1.2).0, 0.48977562364538688, EPS);
+    assert_epeq!(gsl_mathieu::mathieu_se(1, 1.0, 0.6).0, 0.45521165792949179, EPS);
+    assert_epeq!(gsl_mathieu::mathieu_se(3, 1.0, 0.6).0, 1.0178497368350213, EPS);
+    assert_epeq!(gsl_mathieu::mathieu_se(2, 1.0, 1.2).0, 0.75757517017442856, EPS);
+}
+
+#[test]
+fn test_mathieu_angular_arrays()
+{
+    let mut work = MathieuWorkspace::new(6, 1.0);
+    let ce = gsl_mathieu::mathieu_ce_array(0, 2, 1.0, 0.6, &mut work);
+    let se = gsl_mathieu::mathieu_se_array(1, 3, 1.0, 0.6, &mut work);
+    assert_eq!(ce.len(), 3);
+    assert_eq!(se.len(), 3);
+    assert_epeq!(ce[0], 0.54870855237248557, EPS);
+    assert_epeq!(ce[2], 0.61850792142225827, EPS);
+    assert_epeq!(se[0], 0.45521165792949179, EPS);
+    assert_epeq!(se[2], 1.0178497368350213, EPS);
+}
+
+#[test]
+fn test_mathieu_radial()
+{
+    // Mc_n and Ms_n satisfy y'' = (a - 2q cosh 2z) y with a = a_n, resp. b_n
+    let q = 1.5;
+    let h = 1e-3;
+    for &z in [0.4, 0.9].iter()
+    {
+        for kind in 1..3
+        {
+            let n = 2;
+            let a = gsl_mathieu::mathieu_a(n, q).0;
+            let y = |z: f64| gsl_mathieu::mathieu_mc(kind, n, q, z).0;
+            let d2 = (y(z + h) - 2.0 * y(z) + y(z - h)) / (h * h);
+            assert!((d2 - (a - 2.0 * q * (2.0 * z).cosh()) * y(z)).abs() < 1e-5);
+
+            let b = gsl_mathieu::mathieu_b(n, q).0;
+            let y = |z: f64| gsl_mathieu::mathieu_ms(kind, n, q, z).0;
+            let d2 = (y(z + h) - 2.0 * y(z) + y(z - h)) / (h * h);
+            assert!((d2 - (b - 2.0 * q * (2.0 * z).cosh()) * y(z)).abs() < 1e-5);
+        }
+    }
+}
+
+#[test]
+fn test_mathieu_radial_arrays()
+{
+    let mut work = MathieuWorkspace::new(6, 2.0);
+    let mc = gsl_mathieu::mathieu_mc_array(1, 0, 4, 1.5, 0.7, &mut work);
+    let ms = gsl_mathieu::mathieu_ms_array(2, 1, 4, 1.5, 0.7, &mut work);
+    assert_eq!(mc.len(), 5);
+    assert_eq!(ms.len(), 4);
+    for n in 0..5
+    {
+        assert_epeq!(mc[n], gsl_mathieu::mathieu_mc(1, n as i32, 1.5, 0.7).0, 1e-8);
+    }
+    for n in 1..5
+    {
+        assert_epeq!(ms[n - 1], gsl_mathieu::mathieu_ms(2, n as i32, 1.5, 0.7).0, 1e-8);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_mathieu_radial_invalid_kind()
+{
+    gsl_mathieu::mathieu_mc(3, 1, 1.0, 0.5);
+}
@@ -0,0 +1,217 @@
+/////////////////////////////////////
+// Ordinary Differential Equations //
+/////////////////////////////////////
+#[macro_use]
+extern crate rustsci;
+
+use std::f64::consts::PI;
+use rustsci::array;
+use rustsci::array::Array;
+use rustsci::matrix;
+use rustsci::ode;
+use rustsci::ode::{Event, IvpOptions, Method, Tolerance};
+
+fn tight<'a>(method: Method) -> IvpOptions<'a>
+{
+    IvpOptions{method: method,
+               rtol: Tolerance::Uniform(1e-10),
+               atol: Tolerance::Uniform(1e-12),
+               ..IvpOptions::default()}
+}
+
+fn last(y: &Vec<Array<f64>>) -> &Array<f64>
+{
+    &y[y.len() - 1]
+}
+
+#[test]
+fn test_exponential_decay()
+{
+    let methods = [Method::RK45, Method::RK23, Method::DOP853, Method::Radau, Method::BDF];
+    for &method in methods.iter()
+    {
+        let res = ode::solve_ivp(|_, y| arr![-0.5 * y[0]], 0.0, 10.0, &arr![2.0],
+                                 &tight(method));
+        assert!(res.success);
+        assert_eq!(res.status, 0);
+        assert_eq!(res.t[0], 0.0);
+        assert_eq!(res.t[res.t.len() - 1], 10.0);
+        assert!((last(&res.y)[0] - 2.0 * (-5f64).exp()).abs() < 1e-7, "{:?}", method);
+    }
+}
+
+#[test]
+fn test_harmonic_oscillator_backwards()
+{
+    // y'' = -y from t = 2 pi back to 0, a full period
+    let f = |_: f64, y: &Array<f64>| arr![y[1], -y[0]];
+    for &method in [Method::RK45, Method::DOP853].iter()
+    {
+        let res = ode::solve_ivp(&f, 2.0 * PI, 0.0, &arr![1.0, 0.0], &tight(method));
+        assert!(res.success);
+        let y = last(&res.y);
+        assert!((y[0] - 1.0).abs() < 1e-8);
+        assert!(y[1].abs() < 1e-8);
+    }
+}
+
+#[test]
+fn test_stiff_robertson()
+{
+    // Robertson's chemical kinetics, reference values at t = 40
+    let f = |_: f64, y: &Array<f64>| arr![-0.04 * y[0] + 1e4 * y[1] * y[2],
+                                          0.04 * y[0] - 1e4 * y[1] * y[2] - 3e7 * y[1] * y[1],
+                                          3e7 * y[1] * y[1]];
+    for &method in [Method::Radau, Method::BDF].iter()
+    {
+        let opts = IvpOptions{method: method,
+                              rtol: Tolerance::Uniform(1e-8),
+                              atol: Tolerance::PerComponent(arr![1e-10, 1e-14, 1e-10]),
+                              ..IvpOptions::default()};
+        let res = ode::solve_ivp(&f, 0.0, 40.0, &arr![1.0, 0.0, 0.0], &opts);
+        assert!(res.success);
+        assert!(res.nlu > 0);
+        assert!(res.njev > 0);
+        // A stiff solver should not need more than a few hundred steps
+        assert!(res.t.len() < 1000, "{:?} took {} steps", method, res.t.len());
+        let y = last(&res.y);
+        assert!((y[0] - 0.7158270687193).abs() < 1e-6, "{:?}: {}", method, y[0]);
+        assert!((y[1] - 9.185534764e-6).abs() < 1e-10, "{:?}: {}", method, y[1]);
+        assert!((y[0] + y[1] + y[2] - 1.0).abs() < 1e-8);
+    }
+}
+
+#[test]
+fn test_user_jacobian()
+{
+    // Stiff linear system with eigenvalues -1 and -1000
+    let f = |_: f64, y: &Array<f64>| arr![-y[0], -1000.0 * (y[1] - y[0].cos())];
+    let mut opts = IvpOptions{method: Method::Radau, ..tight(Method::Radau)};
+    opts.jac = Some(Box::new(|_, y| mat![[-1.0, 0.0], [-1000.0 * y[0].sin(), -1000.0]]));
+    let res = ode::solve_ivp(&f, 0.0, 1.0, &arr![1.0, 1.0], &opts);
+    assert!(res.success);
+    let fd = ode::solve_ivp(&f, 0.0, 1.0, &arr![1.0, 1.0], &tight(Method::Radau));
+    let (y, z) = (last(&res.y), last(&fd.y));
+    assert!((y[0] - (-1f64).exp()).abs() < 1e-8);
+    assert!((y[1] - z[1]).abs() < 1e-8);
+    // The supplied Jacobian saves the difference quotients
+    assert!(res.nfev < fd.nfev);
+}
+
+#[test]
+fn test_t_eval()
+{
+    let t_eval = arr![0.0, 0.5, 1.0, 1.5, 2.0];
+    let opts = IvpOptions{t_eval: Some(t_eval.clone()), ..tight(Method::RK45)};
+    let res = ode::solve_ivp(|t, _| arr![t.cos()], 0.0, 2.0, &arr![0.0], &opts);
+    assert_eq!(res.t, t_eval);
+    for (t, y) in res.t.as_slice().iter().zip(res.y.iter())
+    {
+        assert!((y[0] - t.sin()).abs() < 1e-8);
+    }
+}
+
+#[test]
+#[should_panic(expected = "not ordered in the direction of integration")]
+fn test_t_eval_order()
+{
+    let opts = IvpOptions{t_eval: Some(arr![0.0, 1.0]), ..IvpOptions::default()};
+    ode::solve_ivp(|_, y| arr![y[0]], 1.0, 0.0, &arr![1.0], &opts);
+}
+
+#[test]
+fn test_dense_output()
+{
+    let methods = [Method::RK45, Method::RK23, Method::DOP853, Method::Radau, Method::BDF];
+    for &method in methods.iter()
+    {
+        let opts = IvpOptions{dense_output: true, ..tight(method)};
+        let res = ode::solve_ivp(|_, y| arr![y[1], -y[0]], 0.0, 5.0, &arr![0.0, 1.0], &opts);
+        let sol = res.sol.unwrap();
+        assert_eq!(sol.span(), (0.0, 5.0));
+        let tol = if method == Method::BDF { 1e-6 } else { 1e-8 };
+        for i in 0..51
+        {
+            let t = i as f64 * 0.1;
+            let y = sol.eval(t);
+            assert!((y[0] - t.sin()).abs() < tol, "{:?} at {}: {}", method, t, y[0]);
+            assert!((y[1] - t.cos()).abs() < tol, "{:?} at {}: {}", method, t, y[1]);
+        }
+        let ys = sol.eval_array(&arr![1.0, 2.0]);
+        assert_eq!(ys.len(), 2);
+        assert!((ys[1][0] - 2f64.sin()).abs() < tol);
+    }
+}
+
+#[test]
+fn test_terminal_event()
+{
+    // Projectile launched upwards at 10 m/s hits the ground after 20 / g s
+    let g = 9.81;
+    let opts = IvpOptions{
+        events: vec![Event::new(|_, y| y[0]).terminal(true).direction(-1.0)],
+        ..tight(Method::RK45)
+    };
+    let res = ode::solve_ivp(|_, y| arr![y[1], -g], 0.0, 10.0, &arr![0.0, 10.0], &opts);
+    assert!(res.success);
+    assert_eq!(res.status, 1);
+    assert_eq!(res.t_events[0].len(), 1);
+    assert!((res.t_events[0][0] - 20.0 / g).abs() < 1e-10);
+    assert!((res.y_events[0][0][1] + 10.0).abs() < 1e-8);
+    assert_eq!(res.t[res.t.len() - 1], res.t_events[0][0]);
+}
+
+#[test]
+fn test_non_terminal_events()
+{
+    // Zeros of sin t on (0, 10), split by direction
+    let opts = IvpOptions{
+        events: vec![Event::new(|_, y| y[0]).direction(1.0),
+                     Event::new(|_, y| y[0]).direction(-1.0),
+                     Event::new(|_, y| y[0])],
+        ..tight(Method::DOP853)
+    };
+    let res = ode::solve_ivp(|_, y| arr![y[1], -y[0]], 0.5, 10.0,
+                             &arr![0.5f64.sin(), 0.5f64.cos()], &opts);
+    assert_eq!(res.status, 0);
+    assert_eq!(res.t_events[0].len(), 1);
+    assert_eq!(res.t_events[1].len(), 2);
+    assert_eq!(res.t_events[2].len(), 3);
+    assert!((res.t_events[0][0] - 2.0 * PI).abs() < 1e-8);
+    assert!((res.t_events[1][0] - PI).abs() < 1e-8);
+    assert!((res.t_events[1][1] - 3.0 * PI).abs() < 1e-8);
+    assert!((res.t_events[2][1] - 2.0 * PI).abs() < 1e-8);
+}
+
+#[test]
+fn test_max_and_first_step()
+{
+    let opts = IvpOptions{max_step: 0.1, first_step: Some(0.01), ..IvpOptions::default()};
+    let res = ode::solve_ivp(|_, _| arr![1.0], 0.0, 1.0, &arr![0.0], &opts);
+    assert!(res.t.len() >= 11);
+    assert_eq!(res.t[1], 0.01);
+    for i in 1..res.t.len()
+    {
+        assert!(res.t[i] - res.t[i - 1] <= 0.1 + 1e-15);
+    }
+    assert!((last(&res.y)[0] - 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_step_size_failure()
+{
+    // Blows up at t = 1
+    let opts = IvpOptions{method: Method::RK45, ..IvpOptions::default()};
+    let res = ode::solve_ivp(|_, y| arr![y[0] * y[0]], 0.0, 2.0, &arr![1.0], &opts);
+    assert!(!res.success);
+    assert_eq!(res.status, -1);
+    assert!(res.t[res.t.len() - 1] < 1.0);
+}
+
+#[test]
+#[should_panic(expected = "rtol has 1 components, expected 2")]
+fn test_tolerance_length()
+{
+    let opts = IvpOptions{rtol: Tolerance::PerComponent(arr![1e-6]), ..IvpOptions::default()};
+    ode::solve_ivp(|_, y| arr![y[0], y[1]], 0.0, 1.0, &arr![1.0, 1.0], &opts);
+}
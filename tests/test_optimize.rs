@@ -0,0 +1,606 @@
+//////////////////
+// Optimization //
+//////////////////
+#[macro_use]
+extern crate rustsci;
+
+use rustsci::array;
+use rustsci::array::Array;
+use rustsci::matrix;
+use rustsci::matrix::Matrix;
+use rustsci::matrix::SymMat;
+use rustsci::optimize;
+use rustsci::optimize::{Bounds, Method, MinimizeOptions};
+use rustsci::optimize::{LeastSquaresMethod, LeastSquaresOptions, Loss};
+use rustsci::optimize::{Constraints, LinprogMethod, LinprogOptions, QuadprogOptions};
+
+const METHODS: [Method; 6] = [Method::NelderMead, Method::Powell, Method::CG,
+                              Method::BFGS, Method::LBFGSB, Method::TrustNCG];
+
+const LP_METHODS: [LinprogMethod; 2] = [LinprogMethod::Simplex, LinprogMethod::InteriorPoint];
+
+fn rosen(x: &Array<f64>) -> f64
+{
+    (0..x.len() - 1).fold(0.0, |s, i| {
+        s + 100.0 * (x[i + 1] - x[i] * x[i]).powi(2) + (1.0 - x[i]).powi(2)
+    })
+}
+
+fn rosen_der(x: &Array<f64>) -> Array<f64>
+{
+    let n = x.len();
+    let mut g = vec![0.0; n];
+    for i in 0..n - 1
+    {
+        g[i] += -400.0 * x[i] * (x[i + 1] - x[i] * x[i]) - 2.0 * (1.0 - x[i]);
+        g[i + 1] += 200.0 * (x[i + 1] - x[i] * x[i]);
+    }
+    Array::new(g, array::Order::Row)
+}
+
+fn rosen_hess(x: &Array<f64>) -> Matrix<f64>
+{
+    let n = x.len();
+    let mut h = vec![0.0; n * n];
+    for i in 0..n - 1
+    {
+        h[i * n + i] += 1200.0 * x[i] * x[i] - 400.0 * x[i + 1] + 2.0;
+        h[i * n + i + 1] -= 400.0 * x[i];
+        h[(i + 1) * n + i] -= 400.0 * x[i];
+        h[(i + 1) * n + i + 1] += 200.0;
+    }
+    Matrix::new_from_vec(h, n, n)
+}
+
+fn tight<'a>(method: Method) -> MinimizeOptions<'a>
+{
+    MinimizeOptions{method: method,
+                    jac: Some(Box::new(rosen_der)),
+                    gtol: 1e-6,
+                    xtol: 1e-10,
+                    ftol: Some(1e-14),
+                    maxiter: Some(5000),
+                    maxfev: Some(20000),
+                    ..MinimizeOptions::default()}
+}
+
+#[test]
+fn test_rosenbrock()
+{
+    for &method in METHODS.iter()
+    {
+        let res = optimize::minimize(rosen, &arr![1.3, 0.7, 0.8, 1.9, 1.2], &tight(method));
+        assert!(res.success, "{:?}: {}", method, res.message);
+        assert_eq!(res.status, 0);
+        assert!(res.nit > 0 && res.nfev > 0);
+        for i in 0..5
+        {
+            assert!((res.x[i] - 1.0).abs() < 1e-5, "{:?}: {:?}", method, res.x);
+        }
+        assert!(res.fun < 1e-10);
+        match method
+        {
+            Method::NelderMead | Method::Powell => assert!(res.jac.is_none()),
+            _ => assert!(res.jac.unwrap().as_slice().iter().all(|g| g.abs() < 1e-5)),
+        }
+    }
+}
+
+#[test]
+fn test_difference_gradient()
+{
+    // Without jac the gradient is estimated by forward differences
+    for &method in [Method::CG, Method::BFGS, Method::LBFGSB].iter()
+    {
+        let opts = MinimizeOptions{method: method, ..MinimizeOptions::default()};
+        let res = optimize::minimize(rosen, &arr![-1.2, 1.0], &opts);
+        assert!(res.success, "{:?}: {}", method, res.message);
+        assert!((res.x[0] - 1.0).abs() < 1e-4, "{:?}: {:?}", method, res.x);
+        assert!((res.x[1] - 1.0).abs() < 1e-4, "{:?}: {:?}", method, res.x);
+        assert!(res.nfev >= 3 * res.njev);
+    }
+}
+
+#[test]
+fn test_trust_ncg_hessian()
+{
+    let x0 = arr![1.3, 0.7, 0.8, 1.9, 1.2];
+    let mut opts = tight(Method::TrustNCG);
+    opts.hess = Some(Box::new(rosen_hess));
+    let res = optimize::minimize(rosen, &x0, &opts);
+    assert!(res.success, "{}", res.message);
+    assert!(res.nhev > 0);
+    // Hessian-vector products from gradient differences take more gradients
+    let fd = optimize::minimize(rosen, &x0, &tight(Method::TrustNCG));
+    assert_eq!(fd.nhev, 0);
+    assert!(fd.njev > res.njev);
+    for i in 0..5
+    {
+        assert!((res.x[i] - 1.0).abs() < 1e-8);
+        assert!((fd.x[i] - 1.0).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_lbfgsb_bounds()
+{
+    // The bound x[0] <= 0.5 is active; the gradient pushes against it
+    let mut opts = tight(Method::LBFGSB);
+    opts.bounds = Some(Bounds::new(arr![-2.0, -2.0], arr![0.5, 2.0]));
+    let res = optimize::minimize(rosen, &arr![-1.2, 1.0], &opts);
+    assert!(res.success, "{}", res.message);
+    assert_eq!(res.x[0], 0.5);
+    assert!((res.x[1] - 0.25).abs() < 1e-8);
+    assert!((res.fun - 0.25).abs() < 1e-12);
+
+    // Bounds on both sides of the unconstrained minimum at (3, -3, 0.5), with
+    // a starting point outside them
+    let f = |x: &Array<f64>| (x[0] - 3.0).powi(2) + (x[1] + 3.0).powi(2) + (x[2] - 0.5).powi(2)
+                             + x[0] * x[2];
+    let opts = MinimizeOptions{
+        method: Method::LBFGSB,
+        bounds: Some(Bounds::new(arr![-1.0, -1.0, -1.0], arr![1.0, 1.0, 1.0])),
+        ..MinimizeOptions::default()
+    };
+    let res = optimize::minimize(&f, &arr![5.0, 5.0, 5.0], &opts);
+    assert!(res.success, "{}", res.message);
+    // x[0] = 1 and x[1] = -1 are active, leaving x[2] = 0 free
+    assert!((res.x[0] - 1.0).abs() < 1e-8);
+    assert!((res.x[1] + 1.0).abs() < 1e-8);
+    assert!(res.x[2].abs() < 1e-5);
+}
+
+#[test]
+fn test_lbfgsb_narrow_bounds()
+{
+    // The interval for x[0] is narrower than the difference step, which
+    // must not leave it either way
+    let (lower, upper) = (1.0, 1.0 + 1e-10);
+    let f = |x: &Array<f64>| {
+        assert!(x[0] >= lower && x[0] <= upper, "x[0] = {} is outside the bounds", x[0]);
+        (x[0] - 2.0).powi(2) + (x[1] - 3.0).powi(2)
+    };
+    let opts = MinimizeOptions{
+        method: Method::LBFGSB,
+        bounds: Some(Bounds::new(arr![lower, -5.0], arr![upper, 5.0])),
+        ..MinimizeOptions::default()
+    };
+    let res = optimize::minimize(&f, &arr![1.0, 0.0], &opts);
+    assert!(res.success, "{}", res.message);
+    assert_eq!(res.x[0], upper);
+    assert!((res.x[1] - 3.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_nelder_mead_bounds()
+{
+    let opts = MinimizeOptions{
+        method: Method::NelderMead,
+        bounds: Some(Bounds::new(arr![0.0, 0.0], arr![1.0, 1.0])),
+        xtol: 1e-8,
+        ftol: Some(1e-12),
+        ..MinimizeOptions::default()
+    };
+    let res = optimize::minimize(|x| (x[0] - 2.0).powi(2) + (x[1] - 0.5).powi(2),
+                                 &arr![1.0, 1.0], &opts);
+    assert!(res.success, "{}", res.message);
+    assert_eq!(res.x[0], 1.0);
+    assert!((res.x[1] - 0.5).abs() < 1e-6);
+}
+
+#[test]
+fn test_iteration_limit()
+{
+    for &method in METHODS.iter()
+    {
+        let opts = MinimizeOptions{maxiter: Some(3), ..tight(method)};
+        let res = optimize::minimize(rosen, &arr![-1.2, 1.0], &opts);
+        assert!(!res.success);
+        assert_eq!(res.status, 1, "{:?}: {}", method, res.message);
+        assert!(res.nit <= 3);
+    }
+    let opts = MinimizeOptions{maxfev: Some(10), ..tight(Method::NelderMead)};
+    let res = optimize::minimize(rosen, &arr![-1.2, 1.0], &opts);
+    assert_eq!(res.status, 1);
+    assert_eq!(res.message, "Maximum number of function evaluations has been exceeded.");
+}
+
+#[test]
+#[should_panic(expected = "Hessian has dimensions (2, 2), expected (3, 3)")]
+fn test_hessian_dimensions()
+{
+    let mut opts = tight(Method::TrustNCG);
+    opts.hess = Some(Box::new(|_| mat![[1.0, 0.0], [0.0, 1.0]]));
+    optimize::minimize(rosen, &arr![1.0, 1.0, 1.0], &opts);
+}
+
+#[test]
+#[should_panic(expected = "Method BFGS cannot handle bounds")]
+fn test_bounds_unsupported()
+{
+    let opts = MinimizeOptions{bounds: Some(Bounds::new(arr![0.0], arr![1.0])),
+                               ..MinimizeOptions::default()};
+    optimize::minimize(|x| x[0] * x[0], &arr![0.5], &opts);
+}
+
+#[test]
+#[should_panic(expected = "Lower bound 1 exceeds upper bound 0 for component 0")]
+fn test_bounds_order()
+{
+    Bounds::new(arr![1.0], arr![0.0]);
+}
+
+#[test]
+#[should_panic(expected = "requires the gradient")]
+fn test_trust_ncg_needs_gradient()
+{
+    let opts = MinimizeOptions{method: Method::TrustNCG, ..MinimizeOptions::default()};
+    optimize::minimize(rosen, &arr![0.0, 0.0], &opts);
+}
+
+fn rosen_residuals(x: &Array<f64>) -> Array<f64>
+{
+    arr![10.0 * (x[1] - x[0] * x[0]), 1.0 - x[0]]
+}
+
+#[test]
+fn test_least_squares_rosenbrock()
+{
+    for &method in [LeastSquaresMethod::TRF, LeastSquaresMethod::LM].iter()
+    {
+        let opts = LeastSquaresOptions{method: method, ..LeastSquaresOptions::default()};
+        let res = optimize::least_squares(rosen_residuals, &arr![2.0, 2.0], &opts);
+        assert!(res.success, "{:?}: {}", method, res.message);
+        assert!(res.status > 0);
+        assert!((res.x[0] - 1.0).abs() < 1e-8 && (res.x[1] - 1.0).abs() < 1e-8);
+        assert!(res.cost < 1e-20);
+        assert!(res.optimality < 1e-8);
+        assert_eq!(res.jac.get_dims(), (2, 2));
+        assert_eq!(res.active_mask, vec![0, 0]);
+    }
+}
+
+#[test]
+fn test_least_squares_bounds()
+{
+    // With x[1] >= 1.5 the minimum is on the bound, where
+    // 400 x (1.5 - x^2) + 2 (1 - x) = 0
+    let mut opts = LeastSquaresOptions{
+        bounds: Some(Bounds::new(arr![-1.0 / 0.0, 1.5], arr![1.0 / 0.0, 1.0 / 0.0])),
+        ..LeastSquaresOptions::default()
+    };
+    opts.jac = Some(Box::new(|x| mat![[-20.0 * x[0], 10.0], [-1.0, 0.0]]));
+    let res = optimize::least_squares(rosen_residuals, &arr![2.0, 2.0], &opts);
+    assert!(res.success, "{}", res.message);
+    assert!((res.x[0] - 1.224370748736352523).abs() < 1e-7, "{:?}", res.x);
+    assert!(res.x[1] >= 1.5 && res.x[1] < 1.5 + 1e-8);
+    assert_eq!(res.active_mask, vec![0, -1]);
+    assert!(res.njev > 0);
+}
+
+#[test]
+fn test_least_squares_narrow_bounds()
+{
+    // The difference step for x[0] is wider than its interval
+    let (lower, upper) = (1.0, 1.0 + 1e-10);
+    let f = |x: &Array<f64>| {
+        assert!(x[0] >= lower && x[0] <= upper, "x[0] = {} is outside the bounds", x[0]);
+        arr![x[0] - 2.0, x[1] - 3.0]
+    };
+    let opts = LeastSquaresOptions{
+        bounds: Some(Bounds::new(arr![lower, -5.0], arr![upper, 5.0])),
+        ..LeastSquaresOptions::default()
+    };
+    let res = optimize::least_squares(&f, &arr![1.0, 0.0], &opts);
+    assert!(res.success, "{}", res.message);
+    assert!(res.x[0] > upper - 1e-12);
+    assert!((res.x[1] - 3.0).abs() < 1e-6);
+}
+
+/// A straight line y = 2 + x / 2 with deterministic noise and three gross
+/// outliers
+fn outlier_data() -> (Array<f64>, Array<f64>)
+{
+    let x: Vec<f64> = (0..21).map(|i| i as f64).collect();
+    let mut y: Vec<f64> = x.iter().map(|&t| 2.0 + 0.5 * t + 0.05 * (7.0 * t).sin()).collect();
+    y[4] += 8.0;
+    y[11] -= 6.0;
+    y[17] += 10.0;
+    (Array::new(x, array::Order::Row), Array::new(y, array::Order::Row))
+}
+
+#[test]
+fn test_robust_loss()
+{
+    let (x, y) = outlier_data();
+    let residuals = |p: &Array<f64>|
+    {
+        Array::new((0..x.len()).map(|i| p[0] + p[1] * x[i] - y[i]).collect(),
+                   array::Order::Row)
+    };
+    let fit = |loss| {
+        let opts = LeastSquaresOptions{loss: loss, f_scale: 0.1, ..LeastSquaresOptions::default()};
+        let res = optimize::least_squares(&residuals, &arr![0.0, 0.0], &opts);
+        assert!(res.success, "{:?}: {}", loss, res.message);
+        res.x
+    };
+    let linear = fit(Loss::Linear);
+    assert!((linear[0] - 2.0).abs() > 0.2);
+    for &loss in [Loss::SoftL1, Loss::Huber, Loss::Cauchy].iter()
+    {
+        let p = fit(loss);
+        assert!((p[0] - 2.0).abs() < 0.05 && (p[1] - 0.5).abs() < 0.005, "{:?}: {:?}", loss, p);
+    }
+}
+
+#[test]
+fn test_curve_fit_line()
+{
+    // Against the closed form of linear regression and its covariance
+    let (x, y) = outlier_data();
+    let (popt, pcov) = optimize::curve_fit(|t, p| p[0] + p[1] * t, &x, &y, &arr![1.0, 1.0]);
+    let n = x.len() as f64;
+    let (sx, sy) = (x.as_slice().iter().sum::<f64>(), y.as_slice().iter().sum::<f64>());
+    let sxx = x.as_slice().iter().map(|t| t * t).sum::<f64>();
+    let sxy = (0..x.len()).map(|i| x[i] * y[i]).sum::<f64>();
+    let det = n * sxx - sx * sx;
+    let b = (n * sxy - sx * sy) / det;
+    let a = (sy - b * sx) / n;
+    assert!((popt[0] - a).abs() < 1e-6 && (popt[1] - b).abs() < 1e-6);
+    let ssr = (0..x.len()).map(|i| (y[i] - a - b * x[i]).powi(2)).sum::<f64>();
+    let s_sq = ssr / (n - 2.0);
+    assert!((pcov[(0, 0)] - s_sq * sxx / det).abs() < 1e-6 * pcov[(0, 0)]);
+    assert!((pcov[(1, 1)] - s_sq * n / det).abs() < 1e-6 * pcov[(1, 1)]);
+    assert!((pcov[(0, 1)] + s_sq * sx / det).abs() < 1e-6 * pcov[(0, 1)].abs());
+    assert_eq!(pcov[(0, 1)], pcov[(1, 0)]);
+}
+
+#[test]
+fn test_curve_fit_exponential()
+{
+    // Weighted fit of a decay with bounds on the rate
+    let x = Array::new((0..30).map(|i| i as f64 * 0.2).collect(), array::Order::Row);
+    let y = Array::new(x.as_slice().iter().map(|&t| 3.0 * (-1.3 * t).exp() + 0.5).collect(),
+                       array::Order::Row);
+    let sigma = Array::new_filled(0.01, 30, array::Order::Row);
+    let opts = LeastSquaresOptions{
+        bounds: Some(Bounds::new(arr![0.0, 0.0, -1.0], arr![10.0, 5.0, 1.0])),
+        ..LeastSquaresOptions::default()
+    };
+    let model = |t: f64, p: &Array<f64>| p[0] * (-p[1] * t).exp() + p[2];
+    let (popt, pcov) = optimize::curve_fit_with(model, &x, &y, &arr![1.0, 1.0, 0.0],
+                                                Some(&sigma), &opts);
+    assert!((popt[0] - 3.0).abs() < 1e-7);
+    assert!((popt[1] - 1.3).abs() < 1e-7);
+    assert!((popt[2] - 0.5).abs() < 1e-7);
+    // An exact fit leaves no residual variance
+    assert!(pcov[(1, 1)].abs() < 1e-12);
+}
+
+#[test]
+fn test_least_squares_max_nfev()
+{
+    let opts = LeastSquaresOptions{max_nfev: Some(2), ..LeastSquaresOptions::default()};
+    let res = optimize::least_squares(rosen_residuals, &arr![-1.2, 1.0], &opts);
+    assert!(!res.success);
+    assert_eq!(res.status, 0);
+    assert_eq!(res.nfev, 2);
+}
+
+#[test]
+fn test_lm_jacobian_overflow()
+{
+    // The step from x0 crosses the jump of the tanh, so the difference
+    // quotient overflows although the residual itself is finite
+    let f = |x: &Array<f64>| arr![1e305 * (1e10 * (x[0] - 1.0)).tanh()];
+    let opts = LeastSquaresOptions{method: LeastSquaresMethod::LM,
+                                   ..LeastSquaresOptions::default()};
+    let res = optimize::least_squares(&f, &arr![1.0 - 1e-11], &opts);
+    assert!(!res.success);
+    assert_eq!(res.status, -1);
+    assert_eq!(res.message, "The Jacobian or the damping parameter is not finite.");
+}
+
+#[test]
+#[should_panic(expected = "Method LM supports neither bounds nor robust loss functions")]
+fn test_lm_rejects_loss()
+{
+    let opts = LeastSquaresOptions{method: LeastSquaresMethod::LM, loss: Loss::Huber,
+                                   ..LeastSquaresOptions::default()};
+    optimize::least_squares(rosen_residuals, &arr![2.0, 2.0], &opts);
+}
+
+#[test]
+#[should_panic(expected = "is outside the bounds")]
+fn test_least_squares_infeasible()
+{
+    let opts = LeastSquaresOptions{bounds: Some(Bounds::new(arr![0.0, 0.0], arr![1.0, 1.0])),
+                                   ..LeastSquaresOptions::default()};
+    optimize::least_squares(rosen_residuals, &arr![2.0, 0.5], &opts);
+}
+
+fn lp_options(method: LinprogMethod) -> LinprogOptions
+{
+    LinprogOptions{method: method, ..LinprogOptions::default()}
+}
+
+#[test]
+fn test_linprog_bounds()
+{
+    // x0 is free and x1 >= -3
+    let inf = std::f64::INFINITY;
+    let cons = Constraints::new()
+        .inequality(mat![[-3.0, 1.0], [1.0, 2.0]], arr![6.0, 4.0])
+        .bounds(Bounds::new(arr![-inf, -3.0], arr![inf, inf]));
+    for &method in LP_METHODS.iter()
+    {
+        let res = optimize::linprog(&arr![-1.0, 4.0], &cons, &lp_options(method));
+        assert!(res.success, "{:?}: {}", method, res.message);
+        assert!((res.fun + 22.0).abs() < 1e-6, "{:?}: {}", method, res.fun);
+        assert!((res.x[0] - 10.0).abs() < 1e-6 && (res.x[1] + 3.0).abs() < 1e-6);
+        assert!((res.slack[0] - 39.0).abs() < 1e-6 && res.slack[1].abs() < 1e-6);
+        assert_eq!(res.con.len(), 0);
+    }
+}
+
+#[test]
+fn test_linprog_upper_bounds()
+{
+    let cons = Constraints::new()
+        .inequality(mat![[0.0, 2.0], [3.0, 2.0]], arr![12.0, 18.0])
+        .bounds(Bounds::new(arr![0.0, 0.0], arr![4.0, std::f64::INFINITY]));
+    for &method in LP_METHODS.iter()
+    {
+        let res = optimize::linprog(&arr![-3.0, -5.0], &cons, &lp_options(method));
+        assert!(res.success, "{:?}: {}", method, res.message);
+        assert!((res.x[0] - 2.0).abs() < 1e-6 && (res.x[1] - 6.0).abs() < 1e-6);
+        assert!((res.fun + 36.0).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn test_linprog_transportation()
+{
+    // Two supplies and three demands, shipping costs per unit
+    let a_eq = mat![[1.0, 1.0, 1.0, 0.0, 0.0, 0.0],
+                    [0.0, 0.0, 0.0, 1.0, 1.0, 1.0],
+                    [1.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+                    [0.0, 1.0, 0.0, 0.0, 1.0, 0.0],
+                    [0.0, 0.0, 1.0, 0.0, 0.0, 1.0]];
+    // The equalities are dependent: both sides sum to the total of 50
+    let cons = Constraints::new().equality(a_eq, arr![20.0, 30.0, 10.0, 25.0, 15.0]);
+    let c = arr![8.0, 6.0, 10.0, 9.0, 12.0, 13.0];
+    let simplex = optimize::linprog(&c, &cons, &lp_options(LinprogMethod::Simplex));
+    let ip = optimize::linprog(&c, &cons, &lp_options(LinprogMethod::InteriorPoint));
+    assert!(simplex.success && ip.success);
+    assert!((simplex.fun - 465.0).abs() < 1e-9);
+    assert!((ip.fun - 465.0).abs() < 1e-5);
+    for i in 0..5
+    {
+        assert!(simplex.con[i].abs() < 1e-9 && ip.con[i].abs() < 1e-5);
+    }
+}
+
+#[test]
+fn test_linprog_infeasible()
+{
+    let cons = Constraints::new()
+        .inequality(mat![[1.0, 1.0], [-1.0, -1.0]], arr![1.0, -2.0]);
+    for &method in LP_METHODS.iter()
+    {
+        let res = optimize::linprog(&arr![1.0, 1.0], &cons, &lp_options(method));
+        assert!(!res.success);
+        assert_eq!(res.status, 2, "{:?}", method);
+        assert!(res.x.as_slice().iter().all(|v| v.is_finite()), "{:?}", method);
+    }
+}
+
+#[test]
+fn test_linprog_unbounded()
+{
+    let cons = Constraints::new().inequality(mat![[1.0, -1.0]], arr![1.0]);
+    for &method in LP_METHODS.iter()
+    {
+        let res = optimize::linprog(&arr![-1.0, 0.0], &cons, &lp_options(method));
+        assert!(!res.success);
+        assert_eq!(res.status, 3, "{:?}", method);
+        assert!(res.x.as_slice().iter().all(|v| v.is_finite()), "{:?}", method);
+    }
+}
+
+#[test]
+fn test_linprog_unconstrained()
+{
+    for &method in LP_METHODS.iter()
+    {
+        let res = optimize::linprog(&arr![1.0, 2.0], &Constraints::new(), &lp_options(method));
+        assert!(res.success, "{:?}: {}", method, res.message);
+        assert_eq!(res.x, arr![0.0, 0.0]);
+        let res = optimize::linprog(&arr![-1.0, 2.0], &Constraints::new(), &lp_options(method));
+        assert_eq!(res.status, 3, "{:?}", method);
+    }
+}
+
+#[test]
+fn test_linprog_iteration_limit()
+{
+    let cons = Constraints::new()
+        .inequality(mat![[0.0, 2.0], [3.0, 2.0]], arr![12.0, 18.0]);
+    for &method in LP_METHODS.iter()
+    {
+        let opts = LinprogOptions{method: method, maxiter: 1, ..LinprogOptions::default()};
+        let res = optimize::linprog(&arr![-3.0, -5.0], &cons, &opts);
+        assert_eq!(res.status, 1, "{:?}", method);
+        assert_eq!(res.nit, 1);
+    }
+}
+
+#[test]
+#[should_panic(expected = "A_ub has 3 columns, expected 2")]
+fn test_linprog_dimensions()
+{
+    let cons = Constraints::new().inequality(mat![[1.0, 1.0, 1.0]], arr![1.0]);
+    optimize::linprog(&arr![1.0, 1.0], &cons, &LinprogOptions::default());
+}
+
+#[test]
+fn test_quadprog()
+{
+    // min (x0 - 1)^2 + (x1 - 2.5)^2 over a polygon (Nocedal & Wright, example 16.4)
+    let inf = std::f64::INFINITY;
+    let hess = SymMat::new_from_trig(&mat![[2.0, 0.0], [0.0, 2.0]], true);
+    let cons = Constraints::new()
+        .inequality(mat![[-1.0, 2.0], [1.0, 2.0], [1.0, -2.0]], arr![2.0, 6.0, 2.0])
+        .bounds(Bounds::new(arr![0.0, 0.0], arr![inf, inf]));
+    let res = optimize::quadprog(&hess, &arr![-2.0, -5.0], &cons, &QuadprogOptions::default());
+    assert!(res.success, "{}", res.message);
+    assert!((res.x[0] - 1.4).abs() < 1e-7 && (res.x[1] - 1.7).abs() < 1e-7);
+    assert!((res.fun + 6.45).abs() < 1e-7);
+    assert!(res.slack[0].abs() < 1e-7);
+}
+
+#[test]
+fn test_quadprog_equality()
+{
+    let hess = SymMat::new_from_trig(&mat![[2.0, 1.0, 0.0],
+                                           [1.0, 2.0, 0.0],
+                                           [0.0, 0.0, 2.0]], true);
+    let cons = Constraints::new().equality(mat![[1.0, 1.0, 1.0]], arr![3.0]);
+    let res = optimize::quadprog(&hess, &arr![0.0, 0.0, 0.0], &cons,
+                                 &QuadprogOptions::default());
+    assert!(res.success, "{}", res.message);
+    // Stationarity: H x is a multiple of the constraint normal
+    assert!((res.x[0] - 6.0 / 7.0).abs() < 1e-9 && (res.x[1] - 6.0 / 7.0).abs() < 1e-9);
+    assert!((res.x[2] - 9.0 / 7.0).abs() < 1e-9);
+    assert!(res.con[0].abs() < 1e-9);
+}
+
+#[test]
+fn test_quadprog_unconstrained()
+{
+    let hess = SymMat::new_from_trig(&mat![[4.0, 1.0], [1.0, 2.0]], true);
+    let c = arr![-1.0, -1.0];
+    let res = optimize::quadprog(&hess, &c, &Constraints::new(), &QuadprogOptions::default());
+    assert!(res.success, "{}", res.message);
+    assert!((res.x[0] - 1.0 / 7.0).abs() < 1e-9 && (res.x[1] - 3.0 / 7.0).abs() < 1e-9);
+    assert!((res.fun + 2.0 / 7.0).abs() < 1e-9);
+
+    // A lower bound alone adds no constraint rows
+    let inf = std::f64::INFINITY;
+    let cons = Constraints::new().bounds(Bounds::new(arr![0.2, -inf], arr![inf, inf]));
+    let res = optimize::quadprog(&hess, &c, &cons, &QuadprogOptions::default());
+    assert!(res.success, "{}", res.message);
+    assert!((res.x[0] - 0.2).abs() < 1e-7 && (res.x[1] - 0.4).abs() < 1e-7);
+    assert!((res.fun + 0.28).abs() < 1e-7);
+}
+
+#[test]
+fn test_quadprog_infeasible()
+{
+    let hess = SymMat::new_from_trig(&mat![[2.0, 0.0], [0.0, 2.0]], true);
+    let cons = Constraints::new()
+        .inequality(mat![[1.0, 1.0], [-1.0, -1.0]], arr![1.0, -2.0]);
+    let res = optimize::quadprog(&hess, &arr![0.0, 0.0], &cons, &QuadprogOptions::default());
+    assert!(!res.success);
+    assert_eq!(res.status, 2);
+}
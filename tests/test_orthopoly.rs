@@ -0,0 +1,162 @@
+///////////////////////////////////////////////
+// Special Functions: Orthogonal Polynomials //
+///////////////////////////////////////////////
+#[macro_use]
+extern crate rustsci;
+
+use rustsci::gsl_orthopoly;
+use rustsci::gsl_math;
+
+const EPS: f64 = 0.00000000001;
+
+#[test]
+fn test_laguerre()
+{
+    assert_epeq!(gsl_orthopoly::laguerre_1(0.5, 2.5).0, -1.0, EPS);
+    assert_epeq!(gsl_orthopoly::laguerre_3(2.0, 0.7).0, 4.1678333333333336, EPS);
+    assert_epeq!(gsl_orthopoly::laguerre_n(5, 0.5, 2.5).0, 1.1770833333333333, EPS);
+    assert_epeq!(gsl_orthopoly::laguerre_n(10, 1.5, 3.0).0, -1.575724334716796875, EPS);
+}
+
+#[test]
+fn test_laguerre_array()
+{
+    let arr = gsl_orthopoly::laguerre_array(10, 1.5, 3.0);
+    assert_eq!(arr.len(), 11);
+    assert_epeq!(arr[0], 1.0, EPS);
+    assert_epeq!(arr[1], -0.5, EPS);
+    for n in 2..11
+    {
+        assert_epeq!(arr[n], gsl_orthopoly::laguerre_n(n as i32, 1.5, 3.0).0, EPS);
+    }
+}
+
+#[test]
+fn test_hermite()
+{
+    assert_epeq!(gsl_orthopoly::hermite(5, 1.3).0, -76.70624000000001, EPS);
+    assert_epeq!(gsl_orthopoly::hermite(10, -0.7).0, 38802.826035097599, EPS);
+    assert_epeq!(gsl_orthopoly::hermite_prob(5, 1.3).0, 1.242929999999999, EPS);
+    assert_epeq!(gsl_orthopoly::hermite_prob(6, 2.5).0, -75.546875, EPS);
+    assert_epeq!(gsl_orthopoly::hermite_func(5, 1.3).0, -0.39939146281375077, EPS);
+    assert_epeq!(gsl_orthopoly::hermite_func(20, 3.0).0, 0.31340505517470945, EPS);
+}
+
+#[test]
+fn test_hermite_arrays()
+{
+    let h = gsl_orthopoly::hermite_array(10, -0.7);
+    let he = gsl_orthopoly::hermite_prob_array(6, 2.5);
+    let psi = gsl_orthopoly::hermite_func_array(5, 1.3);
+    assert_eq!(h.len(), 11);
+    assert_eq!(he.len(), 7);
+    assert_eq!(psi.len(), 6);
+    assert_epeq!(h[10], 38802.826035097599, EPS);
+    assert_epeq!(he[6], -75.546875, EPS);
+    assert_epeq!(psi[5], -0.39939146281375077, EPS);
+}
+
+#[test]
+fn test_gegenpoly()
+{
+    assert_epeq!(gsl_orthopoly::gegenpoly_1(1.5, 0.3).0, 0.9, EPS);
+    assert_epeq!(gsl_orthopoly::gegenpoly_n(5, 1.5, 0.3).0, 2.02174875, EPS);
+    assert_epeq!(gsl_orthopoly::gegenpoly_n(10, 0.75, -0.4).0, 0.16068946451249986, EPS);
+    let arr = gsl_orthopoly::gegenpoly_array(10, 0.75, -0.4);
+    assert_eq!(arr.len(), 11);
+    assert_epeq!(arr[10], 0.16068946451249986, EPS);
+}
+
+#[test]
+#[should_panic]
+fn test_gegenpoly_invalid_lambda()
+{
+    gsl_orthopoly::gegenpoly_n(3, -0.5, 0.3);
+}
+
+#[test]
+fn test_jacobi()
+{
+    assert_epeq!(gsl_orthopoly::jacobi(0, 1.0, 1.0, 0.2), 1.0, EPS);
+    assert_epeq!(gsl_orthopoly::jacobi(5, 0.5, 1.5, 0.3), 0.5180175, EPS);
+    assert_epeq!(gsl_orthopoly::jacobi(12, 2.0, -0.5, -0.8), -0.11111720548782574, EPS);
+
+    // P^(0,0)_n are the Legendre polynomials, P_3(x) = (5x^3 - 3x)/2
+    let arr = gsl_orthopoly::jacobi_array(3, 0.0, 0.0, 0.4);
+    assert_eq!(arr.len(), 4);
+    assert_epeq!(arr[3], -0.44, EPS);
+}
+
+#[test]
+fn test_chebyshev()
+{
+    assert_epeq!(gsl_orthopoly::chebyshev_t(7, 0.3), -0.8461632, EPS);
+    assert_epeq!(gsl_orthopoly::chebyshev_t(12, -0.95), -0.78436007751949894, EPS);
+    assert_epeq!(gsl_orthopoly::chebyshev_t(5, 1.7), 137.41712, EPS);
+    assert_epeq!(gsl_orthopoly::chebyshev_u(7, 0.3), -0.6785664, EPS);
+    assert_epeq!(gsl_orthopoly::chebyshev_u(12, -0.95), -2.6716000649390021, EPS);
+
+    let t = gsl_orthopoly::chebyshev_t_array(4, 0.5);
+    let u = gsl_orthopoly::chebyshev_u_array(4, 0.5);
+    assert_eq!(t.len(), 5);
+    assert_epeq!(t[3], -1.0, EPS);
+    assert_epeq!(u[1], 1.0, EPS);
+}
+
+#[test]
+fn test_gauss_legendre()
+{
+    let (x, w) = gsl_orthopoly::gauss_legendre(2);
+    assert_epeq!(x[0], -1.0 / 3f64.sqrt(), EPS);
+    assert_epeq!(x[1],  1.0 / 3f64.sqrt(), EPS);
+    assert_epeq!(w[0], 1.0, EPS);
+    assert_epeq!(w[1], 1.0, EPS);
+
+    // Exact for polynomials of degree 2n - 1
+    let (x, w) = gsl_orthopoly::gauss_legendre(5);
+    let sum: f64 = (0..5).map(|i| w[i] * x[i].powi(8)).sum();
+    assert_epeq!(sum, 2.0 / 9.0, EPS);
+}
+
+#[test]
+fn test_gauss_jacobi()
+{
+    let (x, w) = gsl_orthopoly::gauss_jacobi(4, 0.5, 1.5);
+    assert_epeq!(x[0], -0.68275299855320608, EPS);
+    assert_epeq!(x[3],  0.83859641191770133, EPS);
+    assert_epeq!(w[1],  0.47575176644891926, EPS);
+    assert_epeq!(w[2],  0.67874365492842461, EPS);
+    let sum: f64 = (0..4).map(|i| w[i] * x[i].powi(5)).sum();
+    assert_epeq!(sum, 0.1227184630308513, EPS);
+}
+
+#[test]
+fn test_gauss_chebyshev()
+{
+    // The second kind rule is Gegenbauer with lambda = 1
+    let (xu, wu) = gsl_orthopoly::gauss_chebyshev_u(6);
+    let (xg, wg) = gsl_orthopoly::gauss_gegenbauer(6, 1.0);
+    for i in 0..6
+    {
+        assert!((xu[i] - xg[i]).abs() < EPS);
+        assert_epeq!(wu[i], wg[i], EPS);
+    }
+
+    let (x, w) = gsl_orthopoly::gauss_chebyshev_t(3);
+    let sum: f64 = (0..3).map(|i| w[i] * x[i] * x[i]).sum();
+    assert_epeq!(sum, std::f64::consts::PI / 2.0, EPS);
+}
+
+#[test]
+fn test_gauss_laguerre_hermite()
+{
+    // Integral of x^5 exp(-x) over [0, inf) is 5! = 120
+    let (x, w) = gsl_orthopoly::gauss_laguerre(3, 0.0);
+    let sum: f64 = (0..3).map(|i| w[i] * x[i].powi(5)).sum();
+    assert_epeq!(sum, 120.0, EPS);
+
+    // Integral of x^4 exp(-x^2) over the real line is 3 sqrt(pi) / 4
+    let (x, w) = gsl_orthopoly::gauss_hermite(4);
+    let sum: f64 = (0..4).map(|i| w[i] * x[i].powi(4)).sum();
+    assert_epeq!(sum, 3.0 * std::f64::consts::PI.sqrt() / 4.0, EPS);
+}
@@ -338,3 +338,193 @@ fn test_poly_solve_cubic()
     assert!(gsl_math::gslmath_fcmp(y[1], 2f64, 0.001f64));
     assert!(gsl_math::gslmath_fcmp(y[2], 3f64, 0.001f64));
 }
+
+///////////////////
+// Complex Roots //
+///////////////////
+
+#[test]
+fn test_complex_solve_quadratic()
+{
+    let roots = gsl_poly::poly_complex_solve_quadratic([4.0, -20.0, 26.0]);
+    assert_eq!(roots.len(), 2);
+    assert_epeq!(roots[0][0], 2.5, 1e-9);
+    assert_epeq!(roots[0][1], -0.5, 1e-9);
+    assert_epeq!(roots[1][0], 2.5, 1e-9);
+    assert_epeq!(roots[1][1], 0.5, 1e-9);
+
+    let roots = gsl_poly::poly_complex_solve_quadratic([0.0, 3.0, -21.0]);
+    assert_eq!(roots.len(), 1);
+    assert_epeq!(roots[0][0], 7.0, 1e-9);
+}
+
+#[test]
+fn test_complex_solve_cubic()
+{
+    // z^3 - 1 = 0
+    let roots = gsl_poly::poly_complex_solve_cubic([0.0, 0.0, -1.0]);
+    let half_sqrt3 = 0.75f64.sqrt();
+    assert_eq!(roots.len(), 3);
+    assert_epeq!(roots[0][0], -0.5, 1e-9);
+    assert_epeq!(roots[0][1], -half_sqrt3, 1e-9);
+    assert_epeq!(roots[1][0], -0.5, 1e-9);
+    assert_epeq!(roots[1][1], half_sqrt3, 1e-9);
+    assert_epeq!(roots[2][0], 1.0, 1e-9);
+    assert_eq!(roots[2][1], 0.0);
+}
+
+#[test]
+fn test_complex_solve()
+{
+    // z^5 - 1 = 0 has the fifth roots of unity as roots
+    let roots = gsl_poly::poly_complex_solve(&arr![-1.0, 0.0, 0.0, 0.0, 0.0, 1.0]);
+    assert_eq!(roots.len(), 5);
+    for z in roots.iter()
+    {
+        assert_epeq!(z[0] * z[0] + z[1] * z[1], 1.0, 1e-9);
+    }
+}
+
+/////////////////////
+// Polynomial Type //
+/////////////////////
+
+#[test]
+fn test_polynomial_new()
+{
+    let p = gsl_poly::Polynomial::new(vec![1.0, 2.0, 0.0, 0.0]);
+    assert_eq!(p.coeffs(), &[1.0, 2.0]);
+    assert_eq!(p.degree(), 1);
+    let zero = gsl_poly::Polynomial::<f64>::new(vec![]);
+    assert_eq!(zero.coeffs(), &[0.0]);
+    assert_eq!(zero.degree(), 0);
+    let p = gsl_poly::Polynomial::from_array(&arr![3.0, 0.0, 1.0]);
+    assert_eq!(p.to_array(), arr![3.0, 0.0, 1.0]);
+}
+
+#[test]
+fn test_polynomial_arithmetic()
+{
+    let p = gsl_poly::Polynomial::new(vec![1.0, 2.0, 3.0]);
+    let q = gsl_poly::Polynomial::new(vec![4.0, 5.0]);
+    assert_eq!((p.clone() + q.clone()).coeffs(), &[5.0, 7.0, 3.0]);
+    assert_eq!((p.clone() - q.clone()).coeffs(), &[-3.0, -3.0, 3.0]);
+    assert_eq!((p.clone() * q.clone()).coeffs(), &[4.0, 13.0, 22.0, 15.0]);
+    assert_eq!((p.clone() - p.clone()).degree(), 0);
+
+    // Integer coefficients work for everything but division
+    let a = gsl_poly::Polynomial::new(vec![1i32, 1]);
+    assert_eq!((a.clone() * a.clone()).coeffs(), &[1, 2, 1]);
+}
+
+#[test]
+fn test_polynomial_divmod()
+{
+    // x^3 - 2x^2 - 4 = (x^2 + x + 3)(x - 3) + 5
+    let p = gsl_poly::Polynomial::new(vec![-4.0, 0.0, -2.0, 1.0]);
+    let d = gsl_poly::Polynomial::new(vec![-3.0, 1.0]);
+    let (q, r) = p.divmod(&d);
+    assert_eq!(q.coeffs(), &[3.0, 1.0, 1.0]);
+    assert_eq!(r.coeffs(), &[5.0]);
+    assert_eq!(p.clone() / d.clone(), q);
+    assert_eq!(p.clone() % d.clone(), r);
+    assert_eq!(q * d.clone() + r, p);
+
+    // A divisor of higher degree leaves everything in the remainder
+    let (q, r) = d.divmod(&p);
+    assert_eq!(q.coeffs(), &[0.0]);
+    assert_eq!(r, d);
+}
+
+#[test]
+#[should_panic]
+fn test_polynomial_divide_by_zero()
+{
+    let p = gsl_poly::Polynomial::new(vec![1.0, 1.0]);
+    p.divmod(&gsl_poly::Polynomial::new(vec![0.0]));
+}
+
+#[test]
+fn test_polynomial_eval_compose()
+{
+    let p = gsl_poly::Polynomial::new(vec![1.0, 0.0, 1.0]);
+    assert_eq!(p.eval(2.0), 5.0);
+    assert_eq!(p.eval_array(&arr![0.0, 1.0, 3.0]), arr![1.0, 2.0, 10.0]);
+    // (x + 1)^2 + 1
+    let q = gsl_poly::Polynomial::new(vec![1.0, 1.0]);
+    assert_eq!(p.compose(&q).coeffs(), &[2.0, 2.0, 1.0]);
+}
+
+#[test]
+fn test_polynomial_calculus()
+{
+    let p = gsl_poly::Polynomial::new(vec![1.0, 2.0, 3.0, 4.0]);
+    assert_eq!(p.derivative(0), p);
+    assert_eq!(p.derivative(1).coeffs(), &[2.0, 6.0, 12.0]);
+    assert_eq!(p.derivative(2).coeffs(), &[6.0, 24.0]);
+    assert_eq!(p.derivative(5).coeffs(), &[0.0]);
+    assert_eq!(p.derivative(1).antiderivative(1).coeffs(), &[0.0, 2.0, 3.0, 4.0]);
+    assert_eq!(p.antiderivative(2).derivative(2), p);
+
+    let x2 = gsl_poly::Polynomial::new(vec![0.0, 0.0, 1.0]);
+    assert_epeq!(x2.integrate(0.0, 3.0), 9.0, EPS);
+    assert_epeq!(x2.integrate(3.0, 0.0), -9.0, EPS);
+}
+
+#[test]
+fn test_polynomial_roots()
+{
+    // x^2 + 1
+    let roots = gsl_poly::Polynomial::new(vec![1.0, 0.0, 1.0]).roots();
+    assert_eq!(roots.len(), 2);
+    assert_epeq!(roots[0][1], -1.0, 1e-9);
+    assert_epeq!(roots[1][1], 1.0, 1e-9);
+
+    // 2x - 3
+    let roots = gsl_poly::Polynomial::new(vec![-3.0, 2.0]).roots();
+    assert_eq!(roots, vec![[1.5, 0.0]]);
+
+    assert!(gsl_poly::Polynomial::new(vec![2.0]).roots().is_empty());
+
+    // Cubic with a non-unit leading coefficient
+    let p = gsl_poly::Polynomial::from_roots(&arr![3.0, 1.0, 2.0]) *
+        gsl_poly::Polynomial::new(vec![2.0]);
+    let roots = p.roots();
+    assert_eq!(roots.len(), 3);
+    for (z, &expected) in roots.iter().zip([1.0, 2.0, 3.0].iter())
+    {
+        assert_epeq!(z[0], expected, 1e-9);
+        assert!(z[1].abs() < 1e-9);
+    }
+
+    // Quintic solved through the companion matrix
+    let p = gsl_poly::Polynomial::from_roots(&arr![-2.0, -1.0, 0.5, 1.0, 4.0]);
+    let roots = p.roots();
+    assert_eq!(roots.len(), 5);
+    for (z, &expected) in roots.iter().zip([-2.0, -1.0, 0.5, 1.0, 4.0].iter())
+    {
+        assert_epeq!(z[0], expected, 1e-9);
+        assert!(z[1].abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_polynomial_fit()
+{
+    let x = arr![-1.0, -0.5, 0.0, 0.5, 1.0, 1.5, 2.0];
+    let p = gsl_poly::Polynomial::new(vec![1.0, -2.0, 3.0]);
+    let y = p.eval_array(&x);
+    let fitted = gsl_poly::Polynomial::fit(&x, &y, 2);
+    assert_eq!(fitted.degree(), 2);
+    for k in 0..3
+    {
+        assert_epeq!(fitted.coeffs()[k], p.coeffs()[k], 1e-9);
+    }
+
+    // Straight line through noisy points: least squares slope and intercept
+    let x = arr![0.0, 1.0, 2.0, 3.0];
+    let y = arr![1.0, 3.0, 2.0, 4.0];
+    let line = gsl_poly::Polynomial::fit(&x, &y, 1);
+    assert_epeq!(line.coeffs()[0], 1.3, 1e-9);
+    assert_epeq!(line.coeffs()[1], 0.8, 1e-9);
+}
@@ -0,0 +1,179 @@
+//////////////////////////////////
+// Orthogonal Polynomial Series //
+//////////////////////////////////
+#[macro_use]
+extern crate rustsci;
+
+use rustsci::array;
+use rustsci::gsl_math;
+use rustsci::gsl_poly::Polynomial;
+use rustsci::poly_series::{Basis, Series};
+
+const EPS: f64 = 0.00000000001;
+
+const BASES: [Basis; 4] = [Basis::Chebyshev, Basis::Legendre, Basis::Laguerre, Basis::Hermite];
+
+/// Asserts two coefficient lists agree to an absolute tolerance, treating
+/// missing trailing coefficients as zero
+fn assert_coeffs(a: &[f64], b: &[f64], tol: f64)
+{
+    for k in 0..a.len().max(b.len())
+    {
+        let ak = if k < a.len() { a[k] } else { 0.0 };
+        let bk = if k < b.len() { b[k] } else { 0.0 };
+        assert!((ak - bk).abs() < tol, "coefficient {}: {} != {}", k, ak, bk);
+    }
+}
+
+#[test]
+fn test_series_eval()
+{
+    let c = vec![1.0, 2.0, 3.0];
+    assert_epeq!(Series::new(Basis::Chebyshev, c.clone()).eval(0.5), 0.5,   EPS);
+    assert_epeq!(Series::new(Basis::Legendre,  c.clone()).eval(0.5), 1.625, EPS);
+    assert_epeq!(Series::new(Basis::Laguerre,  c.clone()).eval(0.5), 2.375, EPS);
+    assert_eq!(Series::new(Basis::Hermite, c.clone()).eval(0.5), 0.0);
+
+    let s = Series::new(Basis::Legendre, c);
+    assert_eq!(s.eval_array(&arr![0.5, 1.0]), arr![1.625, 6.0]);
+    assert_eq!(s.degree(), 2);
+    assert_eq!(s.domain(), [-1.0, 1.0]);
+    assert_eq!(Series::new(Basis::Laguerre, vec![1.0]).window(), [0.0, 1.0]);
+}
+
+#[test]
+fn test_series_domain_mapping()
+{
+    let s = Series::new_mapped(Basis::Chebyshev, vec![0.0, 1.0], [0.0, 10.0], [-1.0, 1.0]);
+    assert_eq!(s.map_parameters(), (-1.0, 0.2));
+    assert_eq!(s.eval(10.0), 1.0);
+    assert_eq!(s.eval(0.0), -1.0);
+    assert_epeq!(s.eval(7.5), 0.5, EPS);
+}
+
+#[test]
+fn test_series_to_polynomial()
+{
+    let p = Series::new(Basis::Chebyshev, vec![0.0, 0.0, 1.0]).to_polynomial();
+    assert_coeffs(p.coeffs(), &[-1.0, 0.0, 2.0], EPS);
+    let p = Series::new(Basis::Legendre, vec![0.0, 0.0, 1.0]).to_polynomial();
+    assert_coeffs(p.coeffs(), &[-0.5, 0.0, 1.5], EPS);
+    let p = Series::new(Basis::Laguerre, vec![0.0, 0.0, 1.0]).to_polynomial();
+    assert_coeffs(p.coeffs(), &[1.0, -2.0, 0.5], EPS);
+    let p = Series::new(Basis::Hermite, vec![0.0, 0.0, 0.0, 1.0]).to_polynomial();
+    assert_coeffs(p.coeffs(), &[0.0, -12.0, 0.0, 8.0], EPS);
+
+    // T_1 on [0, 2] is x - 1
+    let s = Series::new_mapped(Basis::Chebyshev, vec![0.0, 1.0], [0.0, 2.0], [-1.0, 1.0]);
+    assert_coeffs(s.to_polynomial().coeffs(), &[-1.0, 1.0], EPS);
+}
+
+#[test]
+fn test_series_from_polynomial()
+{
+    let p = Polynomial::new(vec![1.0, -2.0, 3.0, 4.0]);
+    for &basis in BASES.iter()
+    {
+        let s = Series::from_polynomial(basis, &p, [-1.0, 3.0]);
+        assert_eq!(s.basis(), basis);
+        assert_eq!(s.degree(), 3);
+        assert_coeffs(s.to_polynomial().coeffs(), p.coeffs(), 1e-10);
+        assert_epeq!(s.eval(2.2), p.eval(2.2), 1e-10);
+    }
+}
+
+#[test]
+fn test_series_fit()
+{
+    // A degree 30 fit to Exp[x] over [0, 10] is far beyond what the power
+    // basis can represent, but well conditioned in the Chebyshev basis
+    let n = 100;
+    let x = array::Array::new((0..n).map(|i| 10.0 * i as f64 / (n - 1) as f64).collect(),
+                              array::Order::Row);
+    let y = array::Array::new(x.as_slice().iter().map(|x| x.exp()).collect(),
+                              array::Order::Row);
+    let s = Series::fit(Basis::Chebyshev, &x, &y, 30);
+    assert_eq!(s.domain(), [0.0, 10.0]);
+    for &t in [0.0, 3.7, 9.5, 10.0].iter()
+    {
+        assert_epeq!(s.eval(t), f64::exp(t), 1e-8);
+    }
+
+    // Exact recovery of a quadratic in every basis
+    let x = arr![-1.0, -0.5, 0.0, 0.5, 1.0, 1.5, 2.0];
+    let p = Polynomial::new(vec![1.0, -2.0, 3.0]);
+    let y = p.eval_array(&x);
+    for &basis in BASES.iter()
+    {
+        let s = Series::fit(basis, &x, &y, 2);
+        assert_eq!(s.domain(), [-1.0, 2.0]);
+        assert_coeffs(s.to_polynomial().coeffs(), p.coeffs(), 1e-9);
+    }
+}
+
+#[test]
+fn test_series_fit_weighted()
+{
+    // A zero weight removes the last point from the fit
+    let x = arr![0.0, 1.0, 2.0, 3.0];
+    let y = arr![1.0, 3.0, 2.0, 4.0];
+    let s = Series::fit_weighted(Basis::Legendre, &x, &y, 1, &arr![1.0, 1.0, 1.0, 0.0]);
+    assert_epeq!(s.eval(0.0), 1.5, 1e-9);
+    assert_epeq!(s.eval(2.0), 2.5, 1e-9);
+}
+
+#[test]
+fn test_series_calculus()
+{
+    for &basis in BASES.iter()
+    {
+        let s = Series::new_mapped(basis, vec![1.0, 2.0, 3.0, 4.0, 5.0], [0.0, 4.0],
+                                   basis.default_window());
+        let p = s.to_polynomial();
+        assert_coeffs(s.derivative(1).to_polynomial().coeffs(), p.derivative(1).coeffs(), 1e-9);
+        assert_coeffs(s.derivative(2).to_polynomial().coeffs(), p.derivative(2).coeffs(), 1e-9);
+        assert_coeffs(s.derivative(6).coeffs(), &[0.0], EPS);
+        assert_coeffs(s.integral(1).to_polynomial().coeffs(),
+                      p.antiderivative(1).coeffs(), 1e-9);
+        assert_coeffs(s.integral(2).to_polynomial().coeffs(),
+                      p.antiderivative(2).coeffs(), 1e-9);
+        assert_epeq!(s.integrate(0.5, 3.0), p.integrate(0.5, 3.0), 1e-10);
+    }
+
+    assert_epeq!(Series::new(Basis::Legendre, vec![1.0]).integrate(-1.0, 1.0), 2.0, EPS);
+    assert!(Series::new(Basis::Legendre, vec![0.0, 0.0, 1.0]).integrate(-1.0, 1.0).abs() < EPS);
+    assert_epeq!(Series::new(Basis::Chebyshev, vec![0.0, 0.0, 1.0]).integrate(-1.0, 1.0),
+                 -2.0 / 3.0, EPS);
+}
+
+#[test]
+fn test_series_roots()
+{
+    let expected = [0.5, 1.5, 3.0, 3.5];
+    let p = Polynomial::from_roots(&arr![3.5, 0.5, 3.0, 1.5]);
+    for &basis in BASES.iter()
+    {
+        let roots = Series::from_polynomial(basis, &p, [0.0, 4.0]).roots();
+        assert_eq!(roots.len(), 4);
+        for (z, &r) in roots.iter().zip(expected.iter())
+        {
+            assert_epeq!(z[0], r, 1e-9);
+            assert!(z[1].abs() < 1e-9);
+        }
+    }
+
+    // H_3(x) = 8x^3 - 12x
+    let roots = Series::new(Basis::Hermite, vec![0.0, 0.0, 0.0, 1.0, 0.0]).roots();
+    assert_eq!(roots.len(), 3);
+    assert_epeq!(roots[0][0], -f64::sqrt(1.5), 1e-9);
+    assert!(roots[1][0].abs() < 1e-9);
+    assert_epeq!(roots[2][0], f64::sqrt(1.5), 1e-9);
+
+    // x^2 + 1 = (T_2 + 3 T_0) / 2 has roots -i and i
+    let roots = Series::new(Basis::Chebyshev, vec![1.5, 0.0, 0.5]).roots();
+    assert!(roots[0][0].abs() < 1e-9);
+    assert_epeq!(roots[0][1], -1.0, 1e-9);
+    assert_epeq!(roots[1][1], 1.0, 1e-9);
+
+    assert!(Series::new(Basis::Legendre, vec![2.0, 0.0]).roots().is_empty());
+}
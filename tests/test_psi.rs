@@ -0,0 +1,56 @@
+///////////////////////////////////////////////
+// Special Functions: Psi (Digamma) Function //
+///////////////////////////////////////////////
+#[macro_use]
+extern crate rustsci;
+
+use rustsci::gsl_psi;
+use rustsci::gsl_math;
+
+const EPS: f64 = 0.00000000001;
+
+#[test]
+fn test_psi_int()
+{
+    assert_epeq!(gsl_psi::psi_int(1).0, -0.57721566490153286, EPS);
+    assert_epeq!(gsl_psi::psi_int(5).0, 1.5061176684318005, EPS);
+    assert_epeq!(gsl_psi::psi_int(100).0, 4.6001618527380874, EPS);
+}
+
+#[test]
+fn test_psi()
+{
+    assert_epeq!(gsl_psi::psi(2.5).0, 0.70315664064524319, EPS);
+    assert_epeq!(gsl_psi::psi(-0.5).0, 0.036489973978576521, EPS);
+    assert_epeq!(gsl_psi::psi(1e-3).0, -1000.5755719318103, EPS);
+}
+
+#[test]
+#[should_panic]
+fn test_psi_pole()
+{
+    gsl_psi::psi(-2.0);
+}
+
+#[test]
+fn test_psi_1piy()
+{
+    assert_epeq!(gsl_psi::psi_1piy(2.0).0, 0.71459151537397753, EPS);
+    assert_epeq!(gsl_psi::psi_1piy(0.3).0, -0.47675489338747278, EPS);
+}
+
+#[test]
+fn test_psi_1()
+{
+    assert_epeq!(gsl_psi::psi_1_int(5).0, 0.22132295573711533, EPS);
+    assert_epeq!(gsl_psi::psi_1(0.5).0, 4.9348022005446793, EPS);
+    assert_epeq!(gsl_psi::psi_1(-1.5).0, 9.3792466449891238, EPS);
+}
+
+#[test]
+fn test_psi_n()
+{
+    assert_epeq!(gsl_psi::psi_n(0, 2.5).0, 0.70315664064524319, EPS);
+    assert_epeq!(gsl_psi::psi_n(2, 3.5).0, -0.10820405164172740, EPS);
+    assert_epeq!(gsl_psi::psi_n(3, 0.25).0, 1538.7821440091884, EPS);
+}
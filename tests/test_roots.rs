@@ -0,0 +1,226 @@
+//////////////////
+// Root Finding //
+//////////////////
+#[macro_use]
+extern crate rustsci;
+
+use rustsci::array;
+use rustsci::array::Array;
+use rustsci::matrix;
+use rustsci::gsl_math;
+use rustsci::roots;
+use rustsci::roots::{RootOptions, RootResult, SystemMethod, SystemOptions};
+
+const EPS: f64 = 0.00000000001;
+
+const WALLIS: f64 = 2.094551481542326591;
+const DOTTIE: f64 = 0.739085133215160641;
+
+fn wallis(x: f64) -> f64
+{
+    x * x * x - 2.0 * x - 5.0
+}
+
+fn check(r: RootResult, root: f64)
+{
+    assert!(r.converged, "{}", r.message);
+    assert_eq!(r.message, "converged");
+    assert!(r.iterations > 0);
+    assert!(r.function_calls >= r.iterations);
+    assert_epeq!(r.root, root, EPS);
+}
+
+#[test]
+fn test_bracketing()
+{
+    let opts = RootOptions::default();
+    check(roots::bisect(wallis, 2.0, 3.0, &opts), WALLIS);
+    check(roots::false_position(wallis, 2.0, 3.0, &opts), WALLIS);
+    check(roots::ridder(wallis, 2.0, 3.0, &opts), WALLIS);
+    check(roots::brentq(wallis, 2.0, 3.0, &opts), WALLIS);
+    check(roots::toms748(wallis, 2.0, 3.0, &opts), WALLIS);
+    // Either orientation of the bracket
+    let f = |x: f64| x.cos() - x;
+    check(roots::bisect(&f, 1.0, 0.0, &opts), DOTTIE);
+    check(roots::false_position(&f, 1.0, 0.0, &opts), DOTTIE);
+    check(roots::ridder(&f, 1.0, 0.0, &opts), DOTTIE);
+    check(roots::brentq(&f, 1.0, 0.0, &opts), DOTTIE);
+    check(roots::toms748(&f, 1.0, 0.0, &opts), DOTTIE);
+}
+
+#[test]
+fn test_bracketing_efficiency()
+{
+    // Bisection halves the bracket each iteration; the others do better
+    let opts = RootOptions::default();
+    let f = |x: f64| x.exp() - 10.0;
+    let b = roots::bisect(&f, 0.0, 5.0, &opts);
+    let fp = roots::false_position(&f, 0.0, 5.0, &opts);
+    let r = roots::ridder(&f, 0.0, 5.0, &opts);
+    let q = roots::brentq(&f, 0.0, 5.0, &opts);
+    let t = roots::toms748(&f, 0.0, 5.0, &opts);
+    assert!(b.iterations > 35);
+    for res in [fp, r, q, t].iter()
+    {
+        assert_epeq!(res.root, 10f64.ln(), EPS);
+        assert!(res.function_calls < 25, "{:?}", res);
+    }
+}
+
+#[test]
+fn test_root_at_end_point()
+{
+    let opts = RootOptions::default();
+    let r = roots::brentq(|x| x - 1.0, 1.0, 2.0, &opts);
+    assert!(r.converged);
+    assert_eq!(r.root, 1.0);
+    assert_eq!(r.iterations, 0);
+    assert_eq!(r.function_calls, 2);
+    assert_eq!(roots::toms748(|x| x - 2.0, 1.0, 2.0, &opts).root, 2.0);
+}
+
+#[test]
+#[should_panic(expected = "must have different signs")]
+fn test_no_sign_change()
+{
+    roots::brentq(|x| x * x + 1.0, -1.0, 1.0, &RootOptions::default());
+}
+
+#[test]
+#[should_panic(expected = "Invalid value for rtol")]
+fn test_rtol_too_small()
+{
+    let opts = RootOptions{rtol: 1e-20, ..RootOptions::default()};
+    roots::bisect(wallis, 2.0, 3.0, &opts);
+}
+
+#[test]
+fn test_iteration_limit()
+{
+    let opts = RootOptions{maxiter: 5, ..RootOptions::default()};
+    let r = roots::bisect(wallis, 2.0, 3.0, &opts);
+    assert!(!r.converged);
+    assert_eq!(r.iterations, 5);
+    assert!(r.message.starts_with("Failed to converge after 5 iterations"));
+    // The estimate is still inside the final bracket
+    assert!((r.root - WALLIS).abs() < 1.0 / 32.0);
+}
+
+#[test]
+fn test_newton_and_halley()
+{
+    let opts = RootOptions::default();
+    let df = |x: f64| 3.0 * x * x - 2.0;
+    let n = roots::newton(wallis, &df, 2.0, &opts);
+    check(n.clone(), WALLIS);
+    let h = roots::halley(wallis, &df, |x| 6.0 * x, 2.0, &opts);
+    check(h.clone(), WALLIS);
+    assert!(h.iterations <= n.iterations);
+    let r = roots::newton(|x| x.powi(5) - x - 1.0, |x| 5.0 * x.powi(4) - 1.0, 1.0, &opts);
+    check(r, 1.167303978261418684);
+}
+
+#[test]
+fn test_newton_zero_derivative()
+{
+    let r = roots::newton(|x| x * x - 1.0, |x| 2.0 * x, 0.0, &RootOptions::default());
+    assert!(!r.converged);
+    assert_eq!(r.message, "Derivative was zero.");
+}
+
+#[test]
+fn test_secant()
+{
+    let opts = RootOptions::default();
+    check(roots::secant(wallis, 2.0, &opts), WALLIS);
+    check(roots::secant(|x| x.cos() - x, 0.0, &opts), DOTTIE);
+    check(roots::secant(|x| x.exp() - 10.0, 2.0, &opts), 10f64.ln());
+}
+
+const SYSTEM_METHODS: [SystemMethod; 3] = [SystemMethod::Hybrid,
+                                           SystemMethod::LevenbergMarquardt,
+                                           SystemMethod::Broyden];
+
+fn system_opts<'a>(method: SystemMethod) -> SystemOptions<'a>
+{
+    SystemOptions{method: method, xtol: 1e-12, ..SystemOptions::default()}
+}
+
+#[test]
+fn test_root_system()
+{
+    let f = |x: &Array<f64>| arr![x[0] + 0.5 * (x[0] - x[1]).powi(3) - 1.0,
+                                  0.5 * (x[1] - x[0]).powi(3) + x[1]];
+    for &method in SYSTEM_METHODS.iter()
+    {
+        let res = roots::root(&f, &arr![0.0, 0.0], &system_opts(method));
+        assert!(res.success, "{:?}: {}", method, res.message);
+        assert_epeq!(res.x[0], 0.841163901914009664, 1e-9);
+        assert_epeq!(res.x[1], 0.158836098085990336, 1e-9);
+        assert!(res.fun[0].abs() < 1e-10 && res.fun[1].abs() < 1e-10);
+        assert!(res.nfev > 0 && res.njev > 0 && res.nit > 0);
+    }
+}
+
+#[test]
+fn test_root_rosenbrock()
+{
+    let f = |x: &Array<f64>| arr![10.0 * (x[1] - x[0] * x[0]), 1.0 - x[0]];
+    let jac = |x: &Array<f64>| mat![[-20.0 * x[0], 10.0], [-1.0, 0.0]];
+    for &method in SYSTEM_METHODS.iter()
+    {
+        let mut opts = system_opts(method);
+        opts.jac = Some(Box::new(jac));
+        let res = roots::root(&f, &arr![-1.2, 1.0], &opts);
+        assert!(res.success, "{:?}: {}", method, res.message);
+        assert_epeq!(res.x[0], 1.0, 1e-9);
+        assert_epeq!(res.x[1], 1.0, 1e-9);
+    }
+}
+
+#[test]
+fn test_chemical_equilibrium()
+{
+    // A + B <=> C with K = 10 from 1 mol of A and 2 mol of B; the unknowns
+    // are the final amounts of A, B and C
+    let k = 10.0;
+    let f = |x: &Array<f64>| arr![x[0] + x[2] - 1.0,
+                                  x[1] + x[2] - 2.0,
+                                  x[2] - k * x[0] * x[1]];
+    let c = 0.915571122977523981;
+    for &method in SYSTEM_METHODS.iter()
+    {
+        let res = roots::root(&f, &arr![0.5, 1.5, 0.5], &system_opts(method));
+        assert!(res.success, "{:?}: {}", method, res.message);
+        assert_epeq!(res.x[2], c, 1e-9);
+        assert_epeq!(res.x[0], 1.0 - c, 1e-9);
+        assert_epeq!(res.x[1], 2.0 - c, 1e-9);
+    }
+    let x = roots::fsolve(&f, &arr![0.5, 1.5, 0.5]);
+    assert_epeq!(x[2], c, 1e-7);
+}
+
+#[test]
+fn test_root_maxfev()
+{
+    let opts = SystemOptions{maxfev: Some(3), ..SystemOptions::default()};
+    let res = roots::root(|x| arr![x[0].exp() - 1e6 * x[1], x[0] * x[1] - 1.0],
+                          &arr![50.0, 1.0], &opts);
+    assert!(!res.success);
+    assert!(res.message.contains("maxfev = 3"));
+    assert!(res.nfev <= 3);
+}
+
+#[test]
+#[should_panic(expected = "fsolve failed to converge")]
+fn test_fsolve_no_root()
+{
+    roots::fsolve(|x| arr![x[0] * x[0] + 1.0], &arr![1.0]);
+}
+
+#[test]
+#[should_panic(expected = "System returned 1 values, expected 2")]
+fn test_root_dimensions()
+{
+    roots::root(|x| arr![x[0]], &arr![1.0, 2.0], &SystemOptions::default());
+}
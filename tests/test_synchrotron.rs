@@ -0,0 +1,31 @@
+//////////////////////////////////////////////
+// Special Functions: Synchrotron Functions //
+//////////////////////////////////////////////
+#[macro_use]
+extern crate rustsci;
+
+use rustsci::gsl_synchrotron;
+use rustsci::gsl_math;
+
+const EPS: f64 = 0.00000000001;
+
+#[test]
+fn test_synchrotron_1()
+{
+    assert_epeq!(gsl_synchrotron::synchrotron_1(0.7).0, 0.78787522632653241, EPS);
+    assert_epeq!(gsl_synchrotron::synchrotron_1(2.5).0, 0.19814490804441306, EPS);
+}
+
+#[test]
+fn test_synchrotron_2()
+{
+    assert_epeq!(gsl_synchrotron::synchrotron_2(0.7).0, 0.57034889757766433, EPS);
+    assert_epeq!(gsl_synchrotron::synchrotron_2(2.5).0, 0.16813830542905834, EPS);
+}
+
+#[test]
+#[should_panic]
+fn test_synchrotron_negative()
+{
+    gsl_synchrotron::synchrotron_1(-0.1);
+}
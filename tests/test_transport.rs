@@ -0,0 +1,27 @@
+////////////////////////////////////////////
+// Special Functions: Transport Functions //
+////////////////////////////////////////////
+#[macro_use]
+extern crate rustsci;
+
+use rustsci::gsl_transport;
+use rustsci::gsl_math;
+
+const EPS: f64 = 0.00000000001;
+
+#[test]
+fn test_transport()
+{
+    assert_epeq!(gsl_transport::transport_2(2.0).0, 1.8017185674405777, EPS);
+    assert_epeq!(gsl_transport::transport_3(2.0).0, 1.7063547219458659, EPS);
+    assert_epeq!(gsl_transport::transport_4(2.0).0, 2.2010881024333408, EPS);
+    assert_epeq!(gsl_transport::transport_5(2.0).0, 3.2292901663684049, EPS);
+    assert_epeq!(gsl_transport::transport_3(30.0).0, 7.2123414161609465, EPS);
+}
+
+#[test]
+#[should_panic]
+fn test_transport_negative()
+{
+    gsl_transport::transport_4(-1.0);
+}
@@ -0,0 +1,116 @@
+///////////////////////////////////////////////////
+// Special Functions: Zeta and Polylog Functions //
+///////////////////////////////////////////////////
+#[macro_use]
+extern crate rustsci;
+
+use rustsci::gsl_zeta;
+use rustsci::gsl_math;
+
+const EPS: f64 = 0.00000000001;
+
+#[test]
+fn test_zeta()
+{
+    assert_epeq!(gsl_zeta::zeta_int(2).0,   1.6449340668482264365,   EPS);
+    assert_epeq!(gsl_zeta::zeta_int(3).0,   1.2020569031595942854,   EPS);
+    assert_epeq!(gsl_zeta::zeta_int(-5).0, -0.003968253968253968254, EPS);
+
+    assert_epeq!(gsl_zeta::zeta(2.0).0,   1.6449340668482264365,  EPS);
+    assert_epeq!(gsl_zeta::zeta(0.5).0,  -1.4603545088095868129,  EPS);
+    assert_epeq!(gsl_zeta::zeta(-0.5).0, -0.20788622497735456602, EPS);
+}
+
+#[test]
+#[should_panic]
+fn test_zeta_pole()
+{
+    gsl_zeta::zeta(1.0);
+}
+
+#[test]
+fn test_zetam1()
+{
+    assert_epeq!(gsl_zeta::zetam1_int(5).0, 0.036927755143369926331, EPS);
+    assert_epeq!(gsl_zeta::zetam1(5.0).0,   0.036927755143369926331, EPS);
+    assert_epeq!(gsl_zeta::zetam1(20.5).0,  6.745156181678561436e-7, EPS);
+}
+
+#[test]
+fn test_hzeta()
+{
+    assert_epeq!(gsl_zeta::hzeta(2.0, 1.0).0, 1.6449340668482264365,  EPS);
+    assert_epeq!(gsl_zeta::hzeta(2.0, 2.0).0, 0.64493406684822643647, EPS);
+    assert_epeq!(gsl_zeta::hzeta(5.0, 0.5).0, 32.144760409444467716,  EPS);
+}
+
+#[test]
+fn test_eta()
+{
+    assert_epeq!(gsl_zeta::eta_int(1).0,  0.69314718055994530942, EPS);
+    assert_epeq!(gsl_zeta::eta_int(2).0,  0.82246703342411321824, EPS);
+    assert_epeq!(gsl_zeta::eta_int(-1).0, 0.25,                   EPS);
+    assert_epeq!(gsl_zeta::eta(0.5).0,    0.60489864342163037025, EPS);
+}
+
+#[test]
+fn test_dilog()
+{
+    assert_epeq!(gsl_zeta::dilog(0.5).0,   0.5822405264650125059,  EPS);
+    assert_epeq!(gsl_zeta::dilog(-1.0).0, -0.82246703342411321824, EPS);
+    assert_epeq!(gsl_zeta::dilog(-3.0).0, -1.9393754207667089531,  EPS);
+}
+
+#[test]
+fn test_complex_dilog()
+{
+    // Li_2(i) = -pi^2/48 + i G, with G Catalan's constant
+    let (val, _) = gsl_zeta::complex_dilog(1.0, 1.5707963267948966);
+    assert_epeq!(val[0], -0.20561675835602830456, EPS);
+    assert_epeq!(val[1],  0.91596559417721901505, EPS);
+
+    let (val, _) = gsl_zeta::complex_dilog_xy(0.5, 0.5);
+    assert_epeq!(val[0], 0.45398526915029558331, EPS);
+    assert_epeq!(val[1], 0.64376733288926874874, EPS);
+
+    let (val, _) = gsl_zeta::complex_spence_xy(0.5, 0.5);
+    assert_epeq!(val[0],  0.45398526915029558331, EPS);
+    assert_epeq!(val[1], -0.64376733288926874874, EPS);
+}
+
+#[test]
+fn test_polylog_special_orders()
+{
+    assert_epeq!(gsl_zeta::polylog(1.0, 0.5).0,  0.69314718055994530942, EPS);
+    assert_epeq!(gsl_zeta::polylog(2.0, 0.5).0,  0.5822405264650125059,  EPS);
+    assert_epeq!(gsl_zeta::polylog(3.0, 1.0).0,  1.2020569031595942854,  EPS);
+    assert_epeq!(gsl_zeta::polylog(3.0, -1.0).0, -0.9015426773696957141, EPS);
+    assert_eq!(gsl_zeta::polylog(3.0, 0.0).0, 0.0);
+}
+
+#[test]
+fn test_polylog_integer_order()
+{
+    assert_epeq!(gsl_zeta::polylog(3.0, 0.5).0,   0.53721319360804020094, EPS);
+    assert_epeq!(gsl_zeta::polylog(3.0, 0.9).0,   1.0496589501864399017,  EPS);
+    assert_epeq!(gsl_zeta::polylog(3.0, -0.7).0, -0.64866632128523545511, EPS);
+    assert_epeq!(gsl_zeta::polylog(3.0, -2.0).0, -1.668283363966571212,   EPS);
+    assert_epeq!(gsl_zeta::polylog(4.0, -10.0).0, -7.3265702480270797233, EPS);
+    assert_epeq!(gsl_zeta::polylog(-2.0, -3.0).0,  0.09375,               EPS);
+}
+
+#[test]
+fn test_polylog_real_order()
+{
+    assert_epeq!(gsl_zeta::polylog(2.5, 0.7).0,   0.82179287094277156032, EPS);
+    assert_epeq!(gsl_zeta::polylog(2.5, -0.8).0, -0.71083089490285636944, EPS);
+    assert_epeq!(gsl_zeta::polylog(0.5, 0.3).0,   0.38477744513420899274, EPS);
+    assert_epeq!(gsl_zeta::polylog(-1.5, 0.8).0,  56.489241971432243685,  EPS);
+}
+
+#[test]
+#[should_panic]
+fn test_polylog_complex_valued()
+{
+    gsl_zeta::polylog(3.0, 2.0);
+}